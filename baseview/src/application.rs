@@ -8,6 +8,7 @@ use tuix_core::events::{Event, Propagation};
 use tuix_core::state::hierarchy::IntoHierarchyIterator;
 use tuix_core::state::mouse::{MouseButton, MouseButtonState};
 use tuix_core::state::Fonts;
+use tuix_core::systems::apply_hover;
 use tuix_core::window::WindowWidget;
 use tuix_core::{
     Entity, EventManager, Hierarchy, Length, PropSet, Size, State, Visibility, WindowDescription,
@@ -101,15 +102,16 @@ impl ApplicationRunner {
         let bold_font = include_bytes!("../../resources/Roboto-Bold.ttf");
         let icon_font = include_bytes!("../../resources/entypo.ttf");
 
-        let fonts = Fonts {
-            regular: Some(canvas.add_font_mem(regular_font).expect("Cannot add font")),
-            bold: Some(canvas.add_font_mem(bold_font).expect("Cannot add font")),
-            icons: Some(canvas.add_font_mem(icon_font).expect("Cannot add font")),
-        };
+        let fonts = Fonts::new(
+            canvas.add_font_mem(regular_font).expect("Cannot add font"),
+            canvas.add_font_mem(bold_font).expect("Cannot add font"),
+            canvas.add_font_mem(icon_font).expect("Cannot add font"),
+        );
 
         state.fonts = fonts;
 
-        canvas.scale(scale as f32, scale as f32);
+        // The layout is in logical pixels, which are scaled by the event manager when drawing
+        state.scale_factor = scale as f32;
 
         state
             .style
@@ -122,10 +124,10 @@ impl ApplicationRunner {
 
         state
             .transform
-            .set_width(state.get_root(), physical_size.width as f32);
+            .set_width(state.get_root(), logical_size.width as f32);
         state
             .transform
-            .set_height(state.get_root(), physical_size.height as f32);
+            .set_height(state.get_root(), logical_size.height as f32);
         state.transform.set_opacity(state.get_root(), 1.0);
 
         WindowWidget::new().build_window(&mut state);
@@ -164,6 +166,14 @@ impl ApplicationRunner {
     */
 
     pub fn on_frame_update(&mut self) {
+        if self.state.caret_blink_due() {
+            self.state.request_redraw(self.state.focused);
+        }
+
+        self.state.reload_changed_stylesheets();
+
+        self.state.tooltip_due();
+
         if self.state.apply_animations() {
             self.state.insert_event(
                 Event::new(WindowEvent::Relayout)
@@ -179,6 +189,11 @@ impl ApplicationRunner {
                 self.should_redraw = true;
             }
         }
+
+        if self.state.needs_redraw {
+            self.state.needs_redraw = false;
+            self.should_redraw = true;
+        }
     }
 
     pub fn render(&mut self) -> bool {
@@ -208,130 +223,12 @@ impl ApplicationRunner {
                     self.state.mouse.cursorx = cursorx;
                     self.state.mouse.cursory = cursory;
 
-                    let mut hovered_widget = Entity::new(0, 0);
-
-                    // This only really needs to be computed when the hierarchy changes
-                    // Can be optimised
-                    let mut draw_hierarchy: Vec<Entity> =
-                        self.state.hierarchy.into_iter().collect();
-
-                    draw_hierarchy
-                        .sort_by_cached_key(|entity| self.state.transform.get_z_order(*entity));
-
-                    for widget in draw_hierarchy.into_iter() {
-                        // Skip invisible widgets
-                        if self.state.transform.get_visibility(widget) == Visibility::Invisible {
-                            continue;
-                        }
-
-                        // This shouldn't be here but there's a bug if it isn't
-                        if self.state.transform.get_opacity(widget) == 0.0 {
-                            continue;
-                        }
-
-                        // Skip non-hoverable widgets
-                        if self.state.transform.get_hoverability(widget) != true {
-                            continue;
-                        }
-
-                        let border_width = match self
-                            .state
-                            .style
-                            .border_width
-                            .get(widget)
-                            .cloned()
-                            .unwrap_or_default()
-                        {
-                            Length::Pixels(val) => val,
-                            //Length::Percentage(val) => parent_width * val,
-                            _ => 0.0,
-                        };
-
-                        let posx = self.state.transform.get_posx(widget) - (border_width / 2.0);
-                        let posy = self.state.transform.get_posy(widget) - (border_width / 2.0);
-                        let width = self.state.transform.get_width(widget) + (border_width);
-                        let height = self.state.transform.get_height(widget) + (border_width);
-
-                        let clip_widget = self.state.transform.get_clip_widget(widget);
-
-                        let clip_posx = self.state.transform.get_posx(clip_widget);
-                        let clip_posy = self.state.transform.get_posy(clip_widget);
-                        let clip_width = self.state.transform.get_width(clip_widget);
-                        let clip_height = self.state.transform.get_height(clip_widget);
-
-                        if cursorx >= posx
-                            && cursorx >= clip_posx
-                            && cursorx < (posx + width)
-                            && cursorx < (clip_posx + clip_width)
-                            && cursory >= posy
-                            && cursory >= clip_posy
-                            && cursory < (posy + height)
-                            && cursory < (clip_posy + clip_height)
-                        {
-                            hovered_widget = widget;
-                            if let Some(pseudo_classes) =
-                                self.state.style.pseudo_classes.get_mut(hovered_widget)
-                            {
-                                pseudo_classes.set_over(true);
-                            }
-                        } else {
-                            if let Some(pseudo_classes) =
-                                self.state.style.pseudo_classes.get_mut(hovered_widget)
-                            {
-                                pseudo_classes.set_over(false);
-                            }
-                        }
-                    }
-
-                    if hovered_widget != self.state.hovered {
-                        // Useful for debugging
-
-                        // println!(
-                        //     "Hover changed to {:?} parent: {:?}, posx: {}, posy: {} width: {} height: {} z_order: {}",
-                        //     hovered_widget,
-                        //     self.state.hierarchy.get_parent(hovered_widget),
-                        //     self.state.transform.get_posx(hovered_widget),
-                        //     self.state.transform.get_posy(hovered_widget),
-                        //     self.state.transform.get_width(hovered_widget),
-                        //     self.state.transform.get_height(hovered_widget),
-                        //     self.state.transform.get_z_order(hovered_widget),
-                        // );
-
-                        if let Some(pseudo_classes) =
-                            self.state.style.pseudo_classes.get_mut(hovered_widget)
-                        {
-                            pseudo_classes.set_hover(true);
-                        }
-
-                        if let Some(pseudo_classes) =
-                            self.state.style.pseudo_classes.get_mut(self.state.hovered)
-                        {
-                            pseudo_classes.set_hover(false);
-                        }
+                    apply_hover(&mut self.state);
 
-                        self.state.insert_event(
-                            Event::new(WindowEvent::MouseOver).target(hovered_widget),
-                        );
-                        self.state.insert_event(
-                            Event::new(WindowEvent::MouseOut).target(self.state.hovered),
-                        );
-
-                        self.state
-                            .insert_event(Event::new(WindowEvent::Restyle).origin(hovered_widget));
-                        self.state.insert_event(
-                            Event::new(WindowEvent::Restyle).origin(self.state.hovered),
-                        );
-
-                        self.state.hovered = hovered_widget;
-                        self.state.active = Entity::null();
-
-                        self.state.insert_event(Event::new(WindowEvent::Redraw));
-                    }
-
-                    if self.state.captured != Entity::null() {
+                    if self.state.captured() != Entity::null() {
                         self.state.insert_event(
                             Event::new(WindowEvent::MouseMove(cursorx, cursory))
-                                .target(self.state.captured)
+                                .target(self.state.captured())
                                 .propagate(Propagation::Direct),
                         );
                     } else if self.state.hovered != Entity::new(0, 0) {
@@ -353,6 +250,9 @@ impl ApplicationRunner {
                         baseview::MouseButton::Forward => MouseButton::Other(5),
                     };
 
+                    // The entity under the cursor may have been hidden or made transparent since the cursor last moved
+                    apply_hover(&mut self.state);
+
                     match b {
                         MouseButton::Left => {
                             self.state.mouse.left.state = MouseButtonState::Pressed;
@@ -366,6 +266,8 @@ impl ApplicationRunner {
                         _ => {}
                     };
 
+                    let double_click = self.state.mouse.record_click(b);
+
                     if self.state.hovered != Entity::null()
                         && self.state.active != self.state.hovered
                     {
@@ -373,10 +275,10 @@ impl ApplicationRunner {
                         self.state.insert_event(Event::new(WindowEvent::Restyle));
                     }
 
-                    if self.state.captured != Entity::null() {
+                    if self.state.captured() != Entity::null() {
                         self.state.insert_event(
                             Event::new(WindowEvent::MouseDown(b))
-                                .target(self.state.captured)
+                                .target(self.state.captured())
                                 .propagate(Propagation::Direct),
                         );
                     } else {
@@ -395,17 +297,23 @@ impl ApplicationRunner {
                         MouseButton::Middle => {
                             self.state.mouse.middle.pos_down =
                                 (self.state.mouse.cursorx, self.state.mouse.cursory);
-                            self.state.mouse.left.pressed = self.state.hovered;
+                            self.state.mouse.middle.pressed = self.state.hovered;
                         }
 
                         MouseButton::Right => {
                             self.state.mouse.right.pos_down =
                                 (self.state.mouse.cursorx, self.state.mouse.cursory);
-                            self.state.mouse.left.pressed = self.state.hovered;
+                            self.state.mouse.right.pressed = self.state.hovered;
                         }
 
                         _ => {}
                     }
+
+                    if double_click {
+                        self.state.insert_event(
+                            Event::new(WindowEvent::MouseDoubleClick(b)).target(self.state.hovered),
+                        );
+                    }
                 }
                 baseview::MouseEvent::ButtonReleased(button) => {
                     let b = match button {
@@ -433,10 +341,10 @@ impl ApplicationRunner {
                     self.state.active = Entity::null();
                     self.state.insert_event(Event::new(WindowEvent::Restyle));
 
-                    if self.state.captured != Entity::null() {
+                    if self.state.captured() != Entity::null() {
                         self.state.insert_event(
                             Event::new(WindowEvent::MouseUp(b))
-                                .target(self.state.captured)
+                                .target(self.state.captured())
                                 .propagate(Propagation::Direct),
                         );
                     } else {
@@ -455,17 +363,28 @@ impl ApplicationRunner {
                         MouseButton::Middle => {
                             self.state.mouse.middle.pos_up =
                                 (self.state.mouse.cursorx, self.state.mouse.cursory);
-                            self.state.mouse.left.released = self.state.hovered;
+                            self.state.mouse.middle.released = self.state.hovered;
                         }
 
                         MouseButton::Right => {
                             self.state.mouse.right.pos_up =
                                 (self.state.mouse.cursorx, self.state.mouse.cursory);
-                            self.state.mouse.left.released = self.state.hovered;
+                            self.state.mouse.right.released = self.state.hovered;
                         }
 
                         _ => {}
                     }
+
+                    if b == MouseButton::Right && self.state.hovered != Entity::null() {
+                        self.state.insert_event(
+                            Event::new(WindowEvent::ContextMenu(
+                                self.state.hovered,
+                                self.state.mouse.cursorx,
+                                self.state.mouse.cursory,
+                            ))
+                            .target(self.state.hovered),
+                        );
+                    }
                 }
                 baseview::MouseEvent::WheelScrolled(scroll_delta) => {
                     let (lines_x, lines_y) = match scroll_delta {
@@ -488,10 +407,12 @@ impl ApplicationRunner {
                         ),
                     };
 
-                    if self.state.captured != Entity::null() {
+                    let (lines_x, lines_y) = self.state.scroll_delta(lines_x, lines_y);
+
+                    if self.state.captured() != Entity::null() {
                         self.state.insert_event(
                             Event::new(WindowEvent::MouseScroll(lines_x, lines_y))
-                                .target(self.state.captured)
+                                .target(self.state.captured())
                                 .propagate(Propagation::Direct),
                         );
                     } else {
@@ -578,11 +499,17 @@ impl ApplicationRunner {
 
                 match s {
                     MouseButtonState::Pressed => {
+                        // Accelerators take priority over the focused entity
+                        if self.state.trigger_accelerator(&event.key) {
+                            return;
+                        }
+
                         if self.state.focused != Entity::null() {
                             self.state.insert_event(
                                 Event::new(WindowEvent::KeyDown(
                                     event.code,
                                     Some(event.key.clone()),
+                                    event.repeat,
                                 ))
                                 .target(self.state.focused)
                                 .propagate(Propagation::DownUp),
@@ -592,6 +519,7 @@ impl ApplicationRunner {
                                 Event::new(WindowEvent::KeyDown(
                                     event.code,
                                     Some(event.key.clone()),
+                                    event.repeat,
                                 ))
                                 .target(self.state.hovered)
                                 .propagate(Propagation::DownUp),
@@ -645,11 +573,6 @@ impl ApplicationRunner {
                         (window_info.physical_size().height as f64 / self.scale_factor),
                     );
 
-                    let physical_size = (
-                        window_info.physical_size().width,
-                        window_info.physical_size().height,
-                    );
-
                     self.state
                         .style
                         .width
@@ -661,10 +584,12 @@ impl ApplicationRunner {
 
                     self.state
                         .transform
-                        .set_width(self.state.root, physical_size.0 as f32);
+                        .set_width(self.state.root, logical_size.0 as f32);
                     self.state
                         .transform
-                        .set_height(self.state.root, physical_size.1 as f32);
+                        .set_height(self.state.root, logical_size.1 as f32);
+
+                    self.state.set_scale_factor(self.scale_factor as f32);
 
                     self.state
                         .insert_event(Event::new(WindowEvent::Restyle).origin(self.state.root));
@@ -1,4 +1,4 @@
-use crate::{Entity, EventHandler, EventManager, Selector, State};
+use crate::{CursorIcon, Entity, EventHandler, EventManager, Role, Selector, State};
 
 use crate::{Align, Display, FlexDirection, Hierarchy, Justify};
 
@@ -80,7 +80,10 @@ impl<'a> Builder<'a> {
     }
 
     pub fn set_opacity(mut self, val: f32) -> Self {
-        self.state.style.opacity.insert(self.entity, Opacity(val));
+        self.state
+            .style
+            .opacity
+            .insert(self.entity, Opacity(val.max(0.0).min(1.0)));
 
         self
     }
@@ -99,6 +102,33 @@ impl<'a> Builder<'a> {
         self
     }
 
+    pub fn set_cursor(mut self, val: CursorIcon) -> Self {
+        self.state.style.cursor.insert(self.entity, val);
+
+        self
+    }
+
+    pub fn set_role(mut self, val: Role) -> Self {
+        self.state.style.role.insert(self.entity, val);
+
+        self
+    }
+
+    pub fn set_accessible_label(mut self, val: &str) -> Self {
+        self.state
+            .style
+            .accessible_label
+            .insert(self.entity, val.to_string());
+
+        self
+    }
+
+    pub fn set_geometry_listener(mut self, val: bool) -> Self {
+        self.entity.set_geometry_listener(self.state, val);
+
+        self
+    }
+
     pub fn set_clip_widget(mut self, val: Entity) -> Self {
         self.state.style.clip_widget.insert(self.entity, val);
 
@@ -148,6 +178,15 @@ impl<'a> Builder<'a> {
         self
     }
 
+    pub fn set_background_gradient(mut self, val: LinearGradient) -> Self {
+        self.state
+            .style
+            .background_gradient
+            .insert(self.entity, val);
+
+        self
+    }
+
     // Positioning
 
     pub fn set_position(mut self, val: Position) -> Self {
@@ -300,6 +339,41 @@ impl<'a> Builder<'a> {
         self
     }
 
+    // Child Space
+
+    pub fn set_child_space(mut self, val: Length) -> Self {
+        self.state.style.child_left.insert(self.entity, val);
+        self.state.style.child_right.insert(self.entity, val);
+        self.state.style.child_top.insert(self.entity, val);
+        self.state.style.child_bottom.insert(self.entity, val);
+
+        self
+    }
+
+    pub fn set_child_left(mut self, val: Length) -> Self {
+        self.state.style.child_left.insert(self.entity, val);
+
+        self
+    }
+
+    pub fn set_child_right(mut self, val: Length) -> Self {
+        self.state.style.child_right.insert(self.entity, val);
+
+        self
+    }
+
+    pub fn set_child_top(mut self, val: Length) -> Self {
+        self.state.style.child_top.insert(self.entity, val);
+
+        self
+    }
+
+    pub fn set_child_bottom(mut self, val: Length) -> Self {
+        self.state.style.child_bottom.insert(self.entity, val);
+
+        self
+    }
+
     // Flex Item
 
     pub fn set_flex_grow(mut self, val: f32) -> Self {
@@ -360,6 +434,26 @@ impl<'a> Builder<'a> {
         self
     }
 
+    // Outline
+
+    pub fn set_outline_width(mut self, val: Length) -> Self {
+        self.state.style.outline_width.insert(self.entity, val);
+
+        self
+    }
+
+    pub fn set_outline_color(mut self, val: Color) -> Self {
+        self.state.style.outline_color.insert(self.entity, val);
+
+        self
+    }
+
+    pub fn set_outline_offset(mut self, val: Length) -> Self {
+        self.state.style.outline_offset.insert(self.entity, val);
+
+        self
+    }
+
     pub fn set_border_width(mut self, val: Length) -> Self {
         self.state.style.border_width.insert(self.entity, val);
 
@@ -416,6 +510,25 @@ impl<'a> Builder<'a> {
         self
     }
 
+    // Text Wrapping
+    pub fn set_text_wrap(mut self, value: bool) -> Self {
+        self.state.style.text_wrap.insert(self.entity, value);
+
+        self
+    }
+
+    pub fn set_selection_color(mut self, value: Color) -> Self {
+        self.state.style.selection_color.insert(self.entity, value);
+
+        self
+    }
+
+    pub fn set_caret_color(mut self, value: Color) -> Self {
+        self.state.style.caret_color.insert(self.entity, value);
+
+        self
+    }
+
     pub fn set_next_focus(mut self, val: Entity) -> Self {
         if let Some(entity) = self.state.style.focus_order.get_mut(self.entity) {
             entity.next = val;
@@ -476,4 +589,17 @@ impl<'a> Builder<'a> {
 
         self
     }
+
+    pub fn set_translate(mut self, x: f32, y: f32) -> Self {
+        self.state.style.translate_x.insert(self.entity, x);
+        self.state.style.translate_y.insert(self.entity, y);
+
+        self
+    }
+
+    pub fn set_transform_origin(mut self, val: TransformOrigin) -> Self {
+        self.state.style.transform_origin.insert(self.entity, val);
+
+        self
+    }
 }
@@ -5,12 +5,17 @@ use std::any::{Any, TypeId};
 use std::fmt::Debug;
 
 // Determines how the event propagates through the hierarchy
+// Propagation decides which entities an event is routed to before it is sent,
+// while consuming an event (see Event::consume) stops it part way along that route
 #[derive(Debug, Clone)]
 pub enum Propagation {
     Down,
     Up,
     DownUp,
     Fall,
+    // Sent to the ancestors of the target from the root down, then to the target
+    Capture,
+    // Sent only to the target
     Direct,
 }
 
@@ -28,6 +33,9 @@ pub trait Message: Any + MessageClone + Debug {
 
     // Perform the test.
     fn equals_a(&self, _: &dyn Message) -> bool;
+
+    // The name of the concrete type, for debugging
+    fn type_name(&self) -> &'static str;
 }
 
 // An Any is not normally clonable. This is a way around that.
@@ -90,6 +98,10 @@ impl<S: 'static + PartialEq + std::fmt::Debug + Clone> Message for S {
             .downcast_ref::<S>()
             .map_or(false, |a| self == a)
     }
+
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<S>()
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -100,8 +112,10 @@ pub struct Event {
     pub target: Entity,
     // How the event propagates through the tree.
     pub propagation: Propagation,
-    // Whether the event can be cancelled (consumed?)
+    // Whether the event can be cancelled (consumed)
     pub cancellable: bool,
+    // Whether the event has been consumed, preventing it from reaching further entities
+    pub consumed: bool,
     // Whether the event is unique (only the latest copy can exist in a queue at a time)
     pub unique: bool,
     pub order: i32,
@@ -125,6 +139,7 @@ impl Event {
             target: Entity::new(0, 0),
             propagation: Propagation::DownUp,
             cancellable: true,
+            consumed: false,
             unique: true,
             order: 0,
             message: Box::new(message),
@@ -159,6 +174,18 @@ impl Event {
         self
     }
 
+    // Stops the event from propagating to any further entities
+    // Has no effect if the event is not cancellable
+    pub fn consume(&mut self) {
+        if self.cancellable {
+            self.consumed = true;
+        }
+    }
+
+    pub fn is_consumed(&self) -> bool {
+        self.consumed
+    }
+
     pub fn get_propagate_up(&self) -> bool {
         match self.propagation {
             Propagation::Up => true,
@@ -175,6 +202,13 @@ impl Event {
         }
     }
 
+    pub fn get_propagate_capture(&self) -> bool {
+        match self.propagation {
+            Propagation::Capture => true,
+            _ => false,
+        }
+    }
+
     pub fn get_propagate_fall(&self) -> bool {
         match self.propagation {
             Propagation::Fall => true,
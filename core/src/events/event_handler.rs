@@ -2,7 +2,7 @@ use crate::events::{Event, EventManager, Message};
 
 use crate::build_handler::Builder;
 
-use crate::{Entity, Hierarchy, State};
+use crate::{draw_background, draw_text, Entity, GeometryChanged, Hierarchy, State};
 
 use std::collections::{HashMap, VecDeque};
 
@@ -22,307 +22,28 @@ pub enum WidgetEvent {
 
 pub trait EventHandler {
     // Called when events are flushed
+    // Returning true, or calling event.consume(), stops the event propagating any further
     fn on_event(&mut self, state: &mut State, entity: Entity, event: &mut Event) -> bool {
         false
     }
 
+    // Called when a layout moves or resizes the entity, before the GeometryChanged event is sent to on_event
+    // Only called for entities listening for geometry changes, see PropSet::set_geometry_listener
+    fn on_geometry_changed(
+        &mut self,
+        state: &mut State,
+        entity: Entity,
+        geometry_changed: GeometryChanged,
+    ) {
+    }
+
     // Called when a redraw occurs
     fn on_draw(&mut self, state: &mut State, entity: Entity, canvas: &mut Canvas<OpenGl>) {
-        // Skip window
-        if entity == Entity::new(0, 0) {
+        if !draw_background(state, entity, canvas) {
             return;
         }
 
-        // Skip invisible widgets
-        if state.transform.get_visibility(entity) == Visibility::Invisible {
-            return;
-        }
-
-        if state.transform.get_opacity(entity) == 0.0 {
-            return;
-        }
-
-        let posx = state.transform.get_posx(entity);
-        let posy = state.transform.get_posy(entity);
-        let width = state.transform.get_width(entity);
-        let height = state.transform.get_height(entity);
-
-        let padding_left = match state
-            .style
-            .padding_left
-            .get(entity)
-            .unwrap_or(&Length::Auto)
-        {
-            Length::Pixels(val) => val,
-            _ => &0.0,
-        };
-
-        let padding_right = match state
-            .style
-            .padding_right
-            .get(entity)
-            .unwrap_or(&Length::Auto)
-        {
-            Length::Pixels(val) => val,
-            _ => &0.0,
-        };
-
-        let padding_top = match state.style.padding_top.get(entity).unwrap_or(&Length::Auto) {
-            Length::Pixels(val) => val,
-            _ => &0.0,
-        };
-
-        let padding_bottom = match state
-            .style
-            .padding_bottom
-            .get(entity)
-            .unwrap_or(&Length::Auto)
-        {
-            Length::Pixels(val) => val,
-            _ => &0.0,
-        };
-
-        let background_color = state
-            .style
-            .background_color
-            .get(entity)
-            .cloned()
-            .unwrap_or_default();
-
-        let font_color = state
-            .style
-            .font_color
-            .get(entity)
-            .cloned()
-            .unwrap_or(crate::Color::rgb(255, 255, 255));
-
-        let border_color = state
-            .style
-            .border_color
-            .get(entity)
-            .cloned()
-            .unwrap_or_default();
-
-        let shadow_color = state
-            .style
-            .shadow_color
-            .get(entity)
-            .cloned()
-            .unwrap_or_default();
-
-        let parent = state
-            .hierarchy
-            .get_parent(entity)
-            .expect("Failed to find parent somehow");
-
-        let parent_width = state.transform.get_width(parent);
-
-        let border_radius_top_left = match state
-            .style
-            .border_radius_top_left
-            .get(entity)
-            .cloned()
-            .unwrap_or_default()
-        {
-            Length::Pixels(val) => val,
-            Length::Percentage(val) => parent_width * val,
-            _ => 0.0,
-        };
-
-        let border_radius_top_right = match state
-            .style
-            .border_radius_top_right
-            .get(entity)
-            .cloned()
-            .unwrap_or_default()
-        {
-            Length::Pixels(val) => val,
-            Length::Percentage(val) => parent_width * val,
-            _ => 0.0,
-        };
-
-        let border_radius_bottom_left = match state
-            .style
-            .border_radius_bottom_left
-            .get(entity)
-            .cloned()
-            .unwrap_or_default()
-        {
-            Length::Pixels(val) => val,
-            Length::Percentage(val) => parent_width * val,
-            _ => 0.0,
-        };
-
-        let border_radius_bottom_right = match state
-            .style
-            .border_radius_bottom_right
-            .get(entity)
-            .cloned()
-            .unwrap_or_default()
-        {
-            Length::Pixels(val) => val,
-            Length::Percentage(val) => parent_width * val,
-            _ => 0.0,
-        };
-
-        let opacity = state.transform.get_opacity(entity);
-
-        let mut background_color: femtovg::Color = background_color.into();
-        background_color.set_alphaf(background_color.a * opacity);
-
-        let mut border_color: femtovg::Color = border_color.into();
-        border_color.set_alphaf(border_color.a * opacity);
-
-        let mut shadow_color: femtovg::Color = shadow_color.into();
-        shadow_color.set_alphaf(shadow_color.a * opacity);
-
-        let border_width = match state
-            .style
-            .border_width
-            .get(entity)
-            .cloned()
-            .unwrap_or_default()
-        {
-            Length::Pixels(val) => val,
-            Length::Percentage(val) => parent_width * val,
-            _ => 0.0,
-        };
-
-        // Skip widgets with no width or no height
-        if width + 2.0 * border_width + padding_left + padding_right == 0.0
-            || height + 2.0 * border_width + padding_top + padding_bottom == 0.0
-        {
-            return;
-        }
-
-        // Apply transformations
-        let rotate = state.style.rotate.get(entity).unwrap_or(&0.0);
-        let scaley = state.style.scaley.get(entity).cloned().unwrap_or_default();
-
-        canvas.save();
-        canvas.translate(posx + width / 2.0, posy + height / 2.0);
-        canvas.rotate(rotate.to_radians());
-        canvas.translate(-(posx + width / 2.0), -(posy + height / 2.0));
-
-        //let pt = canvas.transform().inversed().transform_point(posx + width / 2.0, posy + height / 2.0);
-        //canvas.translate(posx + width / 2.0, posy + width / 2.0);
-        // canvas.translate(pt.0, pt.1);
-        // canvas.scale(1.0, scaley.0);
-        // canvas.translate(-pt.0, -pt.1);
-
-        // Apply Scissor
-        let clip_entity = state.transform.get_clip_widget(entity);
-
-        let clip_posx = state.transform.get_posx(clip_entity);
-        let clip_posy = state.transform.get_posy(clip_entity);
-        let clip_width = state.transform.get_width(clip_entity);
-        let clip_height = state.transform.get_height(clip_entity);
-
-        canvas.scissor(clip_posx, clip_posy, clip_width, clip_height);
-
-        let shadow_h_offset = state
-            .style
-            .shadow_h_offset
-            .get(entity)
-            .cloned()
-            .unwrap_or_default();
-
-        // Draw shadow
-        // let mut path = Path::new();
-        // path.rounded_rect_varying(posx, posy, width, height, border_radius_top_left, border_radius_top_right, border_radius_bottom_right, border_radius_bottom_left);
-        // let mut paint = Paint::color(background_color);
-        // canvas.fill_path(&mut path, paint);
-
-        // Draw rounded rect
-        let mut path = Path::new();
-        path.rounded_rect_varying(
-            posx + (border_width / 2.0),
-            posy + (border_width / 2.0),
-            width - border_width,
-            height - border_width,
-            border_radius_top_left,
-            border_radius_top_right,
-            border_radius_bottom_right,
-            border_radius_bottom_left,
-        );
-        let mut paint = Paint::color(background_color);
-        canvas.fill_path(&mut path, &paint);
-
-        // Draw border
-        let mut paint = Paint::color(border_color);
-        paint.set_line_width(border_width);
-        canvas.stroke_path(&mut path, &paint);
-
-        // Draw text
-        if let Some(text) = state.style.text.get_mut(entity) {
-            let font_id = match text.font.as_ref() {
-                "Sans" => state.fonts.regular.unwrap(),
-                "Icons" => state.fonts.icons.unwrap(),
-                _ => state.fonts.regular.unwrap(),
-            };
-
-            let mut x = posx + (border_width / 2.0);
-            let mut y = posy + (border_width / 2.0);
-
-            let text_string = text.text.to_owned();
-
-            let text_align = state
-                .style
-                .text_align
-                .get(entity)
-                .cloned()
-                .unwrap_or_default();
-            let text_justify = state
-                .style
-                .text_justify
-                .get(entity)
-                .cloned()
-                .unwrap_or_default();
-
-            let align = match text_justify {
-                Justify::Start => {
-                    x += padding_left;
-                    Align::Left
-                }
-                Justify::Center => {
-                    x += 0.5 * width;
-                    Align::Center
-                }
-                Justify::End => {
-                    x += width - padding_right;
-                    Align::Right
-                }
-            };
-
-            let baseline = match text_align {
-                crate::Align::Start => {
-                    y += padding_top;
-                    Baseline::Top
-                }
-                crate::Align::Center => {
-                    y += 0.5 * height;
-                    Baseline::Middle
-                }
-                crate::Align::End => {
-                    y += height - padding_bottom;
-                    Baseline::Bottom
-                }
-            };
-
-            let mut font_color: femtovg::Color = font_color.into();
-            font_color.set_alphaf(font_color.a * opacity);
-
-            let font_size = state.style.font_size.get(entity).cloned().unwrap_or(16.0);
-
-            let mut paint = Paint::color(font_color);
-            paint.set_font_size(font_size);
-            paint.set_font(&[font_id]);
-            paint.set_text_align(align);
-            paint.set_text_baseline(baseline);
-            paint.set_anti_alias(false);
-
-            canvas.fill_text(x, y, &text_string, &paint);
-        }
+        draw_text(state, entity, canvas);
 
         canvas.restore();
 
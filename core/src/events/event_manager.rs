@@ -1,18 +1,37 @@
 use crate::{
-    BuildHandler, Builder, CursorIcon, Entity, Event, EventHandler, Hierarchy, HierarchyTree,
-    IntoHierarchyIterator, IntoParentIterator, State, WidgetEvent, WindowEvent,
+    draw_inspector, get_draw_bounds, load_fonts, BuildHandler, Builder, CursorIcon, Entity, Event,
+    EventHandler, GeometryChanged, Hierarchy, HierarchyTree, IntoHierarchyIterator,
+    IntoParentIterator, Propagation, Rect, State, Visibility, WidgetEvent, WindowEvent,
 };
 use std::collections::{HashMap, VecDeque};
 
+use crate::systems::{
+    apply_clipping, apply_layout, apply_styles, apply_styles_from, apply_visibility,
+    apply_z_ordering,
+};
+
 use std::time::{Duration, Instant};
 
 use femtovg::{
     renderer::OpenGl, Align, Baseline, Canvas, Color, FillRule, FontId, ImageFlags, ImageId,
-    LineCap, LineJoin, Paint, Path, Renderer, Solidity,
+    LineCap, LineJoin, Paint, Path, PixelFormat, RenderTarget, Renderer, Solidity,
 };
 
 use fnv::FnvHashMap;
 
+// Fraction of the window above which a full repaint is used instead of a dirty rectangle
+const DIRTY_RECT_THRESHOLD: f32 = 0.5;
+
+// Returns the bounds computed for an entity by the last layout
+fn get_bounds(state: &State, entity: Entity) -> Rect {
+    Rect::new(
+        state.transform.get_posx(entity),
+        state.transform.get_posy(entity),
+        state.transform.get_width(entity),
+        state.transform.get_height(entity),
+    )
+}
+
 pub struct EventManager {
     pub event_handlers: FnvHashMap<Entity, Box<EventHandler>>,
     pub event_queue: Vec<Event>,
@@ -23,6 +42,9 @@ pub struct EventManager {
     prev_width: f32,
     prev_height: f32,
     prev_dpi_factor: f64,
+
+    // The image frames are drawn into when dirty rectangles are enabled, and its size in physical pixels
+    frame_image: Option<(ImageId, u32, u32)>,
 }
 
 impl EventManager {
@@ -37,6 +59,45 @@ impl EventManager {
             prev_width: 0.0,
             prev_height: 0.0,
             prev_dpi_factor: 1.0,
+
+            frame_image: None,
+        }
+    }
+
+    // Returns the image frames are drawn into, creating it if the window has changed size
+    // The flag is true for a new image, which has to be repainted completely
+    fn frame_image(
+        &mut self,
+        canvas: &mut Canvas<OpenGl>,
+        width: u32,
+        height: u32,
+    ) -> Option<(ImageId, bool)> {
+        if let Some((image, image_width, image_height)) = self.frame_image {
+            if image_width == width && image_height == height {
+                return Some((image, false));
+            }
+        }
+
+        self.delete_frame_image(canvas);
+
+        // Images drawn into are upside down when drawn to the window
+        let image = canvas
+            .create_image_empty(
+                width as usize,
+                height as usize,
+                PixelFormat::Rgba8,
+                ImageFlags::FLIP_Y | ImageFlags::NEAREST,
+            )
+            .ok()?;
+
+        self.frame_image = Some((image, width, height));
+
+        Some((image, true))
+    }
+
+    fn delete_frame_image(&mut self, canvas: &mut Canvas<OpenGl>) {
+        if let Some((image, _, _)) = self.frame_image.take() {
+            canvas.delete_image(image);
         }
     }
 
@@ -57,6 +118,11 @@ impl EventManager {
         // Move event handlers from state to event manager
         self.event_handlers.extend(state.event_handlers.drain());
 
+        // Remove event handlers of entities removed from state
+        for entity in state.removed_entities.drain(..) {
+            self.event_handlers.remove(&entity);
+        }
+
         // Move events from state into event manager
         let event_queue = state.event_queue.clone();
 
@@ -69,12 +135,22 @@ impl EventManager {
 
         // Loop over the events in the event manager queue
         'events: for event in self.event_queue.iter_mut() {
-            //println!("Event: {:?}", event);
+            if state.event_tracing {
+                println!(
+                    "Event: {} {:?} target: {} origin: {} propagation: {:?}",
+                    event.message.type_name(),
+                    event.message,
+                    event.target,
+                    event.origin,
+                    event.propagation,
+                );
+            }
 
             if let Some(window_event) = event.message.downcast::<WindowEvent>() {
                 match window_event {
                     WindowEvent::Redraw => {
                         needs_redraw = true;
+                        state.add_redraw_region(event.origin);
                     }
 
                     /*
@@ -111,7 +187,7 @@ impl EventManager {
             if event.target == Entity::null() {
                 for entity in hierarchy.into_iter() {
                     if let Some(event_handler) = self.event_handlers.get_mut(&entity) {
-                        if event_handler.on_event(state, entity, event) {
+                        if event_handler.on_event(state, entity, event) || event.is_consumed() {
                             break;
                         }
                     }
@@ -130,7 +206,24 @@ impl EventManager {
 
                     // Send event to all entities before the target
                     if let Some(event_handler) = self.event_handlers.get_mut(&entity) {
-                        if event_handler.on_event(state, entity, event) {
+                        if event_handler.on_event(state, entity, event) || event.is_consumed() {
+                            continue 'events;
+                        }
+                    }
+                }
+            }
+
+            // Propagate down from root to target through the ancestors of the target
+            if event.get_propagate_capture() {
+                let mut ancestors: Vec<Entity> = target
+                    .parent_iter(&hierarchy)
+                    .filter(|entity| *entity != target)
+                    .collect();
+                ancestors.reverse();
+
+                for entity in ancestors.into_iter() {
+                    if let Some(event_handler) = self.event_handlers.get_mut(&entity) {
+                        if event_handler.on_event(state, entity, event) || event.is_consumed() {
                             continue 'events;
                         }
                     }
@@ -139,7 +232,14 @@ impl EventManager {
 
             // Send event to target
             if let Some(event_handler) = self.event_handlers.get_mut(&event.target) {
-                if event_handler.on_event(state, event.target, event) {
+                if let Some(WindowEvent::GeometryChanged(geometry_changed)) =
+                    event.message.downcast::<WindowEvent>()
+                {
+                    let geometry_changed = *geometry_changed;
+                    event_handler.on_geometry_changed(state, event.target, geometry_changed);
+                }
+
+                if event_handler.on_event(state, event.target, event) || event.is_consumed() {
                     continue 'events;
                 }
             }
@@ -155,7 +255,7 @@ impl EventManager {
 
                     // Send event to all entities before the target
                     if let Some(event_handler) = self.event_handlers.get_mut(&entity) {
-                        if event_handler.on_event(state, entity, event) {
+                        if event_handler.on_event(state, entity, event) || event.is_consumed() {
                             continue 'events;
                         }
                     }
@@ -172,7 +272,7 @@ impl EventManager {
                     }
 
                     if let Some(event_handler) = self.event_handlers.get_mut(&widget) {
-                        if event_handler.on_event(state, widget, event) {
+                        if event_handler.on_event(state, widget, event) || event.is_consumed() {
                             continue 'events;
                         }
                     }
@@ -180,23 +280,114 @@ impl EventManager {
             }
         }
 
+        // Remove event handlers of entities removed while handling events
+        for entity in state.removed_entities.drain(..) {
+            self.event_handlers.remove(&entity);
+        }
+
+        // Apply the restyles and relayouts requested by the flushed events in a single pass
+        let restyle_roots: Vec<Entity> = state.restyle_roots.drain(..).collect();
+        if state.needs_restyle {
+            state.needs_restyle = false;
+            apply_styles(state, &state.hierarchy.clone());
+        } else if !restyle_roots.is_empty() {
+            let hierarchy = state.hierarchy.clone();
+            for root in restyle_roots.iter() {
+                // Skip removed entities and branches contained in another branch being restyled
+                if !state.is_alive(*root)
+                    || root
+                        .parent_iter(&hierarchy)
+                        .skip(1)
+                        .any(|ancestor| restyle_roots.contains(&ancestor))
+                {
+                    continue;
+                }
+
+                apply_styles_from(state, &hierarchy, *root);
+            }
+        }
+
+        // TODO - Only lay out the branches containing changed entities, the whole tree is laid out for now
+        if state.needs_relayout {
+            state.needs_relayout = false;
+            let hierarchy = state.hierarchy.clone();
+
+            // The bounds of the entities listening for geometry changes, to compare with after the layout
+            let previous_bounds: Vec<(Entity, Rect)> = state
+                .geometry_listeners
+                .iter()
+                .map(|entity| (*entity, get_bounds(state, *entity)))
+                .collect();
+
+            apply_z_ordering(state, &hierarchy);
+            apply_visibility(state, &hierarchy);
+            apply_clipping(state, &hierarchy);
+            apply_layout(state, &hierarchy);
+
+            for (entity, previous) in previous_bounds.into_iter() {
+                let bounds = get_bounds(state, entity);
+                let geometry_changed = GeometryChanged {
+                    posx: bounds.x != previous.x,
+                    posy: bounds.y != previous.y,
+                    width: bounds.w != previous.w,
+                    height: bounds.h != previous.h,
+                };
+
+                if geometry_changed != GeometryChanged::default() {
+                    state.insert_event(
+                        Event::new(WindowEvent::GeometryChanged(geometry_changed))
+                            .target(entity)
+                            .propagate(Propagation::Direct),
+                    );
+                }
+            }
+
+            // Sent after the geometry changes so listeners see the final bounds
+            state.insert_event(
+                Event::new(WindowEvent::LayoutComplete)
+                    .target(state.root)
+                    .propagate(Propagation::Fall),
+            );
+
+            // Entities may have moved so the whole window is repainted
+            state.redraw_all = true;
+        }
+
+        // Redraws requested directly while handling the events
+        if state.needs_redraw {
+            state.needs_redraw = false;
+            needs_redraw = true;
+        }
+
         return needs_redraw;
     }
 
     pub fn draw(&mut self, state: &mut State, hierarchy: &Hierarchy, canvas: &mut Canvas<OpenGl>) {
+        if state.headless {
+            return;
+        }
+
         //let dpi_factor = window.handle.window().scale_factor();
         //let size = window.handle.window().inner_size();
 
+        // The layout is in logical pixels and the canvas in physical pixels
         let width = state.transform.get_width(state.root);
         let height = state.transform.get_height(state.root);
-        // TODO: Move this to the window widget
-        let dpi_factor = 1.0;
+        let dpi_factor = state.scale_factor;
 
         if (self.prev_width != width
             || self.prev_height != height
-            || self.prev_dpi_factor != dpi_factor)
+            || self.prev_dpi_factor != dpi_factor as f64)
         {
-            canvas.set_size(width as u32, height as u32, dpi_factor as f32);
+            canvas.set_size(
+                (width * dpi_factor).round() as u32,
+                (height * dpi_factor).round() as u32,
+                dpi_factor,
+            );
+
+            self.prev_width = width;
+            self.prev_height = height;
+            self.prev_dpi_factor = dpi_factor as f64;
         }
 
         let background_color: femtovg::Color = state
@@ -207,21 +398,493 @@ impl EventManager {
             .unwrap_or_default()
             .into();
 
-        canvas.clear_rect(0, 0, width as u32, height as u32, background_color);
+        let physical_width = (width * dpi_factor).round() as u32;
+        let physical_height = (height * dpi_factor).round() as u32;
+
+        // The contents of the window are undefined after a frame is presented, so partial repaints are drawn
+        // into an image which keeps the previous frame and is then copied to the window
+        let frame_image = if state.dirty_rects {
+            self.frame_image(canvas, physical_width, physical_height)
+        } else {
+            self.delete_frame_image(canvas);
+            None
+        };
+
+        let previous_frame = match frame_image {
+            Some((_, new_image)) => !new_image,
+            None => false,
+        };
+
+        // Only repaint the changed region if it is small enough to be worth it
+        // The inspector label can be drawn outside of the changed region so everything is repainted while it's enabled
+        let window = Rect::new(0.0, 0.0, width, height);
+        state.draw_region = match state.redraw_region {
+            Some(region) if previous_frame && !state.redraw_all && !state.inspector => {
+                let region = region.intersection(&window);
+                if region.area() < DIRTY_RECT_THRESHOLD * window.area() {
+                    Some(region)
+                } else {
+                    None
+                }
+            }
 
-        canvas.reset();
+            _ => None,
+        };
 
-        let hierarchy = state.hierarchy.clone();
+        state.redraw_region = None;
+        state.redraw_all = false;
+
+        if let Some((image, _)) = frame_image {
+            canvas.set_render_target(RenderTarget::Image(image));
+        }
+
+        // Clearing isn't affected by the canvas transform so the region is converted to physical pixels
+        let clear = state.draw_region.unwrap_or(window);
+        canvas.clear_rect(
+            (clear.x * dpi_factor).floor() as u32,
+            (clear.y * dpi_factor).floor() as u32,
+            (clear.w * dpi_factor).ceil() as u32 + 1,
+            (clear.h * dpi_factor).ceil() as u32 + 1,
+            background_color,
+        );
+
+        load_fonts(state, canvas);
 
-        let mut draw_hierarchy: Vec<Entity> = hierarchy.into_iter().collect();
-        draw_hierarchy.sort_by_cached_key(|entity| state.transform.get_z_order(*entity));
+        canvas.reset();
+        canvas.scale(dpi_factor, dpi_factor);
 
-        for widget in draw_hierarchy.into_iter() {
+        for widget in draw_list(state).into_iter() {
             if let Some(event_handler) = self.event_handlers.get_mut(&widget) {
+                // Widgets which don't clip themselves with draw_background are still kept within the region
+                canvas.save();
+                if let Some(region) = state.draw_region {
+                    canvas.scissor(region.x, region.y, region.w, region.h);
+                }
+
                 event_handler.on_draw(state, widget, canvas);
+
+                canvas.restore();
             }
         }
 
+        if state.inspector {
+            draw_inspector(state, canvas);
+        }
+
+        // Copy the frame to the window
+        if let Some((image, _)) = frame_image {
+            canvas.set_render_target(RenderTarget::Screen);
+            canvas.reset();
+            canvas.clear_rect(
+                0,
+                0,
+                physical_width,
+                physical_height,
+                Color::rgba(0, 0, 0, 0),
+            );
+
+            let (image_width, image_height) = (physical_width as f32, physical_height as f32);
+            let mut path = Path::new();
+            path.rect(0.0, 0.0, image_width, image_height);
+            let paint = Paint::image(image, 0.0, 0.0, image_width, image_height, 0.0, 1.0);
+            canvas.fill_path(&mut path, &paint);
+        }
+
         canvas.flush();
     }
 }
+
+// Returns the entities to draw in the order they're drawn
+fn draw_list(state: &State) -> Vec<Entity> {
+    let mut draw_hierarchy: Vec<Entity> = state.hierarchy.into_iter().collect();
+    // Overlays are drawn in a final pass after the rest of the tree
+    draw_hierarchy.sort_by_cached_key(|entity| {
+        (
+            state.transform.is_overlay(*entity),
+            state.transform.get_z_order(*entity),
+        )
+    });
+
+    draw_hierarchy.retain(|widget| {
+        // Skip hidden entities, including the descendants of hidden entities, so widgets with their own
+        // on_draw don't need to check
+        if state.transform.get_visibility(*widget) == Visibility::Invisible {
+            return false;
+        }
+
+        // Skip entities outside of the region being repainted
+        match state.draw_region {
+            Some(region) => {
+                *widget == state.root || get_draw_bounds(state, *widget).intersects(&region)
+            }
+            None => true,
+        }
+    });
+
+    draw_hierarchy
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BuildHandler, Length, PropSet};
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum TestEvent {
+        Ping,
+    }
+
+    // Records the entities which receive a TestEvent, optionally consuming it
+    struct Recorder {
+        received: Rc<RefCell<Vec<Entity>>>,
+        consume: bool,
+    }
+
+    impl Recorder {
+        fn new(received: &Rc<RefCell<Vec<Entity>>>) -> Self {
+            Recorder {
+                received: received.clone(),
+                consume: false,
+            }
+        }
+
+        fn consuming(mut self) -> Self {
+            self.consume = true;
+
+            self
+        }
+    }
+
+    impl BuildHandler for Recorder {
+        type Ret = Entity;
+        fn on_build(&mut self, _state: &mut State, entity: Entity) -> Self::Ret {
+            entity
+        }
+    }
+
+    impl EventHandler for Recorder {
+        fn on_event(&mut self, _state: &mut State, entity: Entity, event: &mut Event) -> bool {
+            if let Some(TestEvent::Ping) = event.message.downcast::<TestEvent>() {
+                self.received.borrow_mut().push(entity);
+
+                if self.consume {
+                    event.consume();
+                }
+            }
+
+            false
+        }
+    }
+
+    #[test]
+    fn consumed_event_does_not_reach_sibling() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let root = state.root;
+
+        let received = Rc::new(RefCell::new(Vec::new()));
+
+        let parent = Recorder::new(&received).build(&mut state, root, |builder| builder);
+        let first = Recorder::new(&received)
+            .consuming()
+            .build(&mut state, parent, |builder| builder);
+        let _second = Recorder::new(&received).build(&mut state, parent, |builder| builder);
+
+        state.insert_event(
+            Event::new(TestEvent::Ping)
+                .target(parent)
+                .propagate(Propagation::Fall),
+        );
+        event_manager.flush_events(&mut state);
+
+        assert_eq!(*received.borrow(), vec![parent, first]);
+    }
+
+    #[test]
+    fn parent_consuming_event_stops_it_reaching_children() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let root = state.root;
+
+        let received = Rc::new(RefCell::new(Vec::new()));
+
+        let parent = Recorder::new(&received)
+            .consuming()
+            .build(&mut state, root, |builder| builder);
+        let _first = Recorder::new(&received).build(&mut state, parent, |builder| builder);
+        let _second = Recorder::new(&received).build(&mut state, parent, |builder| builder);
+
+        state.insert_event(
+            Event::new(TestEvent::Ping)
+                .target(parent)
+                .propagate(Propagation::Fall),
+        );
+        event_manager.flush_events(&mut state);
+
+        assert_eq!(*received.borrow(), vec![parent]);
+    }
+
+    #[test]
+    fn capture_visits_ancestors_from_the_root_down_then_the_target() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let root = state.root;
+
+        let received = Rc::new(RefCell::new(Vec::new()));
+
+        let top = Recorder::new(&received).build(&mut state, root, |builder| builder);
+        let middle = Recorder::new(&received).build(&mut state, top, |builder| builder);
+        let bottom = Recorder::new(&received).build(&mut state, middle, |builder| builder);
+
+        state.insert_event(
+            Event::new(TestEvent::Ping)
+                .target(bottom)
+                .propagate(Propagation::Capture),
+        );
+        event_manager.flush_events(&mut state);
+
+        assert_eq!(*received.borrow(), vec![top, middle, bottom]);
+    }
+
+    #[test]
+    fn capture_stops_at_an_ancestor_which_consumes() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let root = state.root;
+
+        let received = Rc::new(RefCell::new(Vec::new()));
+
+        let top = Recorder::new(&received).build(&mut state, root, |builder| builder);
+        let middle = Recorder::new(&received)
+            .consuming()
+            .build(&mut state, top, |builder| builder);
+        let bottom = Recorder::new(&received).build(&mut state, middle, |builder| builder);
+
+        state.insert_event(
+            Event::new(TestEvent::Ping)
+                .target(bottom)
+                .propagate(Propagation::Capture),
+        );
+        event_manager.flush_events(&mut state);
+
+        assert_eq!(*received.borrow(), vec![top, middle]);
+    }
+
+    // Records the GeometryChanged events it receives
+    struct GeometryRecorder {
+        received: Rc<RefCell<Vec<GeometryChanged>>>,
+    }
+
+    impl BuildHandler for GeometryRecorder {
+        type Ret = Entity;
+        fn on_build(&mut self, _state: &mut State, entity: Entity) -> Self::Ret {
+            entity
+        }
+    }
+
+    impl EventHandler for GeometryRecorder {
+        fn on_event(&mut self, _state: &mut State, _entity: Entity, event: &mut Event) -> bool {
+            if let Some(WindowEvent::GeometryChanged(geometry_changed)) =
+                event.message.downcast::<WindowEvent>()
+            {
+                self.received.borrow_mut().push(*geometry_changed);
+            }
+
+            false
+        }
+    }
+
+    fn flush(state: &mut State, event_manager: &mut EventManager) {
+        while !state.event_queue.is_empty() {
+            event_manager.flush_events(state);
+        }
+    }
+
+    #[test]
+    fn only_geometry_listeners_receive_geometry_changed() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let root = state.root;
+
+        let listener_events = Rc::new(RefCell::new(Vec::new()));
+        let other_events = Rc::new(RefCell::new(Vec::new()));
+
+        let listener = GeometryRecorder {
+            received: listener_events.clone(),
+        }
+        .build(&mut state, root, |builder| {
+            builder
+                .set_width(Length::Pixels(100.0))
+                .set_height(Length::Pixels(50.0))
+                .set_geometry_listener(true)
+        });
+
+        let other = GeometryRecorder {
+            received: other_events.clone(),
+        }
+        .build(&mut state, root, |builder| {
+            builder
+                .set_width(Length::Pixels(100.0))
+                .set_height(Length::Pixels(50.0))
+        });
+
+        flush(&mut state, &mut event_manager);
+
+        // The first layout gives the listener its size
+        assert_eq!(listener_events.borrow().len(), 1);
+        listener_events.borrow_mut().clear();
+
+        // Resizing both only notifies the listener, with just the width changed
+        listener.set_width(&mut state, Length::Pixels(200.0));
+        other.set_width(&mut state, Length::Pixels(200.0));
+        flush(&mut state, &mut event_manager);
+
+        assert_eq!(
+            *listener_events.borrow(),
+            vec![GeometryChanged {
+                posx: false,
+                posy: false,
+                width: true,
+                height: false,
+            }]
+        );
+        assert!(other_events.borrow().is_empty());
+    }
+
+    #[test]
+    fn unchanged_listener_receives_no_geometry_changed() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let root = state.root;
+
+        let received = Rc::new(RefCell::new(Vec::new()));
+
+        GeometryRecorder {
+            received: received.clone(),
+        }
+        .build(&mut state, root, |builder| {
+            builder
+                .set_width(Length::Pixels(100.0))
+                .set_height(Length::Pixels(50.0))
+                .set_geometry_listener(true)
+        });
+        let other = state.add(root);
+
+        flush(&mut state, &mut event_manager);
+        received.borrow_mut().clear();
+
+        // A layout which doesn't move or resize the listener sends nothing
+        other.set_height(&mut state, Length::Pixels(60.0));
+        flush(&mut state, &mut event_manager);
+
+        assert!(received.borrow().is_empty());
+    }
+
+    // Records the geometry changes passed to its on_geometry_changed hook
+    struct ResizeRecorder {
+        received: Rc<RefCell<Vec<GeometryChanged>>>,
+    }
+
+    impl BuildHandler for ResizeRecorder {
+        type Ret = Entity;
+        fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+            entity.set_geometry_listener(state, true);
+
+            entity
+        }
+    }
+
+    impl EventHandler for ResizeRecorder {
+        fn on_geometry_changed(
+            &mut self,
+            _state: &mut State,
+            _entity: Entity,
+            geometry_changed: GeometryChanged,
+        ) {
+            self.received.borrow_mut().push(geometry_changed);
+        }
+    }
+
+    #[test]
+    fn window_resize_calls_on_geometry_changed() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let root = state.root;
+
+        let received = Rc::new(RefCell::new(Vec::new()));
+
+        // Half the width of the window
+        ResizeRecorder {
+            received: received.clone(),
+        }
+        .build(&mut state, root, |builder| {
+            builder
+                .set_width(Length::Percentage(0.5))
+                .set_height(Length::Pixels(50.0))
+        });
+
+        flush(&mut state, &mut event_manager);
+
+        // The first layout gives the entity its size
+        assert_eq!(received.borrow().len(), 1);
+        received.borrow_mut().clear();
+
+        // Resizing the window only changes the width of the entity
+        state.insert_event(Event::new(WindowEvent::WindowResize(400.0, 600.0)).target(root));
+        flush(&mut state, &mut event_manager);
+
+        assert_eq!(
+            *received.borrow(),
+            vec![GeometryChanged {
+                posx: false,
+                posy: false,
+                width: true,
+                height: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn descendants_of_hidden_entity_are_not_drawn() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let root = state.root;
+
+        let parent = state
+            .add(root)
+            .set_width(&mut state, Length::Pixels(200.0))
+            .set_height(&mut state, Length::Pixels(200.0));
+        let child = state
+            .add(parent)
+            .set_width(&mut state, Length::Pixels(100.0))
+            .set_height(&mut state, Length::Pixels(100.0));
+        let grandchild = state
+            .add(child)
+            .set_width(&mut state, Length::Pixels(50.0))
+            .set_height(&mut state, Length::Pixels(50.0));
+
+        flush(&mut state, &mut event_manager);
+        let drawn = draw_list(&state);
+        assert!(drawn.contains(&parent) && drawn.contains(&child) && drawn.contains(&grandchild));
+
+        parent.set_visibility(&mut state, Visibility::Invisible);
+        flush(&mut state, &mut event_manager);
+        let drawn = draw_list(&state);
+        assert!(drawn
+            .iter()
+            .all(|entity| ![parent, child, grandchild].contains(entity)));
+
+        // Setting a child to visible doesn't draw it inside a hidden parent
+        child.set_visibility(&mut state, Visibility::Visible);
+        flush(&mut state, &mut event_manager);
+        assert!(!draw_list(&state).contains(&child));
+
+        // Showing the parent again draws its descendants
+        parent.set_visibility(&mut state, Visibility::Visible);
+        flush(&mut state, &mut event_manager);
+        assert!(draw_list(&state).contains(&grandchild));
+    }
+}
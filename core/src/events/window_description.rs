@@ -13,6 +13,7 @@ pub struct WindowDescription {
     pub title: String,
     pub inner_size: Size,
     pub min_inner_size: Size,
+    pub resizable: bool,
     // Change this to resource id when the resource manager is working
     pub icon: Option<Vec<u8>>,
     pub icon_width: u32,
@@ -25,6 +26,7 @@ impl WindowDescription {
             title: "Default".to_string(),
             inner_size: Size::new(800, 600),
             min_inner_size: Size::new(100, 100),
+            resizable: true,
             icon: None,
             icon_width: 0,
             icon_height: 0,
@@ -49,6 +51,12 @@ impl WindowDescription {
         self
     }
 
+    pub fn with_resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+
+        self
+    }
+
     pub fn with_icon(mut self, icon: Vec<u8>, width: u32, height: u32) -> Self {
         self.icon = Some(icon);
         self.icon_width = width;
@@ -1,14 +1,47 @@
+use crate::entity::Entity;
 use crate::state::mouse::MouseButton;
 
 use keyboard_types::{Code, Key};
 
+// The mouse cursor shown by the window, set per entity with PropSet::set_cursor
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum CursorIcon {
     Arrow,
     NResize,
     EResize,
+    SResize,
+    WResize,
+    EwResize,
+    NsResize,
+    Text,
+    Hand,
+    Move,
+    Crosshair,
+    NotAllowed,
+    Wait,
 }
 
+impl Default for CursorIcon {
+    fn default() -> Self {
+        CursorIcon::Arrow
+    }
+}
+
+// The parts of an entity's bounds which changed in a layout pass
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct GeometryChanged {
+    pub posx: bool,
+    pub posy: bool,
+    pub width: bool,
+    pub height: bool,
+}
+
+// Mouse events produced by each button:
+// Left - MouseDown, MouseUp and MouseDoubleClick
+// Middle - MouseDown, MouseUp and MouseDoubleClick
+// Right - MouseDown, MouseUp and MouseDoubleClick, plus ContextMenu when released over a hovered entity
+// Other - MouseDown and MouseUp
+// MouseDown and MouseUp are sent to the captured entity if there is one, otherwise to the hovered entity
 #[derive(Debug, Clone, PartialEq)]
 pub enum WindowEvent {
     Test,
@@ -16,17 +49,42 @@ pub enum WindowEvent {
     WindowResize(f32, f32),
     MouseDown(MouseButton),
     MouseUp(MouseButton),
+    MouseDoubleClick(MouseButton),
+    // Sent to the hovered entity with the cursor position when the right button is released
+    ContextMenu(Entity, f32, f32),
     MouseMove(f32, f32),
     MouseScroll(f32, f32),
-    MouseOver,
-    MouseOut,
+    // Sent to the newly hovered entity when the hovered entity changes
+    MouseOver(Entity),
+    // Sent to the previously hovered entity when the hovered entity changes
+    MouseOut(Entity),
     CharInput(char),
-    KeyDown(Code, Option<Key>),
+    // Sent to the focused entity while an input method is composing text, with the character index
+    // of the caret within the composed text, or None to hide the caret. An empty string ends the composition
+    ImePreedit(String, Option<usize>),
+    // Sent to the focused entity with the text produced by an input method when composition is finished
+    // The glutin backend doesn't send either of these yet, as its version of winit doesn't report composition
+    ImeCommit(String),
+    // The bool is true when the press is repeated by the OS while the key is held down
+    KeyDown(Code, Option<Key>, bool),
     KeyUp(Code, Option<Key>),
     SetCursor(CursorIcon),
+    // Sent to the window to change the properties of the window created by the backend
+    SetTitle(String),
+    SetInnerSize(u32, u32),
+    SetMinSize(u32, u32),
+    SetResizable(bool),
     MouseCaptureEvent,
     MouseCaptureOutEvent,
+    // Repaints without recomputing layout, for changes to appearance only such as colors
+    // State::request_redraw does the same without going through the event queue
     Redraw,
+    // Reapplies the style rules to the origin's branch, or the whole tree if the origin is null
     Restyle,
+    // Recomputes the position and size of entities and repaints, for changes which affect geometry
     Relayout,
+    // Sent after a layout pass to the entities listening for changes to their bounds, see PropSet::set_geometry_listener
+    GeometryChanged(GeometryChanged),
+    // Sent to the root and every entity below it after a layout pass, once the final bounds are known
+    LayoutComplete,
 }
@@ -0,0 +1,101 @@
+use crate::state::mouse::ModifiersState;
+
+use keyboard_types::Key;
+
+// A key and modifier combination which triggers an event regardless of the focused entity
+#[derive(Debug, Clone, PartialEq)]
+pub struct Accelerator {
+    pub key: Key,
+    pub modifiers: ModifiersState,
+}
+
+impl Accelerator {
+    pub fn new(key: Key) -> Self {
+        Accelerator {
+            key,
+            modifiers: ModifiersState::default(),
+        }
+    }
+
+    pub fn with_ctrl(mut self) -> Self {
+        self.modifiers.ctrl = true;
+
+        self
+    }
+
+    pub fn with_shift(mut self) -> Self {
+        self.modifiers.shift = true;
+
+        self
+    }
+
+    pub fn with_alt(mut self) -> Self {
+        self.modifiers.alt = true;
+
+        self
+    }
+
+    pub fn with_logo(mut self) -> Self {
+        self.modifiers.logo = true;
+
+        self
+    }
+
+    // Uses the modifier for shortcuts on the platform, command on macOS and ctrl on other platforms
+    pub fn with_ctrl_or_cmd(self) -> Self {
+        if cfg!(target_os = "macos") {
+            self.with_logo()
+        } else {
+            self.with_ctrl()
+        }
+    }
+
+    // Returns true if the pressed key and modifiers match the accelerator
+    // Characters are compared ignoring case because shift changes the character produced by a key
+    pub fn matches(&self, key: &Key, modifiers: &ModifiersState) -> bool {
+        if self.modifiers != *modifiers {
+            return false;
+        }
+
+        match (&self.key, key) {
+            (Key::Character(a), Key::Character(b)) => a.to_lowercase() == b.to_lowercase(),
+            (a, b) => a == b,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctrl() -> ModifiersState {
+        ModifiersState {
+            ctrl: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn matches_characters_ignoring_case() {
+        let accelerator = Accelerator::new(Key::Character("s".to_string())).with_ctrl();
+
+        assert!(accelerator.matches(&Key::Character("s".to_string()), &ctrl()));
+        assert!(accelerator.matches(&Key::Character("S".to_string()), &ctrl()));
+        assert!(!accelerator.matches(&Key::Character("a".to_string()), &ctrl()));
+    }
+
+    #[test]
+    fn modifiers_must_match_exactly() {
+        let accelerator = Accelerator::new(Key::Character("s".to_string())).with_ctrl();
+        let key = Key::Character("s".to_string());
+
+        assert!(!accelerator.matches(&key, &ModifiersState::default()));
+        assert!(!accelerator.matches(
+            &key,
+            &ModifiersState {
+                shift: true,
+                ..ctrl()
+            }
+        ));
+    }
+}
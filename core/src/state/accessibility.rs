@@ -0,0 +1,104 @@
+// Groundwork for screen reader support
+// Entities are given a role and optionally a label, and the state can produce a flat list of the accessible
+// entities for an accessibility integration to pass on to the platform
+
+use crate::{Entity, Rect};
+
+// What an entity is to assistive technology, set per entity with PropSet::set_role
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Role {
+    // Not exposed to assistive technology
+    None,
+    Window,
+    Group,
+    Button,
+    Label,
+    Checkbox,
+    Switch,
+    RadioButton,
+    Slider,
+    Textbox,
+    Menu,
+    MenuItem,
+    List,
+    ListItem,
+    Tab,
+    Tooltip,
+    Image,
+}
+
+impl Default for Role {
+    fn default() -> Self {
+        Role::None
+    }
+}
+
+// An accessible entity returned by State::accessibility_tree
+#[derive(Debug, Clone)]
+pub struct AccessNode {
+    pub entity: Entity,
+    pub role: Role,
+    // The accessible label of the entity, or its text if it has no label
+    pub label: String,
+    pub bounds: Rect,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        BuildHandler, Button, Event, EventManager, Length, State, VBox, Visibility, WindowEvent,
+    };
+
+    #[test]
+    fn labelled_widgets_are_in_the_tree() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let root = state.root;
+
+        let form = VBox::new().build(&mut state, root, |builder| {
+            builder.set_width(Length::Pixels(200.0))
+        });
+
+        // Widgets have a role and use their text as their label
+        let save = Button::with_label("Save").build(&mut state, form, |builder| {
+            builder
+                .set_width(Length::Pixels(100.0))
+                .set_height(Length::Pixels(30.0))
+        });
+
+        // A label describes widgets without text
+        let close = Button::new().build(&mut state, form, |builder| {
+            builder
+                .set_width(Length::Pixels(30.0))
+                .set_height(Length::Pixels(30.0))
+                .set_accessible_label("Close")
+        });
+
+        // Hidden widgets are left out
+        let hidden = Button::with_label("Hidden").build(&mut state, form, |builder| {
+            builder.set_visibility(Visibility::Invisible)
+        });
+
+        state.insert_event(Event::new(WindowEvent::Restyle).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        while !state.event_queue.is_empty() {
+            event_manager.flush_events(&mut state);
+        }
+
+        let tree = state.accessibility_tree();
+
+        let node = tree.iter().find(|node| node.entity == save).unwrap();
+        assert_eq!(node.role, Role::Button);
+        assert_eq!(node.label, "Save");
+        assert_eq!((node.bounds.w, node.bounds.h), (100.0, 30.0));
+
+        let node = tree.iter().find(|node| node.entity == close).unwrap();
+        assert_eq!(node.label, "Close");
+
+        assert!(tree.iter().all(|node| node.entity != hidden));
+
+        // Containers have no role unless given one
+        assert!(tree.iter().all(|node| node.entity != form));
+    }
+}
@@ -1,9 +1,10 @@
 #![allow(warnings)]
 
 use crate::entity::Entity;
+use crate::state::storage::animatable_storage::AnimatableStorage;
 use crate::state::storage::dense_storage::DenseStorage;
 pub use crate::state::style::*;
-use crate::{PropSet, State};
+use crate::{Event, PropSet, State, WindowEvent};
 use std::time::{Duration, Instant};
 
 use crate::state::style::Color;
@@ -101,6 +102,56 @@ impl Transition {
     }
 }
 
+// Maps the linear progress of an animation to the progress of the interpolation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Default for Easing {
+    fn default() -> Self {
+        Easing::Linear
+    }
+}
+
+impl Easing {
+    // Applies the (cubic) easing curve to t between 0.0 and 1.0
+    pub fn apply(&self, t: f32) -> f32 {
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+        }
+    }
+}
+
+// What an animation does when it reaches the end
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LoopMode {
+    // Plays once and then finishes
+    Once,
+    // Restarts from the beginning
+    Loop,
+    // Plays backwards to the beginning and then forwards again
+    PingPong,
+}
+
+impl Default for LoopMode {
+    fn default() -> Self {
+        LoopMode::Once
+    }
+}
+
 pub trait Interpolator {
     fn interpolate(start: &Self, end: &Self, t: f32) -> Self;
 }
@@ -117,6 +168,10 @@ pub struct AnimationState<Prop: Interpolator> {
     pub delay: f32,
     // Animation keyframes (time, value)
     pub keyframes: Vec<(f32, Prop)>,
+    // The easing applied to the progress of the animation
+    pub easing: Easing,
+    // What the animation does when it reaches the end
+    pub loop_mode: LoopMode,
     // The output of the animation
     pub output: Option<Prop>,
     // A flag used to check if the animation is finished
@@ -147,6 +202,8 @@ where
             duration: Duration::new(0, 0),
             delay: 0.0,
             keyframes: Vec::new(),
+            easing: Easing::default(),
+            loop_mode: LoopMode::default(),
             output: None,
             persistent: false,
             t0: 0.0,
@@ -170,6 +227,18 @@ where
         self
     }
 
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+
+        self
+    }
+
+    pub fn with_loop_mode(mut self, loop_mode: LoopMode) -> Self {
+        self.loop_mode = loop_mode;
+
+        self
+    }
+
     pub fn with_keyframe(mut self, key: (f32, Prop)) -> Self {
         self.keyframes.push(key);
 
@@ -201,6 +270,11 @@ where
         self
     }
 
+    // Looping animations never finish
+    pub fn is_finished(&self) -> bool {
+        self.loop_mode == LoopMode::Once && self.t0 == 1.0
+    }
+
     pub fn get_output(&self) -> Option<&Prop> {
         self.output.as_ref()
     }
@@ -217,6 +291,8 @@ where
             duration: Duration::new(0, 0),
             delay: 0.0,
             keyframes: Vec::new(),
+            easing: Easing::default(),
+            loop_mode: LoopMode::default(),
             output: None,
             persistent: true,
             t0: 0.0,
@@ -229,6 +305,207 @@ where
     }
 }
 
+// Extension trait for playing animations on the style properties of an entity
+pub trait AnimExt: Sized {
+    // Animates a property from one value to another over the duration
+    // The property is selected from the style, e.g. |style| &mut style.width
+    // The property keeps the final value once the animation has finished
+    fn animate<T, F>(
+        self,
+        state: &mut State,
+        property: F,
+        from: T,
+        to: T,
+        duration: Duration,
+    ) -> Self
+    where
+        T: Default + Clone + Interpolator + std::fmt::Debug + PartialEq + 'static,
+        F: FnOnce(&mut Style) -> &mut AnimatableStorage<T>;
+
+    fn animate_background_color(
+        self,
+        state: &mut State,
+        from: Color,
+        to: Color,
+        duration: Duration,
+    ) -> Self {
+        self.animate(
+            state,
+            |style| &mut style.background_color,
+            from,
+            to,
+            duration,
+        )
+    }
+
+    fn animate_opacity(self, state: &mut State, from: f32, to: f32, duration: Duration) -> Self {
+        self.animate(
+            state,
+            |style| &mut style.opacity,
+            Opacity(from),
+            Opacity(to),
+            duration,
+        )
+    }
+
+    fn animate_width(
+        self,
+        state: &mut State,
+        from: Length,
+        to: Length,
+        duration: Duration,
+    ) -> Self {
+        self.animate(state, |style| &mut style.width, from, to, duration)
+    }
+
+    fn animate_height(
+        self,
+        state: &mut State,
+        from: Length,
+        to: Length,
+        duration: Duration,
+    ) -> Self {
+        self.animate(state, |style| &mut style.height, from, to, duration)
+    }
+
+    // Animates the height of an entity to zero and then stops displaying it
+    // The content is clipped while the height is animated
+    fn collapse(self, state: &mut State, duration: Duration) -> Self;
+
+    // Displays a collapsed entity again, animating its height back to what it was before collapsing
+    fn expand(self, state: &mut State, duration: Duration) -> Self;
+}
+
+impl AnimExt for Entity {
+    fn animate<T, F>(
+        self,
+        state: &mut State,
+        property: F,
+        from: T,
+        to: T,
+        duration: Duration,
+    ) -> Self
+    where
+        T: Default + Clone + Interpolator + std::fmt::Debug + PartialEq + 'static,
+        F: FnOnce(&mut Style) -> &mut AnimatableStorage<T>,
+    {
+        let animation = AnimationState::new()
+            .with_duration(duration)
+            .with_keyframe((0.0, from))
+            .with_keyframe((1.0, to.clone()));
+
+        let storage = property(&mut state.style);
+        // The inline value is used once the animation is removed
+        storage.insert(self, to);
+        storage.play(self, animation);
+
+        // Animations are driven by the application loop, which keeps redrawing while any are active
+        state.insert_event(Event::new(WindowEvent::Redraw));
+
+        self
+    }
+
+    fn collapse(self, state: &mut State, duration: Duration) -> Self {
+        if state
+            .collapses
+            .get(&self)
+            .map_or(false, |collapse| collapse.collapsed)
+        {
+            return self;
+        }
+
+        let height = state.transform.get_height(self);
+
+        // The styles to restore are only recorded when first collapsing, not when reversing an expand
+        if !state.collapses.contains_key(&self) {
+            let collapse = Collapse {
+                expanded_height: height,
+                height: state.style.height.get_inline(self).cloned(),
+                display: state.style.display.get_inline(self).cloned(),
+                overflow: state.style.overflow.get_inline(self).cloned(),
+                collapsed: false,
+                end: None,
+            };
+            state.collapses.insert(self, collapse);
+        }
+
+        if let Some(collapse) = state.collapses.get_mut(&self) {
+            collapse.collapsed = true;
+            collapse.end = Some(Instant::now() + duration);
+        }
+
+        self.set_overflow(state, Overflow::Hidden).animate_height(
+            state,
+            Length::Pixels(height),
+            Length::Pixels(0.0),
+            duration,
+        )
+    }
+
+    fn expand(self, state: &mut State, duration: Duration) -> Self {
+        let (expanded_height, display) = match state.collapses.get_mut(&self) {
+            Some(collapse) if collapse.collapsed => {
+                collapse.collapsed = false;
+                collapse.end = Some(Instant::now() + duration);
+                (collapse.expanded_height, collapse.display)
+            }
+
+            _ => return self,
+        };
+
+        // Starts from the current height when reversing part way through a collapse
+        let height = if state.style.display.get(self) == Some(&Display::None) {
+            0.0
+        } else {
+            state.transform.get_height(self)
+        };
+
+        match display {
+            Some(display) => {
+                self.set_display(state, display);
+            }
+
+            None => {
+                state.style.display.remove_inline(self);
+                restyle_inline_removed(state, self);
+            }
+        }
+
+        self.animate_height(
+            state,
+            Length::Pixels(height),
+            Length::Pixels(expanded_height),
+            duration,
+        )
+    }
+}
+
+// Queues the restyle, relayout and redraw of an entity whose inline styles were removed, so its rules apply again
+pub(crate) fn restyle_inline_removed(state: &mut State, entity: Entity) {
+    state.insert_event(Event::new(WindowEvent::Restyle).origin(entity));
+    state.insert_event(
+        Event::new(WindowEvent::Relayout)
+            .target(Entity::null())
+            .origin(entity),
+    );
+    state.insert_event(Event::new(WindowEvent::Redraw));
+}
+
+// An entity being collapsed or expanded with AnimExt::collapse and AnimExt::expand
+// Kept until the entity has been expanded again so its styles can be restored
+#[derive(Clone, Debug)]
+pub struct Collapse {
+    // The height of the entity before it was collapsed
+    pub expanded_height: f32,
+    // The inline styles of the entity before it was collapsed, None if it had none
+    pub height: Option<Length>,
+    pub display: Option<Display>,
+    pub overflow: Option<Overflow>,
+    pub collapsed: bool,
+    // When the running collapse or expand animation finishes
+    pub end: Option<Instant>,
+}
+
 impl Interpolator for Color {
     fn interpolate(start: &Self, end: &Self, t: f32) -> Self {
         Color::interpolate(start.clone(), end.clone(), t as f64)
@@ -246,3 +523,19 @@ impl Interpolator for i32 {
         return ((start + (end - start)) as f32 * t).round() as i32;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ease_in_out_starts_and_ends_slowly() {
+        assert_eq!(Easing::EaseInOut.apply(0.0), 0.0);
+        assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+        assert_eq!(Easing::EaseInOut.apply(1.0), 1.0);
+
+        // Slower than linear in the first quarter and faster in the second
+        assert!(Easing::EaseInOut.apply(0.25) < 0.25);
+        assert!(Easing::EaseInOut.apply(0.75) > 0.75);
+    }
+}
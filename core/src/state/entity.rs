@@ -7,6 +7,7 @@ use std::hash::{Hash, Hasher};
 // is stored and loaded using the entities.
 
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Entity {
     id: u32,
 }
@@ -112,6 +113,10 @@ impl EntityManager {
     }
 
     pub fn is_alive(&self, entity: Entity) -> bool {
-        return self.generations[entity.index()] as usize == entity.generation();
+        self.generations
+            .get(entity.index())
+            .map_or(false, |generation| {
+                *generation as usize == entity.generation()
+            })
     }
 }
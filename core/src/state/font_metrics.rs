@@ -0,0 +1,76 @@
+use fnv::FnvHashMap;
+
+// The default fonts, also loaded by the renderer under the same names
+const REGULAR_FONT: &[u8] = include_bytes!("../../../resources/Roboto-Regular.ttf");
+const BOLD_FONT: &[u8] = include_bytes!("../../../resources/Roboto-Bold.ttf");
+const ICON_FONT: &[u8] = include_bytes!("../../../resources/entypo.ttf");
+
+// The metrics of a font read from its file, used to measure text during layout without a renderer
+pub struct FontMetrics {
+    data: Vec<u8>,
+    units_per_em: f32,
+    // Distance from the descender to the ascender in font units
+    height: f32,
+    // Advance widths in font units, read from the font the first time a character is measured
+    advances: FnvHashMap<char, f32>,
+}
+
+impl FontMetrics {
+    pub fn new(data: Vec<u8>) -> Result<Self, ttf_parser::FaceParsingError> {
+        let face = ttf_parser::Face::from_slice(&data, 0)?;
+        let units_per_em = face.units_per_em().unwrap_or(1000) as f32;
+        let height = face.height() as f32;
+
+        Ok(FontMetrics {
+            data,
+            units_per_em,
+            height,
+            advances: FnvHashMap::default(),
+        })
+    }
+
+    // Returns the width of a single line of text and the height of the font at the given size
+    // Kerning and shaping are ignored, so the width can differ slightly from the drawn text
+    pub fn measure(&mut self, text: &str, font_size: f32) -> (f32, f32) {
+        let scale = font_size / self.units_per_em;
+
+        let mut face = None;
+        let mut width = 0.0;
+        for c in text.chars() {
+            let data = &self.data;
+            let advance = *self.advances.entry(c).or_insert_with(|| {
+                // Only parsed when a character isn't cached yet
+                let face = face.get_or_insert_with(|| ttf_parser::Face::from_slice(data, 0).ok());
+                face.as_ref()
+                    .and_then(|face| {
+                        face.glyph_index(c)
+                            .and_then(|glyph| face.glyph_hor_advance(glyph))
+                    })
+                    .unwrap_or(0) as f32
+            });
+
+            width += advance;
+        }
+
+        (width * scale, self.height * scale)
+    }
+}
+
+// Returns the metrics of the default fonts, keyed by the names the renderer loads them under
+pub fn default_font_metrics() -> FnvHashMap<String, FontMetrics> {
+    let mut font_metrics = FnvHashMap::default();
+
+    for (name, data) in [
+        ("sans", REGULAR_FONT),
+        ("bold", BOLD_FONT),
+        ("icons", ICON_FONT),
+    ]
+    .iter()
+    {
+        if let Ok(metrics) = FontMetrics::new(data.to_vec()) {
+            font_metrics.insert(name.to_string(), metrics);
+        }
+    }
+
+    font_metrics
+}
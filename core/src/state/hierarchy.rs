@@ -1,5 +1,29 @@
 use crate::entity::Entity;
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HierarchyError {
+    // The entity is not in the hierarchy
+    InvalidEntity,
+    // The new parent is not in the hierarchy
+    InvalidParent,
+    // The new parent is the entity itself or one of its descendants
+    WouldCycle,
+}
+
+impl std::fmt::Display for HierarchyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            HierarchyError::InvalidEntity => write!(f, "entity is not in the hierarchy"),
+            HierarchyError::InvalidParent => write!(f, "parent is not in the hierarchy"),
+            HierarchyError::WouldCycle => {
+                write!(f, "parent is the entity or one of its descendants")
+            }
+        }
+    }
+}
+
+impl std::error::Error for HierarchyError {}
+
 #[derive(Clone)]
 pub struct Hierarchy {
     pub entities: Vec<Entity>,
@@ -74,6 +98,18 @@ impl Hierarchy {
         return self.parent[entity.index()];
     }
 
+    // Returns the number of ancestors of an entity, so the root has a depth of 0
+    pub fn get_depth(&self, entity: Entity) -> usize {
+        let mut depth = 0;
+        let mut parent = self.get_parent(entity);
+        while let Some(p) = parent {
+            depth += 1;
+            parent = self.get_parent(p);
+        }
+
+        depth
+    }
+
     pub fn get_first_child(&self, entity: Entity) -> Option<Entity> {
         return self.first_child[entity.index()];
     }
@@ -100,6 +136,10 @@ impl Hierarchy {
         false
     }
 
+    pub fn is_last_child(&self, entity: Entity) -> bool {
+        self.get_parent(entity).is_some() && self.get_next_sibling(entity).is_none()
+    }
+
     // Checks if entity1 is sibling of entity2
     pub fn is_sibling(&self, entity1: Entity, entity2: Entity) -> bool {
         if let Some(parent1) = self.get_parent(entity1) {
@@ -111,14 +151,29 @@ impl Hierarchy {
         false
     }
 
+    // Returns the position of an entity among its siblings, or None for an entity without a parent
+    pub fn sibling_index(&self, entity: Entity) -> Option<usize> {
+        let parent = self.get_parent(entity)?;
+        parent.child_iter(self).position(|child| child == entity)
+    }
+
+    // Returns the number of children of an entity's parent, including the entity itself
+    pub fn num_siblings(&self, entity: Entity) -> usize {
+        match self.get_parent(entity) {
+            Some(parent) => parent.child_iter(self).count(),
+            None => 0,
+        }
+    }
+
     pub fn remove_children(&mut self, _entity: Entity) {}
 
     pub fn has_children(&self, entity: Entity) -> bool {
         self.first_child[entity.index()].is_some()
     }
 
+    // Removes an entity and all of its descendants from the hierarchy
     pub fn remove(&mut self, entity: Entity) {
-        if let Some(child) = self.get_first_child(entity) {
+        while let Some(child) = self.get_first_child(entity) {
             self.remove(child);
         }
 
@@ -137,13 +192,62 @@ impl Hierarchy {
         }
 
         self.parent[entity.index()] = None;
+        self.first_child[entity.index()] = None;
+        self.next_sibling[entity.index()] = None;
+        self.prev_sibling[entity.index()] = None;
+
+        self.entities.retain(|e| *e != entity);
     }
 
-    pub fn set_parent(&mut self, entity: Entity, parent: Entity) {
-        if let Some(old_parent) = self.get_parent(entity) {
-            if self.is_first_child(entity) {
-                self.first_child[old_parent.index()] = self.get_next_sibling(entity);
-            }
+    // Makes an entity the first child of its parent, does nothing if it already is
+    pub fn move_to_front(&mut self, entity: Entity) {
+        let parent = match self.get_parent(entity) {
+            Some(parent) => parent,
+            None => return,
+        };
+
+        if self.is_first_child(entity) {
+            return;
+        }
+
+        self.unlink(entity, parent);
+
+        let first_child = self.first_child[parent.index()];
+        if let Some(first_child) = first_child {
+            self.prev_sibling[first_child.index()] = Some(entity);
+        }
+
+        self.next_sibling[entity.index()] = first_child;
+        self.first_child[parent.index()] = Some(entity);
+    }
+
+    // Makes an entity the last child of its parent, does nothing if it already is
+    pub fn move_to_back(&mut self, entity: Entity) {
+        let parent = match self.get_parent(entity) {
+            Some(parent) => parent,
+            None => return,
+        };
+
+        if self.get_next_sibling(entity).is_none() {
+            return;
+        }
+
+        self.unlink(entity, parent);
+
+        let last_child = self.get_last_child(parent);
+        if let Some(last_child) = last_child {
+            self.next_sibling[last_child.index()] = Some(entity);
+        } else {
+            self.first_child[parent.index()] = Some(entity);
+        }
+
+        self.prev_sibling[entity.index()] = last_child;
+    }
+
+    // Removes an entity from the list of its siblings without changing its parent or children
+    fn unlink(&mut self, entity: Entity, parent: Entity) {
+        if self.is_first_child(entity) {
+            self.first_child[parent.index()] = self.get_next_sibling(entity);
         }
 
         if let Some(prev_sibling) = self.get_prev_sibling(entity) {
@@ -154,6 +258,32 @@ impl Hierarchy {
             self.prev_sibling[next_sibling.index()] = self.get_prev_sibling(entity);
         }
 
+        self.next_sibling[entity.index()] = None;
+        self.prev_sibling[entity.index()] = None;
+    }
+
+    // Moves an entity, along with its descendants, to be the last child of a new parent
+    pub fn set_parent(&mut self, entity: Entity, parent: Entity) -> Result<(), HierarchyError> {
+        if entity.index() >= self.parent.len() || !self.entities.contains(&entity) {
+            return Err(HierarchyError::InvalidEntity);
+        }
+
+        if parent.index() >= self.parent.len() || !self.entities.contains(&parent) {
+            return Err(HierarchyError::InvalidParent);
+        }
+
+        // The new parent can't be the entity itself or one of its descendants
+        if entity
+            .branch_iter(self)
+            .any(|descendant| descendant == parent)
+        {
+            return Err(HierarchyError::WouldCycle);
+        }
+
+        if let Some(old_parent) = self.get_parent(entity) {
+            self.unlink(entity, old_parent);
+        }
+
         if self.first_child[parent.index()] == None {
             self.first_child[parent.index()] = Some(entity);
         } else {
@@ -172,6 +302,8 @@ impl Hierarchy {
         }
 
         self.parent[entity.index()] = Some(parent);
+
+        Ok(())
     }
 
     pub fn add(&mut self, entity: Entity, parent: Option<Entity>) {
@@ -282,6 +414,64 @@ impl<'a> Iterator for HierarchyIterator<'a> {
     }
 }
 
+// Iterator for iterating through an entity and its descendants from top to bottom
+pub struct BranchIterator<'a> {
+    hierarchy: &'a Hierarchy,
+    start_node: Entity,
+    current_node: Option<Entity>,
+}
+
+impl<'a> Iterator for BranchIterator<'a> {
+    type Item = Entity;
+    fn next(&mut self) -> Option<Entity> {
+        let r = self.current_node;
+
+        if let Some(current) = self.current_node {
+            if let Some(child) = self.hierarchy.first_child[current.index()] {
+                self.current_node = Some(child);
+            } else {
+                let mut temp = Some(current);
+                while let Some(entity) = temp {
+                    // Stop at the end of the branch instead of moving on to the siblings of the start node
+                    if entity == self.start_node {
+                        break;
+                    }
+
+                    if let Some(sibling) = self.hierarchy.next_sibling[entity.index()] {
+                        self.current_node = Some(sibling);
+                        return r;
+                    } else {
+                        temp = self.hierarchy.parent[entity.index()];
+                    }
+                }
+
+                self.current_node = None;
+            }
+        }
+
+        return r;
+    }
+}
+
+pub trait IntoBranchIterator<'a> {
+    type Item;
+    type IntoIter: Iterator<Item = Self::Item>;
+    fn branch_iter(self, hierarchy: &'a Hierarchy) -> Self::IntoIter;
+}
+
+impl<'a> IntoBranchIterator<'a> for &'a Entity {
+    type Item = Entity;
+    type IntoIter = BranchIterator<'a>;
+
+    fn branch_iter(self, h: &'a Hierarchy) -> Self::IntoIter {
+        BranchIterator {
+            hierarchy: h,
+            start_node: *self,
+            current_node: Some(*self),
+        }
+    }
+}
+
 // Iterator for iterating through the parents of widgets.
 pub struct ParentIterator<'a> {
     hierarchy: &'a Hierarchy,
@@ -350,8 +540,16 @@ impl<'a> DoubleEndedIterator for ChildIterator<'a> {
 
 pub trait IntoChildIterator<'a> {
     type Item;
-    type IntoIter: Iterator<Item = Self::Item>;
+    type IntoIter: DoubleEndedIterator<Item = Self::Item>;
     fn child_iter(self, hierarchy: &'a Hierarchy) -> Self::IntoIter;
+
+    // Iterates the children from last to first, which is top to bottom for siblings with the same z-order
+    fn child_iter_rev(self, hierarchy: &'a Hierarchy) -> std::iter::Rev<Self::IntoIter>
+    where
+        Self: Sized,
+    {
+        self.child_iter(hierarchy).rev()
+    }
 }
 
 impl<'a> IntoChildIterator<'a> for &'a Entity {
@@ -447,3 +645,166 @@ impl<'a> HierarchyTree<'a> for Entity {
     // }
     //}
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A root with a single parent which has the given number of children
+    fn parent_with_children(num_children: u16) -> (Hierarchy, Entity, Vec<Entity>) {
+        let mut hierarchy = Hierarchy::new();
+
+        let root = Entity::new(0, 0);
+        let parent = Entity::new(1, 0);
+        hierarchy.add(root, None);
+        hierarchy.add(parent, Some(root));
+
+        let children: Vec<Entity> = (0..num_children)
+            .map(|index| {
+                let child = Entity::new(index + 2, 0);
+                hierarchy.add(child, Some(parent));
+                child
+            })
+            .collect();
+
+        (hierarchy, parent, children)
+    }
+
+    fn children(hierarchy: &Hierarchy, parent: Entity) -> Vec<Entity> {
+        parent.child_iter(hierarchy).collect()
+    }
+
+    #[test]
+    fn move_to_front_and_back_reorder_siblings() {
+        let (mut hierarchy, parent, c) = parent_with_children(3);
+
+        hierarchy.move_to_front(c[2]);
+        assert_eq!(children(&hierarchy, parent), vec![c[2], c[0], c[1]]);
+
+        hierarchy.move_to_back(c[2]);
+        assert_eq!(children(&hierarchy, parent), vec![c[0], c[1], c[2]]);
+
+        hierarchy.move_to_back(c[0]);
+        assert_eq!(children(&hierarchy, parent), vec![c[1], c[2], c[0]]);
+
+        hierarchy.move_to_front(c[0]);
+        assert_eq!(children(&hierarchy, parent), vec![c[0], c[1], c[2]]);
+    }
+
+    #[test]
+    fn middle_child_moves_both_ways_keeping_links_consistent() {
+        let (mut hierarchy, parent, c) = parent_with_children(3);
+
+        hierarchy.move_to_front(c[1]);
+        assert_eq!(children(&hierarchy, parent), vec![c[1], c[0], c[2]]);
+
+        hierarchy.move_to_back(c[1]);
+        assert_eq!(children(&hierarchy, parent), vec![c[0], c[2], c[1]]);
+
+        let reversed: Vec<Entity> = parent.child_iter(&hierarchy).rev().collect();
+        assert_eq!(reversed, vec![c[1], c[2], c[0]]);
+        assert_eq!(hierarchy.get_prev_sibling(c[0]), None);
+        assert_eq!(hierarchy.get_next_sibling(c[1]), None);
+    }
+
+    #[test]
+    fn moving_child_already_in_position_does_nothing() {
+        let (mut hierarchy, parent, c) = parent_with_children(3);
+
+        hierarchy.move_to_front(c[0]);
+        hierarchy.move_to_back(c[2]);
+
+        assert_eq!(children(&hierarchy, parent), vec![c[0], c[1], c[2]]);
+        assert_eq!(hierarchy.get_prev_sibling(c[0]), None);
+        assert_eq!(hierarchy.get_next_sibling(c[2]), None);
+    }
+
+    #[test]
+    fn set_parent_refuses_to_create_a_cycle() {
+        let (mut hierarchy, parent, c) = parent_with_children(1);
+        let child = c[0];
+        let grandchild = Entity::new(3, 0);
+        hierarchy.add(grandchild, Some(child));
+
+        assert_eq!(
+            hierarchy.set_parent(parent, child),
+            Err(HierarchyError::WouldCycle)
+        );
+        assert_eq!(
+            hierarchy.set_parent(parent, grandchild),
+            Err(HierarchyError::WouldCycle)
+        );
+        assert_eq!(
+            hierarchy.set_parent(parent, parent),
+            Err(HierarchyError::WouldCycle)
+        );
+
+        // The failed moves leave the hierarchy unchanged
+        assert_eq!(hierarchy.get_parent(child), Some(parent));
+        assert_eq!(hierarchy.get_parent(parent), Some(Entity::new(0, 0)));
+    }
+
+    #[test]
+    fn set_parent_checks_the_new_parent_exists() {
+        let (mut hierarchy, _, c) = parent_with_children(1);
+
+        assert_eq!(
+            hierarchy.set_parent(c[0], Entity::new(1000, 0)),
+            Err(HierarchyError::InvalidParent)
+        );
+    }
+
+    #[test]
+    fn set_parent_moves_the_branch() {
+        let (mut hierarchy, parent, c) = parent_with_children(1);
+        let child = c[0];
+        let grandchild = Entity::new(3, 0);
+        let other = Entity::new(4, 0);
+        hierarchy.add(grandchild, Some(child));
+        hierarchy.add(other, Some(Entity::new(0, 0)));
+
+        assert_eq!(hierarchy.set_parent(child, other), Ok(()));
+        assert_eq!(hierarchy.get_parent(child), Some(other));
+        assert_eq!(hierarchy.get_parent(grandchild), Some(child));
+        assert_eq!(children(&hierarchy, parent), vec![]);
+        assert_eq!(children(&hierarchy, other), vec![child]);
+    }
+
+    #[test]
+    fn child_iter_rev_iterates_last_to_first() {
+        let (hierarchy, parent, c) = parent_with_children(3);
+
+        let reversed: Vec<Entity> = parent.child_iter_rev(&hierarchy).collect();
+        assert_eq!(reversed, vec![c[2], c[1], c[0]]);
+        assert_eq!(
+            reversed,
+            parent.child_iter(&hierarchy).rev().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn sibling_index_counts_from_the_first_child() {
+        let (mut hierarchy, _, c) = parent_with_children(4);
+
+        for (index, child) in c.iter().enumerate() {
+            assert_eq!(hierarchy.sibling_index(*child), Some(index));
+            assert_eq!(hierarchy.num_siblings(*child), 4);
+        }
+
+        // The root has no parent and so no siblings
+        assert_eq!(hierarchy.sibling_index(Entity::new(0, 0)), None);
+        assert_eq!(hierarchy.num_siblings(Entity::new(0, 0)), 0);
+
+        // Removing a child shifts the ones after it
+        hierarchy.remove(c[1]);
+        assert_eq!(hierarchy.sibling_index(c[0]), Some(0));
+        assert_eq!(hierarchy.sibling_index(c[2]), Some(1));
+        assert_eq!(hierarchy.sibling_index(c[3]), Some(2));
+        assert_eq!(hierarchy.num_siblings(c[3]), 3);
+
+        // Reordering changes the index
+        hierarchy.move_to_front(c[3]);
+        assert_eq!(hierarchy.sibling_index(c[3]), Some(0));
+        assert_eq!(hierarchy.sibling_index(c[2]), Some(2));
+    }
+}
@@ -0,0 +1,29 @@
+// Names of the glyphs in the built in icons font (entypo), registered with every state
+// More names, including for glyphs in other fonts, can be added with State::register_icon
+pub const DEFAULT_ICONS: &[(&str, char)] = &[
+    ("check", '\u{2713}'),
+    ("close", '\u{2715}'),
+    ("close-circled", '\u{2716}'),
+    ("plus", '\u{2b}'),
+    ("minus", '\u{2d}'),
+    ("menu", '\u{2630}'),
+    ("search", '\u{1f50d}'),
+    ("home", '\u{2302}'),
+    ("settings", '\u{2699}'),
+    ("user", '\u{1f464}'),
+    ("lock", '\u{1f512}'),
+    ("heart", '\u{2665}'),
+    ("star", '\u{2605}'),
+    ("pencil", '\u{270e}'),
+    ("trash", '\u{e729}'),
+    ("save", '\u{1f4be}'),
+    ("info", '\u{2139}'),
+    ("warning", '\u{26a0}'),
+    ("play", '\u{25b6}'),
+    ("refresh", '\u{27f3}'),
+    ("login", '\u{e740}'),
+    ("down", '\u{e75c}'),
+    ("left", '\u{e75d}'),
+    ("right", '\u{e75e}'),
+    ("up", '\u{e75f}'),
+];
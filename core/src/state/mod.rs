@@ -22,19 +22,66 @@ pub use mouse::*;
 pub mod resource;
 pub use resource::*;
 
+pub mod text_layout;
+pub use text_layout::*;
+
+pub mod font_metrics;
+pub use font_metrics::*;
+
+pub mod accelerator;
+pub use accelerator::*;
+
+pub mod style_watcher;
+pub use style_watcher::*;
+
+pub mod accessibility;
+pub use accessibility::*;
+
+pub mod icons;
+pub use icons::*;
+
 pub use crate::events::{Builder, Event, EventHandler, Propagation};
 pub use crate::window_event::WindowEvent;
 
-use femtovg::FontId;
+use femtovg::{FontId, ImageId};
 
 use std::collections::{HashMap, VecDeque};
 
-use fnv::FnvHashMap;
+use fnv::{FnvHashMap, FnvHashSet};
+
+use keyboard_types::Key;
+
+use crate::style::themes::{resolve_variables, Theme};
+
+use std::time::{Duration, Instant};
+
+// Time the caret of a textbox being edited stays visible or hidden while blinking
+pub const CARET_BLINK_INTERVAL: Duration = Duration::from_millis(500);
+
+// Default time the cursor must rest over a widget before its tooltip is shown
+pub const TOOLTIP_DELAY: Duration = Duration::from_millis(500);
 
 pub struct Fonts {
     pub regular: Option<FontId>,
     pub bold: Option<FontId>,
     pub icons: Option<FontId>,
+    pub font_ids: HashMap<String, FontId>, // Map of font names to fonts loaded by the renderer
+}
+
+impl Fonts {
+    pub fn new(regular: FontId, bold: FontId, icons: FontId) -> Self {
+        let mut font_ids = HashMap::new();
+        font_ids.insert("sans".to_string(), regular);
+        font_ids.insert("bold".to_string(), bold);
+        font_ids.insert("icons".to_string(), icons);
+
+        Fonts {
+            regular: Some(regular),
+            bold: Some(bold),
+            icons: Some(icons),
+            font_ids,
+        }
+    }
 }
 
 pub struct State {
@@ -45,15 +92,66 @@ pub struct State {
     pub root: Entity,
     pub mouse: MouseState,
     pub modifiers: ModifiersState,
+    pub scroll_sensitivity: f32, // Multiplier applied to mouse wheel deltas by scrolling widgets
+    pub shift_scroll: bool, // When true the mouse wheel scrolls horizontally while shift is held
+    pub scale_factor: f32, // Physical pixels per logical pixel, layout and events use logical pixels
     pub hovered: Entity,
     pub active: Entity,
-    pub captured: Entity,
+    pub capture_stack: Vec<Entity>, // Entities which have captured the mouse, the last one receives mouse events
     pub focused: Entity,
 
     pub event_handlers: FnvHashMap<Entity, Box<dyn EventHandler>>,
     pub event_queue: VecDeque<Event>,
+    pub removed_entities: Vec<Entity>, // Entities whose event handlers need removing from the event manager
+    pub event_tracing: bool,           // Set by trace_events, prints each event as it's dispatched
+    batch_depth: usize,                // Greater than zero while build_batch is running
+
+    pub needs_restyle: bool,        // Set when the whole tree needs restyling
+    pub needs_redraw: bool, // Set by request_redraw, cleared by the backend when it repaints
+    pub headless: bool,     // Set when there is no window or renderer, drawing is skipped
+    pub restyle_roots: Vec<Entity>, // Entities whose branches need restyling
+    pub needs_relayout: bool, // Set by Relayout events, the whole tree is laid out once after the queue is flushed
+    pub geometry_listeners: FnvHashSet<Entity>, // Entities sent GeometryChanged when their bounds change
+
+    pub text_layouts: FnvHashMap<Entity, TextLayout>, // Cached lines and shaped glyphs of text
+    pub font_metrics: FnvHashMap<String, FontMetrics>, // Used to measure text during layout, keyed by font name
+
+    // Dirty rectangles
+    // When enabled, a redraw caused only by changes to entities which don't need a relayout repaints
+    // just the region covering those entities instead of the whole window
+    // Frames are then drawn into an offscreen image which keeps the rest of the previous frame
+    pub dirty_rects: bool,
+    pub redraw_all: bool,
+    pub redraw_region: Option<Rect>, // Region covering the entities changed since the last frame
+    pub draw_region: Option<Rect>, // Region being repainted in the current frame, None when repainting everything
+
+    // When enabled the bounds of entities are rounded to whole device pixels when drawn, so thin borders and text are sharp
+    pub pixel_snapping: bool,
+
+    // The caret of the textbox being edited blinks from the time it was last moved
+    // None when no textbox is being edited so nothing is redrawn
+    pub caret_blink_start: Option<Instant>,
+    caret_blink_phase: u128,
+
+    // Tooltips are shown once the cursor has rested over their widget for the delay
+    pub tooltip_delay: Duration,
+    pub tooltips: FnvHashMap<Entity, Entity>, // Map of widgets to their tooltips
+    pub collapses: FnvHashMap<Entity, Collapse>, // Entities collapsed or being collapsed or expanded
+    tooltip_pending: Option<(Entity, Instant)>, // The tooltip waiting to be shown and when its widget was hovered
+    tooltip_shown: Entity,
+
+    accelerators: Vec<(Accelerator, Event)>, // Global shortcuts and the events they send
+
+    // When enabled the hovered entity is outlined and labelled with its id, element name and size
+    pub inspector: bool,
+    pub inspector_key: Option<Accelerator>, // Toggles the inspector when pressed
+
+    #[cfg(all(feature = "hot-reload", debug_assertions))]
+    stylesheet_watcher: Option<StylesheetWatcher>,
 
-    pub fonts: Fonts, //TODO - Replace with resource manager
+    pub fonts: Fonts,                 //TODO - Replace with resource manager
+    pub icons: HashMap<String, char>, // Map of icon names to the codepoints of their glyphs
+    pub images: FnvHashMap<String, Option<ImageId>>, // Images loaded by the renderer from their paths, None if loading failed
 
     pub resource_manager: ResourceManager, //TODO
 }
@@ -86,21 +184,76 @@ impl State {
             root,
             mouse,
             modifiers,
+            scroll_sensitivity: 1.0,
+            shift_scroll: true,
+            scale_factor: 1.0,
             hovered: Entity::new(0, 0),
             active: Entity::null(),
-            captured: Entity::null(),
+            capture_stack: Vec::new(),
             focused: Entity::new(0, 0),
             event_handlers: FnvHashMap::default(),
             event_queue: VecDeque::new(),
+            removed_entities: Vec::new(),
+            event_tracing: false,
+            batch_depth: 0,
+            needs_restyle: false,
+            needs_redraw: false,
+            headless: false,
+            restyle_roots: Vec::new(),
+            needs_relayout: false,
+            geometry_listeners: FnvHashSet::default(),
+            text_layouts: FnvHashMap::default(),
+            font_metrics: default_font_metrics(),
+            caret_blink_start: None,
+            caret_blink_phase: 0,
+            tooltip_delay: TOOLTIP_DELAY,
+            tooltips: FnvHashMap::default(),
+            collapses: FnvHashMap::default(),
+            tooltip_pending: None,
+            tooltip_shown: Entity::null(),
+            pixel_snapping: false,
+            dirty_rects: false,
+            redraw_all: true,
+            redraw_region: None,
+            draw_region: None,
+            accelerators: Vec::new(),
+            inspector: false,
+            inspector_key: None,
+            #[cfg(all(feature = "hot-reload", debug_assertions))]
+            stylesheet_watcher: None,
             fonts: Fonts {
                 regular: None,
                 bold: None,
                 icons: None,
+                font_ids: HashMap::new(),
             },
+            images: FnvHashMap::default(),
+            icons: DEFAULT_ICONS
+                .iter()
+                .map(|(name, codepoint)| (name.to_string(), *codepoint))
+                .collect(),
             resource_manager: ResourceManager::new(),
         }
     }
 
+    // Creates a state with a window widget as the root but no window or renderer, so the hierarchy,
+    // events, styling and layout can be used in tests
+    // Text is measured with the metrics of the default fonts but nothing is drawn
+    pub fn new_headless() -> Self {
+        let mut state = State::new();
+        state.headless = true;
+
+        crate::WindowWidget::new().build_window(&mut state);
+
+        let root = state.root;
+        state.style.width.insert(root, Length::Pixels(800.0));
+        state.style.height.insert(root, Length::Pixels(600.0));
+        state.transform.set_width(root, 800.0);
+        state.transform.set_height(root, 600.0);
+
+        state
+    }
+
     pub fn build<'a, T>(&'a mut self, entity: Entity, event_handler: T) -> Builder<'a>
     where
         T: EventHandler + 'static,
@@ -111,16 +264,141 @@ impl State {
     }
 
     pub fn insert_stylesheet(&mut self, path: &str) -> Result<(), std::io::Error> {
-        let style_string = std::fs::read_to_string(path.clone())?;
+        let style_string = read_stylesheet(path)?;
         self.resource_manager.stylesheets.push(path.to_owned());
 
         // Parse the theme stylesheet
-        self.style.parse_theme(&style_string);
+        self.style.parse_theme(&resolve_variables(
+            &style_string,
+            &self.resource_manager.theme,
+        ));
         // self.resource_manager.themes.push(style_string);
 
         Ok(())
     }
 
+    // Adds a CSS, JSON or RON stylesheet and restyles the tree, so it can be used after the application has started
+    // JSON and RON stylesheets require the stylesheets feature
+    pub fn load_stylesheet(&mut self, path: &str) -> Result<(), std::io::Error> {
+        // Read first so a stylesheet which fails to parse isn't kept
+        read_stylesheet(path)?;
+        self.resource_manager.stylesheets.push(path.to_owned());
+
+        self.reload_styles()
+    }
+
+    // Loads a stylesheet and reloads the styles whenever the file is saved, so a theme can be edited while the application runs
+    // Requires the hot-reload feature, in release builds the stylesheet is only loaded
+    pub fn watch_stylesheet(&mut self, path: &str) -> Result<(), std::io::Error> {
+        if !self
+            .resource_manager
+            .stylesheets
+            .iter()
+            .any(|stylesheet| stylesheet == path)
+        {
+            self.load_stylesheet(path)?;
+        }
+
+        #[cfg(all(feature = "hot-reload", debug_assertions))]
+        {
+            if self.stylesheet_watcher.is_none() {
+                self.stylesheet_watcher = Some(StylesheetWatcher::new()?);
+            }
+
+            if let Some(stylesheet_watcher) = &mut self.stylesheet_watcher {
+                stylesheet_watcher.watch(path)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Reloads the styles if a watched stylesheet has changed, called by the backends before flushing events
+    pub fn reload_changed_stylesheets(&mut self) {
+        #[cfg(all(feature = "hot-reload", debug_assertions))]
+        {
+            let changed = match &mut self.stylesheet_watcher {
+                Some(stylesheet_watcher) => stylesheet_watcher.changed(),
+                None => false,
+            };
+
+            if changed {
+                if let Err(error) = self.reload_styles() {
+                    eprintln!("WARNING: Failed to reload stylesheets: {}", error);
+                }
+            }
+        }
+    }
+
+    // Returns when the backend should next check the watched stylesheets, None when nothing is watched
+    pub fn next_stylesheet_check(&self) -> Option<Instant> {
+        #[cfg(all(feature = "hot-reload", debug_assertions))]
+        {
+            if self.stylesheet_watcher.is_some() {
+                return Some(Instant::now() + STYLESHEET_DEBOUNCE);
+            }
+        }
+
+        None
+    }
+
+    // Registers the name of an icon so it can be used with the Icon widget, replacing any icon with the same name
+    pub fn register_icon(&mut self, name: &str, codepoint: char) {
+        self.icons.insert(name.to_owned(), codepoint);
+    }
+
+    // Registers the names of a set of icons, e.g. for an icon font added with add_font
+    pub fn register_icons(&mut self, icons: &[(&str, char)]) {
+        for (name, codepoint) in icons.iter() {
+            self.register_icon(name, *codepoint);
+        }
+    }
+
+    // Returns the codepoint of the icon registered with the name
+    pub fn icon(&self, name: &str) -> Option<char> {
+        self.icons.get(name).cloned()
+    }
+
+    // Reads a font file which is then loaded by the renderer under the given name
+    // Returns an error if the file can't be read or isn't a font the renderer can load
+    pub fn add_font(&mut self, name: &str, path: &str) -> Result<(), std::io::Error> {
+        let data = std::fs::read(path)?;
+
+        // Parsed here so a bad font is reported to the caller rather than when the renderer loads it
+        let metrics = FontMetrics::new(data.clone()).map_err(|error| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Failed to parse font {}: {}", path, error),
+            )
+        })?;
+
+        self.font_metrics.insert(name.to_owned(), metrics);
+        self.resource_manager.fonts.push((name.to_owned(), data));
+
+        Ok(())
+    }
+
+    // Returns the names of all fonts which can be used, including those not yet loaded by the renderer
+    pub fn available_fonts(&self) -> Vec<String> {
+        let mut fonts: Vec<String> = self
+            .fonts
+            .font_ids
+            .keys()
+            .cloned()
+            .chain(
+                self.resource_manager
+                    .fonts
+                    .iter()
+                    .map(|(name, _)| name.clone()),
+            )
+            .collect();
+
+        fonts.sort();
+        fonts.dedup();
+
+        fonts
+    }
+
     pub fn insert_theme(&mut self, theme: &str) {
         self.resource_manager.themes.push(theme.to_owned());
 
@@ -128,6 +406,17 @@ impl State {
         // self.style.parse_theme(&overall_theme);
     }
 
+    // Replaces the variables referenced by the themes and stylesheets and restyles the tree
+    pub fn set_theme(&mut self, theme: Theme) -> Result<(), std::io::Error> {
+        self.resource_manager.theme = theme;
+
+        self.reload_styles()
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.resource_manager.theme
+    }
+
     // Removes all style data and then reloads the stylesheets
     // TODO change the error type to allow for parsing errors
     pub fn reload_styles(&mut self) -> Result<(), std::io::Error> {
@@ -138,6 +427,8 @@ impl State {
         // Remove all non-inline style data
         self.style.background_color.remove_styles();
         self.style.font_color.remove_styles();
+        self.style.selection_color.remove_styles();
+        self.style.caret_color.remove_styles();
 
         // Position
         self.style.left.remove_styles();
@@ -157,9 +448,18 @@ impl State {
         self.style.padding_right.remove_styles();
         self.style.padding_top.remove_styles();
         self.style.padding_bottom.remove_styles();
+        // Child Space
+        self.style.child_left.remove_styles();
+        self.style.child_right.remove_styles();
+        self.style.child_top.remove_styles();
+        self.style.child_bottom.remove_styles();
         // Border
         self.style.border_width.remove_styles();
         self.style.border_color.remove_styles();
+        // Outline
+        self.style.outline_width.remove_styles();
+        self.style.outline_color.remove_styles();
+        self.style.outline_offset.remove_styles();
         // Border Radius
         self.style.border_radius_top_left.remove_styles();
         self.style.border_radius_top_right.remove_styles();
@@ -182,6 +482,7 @@ impl State {
         // Text Alignment
         self.style.text_align.remove_styles();
         self.style.text_justify.remove_styles();
+        self.style.text_wrap.remove_styles();
 
         let mut overall_theme = String::new();
 
@@ -193,11 +494,14 @@ impl State {
 
         // Reload the stored stylesheets
         for stylesheet in self.resource_manager.stylesheets.iter() {
-            let theme = std::fs::read_to_string(stylesheet)?;
+            let theme = read_stylesheet(stylesheet)?;
             overall_theme += &theme;
         }
 
-        self.style.parse_theme(&overall_theme);
+        self.style.parse_theme(&resolve_variables(
+            &overall_theme,
+            &self.resource_manager.theme,
+        ));
 
         self.insert_event(Event::new(WindowEvent::Restyle).target(Entity::null()));
         self.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
@@ -206,7 +510,53 @@ impl State {
         Ok(())
     }
 
+    // Prints each event as it is dispatched, with its target, origin, propagation and message
+    // Useful for finding out why an entity isn't receiving an event
+    pub fn trace_events(&mut self, flag: bool) {
+        self.event_tracing = flag;
+    }
+
+    // Rounds drawn bounds, border widths and text positions to whole device pixels
+    // Layout is unaffected, only where entities are drawn changes
+    pub fn pixel_snap(&mut self, flag: bool) {
+        if self.pixel_snapping != flag {
+            self.pixel_snapping = flag;
+            self.redraw_all = true;
+            self.insert_event(Event::new(WindowEvent::Redraw).target(Entity::null()));
+        }
+    }
+
+    // Runs the closure without queueing the restyle, relayout and redraw requested by each change it makes,
+    // then queues a single restyle, relayout and redraw of the whole tree
+    // Useful when building or changing many entities at once, since each queued event is compared with the rest of the queue
+    pub fn build_batch<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut State) -> R,
+    {
+        self.batch_depth += 1;
+        let result = f(self);
+        self.batch_depth -= 1;
+
+        if self.batch_depth == 0 {
+            self.insert_event(Event::new(WindowEvent::Restyle).target(Entity::null()));
+            self.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+            self.insert_event(Event::new(WindowEvent::Redraw).target(Entity::null()));
+        }
+
+        result
+    }
+
     pub fn insert_event(&mut self, mut event: Event) {
+        // Queued once at the end of the batch instead
+        if self.batch_depth > 0 {
+            if let Some(window_event) = event.message.downcast::<WindowEvent>() {
+                match window_event {
+                    WindowEvent::Restyle | WindowEvent::Relayout | WindowEvent::Redraw => return,
+                    _ => {}
+                }
+            }
+        }
+
         if event.unique {
             self.event_queue.retain(|e| e != &event);
         }
@@ -214,42 +564,354 @@ impl State {
         self.event_queue.push_back(event);
     }
 
+    // Returns the events waiting to be flushed, in the order they were inserted
+    // Useful for checking which events a widget sent without opening a window
+    pub fn pending_events(&mut self) -> &[Event] {
+        self.event_queue.make_contiguous()
+    }
+
+    // Removes and returns the events waiting to be flushed, so tests can check the events sent
+    // by a widget in response to the events just flushed
+    pub fn drain_events(&mut self) -> Vec<Event> {
+        self.event_queue.drain(..).collect()
+    }
+
+    // Repaints the entity in the next frame without recomputing layout or going through the event queue
+    // Use this when only the appearance of the entity changes, like a blinking caret or a hover color,
+    // and send a Relayout event instead when the entity moves or changes size
+    pub fn request_redraw(&mut self, entity: Entity) {
+        self.add_redraw_region(entity);
+        self.needs_redraw = true;
+    }
+
+    // Adds the bounds of an entity to the region repainted in the next frame
+    // A null entity or the root causes the whole window to be repainted
+    pub fn add_redraw_region(&mut self, entity: Entity) {
+        if entity == Entity::null() || entity == self.root || !self.is_alive(entity) {
+            self.redraw_all = true;
+            return;
+        }
+
+        let bounds = crate::get_draw_bounds(self, entity);
+        self.redraw_region = Some(match self.redraw_region {
+            Some(region) => region.union(&bounds),
+            None => bounds,
+        });
+    }
+
+    // Shows the caret and restarts its blinking
+    pub fn reset_caret_blink(&mut self) {
+        self.caret_blink_start = Some(Instant::now());
+        self.caret_blink_phase = 0;
+    }
+
+    pub fn stop_caret_blink(&mut self) {
+        self.caret_blink_start = None;
+    }
+
+    fn caret_phase(&self) -> Option<u128> {
+        self.caret_blink_start
+            .map(|start| start.elapsed().as_millis() / CARET_BLINK_INTERVAL.as_millis())
+    }
+
+    pub fn caret_visible(&self) -> bool {
+        self.caret_phase()
+            .map(|phase| phase % 2 == 0)
+            .unwrap_or(true)
+    }
+
+    // Returns the time the caret next changes between visible and hidden
+    pub fn next_caret_blink(&self) -> Option<Instant> {
+        let start = self.caret_blink_start?;
+        let phase = self.caret_phase()?;
+        Some(start + CARET_BLINK_INTERVAL * (phase + 1) as u32)
+    }
+
+    // Returns true once each time the caret changes between visible and hidden
+    pub fn caret_blink_due(&mut self) -> bool {
+        if let Some(phase) = self.caret_phase() {
+            if phase != self.caret_blink_phase {
+                self.caret_blink_phase = phase;
+                return true;
+            }
+        }
+
+        false
+    }
+
+    // Returns the mouse wheel delta to send in a MouseScroll event
+    // While shift is held a vertical delta is sent as a horizontal one, so mice with only a vertical wheel can scroll
+    // horizontally. Deltas which are already horizontal, such as from platforms which swap the axes themselves, are unchanged
+    pub fn scroll_delta(&self, x: f32, y: f32) -> (f32, f32) {
+        if self.shift_scroll && self.modifiers.shift && x == 0.0 {
+            (y, 0.0)
+        } else {
+            (x, y)
+        }
+    }
+
+    // Returns the tooltip of the hovered entity or its nearest ancestor with one
+    fn hovered_tooltip(&self) -> Entity {
+        self.hovered
+            .parent_iter(&self.hierarchy)
+            .find_map(|entity| self.tooltips.get(&entity).cloned())
+            .unwrap_or(Entity::null())
+    }
+
+    // Called when the cursor moves to start the delay before showing the tooltip of the hovered widget,
+    // hide the shown tooltip if the cursor left its widget, or move the shown tooltip if it follows the cursor
+    pub fn update_tooltip(&mut self) {
+        let tooltip = self.hovered_tooltip();
+
+        if self.tooltip_shown != Entity::null() && tooltip != self.tooltip_shown {
+            self.insert_event(
+                Event::new(crate::widgets::TooltipEvent::Hide)
+                    .target(self.tooltip_shown)
+                    .propagate(Propagation::Direct),
+            );
+            self.tooltip_shown = Entity::null();
+        }
+
+        if tooltip == Entity::null() {
+            self.tooltip_pending = None;
+        } else if tooltip == self.tooltip_shown {
+            self.insert_event(
+                Event::new(crate::widgets::TooltipEvent::Move)
+                    .target(tooltip)
+                    .propagate(Propagation::Direct),
+            );
+        } else if self.tooltip_pending.map(|(pending, _)| pending) != Some(tooltip) {
+            self.tooltip_pending = Some((tooltip, Instant::now()));
+        }
+    }
+
+    // Returns the time the pending tooltip is shown
+    pub fn next_tooltip(&self) -> Option<Instant> {
+        self.tooltip_pending
+            .map(|(_, hovered)| hovered + self.tooltip_delay)
+    }
+
+    // Shows the pending tooltip once its delay has passed, returning true when it is shown
+    pub fn tooltip_due(&mut self) -> bool {
+        if let Some((tooltip, hovered)) = self.tooltip_pending {
+            if hovered.elapsed() >= self.tooltip_delay {
+                self.tooltip_pending = None;
+                self.tooltip_shown = tooltip;
+                self.insert_event(
+                    Event::new(crate::widgets::TooltipEvent::Show)
+                        .target(tooltip)
+                        .propagate(Propagation::Direct),
+                );
+
+                return true;
+            }
+        }
+
+        false
+    }
+
+    // Sets the number of physical pixels per logical pixel, called by the backend when the window moves to a display with a different scale
+    // Drawing is scaled by the factor so pixel lengths, border widths and font sizes keep the same physical size on every display
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        if scale_factor == self.scale_factor || scale_factor <= 0.0 {
+            return;
+        }
+
+        self.scale_factor = scale_factor;
+
+        self.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        self.insert_event(Event::new(WindowEvent::Redraw).target(Entity::null()));
+    }
+
+    // Returns the topmost entity containing the point, or None if the point is outside of the window
+    // Invisible, transparent, non-hoverable and undisplayed entities, and points outside an entity's clip region, are skipped
+    pub fn hit_test(&self, x: f32, y: f32) -> Option<Entity> {
+        let mut draw_hierarchy: Vec<Entity> = self.hierarchy.into_iter().collect();
+
+        // Sorted in draw order so the topmost entity is found, with overlays above the rest of the tree
+        draw_hierarchy.sort_by_cached_key(|entity| {
+            (
+                self.transform.is_overlay(*entity),
+                self.transform.get_z_order(*entity),
+            )
+        });
+
+        // Searched from the top down so the first entity containing the point is the topmost one
+        for entity in draw_hierarchy.into_iter().rev() {
+            if self.transform.get_visibility(entity) == Visibility::Invisible
+                || self.style.display.get(entity).cloned().unwrap_or_default() == Display::None
+            {
+                continue;
+            }
+
+            // Fully transparent entities, including the descendants of fully transparent entities, let clicks through
+            if self.transform.get_opacity(entity) == 0.0 {
+                continue;
+            }
+
+            if !self.transform.get_hoverability(entity) {
+                continue;
+            }
+
+            let border_width = match self
+                .style
+                .border_width
+                .get(entity)
+                .cloned()
+                .unwrap_or_default()
+            {
+                Length::Pixels(val) => val,
+                _ => 0.0,
+            };
+
+            let posx = self.transform.get_posx(entity) - (border_width / 2.0);
+            let posy = self.transform.get_posy(entity) - (border_width / 2.0);
+            let width = self.transform.get_width(entity) + border_width;
+            let height = self.transform.get_height(entity) + border_width;
+
+            let clip_widget = self.transform.get_clip_widget(entity);
+
+            let clip_posx = self.transform.get_posx(clip_widget);
+            let clip_posy = self.transform.get_posy(clip_widget);
+            let clip_width = self.transform.get_width(clip_widget);
+            let clip_height = self.transform.get_height(clip_widget);
+
+            if x >= posx
+                && x >= clip_posx
+                && x < (posx + width)
+                && x < (clip_posx + clip_width)
+                && y >= posy
+                && y >= clip_posy
+                && y < (posy + height)
+                && y < (clip_posy + clip_height)
+            {
+                return Some(entity);
+            }
+        }
+
+        None
+    }
+
+    // Sends the event when the key and modifiers of the accelerator are pressed, whichever entity is focused
+    // Events without a target are sent to the root and fall through the tree until consumed
+    pub fn add_accelerator(&mut self, accelerator: Accelerator, event: Event) {
+        self.remove_accelerator(&accelerator);
+        self.accelerators.push((accelerator, event));
+    }
+
+    // Returns the event which was sent by the removed accelerator
+    pub fn remove_accelerator(&mut self, accelerator: &Accelerator) -> Option<Event> {
+        let index = self
+            .accelerators
+            .iter()
+            .position(|(other, _)| other == accelerator)?;
+
+        Some(self.accelerators.remove(index).1)
+    }
+
+    // Called by the backend when a key is pressed, before the key event is sent to the focused entity
+    // Returns true if an accelerator matched, in which case the key event should not be sent
+    pub fn trigger_accelerator(&mut self, key: &Key) -> bool {
+        if let Some(inspector_key) = &self.inspector_key {
+            if inspector_key.matches(key, &self.modifiers) {
+                self.enable_inspector(!self.inspector);
+                return true;
+            }
+        }
+
+        let event = self
+            .accelerators
+            .iter()
+            .find(|(accelerator, _)| accelerator.matches(key, &self.modifiers))
+            .map(|(_, event)| event.clone());
+
+        if let Some(mut event) = event {
+            if event.target == Entity::null() {
+                event = event.target(self.root).propagate(Propagation::Fall);
+            }
+
+            self.insert_event(event);
+
+            return true;
+        }
+
+        false
+    }
+
+    // Shows or hides the outline and label drawn over the hovered entity
+    // The inspector is only drawn so it doesn't affect hit testing or events
+    pub fn enable_inspector(&mut self, flag: bool) {
+        self.inspector = flag;
+        self.request_redraw(self.root);
+    }
+
+    // Sets the key which toggles the inspector, such as Accelerator::new(Key::F12)
+    pub fn set_inspector_key(&mut self, accelerator: Accelerator) {
+        self.inspector_key = Some(accelerator);
+    }
+
     pub fn id2entity(&self, id: &str) -> Option<Entity> {
         self.style.ids.get_by_left(&id.to_string()).cloned()
     }
 
+    // Returns the entity at the top of the capture stack or null if nothing has captured the mouse
+    pub fn captured(&self) -> Entity {
+        self.capture_stack.last().cloned().unwrap_or(Entity::null())
+    }
+
     // This should probably be moved to state.mouse
     pub fn capture(&mut self, id: Entity) {
-        if id != Entity::null() {
+        if id == Entity::null() {
+            return;
+        }
+
+        let captured = self.captured();
+
+        if captured != id {
             self.insert_event(
                 Event::new(WindowEvent::MouseCaptureEvent)
                     .target(id)
                     .propagate(Propagation::Direct),
             );
-        }
 
-        if self.captured != Entity::null() {
-            self.insert_event(
-                Event::new(WindowEvent::MouseCaptureOutEvent)
-                    .target(self.captured)
-                    .propagate(Propagation::Direct),
-            );
+            if captured != Entity::null() {
+                self.insert_event(
+                    Event::new(WindowEvent::MouseCaptureOutEvent)
+                        .target(captured)
+                        .propagate(Propagation::Direct),
+                );
+            }
+
+            // An entity should only appear once in the stack
+            self.capture_stack.retain(|entity| *entity != id);
+            self.capture_stack.push(id);
         }
 
-        self.captured = id;
         self.active = id;
     }
 
     // This should probably be moved to state.mouse
     pub fn release(&mut self, id: Entity) {
-        if self.captured == id {
+        if id != Entity::null() && self.captured() == id {
             self.insert_event(
                 Event::new(WindowEvent::MouseCaptureOutEvent)
-                    .target(self.captured)
+                    .target(id)
                     .propagate(Propagation::Direct),
             );
-            self.captured = Entity::null();
-            self.active = Entity::null();
+
+            self.capture_stack.pop();
+
+            // Capture returns to the previous entity on the stack
+            let captured = self.captured();
+            if captured != Entity::null() {
+                self.insert_event(
+                    Event::new(WindowEvent::MouseCaptureEvent)
+                        .target(captured)
+                        .propagate(Propagation::Direct),
+                );
+            }
+
+            self.active = captured;
         }
     }
 
@@ -263,9 +925,42 @@ impl State {
         self.transform.add(entity);
         self.style.add(entity);
 
+        self.restyle_siblings(entity);
+
         entity
     }
 
+    // Makes an entity the first child of its parent, so it is laid out first and drawn below its siblings
+    pub fn move_to_front(&mut self, entity: Entity) {
+        if self.hierarchy.is_first_child(entity) {
+            return;
+        }
+
+        self.hierarchy.move_to_front(entity);
+        self.restyle_siblings(entity);
+        self.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        self.insert_event(Event::new(WindowEvent::Redraw).target(Entity::null()));
+    }
+
+    // Makes an entity the last child of its parent, so it is laid out last and drawn above its siblings
+    pub fn move_to_back(&mut self, entity: Entity) {
+        if self.hierarchy.get_next_sibling(entity).is_none() {
+            return;
+        }
+
+        self.hierarchy.move_to_back(entity);
+        self.restyle_siblings(entity);
+        self.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        self.insert_event(Event::new(WindowEvent::Redraw).target(Entity::null()));
+    }
+
+    // Restyles the entity and its siblings, whose first-child, last-child and nth-child pseudo-classes may have changed
+    fn restyle_siblings(&mut self, entity: Entity) {
+        if let Some(parent) = self.hierarchy.get_parent(entity) {
+            self.insert_event(Event::new(WindowEvent::Restyle).origin(parent));
+        }
+    }
+
     // TODO
     // pub fn add_with_sibling(&mut self, sibling: Entity) -> Entity {
     //     let entity = self
@@ -279,20 +974,78 @@ impl State {
     //     entity
     // }
 
-    //  TODO
-    // pub fn remove(&mut self, entity: Entity) {
-    //     //self.hierarchy.remove(entity);
-    //     //self.transform.remove(entity);
-    //     //self.style.remove(entity);
-    //     //self.entity_manager.destroy_entity(entity);
-    // }
+    // Removes an entity and all of its descendants, including their widgets and style data
+    pub fn remove(&mut self, entity: Entity) {
+        if entity == Entity::null() || entity == self.root || !self.is_alive(entity) {
+            return;
+        }
+
+        // Collect the branch before it is removed from the hierarchy
+        let branch: Vec<Entity> = entity.branch_iter(&self.hierarchy).collect();
+
+        self.restyle_siblings(entity);
+
+        self.hierarchy.remove(entity);
+
+        for removed in branch.into_iter() {
+            self.transform.remove(removed);
+            self.style.remove(removed);
+            self.event_handlers.remove(&removed);
+            self.text_layouts.remove(&removed);
+            self.collapses.remove(&removed);
+            self.geometry_listeners.remove(&removed);
+            self.tooltips
+                .retain(|widget, tooltip| *widget != removed && *tooltip != removed);
+            self.removed_entities.push(removed);
+
+            self.capture_stack.retain(|e| *e != removed);
+
+            if self.tooltip_shown == removed {
+                self.tooltip_shown = Entity::null();
+            }
+
+            if self.tooltip_pending.map(|(pending, _)| pending) == Some(removed) {
+                self.tooltip_pending = None;
+            }
+
+            if self.hovered == removed {
+                self.hovered = self.root;
+            }
+
+            if self.active == removed {
+                self.active = Entity::null();
+            }
+
+            if self.focused == removed {
+                self.focused = self.root;
+            }
+
+            self.entity_manager.destroy_entity(removed);
+        }
+
+        self.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        self.insert_event(Event::new(WindowEvent::Redraw).target(Entity::null()));
+    }
+
+    // Returns true if the entity has not been removed
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.entity_manager.is_alive(entity)
+    }
 
     pub fn apply_animations(&mut self) -> bool {
         self.style
             .background_color
             .animate(std::time::Instant::now());
         self.style.font_color.animate(std::time::Instant::now());
+        self.style
+            .selection_color
+            .animate(std::time::Instant::now());
+        self.style.caret_color.animate(std::time::Instant::now());
+        self.style.font_size.animate(std::time::Instant::now());
         self.style.border_color.animate(std::time::Instant::now());
+        self.style.outline_width.animate(std::time::Instant::now());
+        self.style.outline_color.animate(std::time::Instant::now());
+        self.style.outline_offset.animate(std::time::Instant::now());
 
         self.style.left.animate(std::time::Instant::now());
         self.style.right.animate(std::time::Instant::now());
@@ -302,6 +1055,9 @@ impl State {
         self.style.height.animate(std::time::Instant::now());
         self.style.opacity.animate(std::time::Instant::now());
         self.style.rotate.animate(std::time::Instant::now());
+        self.style.scaley.animate(std::time::Instant::now());
+        self.style.translate_x.animate(std::time::Instant::now());
+        self.style.translate_y.animate(std::time::Instant::now());
         self.style.flex_grow.animate(std::time::Instant::now());
         self.style.flex_shrink.animate(std::time::Instant::now());
         self.style.flex_basis.animate(std::time::Instant::now());
@@ -313,6 +1069,10 @@ impl State {
         self.style.padding_right.animate(std::time::Instant::now());
         self.style.padding_top.animate(std::time::Instant::now());
         self.style.padding_bottom.animate(std::time::Instant::now());
+        self.style.child_left.animate(std::time::Instant::now());
+        self.style.child_right.animate(std::time::Instant::now());
+        self.style.child_top.animate(std::time::Instant::now());
+        self.style.child_bottom.animate(std::time::Instant::now());
         self.style
             .border_radius_top_left
             .animate(std::time::Instant::now());
@@ -327,9 +1087,22 @@ impl State {
             .animate(std::time::Instant::now());
         self.style.border_width.animate(std::time::Instant::now());
 
-        self.style.background_color.has_animations()
+        let collapsed = self.apply_collapses();
+
+        collapsed
+            || self
+                .collapses
+                .values()
+                .any(|collapse| collapse.end.is_some())
+            || self.style.background_color.has_animations()
             || self.style.font_color.has_animations()
+            || self.style.selection_color.has_animations()
+            || self.style.caret_color.has_animations()
+            || self.style.font_size.has_animations()
             || self.style.border_color.has_animations()
+            || self.style.outline_width.has_animations()
+            || self.style.outline_color.has_animations()
+            || self.style.outline_offset.has_animations()
             || self.style.left.has_animations()
             || self.style.right.has_animations()
             || self.style.top.has_animations()
@@ -338,6 +1111,9 @@ impl State {
             || self.style.height.has_animations()
             || self.style.opacity.has_animations()
             || self.style.rotate.has_animations()
+            || self.style.scaley.has_animations()
+            || self.style.translate_x.has_animations()
+            || self.style.translate_y.has_animations()
             || self.style.flex_grow.has_animations()
             || self.style.flex_shrink.has_animations()
             || self.style.flex_basis.has_animations()
@@ -349,6 +1125,10 @@ impl State {
             || self.style.padding_right.has_animations()
             || self.style.padding_top.has_animations()
             || self.style.padding_bottom.has_animations()
+            || self.style.child_left.has_animations()
+            || self.style.child_right.has_animations()
+            || self.style.child_top.has_animations()
+            || self.style.child_bottom.has_animations()
             || self.style.border_radius_top_left.has_animations()
             || self.style.border_radius_top_right.has_animations()
             || self.style.border_radius_bottom_left.has_animations()
@@ -356,7 +1136,663 @@ impl State {
             || self.style.border_width.has_animations()
     }
 
+    // Finishes collapsing and expanding entities once their height animations have ended
+    // A collapsed entity is no longer displayed and an expanded entity has its styles restored
+    // Returns true if any entity finished collapsing or expanding
+    fn apply_collapses(&mut self) -> bool {
+        let now = Instant::now();
+        let finished: Vec<(Entity, Collapse)> = self
+            .collapses
+            .iter()
+            .filter(|(_, collapse)| collapse.end.map_or(false, |end| end <= now))
+            .map(|(entity, collapse)| (*entity, collapse.clone()))
+            .collect();
+
+        for (entity, collapse) in finished.iter() {
+            if collapse.collapsed {
+                entity.set_display(self, Display::None);
+                if let Some(collapse) = self.collapses.get_mut(entity) {
+                    collapse.end = None;
+                }
+            } else {
+                // The inline values added by collapsing are removed so any rules apply again
+                self.collapses.remove(entity);
+                match collapse.height.clone() {
+                    Some(height) => self.style.height.insert(*entity, height),
+                    None => self.style.height.remove_inline(*entity),
+                }
+                match collapse.overflow {
+                    Some(overflow) => self.style.overflow.insert(*entity, overflow),
+                    None => self.style.overflow.remove_inline(*entity),
+                }
+                animator::restyle_inline_removed(self, *entity);
+            }
+        }
+
+        !finished.is_empty()
+    }
+
+    // Returns the entities with a role in tree order, skipping hidden entities
+    pub fn accessibility_tree(&self) -> Vec<AccessNode> {
+        let mut nodes = Vec::new();
+
+        for entity in self.hierarchy.into_iter() {
+            if self.transform.get_visibility(entity) == Visibility::Invisible {
+                continue;
+            }
+
+            let role = self.style.role.get(entity).cloned().unwrap_or_default();
+            if role == Role::None {
+                continue;
+            }
+
+            let label = match self.style.accessible_label.get(entity) {
+                Some(label) => label.clone(),
+                None => self
+                    .style
+                    .text
+                    .get(entity)
+                    .map(|text| text.text.clone())
+                    .unwrap_or_default(),
+            };
+
+            nodes.push(AccessNode {
+                entity,
+                role,
+                label,
+                bounds: Rect::new(
+                    self.transform.get_posx(entity),
+                    self.transform.get_posy(entity),
+                    self.transform.get_width(entity),
+                    self.transform.get_height(entity),
+                ),
+            });
+        }
+
+        nodes
+    }
+
+    // Returns the bounds of an entity inset by its padding and border, with percentage padding resolved
+    pub fn content_box(&self, entity: Entity) -> Rect {
+        crate::get_content_bounds(self, entity)
+    }
+
+    // Returns every resolved style value of an entity, for inspecting why an entity looks the way it does
+    pub fn computed_style(&self, entity: Entity) -> ComputedStyle {
+        ComputedStyle::new(self, entity)
+    }
+
     pub fn get_root(&self) -> Entity {
         self.root
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        BuildHandler, Button, ButtonEvent, Code, Element, EventManager, PropSet, Textbox,
+        TextboxEvent,
+    };
+
+    #[test]
+    fn release_returns_capture_to_previous_entity() {
+        let mut state = State::new_headless();
+        let root = state.root;
+
+        let a = state.add(root);
+        let b = state.add(root);
+
+        state.capture(a);
+        state.capture(b);
+        assert_eq!(state.captured(), b);
+
+        state.release(b);
+        assert_eq!(state.captured(), a);
+
+        state.release(a);
+        assert_eq!(state.captured(), Entity::null());
+    }
+
+    #[test]
+    fn release_ignores_entity_not_on_top() {
+        let mut state = State::new_headless();
+        let root = state.root;
+
+        let a = state.add(root);
+        let b = state.add(root);
+
+        state.capture(a);
+        state.capture(b);
+
+        // Only the entity at the top of the stack can release it
+        state.release(a);
+        assert_eq!(state.captured(), b);
+    }
+
+    // Returns the content box of an entity relative to its position
+    fn content_offset(state: &State, entity: Entity) -> (f32, f32, f32, f32) {
+        let content = state.content_box(entity);
+        (
+            content.x - state.transform.get_posx(entity),
+            content.y - state.transform.get_posy(entity),
+            content.w,
+            content.h,
+        )
+    }
+
+    #[test]
+    fn content_box_is_inset_by_padding_and_border() {
+        let mut state = State::new_headless();
+        let root = state.root;
+
+        let parent = Element::new().build(&mut state, root, |builder| {
+            builder
+                .set_width(Length::Pixels(200.0))
+                .set_height(Length::Pixels(100.0))
+        });
+
+        let padded = Element::new().build(&mut state, parent, |builder| {
+            builder
+                .set_width(Length::Pixels(100.0))
+                .set_height(Length::Pixels(50.0))
+                .set_padding(Length::Pixels(10.0))
+        });
+
+        let bordered = Element::new().build(&mut state, parent, |builder| {
+            builder
+                .set_width(Length::Pixels(100.0))
+                .set_height(Length::Pixels(50.0))
+                .set_padding(Length::Pixels(10.0))
+                .set_border_width(Length::Pixels(2.0))
+        });
+
+        layout(&mut state);
+
+        assert_eq!(content_offset(&state, padded), (10.0, 10.0, 80.0, 30.0));
+        // The border is inside the bounds, so the content is inset by both
+        assert_eq!(content_offset(&state, bordered), (12.0, 12.0, 76.0, 26.0));
+    }
+
+    #[test]
+    fn content_box_resolves_percentage_padding() {
+        let mut state = State::new_headless();
+        let root = state.root;
+
+        let parent = Element::new().build(&mut state, root, |builder| {
+            builder
+                .set_width(Length::Pixels(200.0))
+                .set_height(Length::Pixels(100.0))
+        });
+
+        // Percentages are of the width or height of the parent
+        let padded = Element::new().build(&mut state, parent, |builder| {
+            builder
+                .set_width(Length::Pixels(100.0))
+                .set_height(Length::Pixels(50.0))
+                .set_padding(Length::Percentage(0.1))
+        });
+
+        layout(&mut state);
+
+        assert_eq!(content_offset(&state, padded), (20.0, 10.0, 60.0, 30.0));
+    }
+
+    #[test]
+    fn headless_state_adds_lays_out_and_removes_entities() {
+        let mut state = State::new_headless();
+        let root = state.root;
+
+        let parent = Element::new().build(&mut state, root, |builder| {
+            builder
+                .set_width(Length::Pixels(100.0))
+                .set_height(Length::Pixels(100.0))
+        });
+
+        let first = state.add(parent);
+        let second = state.add(parent);
+        let grandchild = state.add(second);
+
+        assert_eq!(first.parent(&state.hierarchy), Some(parent));
+        assert_eq!(grandchild.parent(&state.hierarchy), Some(second));
+        assert_eq!(parent.child_iter(&state.hierarchy).count(), 2);
+
+        // The layout runs as normal, only drawing is skipped
+        layout(&mut state);
+        assert_eq!(state.transform.get_width(parent), 100.0);
+
+        state.remove(second);
+
+        assert!(state.is_alive(first));
+        assert!(!state.is_alive(second));
+        assert!(!state.is_alive(grandchild));
+        assert_eq!(parent.child_iter(&state.hierarchy).count(), 1);
+
+        layout(&mut state);
+    }
+
+    #[test]
+    fn remove_destroys_branch_and_style_data() {
+        let mut state = State::new_headless();
+        let root = state.root;
+
+        let labels = state.style.accessible_label.data.len();
+        let colors = state.style.background_color.inline_data.len();
+
+        let panel = state.add(root);
+        let child = state.add(panel);
+
+        for entity in [panel, child].iter() {
+            state
+                .style
+                .accessible_label
+                .insert(*entity, "label".to_string());
+            state
+                .style
+                .background_color
+                .insert(*entity, Color::rgb(255, 0, 0));
+        }
+
+        state.remove(panel);
+
+        assert!(!state.is_alive(panel));
+        assert!(!state.is_alive(child));
+        assert!(state
+            .hierarchy
+            .into_iter()
+            .all(|e| e != panel && e != child));
+        assert!(state.removed_entities.contains(&panel));
+        assert!(state.removed_entities.contains(&child));
+
+        // The style data of both entities is freed rather than left behind for a reused index
+        assert_eq!(state.style.accessible_label.data.len(), labels);
+        assert_eq!(state.style.background_color.inline_data.len(), colors);
+    }
+
+    #[test]
+    fn styles_stay_separate_while_entities_are_created_and_removed() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let root = state.root;
+
+        state.insert_theme(".item { width: 50px; }");
+
+        for round in 0..10 {
+            let mut items = Vec::new();
+            for i in 0..200 {
+                let item = Element::new().build(&mut state, root, |builder| builder.class("item"));
+
+                // Half of the items are styled inline, the other half by the rule
+                if i % 2 == 0 {
+                    item.set_width(&mut state, Length::Pixels(i as f32));
+                }
+
+                items.push(item);
+            }
+
+            while !state.event_queue.is_empty() {
+                event_manager.flush_events(&mut state);
+            }
+
+            // Setting an inline value on an entity linked to the rule leaves the other entities alone
+            items[1].set_height(&mut state, Length::Pixels(10.0));
+            items[1].set_width(&mut state, Length::Pixels(1.0));
+
+            for (i, item) in items.iter().enumerate() {
+                let expected = match i {
+                    1 => Length::Pixels(1.0),
+                    i if i % 2 == 0 => Length::Pixels(i as f32),
+                    _ => Length::Pixels(50.0),
+                };
+
+                assert_eq!(
+                    state.style.width.get(*item),
+                    Some(&expected),
+                    "Round {}, item {}",
+                    round,
+                    i
+                );
+            }
+
+            for item in items.into_iter() {
+                state.remove(item);
+            }
+
+            while !state.event_queue.is_empty() {
+                event_manager.flush_events(&mut state);
+            }
+        }
+
+        // Null entities are ignored
+        Entity::null().set_width(&mut state, Length::Pixels(10.0));
+        assert_eq!(state.style.width.get(Entity::null()), None);
+    }
+
+    // A removed entity and the new entity reusing its index
+    fn stale_and_reused_entity(state: &mut State) -> (Entity, Entity) {
+        let root = state.root;
+
+        let stale = state.add(root);
+        stale.set_width(state, Length::Pixels(10.0));
+        state.remove(stale);
+
+        // Indices are only reused once more than 1024 are free
+        let others: Vec<Entity> = (0..1024).map(|_| state.add(root)).collect();
+        for other in others.into_iter() {
+            state.remove(other);
+        }
+
+        let reused = state.add(root);
+        assert_eq!(reused.index(), stale.index());
+
+        (stale, reused)
+    }
+
+    #[test]
+    fn entity_reusing_a_removed_index_can_be_used() {
+        let mut state = State::new_headless();
+        let (_, reused) = stale_and_reused_entity(&mut state);
+
+        assert_eq!(state.style.width.get(reused), None);
+        reused.set_width(&mut state, Length::Pixels(20.0));
+        assert_eq!(state.style.width.get(reused), Some(&Length::Pixels(20.0)));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "used after being removed")]
+    fn reading_through_a_removed_entity_panics() {
+        let mut state = State::new_headless();
+        let (stale, _) = stale_and_reused_entity(&mut state);
+
+        state.style.width.get(stale);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "used after being removed")]
+    fn setting_through_a_removed_entity_panics() {
+        let mut state = State::new_headless();
+        let (stale, _) = stale_and_reused_entity(&mut state);
+
+        stale.set_width(&mut state, Length::Pixels(30.0));
+    }
+
+    #[test]
+    fn add_font_registers_name() {
+        let mut state = State::new_headless();
+        let root = state.root;
+
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../resources/Roboto-Light.ttf");
+        state.add_font("light", path).unwrap();
+
+        assert!(state.available_fonts().contains(&"light".to_string()));
+
+        // The renderer loads the font data under the name, which resolve_font then looks up
+        let data = std::fs::read(path).unwrap();
+        assert!(state
+            .resource_manager
+            .fonts
+            .iter()
+            .any(|(name, font)| name == "light" && *font == data));
+
+        // Text using the font by name is measured with it rather than the regular font
+        let row = state
+            .add(root)
+            .set_flex_direction(&mut state, FlexDirection::Row);
+        let light = state
+            .add(row)
+            .set_text(&mut state, "Light")
+            .set_font(&mut state, "light".to_string())
+            .set_width(&mut state, Length::Auto);
+        let regular = state
+            .add(row)
+            .set_text(&mut state, "Light")
+            .set_width(&mut state, Length::Auto);
+
+        layout(&mut state);
+
+        let (light_width, _) = state
+            .font_metrics
+            .get_mut("light")
+            .unwrap()
+            .measure("Light", 16.0);
+        assert_eq!(state.transform.get_width(light), light_width);
+        assert_ne!(
+            state.transform.get_width(light),
+            state.transform.get_width(regular)
+        );
+    }
+
+    #[test]
+    fn add_font_rejects_data_which_is_not_a_font() {
+        let mut state = State::new_headless();
+
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml");
+        let error = state.add_font("broken", path).unwrap_err();
+
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert!(!state.available_fonts().contains(&"broken".to_string()));
+        assert!(!state.font_metrics.contains_key("broken"));
+
+        // Text naming the font falls back to the regular font
+        let root = state.root;
+        let row = state
+            .add(root)
+            .set_flex_direction(&mut state, FlexDirection::Row);
+        let broken = state
+            .add(row)
+            .set_text(&mut state, "Broken")
+            .set_font(&mut state, "broken".to_string())
+            .set_width(&mut state, Length::Auto);
+        let regular = state
+            .add(row)
+            .set_text(&mut state, "Broken")
+            .set_width(&mut state, Length::Auto);
+
+        layout(&mut state);
+
+        assert_eq!(
+            state.transform.get_width(broken),
+            state.transform.get_width(regular)
+        );
+    }
+
+    // Lays out the tree, which isn't requested by anything without a window
+    fn layout(state: &mut State) {
+        let mut event_manager = EventManager::new();
+
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        while !state.event_queue.is_empty() {
+            event_manager.flush_events(state);
+        }
+    }
+
+    // Two 200 by 200 boxes, the second overlapping the bottom right corner of the first
+    fn overlapping_boxes(state: &mut State) -> (Entity, Entity) {
+        let root = state.root;
+
+        let first = Element::new().build(state, root, |builder| {
+            builder
+                .set_position(Position::Absolute)
+                .set_left(Length::Pixels(50.0))
+                .set_top(Length::Pixels(50.0))
+                .set_width(Length::Pixels(200.0))
+                .set_height(Length::Pixels(200.0))
+        });
+
+        let second = Element::new().build(state, root, |builder| {
+            builder
+                .set_position(Position::Absolute)
+                .set_left(Length::Pixels(150.0))
+                .set_top(Length::Pixels(150.0))
+                .set_width(Length::Pixels(200.0))
+                .set_height(Length::Pixels(200.0))
+        });
+
+        (first, second)
+    }
+
+    #[test]
+    fn hit_test_finds_later_sibling_where_boxes_overlap() {
+        let mut state = State::new_headless();
+        let (first, second) = overlapping_boxes(&mut state);
+        layout(&mut state);
+
+        assert_eq!(state.hit_test(100.0, 100.0), Some(first));
+        assert_eq!(state.hit_test(200.0, 200.0), Some(second));
+        assert_eq!(state.hit_test(300.0, 300.0), Some(second));
+        assert_eq!(state.hit_test(10.0, 10.0), Some(state.root));
+        assert_eq!(state.hit_test(900.0, 10.0), None);
+    }
+
+    #[test]
+    fn hit_test_finds_sibling_moved_to_back() {
+        let mut state = State::new_headless();
+        let (first, _) = overlapping_boxes(&mut state);
+        state.move_to_back(first);
+        layout(&mut state);
+
+        assert_eq!(state.hit_test(200.0, 200.0), Some(first));
+    }
+
+    #[test]
+    fn hit_test_finds_higher_z_order_where_boxes_overlap() {
+        let mut state = State::new_headless();
+        let (first, second) = overlapping_boxes(&mut state);
+        first.set_z_order(&mut state, 1);
+        layout(&mut state);
+
+        assert_eq!(state.hit_test(200.0, 200.0), Some(first));
+        assert_eq!(state.hit_test(300.0, 300.0), Some(second));
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Shortcut {
+        Save,
+    }
+
+    #[test]
+    fn accelerator_sends_event_to_root() {
+        let mut state = State::new_headless();
+        let save = Accelerator::new(Key::Character("s".to_string())).with_ctrl();
+        state.add_accelerator(save, Event::new(Shortcut::Save));
+        state.drain_events();
+
+        state.modifiers.ctrl = true;
+        assert!(state.trigger_accelerator(&Key::Character("s".to_string())));
+
+        let events = state.drain_events();
+        assert_eq!(events, vec![Event::new(Shortcut::Save)]);
+        assert_eq!(events[0].target, state.root);
+    }
+
+    #[test]
+    fn removed_accelerator_is_not_triggered() {
+        let mut state = State::new_headless();
+        let save = Accelerator::new(Key::Character("s".to_string())).with_ctrl();
+        state.add_accelerator(save.clone(), Event::new(Shortcut::Save));
+
+        assert_eq!(
+            state.remove_accelerator(&save),
+            Some(Event::new(Shortcut::Save))
+        );
+        state.drain_events();
+
+        state.modifiers.ctrl = true;
+        assert!(!state.trigger_accelerator(&Key::Character("s".to_string())));
+        assert!(state.drain_events().is_empty());
+    }
+
+    #[test]
+    fn pending_events_shows_button_press() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let root = state.root;
+
+        let button = Button::with_label("Press").build(&mut state, root, |builder| builder);
+        while !state.event_queue.is_empty() {
+            event_manager.flush_events(&mut state);
+        }
+
+        state.hovered = button;
+        state.insert_event(Event::new(WindowEvent::MouseDown(MouseButton::Left)).target(button));
+        event_manager.flush_events(&mut state);
+
+        assert!(state
+            .pending_events()
+            .iter()
+            .any(|event| event.target == button && event.message.equals_a(&ButtonEvent::Pressed)));
+    }
+
+    #[test]
+    fn drain_events_returns_textbox_value() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let root = state.root;
+
+        let textbox = Textbox::new("").build(&mut state, root, |builder| builder);
+        while !state.event_queue.is_empty() {
+            event_manager.flush_events(&mut state);
+        }
+
+        state.hovered = textbox;
+        state.insert_event(Event::new(WindowEvent::MouseDown(MouseButton::Left)).target(textbox));
+        event_manager.flush_events(&mut state);
+
+        // Flushed one at a time because equal events waiting in the queue replace each other
+        for chr in "hello".chars() {
+            state.insert_event(Event::new(WindowEvent::CharInput(chr)).target(textbox));
+            event_manager.flush_events(&mut state);
+        }
+
+        state.insert_event(
+            Event::new(WindowEvent::KeyDown(Code::Enter, Some(Key::Enter), false)).target(textbox),
+        );
+        event_manager.flush_events(&mut state);
+
+        let events = state.drain_events();
+        assert!(events.iter().any(|event| event.target == textbox
+            && event
+                .message
+                .equals_a(&TextboxEvent::ValueChanged("hello".to_string()))));
+        assert!(state.pending_events().is_empty());
+    }
+
+    #[test]
+    fn expanding_removes_the_inline_styles_added_by_collapsing() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let root = state.root;
+
+        let flush = |state: &mut State, event_manager: &mut EventManager| {
+            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+            while !state.event_queue.is_empty() {
+                event_manager.flush_events(state);
+            }
+        };
+
+        state.insert_theme(".panel { height: 40px; }");
+        let panel = Element::new().build(&mut state, root, |builder| builder.class("panel"));
+
+        state.insert_event(Event::new(WindowEvent::Restyle).target(Entity::null()));
+        flush(&mut state, &mut event_manager);
+        assert_eq!(state.transform.get_height(panel), 40.0);
+
+        panel.collapse(&mut state, Duration::from_millis(0));
+        state.apply_animations();
+        assert_eq!(state.style.display.get(panel), Some(&Display::None));
+
+        panel.expand(&mut state, Duration::from_millis(0));
+        state.apply_animations();
+
+        // The height comes from the rule again rather than an inline copy of it
+        assert_eq!(state.style.height.get_inline(panel), None);
+        assert_eq!(state.style.display.get_inline(panel), None);
+        assert_eq!(state.style.overflow.get_inline(panel), None);
+
+        flush(&mut state, &mut event_manager);
+        assert_eq!(state.style.height.get(panel), Some(&Length::Pixels(40.0)));
+        assert_eq!(state.transform.get_height(panel), 40.0);
+    }
+}
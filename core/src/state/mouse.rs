@@ -1,5 +1,7 @@
 use crate::entity::Entity;
 
+use std::time::{Duration, Instant};
+
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum MouseButton {
     Left,
@@ -14,6 +16,15 @@ pub enum MouseButtonState {
     Released,
 }
 
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Modifier {
+    Shift,
+    Ctrl,
+    Alt,
+    // The windows key, or command on macOS
+    Logo,
+}
+
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub struct ModifiersState {
     pub shift: bool,
@@ -33,6 +44,49 @@ impl Default for ModifiersState {
     }
 }
 
+impl ModifiersState {
+    pub fn get(&self, modifier: Modifier) -> bool {
+        match modifier {
+            Modifier::Shift => self.shift,
+            Modifier::Ctrl => self.ctrl,
+            Modifier::Alt => self.alt,
+            Modifier::Logo => self.logo,
+        }
+    }
+
+    // Returns true if the modifier used for shortcuts is held, command on macOS and ctrl on other platforms
+    pub fn ctrl_or_cmd(&self) -> bool {
+        self.ctrl_or_cmd_on(cfg!(target_os = "macos"))
+    }
+
+    // Like ctrl_or_cmd but for macOS when macos is true, whichever platform this is running on
+    pub fn ctrl_or_cmd_on(&self, macos: bool) -> bool {
+        if macos {
+            self.logo
+        } else {
+            self.ctrl
+        }
+    }
+
+    // Returns true if the modifier is held and no others are
+    pub fn only(&self, modifier: Modifier) -> bool {
+        let mut only = ModifiersState::default();
+        match modifier {
+            Modifier::Shift => only.shift = true,
+            Modifier::Ctrl => only.ctrl = true,
+            Modifier::Alt => only.alt = true,
+            Modifier::Logo => only.logo = true,
+        }
+
+        *self == only
+    }
+
+    // Returns true if no modifiers are held
+    pub fn none(&self) -> bool {
+        *self == ModifiersState::default()
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub struct MouseButtonData {
     pub state: MouseButtonState,
@@ -40,6 +94,8 @@ pub struct MouseButtonData {
     pub pos_up: (f32, f32),
     pub pressed: Entity,
     pub released: Entity,
+    // Time of the last press which could start a double click
+    pub last_click: Option<Instant>,
 }
 
 impl Default for MouseButtonData {
@@ -50,6 +106,7 @@ impl Default for MouseButtonData {
             pos_up: (0.0, 0.0),
             pressed: Entity::null(),
             released: Entity::null(),
+            last_click: None,
         }
     }
 }
@@ -62,6 +119,11 @@ pub struct MouseState {
     pub left: MouseButtonData,
     pub right: MouseButtonData,
     pub middle: MouseButtonData,
+
+    // Maximum time between two presses of the same button to count as a double click
+    pub double_click_time: Duration,
+    // Maximum distance the cursor can move between the two presses of a double click
+    pub double_click_distance: f32,
 }
 
 impl Default for MouseState {
@@ -72,6 +134,103 @@ impl Default for MouseState {
             left: MouseButtonData::default(),
             right: MouseButtonData::default(),
             middle: MouseButtonData::default(),
+            double_click_time: Duration::from_millis(500),
+            double_click_distance: 4.0,
         }
     }
 }
+
+impl MouseState {
+    // Records a press of a button at the current cursor position and returns true if it completes a double click
+    // Must be called before the pos_down of the button is updated
+    pub fn record_click(&mut self, button: MouseButton) -> bool {
+        let cursorx = self.cursorx;
+        let cursory = self.cursory;
+        let double_click_time = self.double_click_time;
+        let double_click_distance = self.double_click_distance;
+
+        let data = match button {
+            MouseButton::Left => &mut self.left,
+            MouseButton::Right => &mut self.right,
+            MouseButton::Middle => &mut self.middle,
+            _ => return false,
+        };
+
+        let is_double_click = match data.last_click {
+            Some(time) => {
+                time.elapsed() <= double_click_time
+                    && (cursorx - data.pos_down.0).abs() <= double_click_distance
+                    && (cursory - data.pos_down.1).abs() <= double_click_distance
+            }
+            None => false,
+        };
+
+        // A third press should start a new double click rather than complete another one
+        data.last_click = if is_double_click {
+            None
+        } else {
+            Some(Instant::now())
+        };
+
+        is_double_click
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ctrl_is_the_shortcut_modifier_off_macos() {
+        let mut modifiers = ModifiersState::default();
+        modifiers.ctrl = true;
+
+        assert!(modifiers.ctrl_or_cmd_on(false));
+        assert!(!modifiers.ctrl_or_cmd_on(true));
+    }
+
+    #[test]
+    fn command_is_the_shortcut_modifier_on_macos() {
+        let mut modifiers = ModifiersState::default();
+        modifiers.logo = true;
+
+        assert!(modifiers.ctrl_or_cmd_on(true));
+        assert!(!modifiers.ctrl_or_cmd_on(false));
+    }
+
+    #[test]
+    fn ctrl_or_cmd_matches_this_platform() {
+        let mut modifiers = ModifiersState::default();
+        modifiers.ctrl = true;
+        modifiers.logo = true;
+
+        assert_eq!(
+            modifiers.ctrl_or_cmd(),
+            modifiers.ctrl_or_cmd_on(cfg!(target_os = "macos"))
+        );
+    }
+
+    #[test]
+    fn none_is_true_only_without_modifiers() {
+        let mut modifiers = ModifiersState::default();
+        assert!(modifiers.none());
+        assert!(!modifiers.ctrl_or_cmd_on(true));
+        assert!(!modifiers.ctrl_or_cmd_on(false));
+
+        modifiers.alt = true;
+        assert!(!modifiers.none());
+    }
+
+    #[test]
+    fn only_is_false_when_other_modifiers_are_held() {
+        let mut modifiers = ModifiersState::default();
+        modifiers.logo = true;
+        assert!(modifiers.only(Modifier::Logo));
+        assert!(!modifiers.only(Modifier::Ctrl));
+
+        modifiers.shift = true;
+        assert!(!modifiers.only(Modifier::Logo));
+        assert!(!modifiers.only(Modifier::Shift));
+        assert!(modifiers.get(Modifier::Shift));
+    }
+}
@@ -1,18 +1,16 @@
 use std::collections::HashMap;
 
+use crate::style::themes::Theme;
+
 // use byteorder::{ByteOrder, LittleEndian};
 // use image::GenericImageView;
 
-pub struct Image {
-    pub width: usize,
-    pub height: usize,
-    pub data: Vec<u32>,
-}
-
 pub struct ResourceManager {
     //pub images: HashMap<String, Image>,
     pub stylesheets: Vec<String>, // Stylesheets refer to a fiel path
     pub themes: Vec<String>,      // Themes are the string content stylesheets
+    pub fonts: Vec<(String, Vec<u8>)>, // Font data waiting to be loaded by the renderer
+    pub theme: Theme, // Values of the variables referenced by the themes and stylesheets
 }
 
 impl ResourceManager {
@@ -21,6 +19,8 @@ impl ResourceManager {
             //images: HashMap::new(),
             stylesheets: Vec::new(),
             themes: Vec::new(),
+            fonts: Vec::new(),
+            theme: Theme::default(),
         }
     }
 
@@ -1,4 +1,5 @@
-use crate::state::animator::{AnimationState, Interpolator};
+use crate::state::animator::{AnimationState, Interpolator, LoopMode};
+use crate::state::storage::generation_check::GenerationCheck;
 use crate::state::Entity;
 
 #[derive(Copy, Clone)]
@@ -198,6 +199,7 @@ pub struct AnimatableStorage<T: Interpolator> {
     pub animations: Vec<AnimationState<T>>,
     // Active Animations
     pub active_animations: Vec<AnimationState<T>>,
+    generations: GenerationCheck,
 }
 
 impl<T> AnimatableStorage<T>
@@ -213,11 +215,18 @@ where
             inline_data: Vec::new(),
             animations: Vec::new(),
             active_animations: Vec::new(),
+            generations: GenerationCheck::default(),
         }
     }
 
     // Insert inline data
     pub fn insert(&mut self, entity: Entity, value: T) {
+        if entity.is_null() {
+            return;
+        }
+
+        self.generations.check(entity);
+
         if entity.index() >= self.entity_indices.len() {
             // Resize entity indices to include new entity
             self.entity_indices
@@ -231,7 +240,8 @@ where
         } else {
             let data_index = self.entity_indices[entity.index()].data_index;
 
-            if data_index.index() >= self.inline_data.len() {
+            // An entity linked to a rule gets a new slot, its index refers to the rule data and not to inline data
+            if !data_index.is_inline() || data_index.index() >= self.inline_data.len() {
                 self.entity_indices[entity.index()].data_index = Index::new(self.inline_data.len())
                     .inherited(false)
                     .inline(true);
@@ -282,6 +292,8 @@ where
             self.active_animations[animation_index].duration = animation.duration;
             self.active_animations[animation_index].delay = animation.delay;
             self.active_animations[animation_index].keyframes = animation.keyframes.clone();
+            self.active_animations[animation_index].easing = animation.easing;
+            self.active_animations[animation_index].loop_mode = animation.loop_mode;
             // FIX ME (Needed because sometimes drawing happens before animation for some reason. Stops output being null if accessed before animated)
             self.active_animations[animation_index].output =
                 Some(animation.keyframes.first().unwrap().1.clone());
@@ -299,10 +311,59 @@ where
         }
     }
 
+    // Plays an animation on the entity without storing it as a definition
+    // Replaces any animation already playing on the entity
+    pub fn play(&mut self, entity: Entity, mut animation: AnimationState<T>) {
+        if animation.keyframes.is_empty() {
+            return;
+        }
+
+        if entity.index() >= self.entity_indices.len() {
+            self.entity_indices
+                .resize(entity.index() + 1, Default::default());
+        }
+
+        animation.active = true;
+        animation.t0 = 0.0;
+        animation.t = 0.0;
+        animation.start_time = std::time::Instant::now();
+        animation.output = Some(animation.keyframes.first().unwrap().1.clone());
+
+        let animation_index = self.entity_indices[entity.index()].animation_id;
+
+        if animation_index < self.active_animations.len() {
+            animation.entities = self.active_animations[animation_index].entities.clone();
+            self.active_animations[animation_index] = animation;
+        } else {
+            animation.entities.push(entity);
+            self.entity_indices[entity.index()].animation_id = self.active_animations.len();
+            self.active_animations.push(animation);
+        }
+    }
+
+    // Stops any animation playing on the entity, returning the property to its inline or rule value
+    pub fn stop(&mut self, entity: Entity) {
+        if entity.index() >= self.entity_indices.len() {
+            return;
+        }
+
+        let animation_index = self.entity_indices[entity.index()].animation_id;
+
+        if let Some(animation) = self.active_animations.get_mut(animation_index) {
+            animation.loop_mode = LoopMode::Once;
+            animation.persistent = false;
+            animation.active = false;
+            animation.t0 = 1.0;
+            animation.t = 1.0;
+        }
+
+        self.remove_innactive_animations();
+    }
+
     pub fn animate(&mut self, current_time: std::time::Instant) {
         for state in self.active_animations.iter_mut() {
             // If the animation is already finished then return false
-            if state.t0 == 1.0 {
+            if state.is_finished() {
                 continue;
             }
 
@@ -323,7 +384,23 @@ where
             // Update time state
             state.t = (elapsed_time.as_secs_f32() / state.duration.as_secs_f32()) - state.delay;
 
-            if state.t >= 1.0 {
+            // Wrap or reverse the time of looping animations once the delay has passed
+            if state.t > 0.0 {
+                match state.loop_mode {
+                    LoopMode::Once => {}
+
+                    LoopMode::Loop => {
+                        state.t = state.t.fract();
+                    }
+
+                    LoopMode::PingPong => {
+                        let cycle = state.t % 2.0;
+                        state.t = if cycle > 1.0 { 2.0 - cycle } else { cycle };
+                    }
+                }
+            }
+
+            if state.t >= 1.0 && state.loop_mode == LoopMode::Once {
                 //Animation is finished
                 state.output = Some(T::interpolate(&start.1, &end.1, 1.0));
 
@@ -336,7 +413,11 @@ where
             } else if state.t <= 0.0 {
                 state.output = Some(start.1.clone());
             } else {
-                state.output = Some(T::interpolate(&start.1, &end.1, state.t));
+                state.output = Some(T::interpolate(
+                    &start.1,
+                    &end.1,
+                    state.easing.apply(state.t),
+                ));
             }
         }
 
@@ -348,14 +429,14 @@ where
         let inactive: Vec<AnimationState<T>> = self
             .active_animations
             .iter()
-            .filter(|e| e.t0 == 1.0 && !e.persistent)
+            .filter(|e| e.is_finished() && !e.persistent)
             .cloned()
             .collect();
 
         // Remove inactive animation states from active animations list
         // Retains persistent animations
         self.active_animations
-            .retain(|e| e.t0 < 1.0 || e.persistent);
+            .retain(|e| !e.is_finished() || e.persistent);
 
         for state in inactive.into_iter() {
             for entity in state.entities.iter() {
@@ -481,7 +562,7 @@ where
 
     pub fn has_animations(&self) -> bool {
         for state in self.active_animations.iter() {
-            if state.t0 < 1.0 {
+            if !state.is_finished() {
                 return true;
             }
         }
@@ -497,6 +578,52 @@ where
         self.entity_indices[entity.index()].data_index = Index::default();
     }
 
+    // Removes the entity, any inline data belonging to it, and its links to active animations
+    pub fn remove(&mut self, entity: Entity) {
+        self.generations.removed(entity);
+
+        if entity.index() >= self.entity_indices.len() {
+            return;
+        }
+
+        self.remove_inline_data(entity);
+
+        for animation in self.active_animations.iter_mut() {
+            animation.entities.retain(|e| *e != entity);
+        }
+
+        self.entity_indices[entity.index()] = DataIndex::default();
+    }
+
+    // Removes the inline value of an entity, which is then styled by its matching rules again once restyled
+    // Any animation playing on the entity is kept
+    pub fn remove_inline(&mut self, entity: Entity) {
+        self.generations.check(entity);
+
+        if entity.index() >= self.entity_indices.len() {
+            return;
+        }
+
+        self.remove_inline_data(entity);
+    }
+
+    fn remove_inline_data(&mut self, entity: Entity) {
+        let data_index = self.entity_indices[entity.index()].data_index;
+
+        if data_index.is_inline() && data_index.index() < self.inline_data.len() {
+            // Move the last inline value into the freed slot and update the entity which pointed to it
+            let last_index = self.inline_data.len() - 1;
+            self.inline_data.swap_remove(data_index.index());
+            if let Some(index) = self.entity_indices.iter_mut().find(|index| {
+                index.data_index.is_inline() && index.data_index.index() == last_index
+            }) {
+                index.data_index.set_value(data_index.index());
+            }
+        }
+
+        self.entity_indices[entity.index()].data_index = Index::default();
+    }
+
     pub fn link_rule(&mut self, entity: Entity, rule_list: &Vec<usize>) -> bool {
         // Check if the entity already has an inline style. If so then rules don't affect it.
         if entity.index() < self.entity_indices.len() {
@@ -550,8 +677,23 @@ where
         self.animations.push(animation_state);
     }
 
+    // Returns the inline value of an entity, ignoring any animation or value linked from a rule
+    pub fn get_inline(&self, entity: Entity) -> Option<&T> {
+        self.generations.check(entity);
+
+        let data_index = self.entity_indices.get(entity.index())?.data_index;
+
+        if data_index.is_inline() {
+            self.inline_data.get(data_index.index())
+        } else {
+            None
+        }
+    }
+
     // Get the current value (either animation or data rule)
     pub fn get(&self, entity: Entity) -> Option<&T> {
+        self.generations.check(entity);
+
         if entity.index() >= self.entity_indices.len() {
             return None;
         }
@@ -667,3 +809,39 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn ping_pong_reverses_past_the_duration() {
+        let mut storage = AnimatableStorage::<f32>::new();
+        let entity = Entity::new(1, 0);
+
+        storage.play(
+            entity,
+            AnimationState::new()
+                .with_duration(Duration::from_secs(1))
+                .with_loop_mode(LoopMode::PingPong)
+                .with_keyframe((0.0, 0.0))
+                .with_keyframe((1.0, 10.0)),
+        );
+
+        let start_time = storage.active_animations[0].start_time;
+
+        storage.animate(start_time + Duration::from_millis(250));
+        assert_eq!(storage.get(entity), Some(&2.5));
+
+        // Plays backwards for the second period
+        storage.animate(start_time + Duration::from_millis(1250));
+        assert_eq!(storage.get(entity), Some(&7.5));
+
+        // And forwards again for the third
+        storage.animate(start_time + Duration::from_millis(2250));
+        assert_eq!(storage.get(entity), Some(&2.5));
+
+        assert!(storage.is_animating(entity));
+    }
+}
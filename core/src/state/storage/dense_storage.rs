@@ -1,8 +1,10 @@
 use crate::entity::Entity;
+use crate::state::storage::generation_check::GenerationCheck;
 
 pub struct DenseStorage<T> {
     pub indices: Vec<u32>,
     pub data: Vec<T>,
+    generations: GenerationCheck,
 }
 
 impl<T> DenseStorage<T>
@@ -13,10 +15,17 @@ where
         DenseStorage {
             indices: Vec::new(),
             data: Vec::new(),
+            generations: GenerationCheck::default(),
         }
     }
 
     pub fn insert(&mut self, entity: Entity, value: T) {
+        if entity.is_null() {
+            return;
+        }
+
+        self.generations.check(entity);
+
         if entity.index() >= self.indices.len() {
             self.indices.resize(entity.index() + 1, std::u32::MAX);
             self.indices[entity.index()] = self.data.len() as u32;
@@ -32,9 +41,30 @@ where
         }
     }
 
-    pub fn remove(&mut self, _entity: Entity) {}
+    pub fn remove(&mut self, entity: Entity) {
+        self.generations.removed(entity);
+
+        if entity.index() >= self.indices.len() {
+            return;
+        }
+
+        let data_index = self.indices[entity.index()] as usize;
+
+        if data_index < self.data.len() {
+            // Move the last value into the freed slot and update the index which pointed to it
+            let last_index = (self.data.len() - 1) as u32;
+            self.data.swap_remove(data_index);
+            if let Some(index) = self.indices.iter_mut().find(|index| **index == last_index) {
+                *index = data_index as u32;
+            }
+        }
+
+        self.indices[entity.index()] = std::u32::MAX;
+    }
 
     pub fn get(&self, entity: Entity) -> Option<&T> {
+        self.generations.check(entity);
+
         if entity.index() >= self.indices.len() {
             return None;
         }
@@ -49,6 +79,8 @@ where
     }
 
     pub fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
+        self.generations.check(entity);
+
         if entity.index() >= self.indices.len() {
             return None;
         }
@@ -63,6 +95,8 @@ where
     }
 
     pub fn set(&mut self, entity: Entity, value: T) {
+        self.generations.check(entity);
+
         if entity.index() >= self.indices.len() {
             self.insert(entity, value);
             return;
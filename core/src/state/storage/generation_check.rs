@@ -0,0 +1,44 @@
+use crate::state::Entity;
+
+// Catches entities being used after they were removed from a storage, when their index may have been reused
+// by a new entity whose data they would silently change
+// Removing an entity raises the lowest generation allowed for its index, the check only runs in debug builds
+#[derive(Clone, Default)]
+pub struct GenerationCheck {
+    #[cfg(debug_assertions)]
+    min_generations: Vec<u16>,
+}
+
+#[cfg(debug_assertions)]
+impl GenerationCheck {
+    pub fn removed(&mut self, entity: Entity) {
+        let index = entity.index();
+        if index >= self.min_generations.len() {
+            self.min_generations.resize(index + 1, 0);
+        }
+
+        let generation = (entity.generation() as u16).wrapping_add(1);
+        if generation > self.min_generations[index] {
+            self.min_generations[index] = generation;
+        }
+    }
+
+    // Panics if the entity was removed
+    pub fn check(&self, entity: Entity) {
+        if let Some(min_generation) = self.min_generations.get(entity.index()) {
+            assert!(
+                entity.generation() >= *min_generation as usize,
+                "Entity {} (generation {}) used after being removed",
+                entity,
+                entity.generation(),
+            );
+        }
+    }
+}
+
+#[cfg(not(debug_assertions))]
+impl GenerationCheck {
+    pub fn removed(&mut self, _entity: Entity) {}
+
+    pub fn check(&self, _entity: Entity) {}
+}
@@ -1,3 +1,4 @@
 pub mod animatable_storage;
 pub mod dense_storage;
+pub mod generation_check;
 pub mod style_storage;
@@ -1,3 +1,4 @@
+use crate::state::storage::generation_check::GenerationCheck;
 use crate::state::Entity;
 // Could use last bit of entity_indices index to denote whether the data is from a rule or an inline property
 
@@ -93,6 +94,7 @@ pub struct StyleStorage<T> {
     pub rule_indices: Vec<usize>,
     pub data: Vec<T>,
     pub inline_data: Vec<T>,
+    generations: GenerationCheck,
 }
 
 impl<T> StyleStorage<T>
@@ -105,11 +107,18 @@ where
             rule_indices: Vec::new(),
             data: Vec::new(),
             inline_data: Vec::new(),
+            generations: GenerationCheck::default(),
         }
     }
 
     //Insert inline style
     pub fn insert(&mut self, entity: Entity, value: T) {
+        if entity.is_null() {
+            return;
+        }
+
+        self.generations.check(entity);
+
         if entity.index() >= self.entity_indices.len() {
             self.entity_indices
                 .resize(entity.index() + 1, Default::default());
@@ -119,7 +128,10 @@ where
         } else {
             let data_index = self.entity_indices[entity.index()].index();
 
-            if data_index >= self.inline_data.len() {
+            // An entity linked to a rule gets a new slot, its index refers to the rule data and not to inline data
+            if !self.entity_indices[entity.index()].is_inline()
+                || data_index >= self.inline_data.len()
+            {
                 self.entity_indices[entity.index()] =
                     Index::new(self.inline_data.len()).inline(true);
 
@@ -178,6 +190,41 @@ where
         self.entity_indices[entity.index()] = Index::default();
     }
 
+    // Removes the entity and any inline data belonging to it
+    pub fn remove(&mut self, entity: Entity) {
+        self.generations.removed(entity);
+        self.remove_inline_data(entity);
+    }
+
+    // Removes the inline value of an entity, which is then styled by its matching rules again once restyled
+    pub fn remove_inline(&mut self, entity: Entity) {
+        self.generations.check(entity);
+        self.remove_inline_data(entity);
+    }
+
+    fn remove_inline_data(&mut self, entity: Entity) {
+        if entity.index() >= self.entity_indices.len() {
+            return;
+        }
+
+        let data_index = self.entity_indices[entity.index()];
+
+        if data_index.is_inline() && data_index.index() < self.inline_data.len() {
+            // Move the last inline value into the freed slot and update the entity which pointed to it
+            let last_index = self.inline_data.len() - 1;
+            self.inline_data.swap_remove(data_index.index());
+            if let Some(index) = self
+                .entity_indices
+                .iter_mut()
+                .find(|index| index.is_inline() && index.index() == last_index)
+            {
+                index.set_value(data_index.index());
+            }
+        }
+
+        self.entity_indices[entity.index()] = Index::default();
+    }
+
     // Returns true if
     pub fn link_rule(&mut self, entity: Entity, rule_list: &Vec<usize>) -> bool {
         // Check if the entity already has an inline style. If so then rules don't affect it.
@@ -225,8 +272,23 @@ where
         }
     }
 
+    // Returns the inline value of an entity, ignoring any value linked from a rule
+    pub fn get_inline(&self, entity: Entity) -> Option<&T> {
+        self.generations.check(entity);
+
+        let data_index = self.entity_indices.get(entity.index())?;
+
+        if data_index.is_inline() {
+            self.inline_data.get(data_index.index())
+        } else {
+            None
+        }
+    }
+
     // Get data linked to entity
     pub fn get(&self, entity: Entity) -> Option<&T> {
+        self.generations.check(entity);
+
         if entity.index() >= self.entity_indices.len() {
             return None;
         }
@@ -249,6 +311,8 @@ where
     }
 
     pub fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
+        self.generations.check(entity);
+
         if entity.index() >= self.entity_indices.len() {
             return None;
         }
@@ -104,6 +104,7 @@ impl From<Color> for femtovg::Color {
 }
 
 /// Compare two colors (Do not take care of alpha)
+// Colors differing only in alpha are equal, so compare the alpha separately where it matters
 impl PartialEq for Color {
     fn eq(&self, other: &Color) -> bool {
         self.r() == other.r() && self.g() == other.g() && self.b() == other.b()
@@ -123,8 +124,69 @@ impl std::fmt::Debug for Color {
     }
 }
 
+// Colors are serialized as a hex string with the alpha first, like #ff000000 for opaque black
+#[cfg(feature = "serde")]
+impl serde::Serialize for Color {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let data = self.data;
+        serializer.serialize_str(&format!("#{:08x}", data))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Color {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+
+        // Unlike Color::from, which falls back to transparent black, a string which isn't a color is an error
+        let digits = hex.trim_start_matches('#');
+        let valid = (digits.len() == 6 || digits.len() == 8)
+            && digits.chars().all(|c| c.is_ascii_hexdigit());
+        if !valid {
+            return Err(serde::de::Error::custom(format!(
+                "invalid color `{}`, expected #rrggbb or #aarrggbb",
+                hex
+            )));
+        }
+
+        Ok(Color::from(digits))
+    }
+}
+
 impl Default for Color {
     fn default() -> Self {
         Color::rgba(0, 0, 0, 0)
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn color_round_trips_with_alpha() {
+        let color = Color::rgba(10, 20, 30, 40);
+
+        let json = serde_json::to_string(&color).unwrap();
+        assert_eq!(json, "\"#280a141e\"");
+
+        let parsed: Color = serde_json::from_str(&json).unwrap();
+        // PartialEq ignores the alpha
+        assert_eq!(parsed, color);
+        assert_eq!(parsed.a(), 40);
+    }
+
+    #[test]
+    fn rgb_color_is_opaque() {
+        let parsed: Color = serde_json::from_str("\"#0a141e\"").unwrap();
+        assert_eq!(parsed, Color::rgb(10, 20, 30));
+        assert_eq!(parsed.a(), 255);
+    }
+
+    #[test]
+    fn invalid_color_is_an_error() {
+        assert!(serde_json::from_str::<Color>("\"red\"").is_err());
+        assert!(serde_json::from_str::<Color>("\"#12345\"").is_err());
+        assert!(serde_json::from_str::<Color>("\"#gg0000\"").is_err());
+    }
+}
@@ -0,0 +1,267 @@
+use crate::state::style::*;
+use crate::{Entity, Rect, State};
+
+// A snapshot of the values used for an entity after resolving its inline styles and the rules matching its
+// element, id, classes and pseudo-classes, with properties the entity doesn't have set reporting their default
+// Visibility and opacity include those inherited from the ancestors of the entity
+#[derive(Debug, Clone)]
+pub struct ComputedStyle {
+    pub element: Option<String>,
+    pub id: Option<String>,
+    pub classes: Vec<String>,
+    pub pseudo_classes: PseudoClasses,
+
+    // General
+    pub display: Display,
+    pub visibility: Visibility,
+    pub opacity: f32,
+    pub overflow: Overflow,
+    pub z_order: i32,
+
+    // Positioning
+    pub position: Position,
+    pub left: Length,
+    pub right: Length,
+    pub top: Length,
+    pub bottom: Length,
+
+    // Size
+    pub width: Length,
+    pub height: Length,
+    pub min_width: Length,
+    pub min_height: Length,
+    pub max_width: Length,
+    pub max_height: Length,
+
+    // Margin
+    pub margin_left: Length,
+    pub margin_right: Length,
+    pub margin_top: Length,
+    pub margin_bottom: Length,
+
+    // Padding
+    pub padding_left: Length,
+    pub padding_right: Length,
+    pub padding_top: Length,
+    pub padding_bottom: Length,
+
+    // Child Space
+    pub child_left: Length,
+    pub child_right: Length,
+    pub child_top: Length,
+    pub child_bottom: Length,
+
+    // Border
+    pub border_width: Length,
+    pub border_color: Color,
+    pub border_radius_top_left: Length,
+    pub border_radius_top_right: Length,
+    pub border_radius_bottom_left: Length,
+    pub border_radius_bottom_right: Length,
+
+    // Outline
+    pub outline_width: Length,
+    pub outline_color: Color,
+    pub outline_offset: Length,
+
+    // Flexbox
+    pub flex_direction: FlexDirection,
+    pub justify_content: JustifyContent,
+    pub align_items: AlignItems,
+    pub align_content: AlignContent,
+    pub align_self: AlignSelf,
+    pub flex_grow: f32,
+    pub flex_shrink: f32,
+    pub flex_basis: f32,
+
+    // Background
+    pub background_color: Color,
+
+    // Text
+    pub font_color: Color,
+    pub font_size: f32,
+    pub text_align: Align,
+    pub text_justify: Justify,
+    pub text_wrap: bool,
+
+    // Text Input
+    pub selection_color: Color,
+    pub caret_color: Color,
+
+    // The bounds computed by the last layout
+    pub bounds: Rect,
+}
+
+impl ComputedStyle {
+    pub fn new(state: &State, entity: Entity) -> Self {
+        let style = &state.style;
+
+        let mut classes: Vec<String> = style
+            .classes
+            .get(entity)
+            .map(|classes| classes.iter().cloned().collect())
+            .unwrap_or_default();
+        classes.sort();
+
+        ComputedStyle {
+            element: style.element_names.get(entity).cloned(),
+            id: style.ids.get_by_right(&entity).cloned(),
+            classes,
+            pseudo_classes: style
+                .pseudo_classes
+                .get(entity)
+                .cloned()
+                .unwrap_or_default(),
+
+            display: style.display.get(entity).cloned().unwrap_or_default(),
+            visibility: state.transform.get_visibility(entity),
+            opacity: state.transform.get_opacity(entity),
+            overflow: style.overflow.get(entity).cloned().unwrap_or_default(),
+            z_order: state.transform.get_z_order(entity),
+
+            position: style.position.get(entity).cloned().unwrap_or_default(),
+            left: style.left.get(entity).cloned().unwrap_or_default(),
+            right: style.right.get(entity).cloned().unwrap_or_default(),
+            top: style.top.get(entity).cloned().unwrap_or_default(),
+            bottom: style.bottom.get(entity).cloned().unwrap_or_default(),
+
+            width: style.width.get(entity).cloned().unwrap_or_default(),
+            height: style.height.get(entity).cloned().unwrap_or_default(),
+            min_width: style.min_width.get(entity).cloned().unwrap_or_default(),
+            min_height: style.min_height.get(entity).cloned().unwrap_or_default(),
+            max_width: style.max_width.get(entity).cloned().unwrap_or_default(),
+            max_height: style.max_height.get(entity).cloned().unwrap_or_default(),
+
+            margin_left: style.margin_left.get(entity).cloned().unwrap_or_default(),
+            margin_right: style.margin_right.get(entity).cloned().unwrap_or_default(),
+            margin_top: style.margin_top.get(entity).cloned().unwrap_or_default(),
+            margin_bottom: style.margin_bottom.get(entity).cloned().unwrap_or_default(),
+
+            padding_left: style.padding_left.get(entity).cloned().unwrap_or_default(),
+            padding_right: style.padding_right.get(entity).cloned().unwrap_or_default(),
+            padding_top: style.padding_top.get(entity).cloned().unwrap_or_default(),
+            padding_bottom: style
+                .padding_bottom
+                .get(entity)
+                .cloned()
+                .unwrap_or_default(),
+
+            child_left: style.child_left.get(entity).cloned().unwrap_or_default(),
+            child_right: style.child_right.get(entity).cloned().unwrap_or_default(),
+            child_top: style.child_top.get(entity).cloned().unwrap_or_default(),
+            child_bottom: style.child_bottom.get(entity).cloned().unwrap_or_default(),
+
+            border_width: style.border_width.get(entity).cloned().unwrap_or_default(),
+            border_color: style.border_color.get(entity).cloned().unwrap_or_default(),
+            border_radius_top_left: style
+                .border_radius_top_left
+                .get(entity)
+                .cloned()
+                .unwrap_or_default(),
+            border_radius_top_right: style
+                .border_radius_top_right
+                .get(entity)
+                .cloned()
+                .unwrap_or_default(),
+            border_radius_bottom_left: style
+                .border_radius_bottom_left
+                .get(entity)
+                .cloned()
+                .unwrap_or_default(),
+            border_radius_bottom_right: style
+                .border_radius_bottom_right
+                .get(entity)
+                .cloned()
+                .unwrap_or_default(),
+
+            outline_width: style.outline_width.get(entity).cloned().unwrap_or_default(),
+            outline_color: style.outline_color.get(entity).cloned().unwrap_or_default(),
+            outline_offset: style
+                .outline_offset
+                .get(entity)
+                .cloned()
+                .unwrap_or_default(),
+
+            flex_direction: style
+                .flex_direction
+                .get(entity)
+                .cloned()
+                .unwrap_or_default(),
+            justify_content: style
+                .justify_content
+                .get(entity)
+                .cloned()
+                .unwrap_or_default(),
+            align_items: style.align_items.get(entity).cloned().unwrap_or_default(),
+            align_content: style.align_content.get(entity).cloned().unwrap_or_default(),
+            align_self: style.align_self.get(entity).cloned().unwrap_or_default(),
+            flex_grow: style.flex_grow.get(entity).cloned().unwrap_or_default(),
+            flex_shrink: style.flex_shrink.get(entity).cloned().unwrap_or_default(),
+            flex_basis: style.flex_basis.get(entity).cloned().unwrap_or_default(),
+
+            background_color: style
+                .background_color
+                .get(entity)
+                .cloned()
+                .unwrap_or_default(),
+
+            // The same defaults used when drawing text
+            font_color: style
+                .font_color
+                .get(entity)
+                .cloned()
+                .unwrap_or(Color::rgb(255, 255, 255)),
+            font_size: style.font_size.get(entity).cloned().unwrap_or(16.0),
+            text_align: style.text_align.get(entity).cloned().unwrap_or_default(),
+            text_justify: style.text_justify.get(entity).cloned().unwrap_or_default(),
+            text_wrap: style.text_wrap.get(entity).cloned().unwrap_or_default(),
+
+            selection_color: style
+                .selection_color
+                .get(entity)
+                .cloned()
+                .unwrap_or(DEFAULT_SELECTION_COLOR),
+            caret_color: style
+                .caret_color
+                .get(entity)
+                .cloned()
+                .unwrap_or(DEFAULT_CARET_COLOR),
+
+            bounds: Rect::new(
+                state.transform.get_posx(entity),
+                state.transform.get_posy(entity),
+                state.transform.get_width(entity),
+                state.transform.get_height(entity),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{apply_styles, BuildHandler, Element, PropSet};
+
+    #[test]
+    fn computed_style_includes_rules_matching_classes() {
+        let mut state = State::new_headless();
+        let root = state.root;
+
+        state.insert_theme(".red { background-color: #ff0000; } .wide { width: 200px; }");
+
+        let entity = Element::new().build(&mut state, root, |builder| {
+            builder.class("red").set_height(Length::Pixels(50.0))
+        });
+
+        let hierarchy = state.hierarchy.clone();
+        apply_styles(&mut state, &hierarchy);
+
+        let computed = state.computed_style(entity);
+        assert_eq!(computed.classes, vec!["red".to_string()]);
+        assert_eq!(computed.background_color, Color::rgb(255, 0, 0));
+        assert_eq!(computed.height, Length::Pixels(50.0));
+
+        // Rules for classes the entity doesn't have don't apply
+        assert_eq!(computed.width, Length::default());
+    }
+}
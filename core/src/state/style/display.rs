@@ -1,6 +1,7 @@
 use crate::state::animator::Interpolator;
 
 #[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Display {
     None,
     Normal,
@@ -15,6 +16,7 @@ impl Default for Display {
 }
 
 #[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Visibility {
     Visible,
     Invisible,
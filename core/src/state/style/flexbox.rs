@@ -1,6 +1,7 @@
 use crate::entity::Entity;
 
 #[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FlexDirection {
     Row,
     //RowReverse,     //TODO
@@ -28,6 +29,7 @@ impl Default for FlexWrap {
 }
 
 #[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum JustifyContent {
     FlexStart,
     FlexEnd,
@@ -45,6 +47,7 @@ impl Default for JustifyContent {
 }
 
 #[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AlignItems {
     //None,
     FlexStart,
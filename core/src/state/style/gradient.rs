@@ -0,0 +1,152 @@
+use crate::style::color::Color;
+
+// The direction a linear gradient runs across the box of an entity
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GradientDirection {
+    LeftToRight,
+    RightToLeft,
+    TopToBottom,
+    BottomToTop,
+    // Angle in degrees, measured clockwise from pointing up, as in CSS
+    // So 0.0 runs bottom to top and 90.0 runs left to right
+    Angle(f32),
+}
+
+impl Default for GradientDirection {
+    fn default() -> Self {
+        GradientDirection::LeftToRight
+    }
+}
+
+impl GradientDirection {
+    // Returns the start and end points of the gradient line across a box, as (start_x, start_y, end_x, end_y)
+    pub fn points(&self, x: f32, y: f32, width: f32, height: f32) -> (f32, f32, f32, f32) {
+        match self {
+            GradientDirection::LeftToRight => (x, y, x + width, y),
+            GradientDirection::RightToLeft => (x + width, y, x, y),
+            GradientDirection::TopToBottom => (x, y, x, y + height),
+            GradientDirection::BottomToTop => (x, y + height, x, y),
+            GradientDirection::Angle(angle) => {
+                let (sin, cos) = angle.to_radians().sin_cos();
+
+                // The gradient line is long enough for the corners of the box to be at the start and end colors
+                let half_length = (width * sin.abs() + height * cos.abs()) / 2.0;
+
+                let center_x = x + width / 2.0;
+                let center_y = y + height / 2.0;
+
+                (
+                    center_x - sin * half_length,
+                    center_y + cos * half_length,
+                    center_x + sin * half_length,
+                    center_y - cos * half_length,
+                )
+            }
+        }
+    }
+}
+
+// A background which blends between colors placed at positions along a direction
+// Built with LinearGradient::new(direction).with_stop(0.0, start).with_stop(1.0, end)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LinearGradient {
+    pub direction: GradientDirection,
+    // Positions from 0.0 at the start of the gradient line to 1.0 at the end, in increasing order
+    stops: Vec<(f32, Color)>,
+}
+
+impl LinearGradient {
+    pub fn new(direction: GradientDirection) -> Self {
+        LinearGradient {
+            direction,
+            stops: Vec::new(),
+        }
+    }
+
+    // Adds a color at a position along the gradient line
+    // Panics if the position is outside of 0.0 to 1.0 or before the position of the previous stop
+    pub fn with_stop(mut self, position: f32, color: Color) -> Self {
+        assert!(
+            position >= 0.0 && position <= 1.0,
+            "Gradient stop position {} is outside of 0.0 to 1.0",
+            position
+        );
+
+        if let Some((previous, _)) = self.stops.last() {
+            assert!(
+                position >= *previous,
+                "Gradient stop position {} is before the previous stop at {}",
+                position,
+                previous
+            );
+        }
+
+        self.stops.push((position, color));
+
+        self
+    }
+
+    pub fn get_stops(&self) -> &[(f32, Color)] {
+        &self.stops
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: Color = Color::rgb(255, 0, 0);
+    const GREEN: Color = Color::rgb(0, 255, 0);
+    const BLUE: Color = Color::rgb(0, 0, 255);
+
+    #[test]
+    fn two_stops_are_kept_in_order() {
+        let gradient = LinearGradient::new(GradientDirection::LeftToRight)
+            .with_stop(0.0, RED)
+            .with_stop(1.0, BLUE);
+
+        assert_eq!(gradient.get_stops(), &[(0.0, RED), (1.0, BLUE)]);
+    }
+
+    #[test]
+    fn three_stops_are_kept_in_order() {
+        let gradient = LinearGradient::new(GradientDirection::Angle(45.0))
+            .with_stop(0.0, RED)
+            .with_stop(0.25, GREEN)
+            .with_stop(1.0, BLUE);
+
+        assert_eq!(
+            gradient.get_stops(),
+            &[(0.0, RED), (0.25, GREEN), (1.0, BLUE)]
+        );
+    }
+
+    #[test]
+    fn stops_may_share_a_position() {
+        let gradient = LinearGradient::new(GradientDirection::TopToBottom)
+            .with_stop(0.5, RED)
+            .with_stop(0.5, BLUE);
+
+        assert_eq!(gradient.get_stops(), &[(0.5, RED), (0.5, BLUE)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of 0.0 to 1.0")]
+    fn stop_outside_the_gradient_panics() {
+        LinearGradient::new(GradientDirection::TopToBottom).with_stop(1.5, RED);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of 0.0 to 1.0")]
+    fn negative_stop_panics() {
+        LinearGradient::new(GradientDirection::TopToBottom).with_stop(-0.1, RED);
+    }
+
+    #[test]
+    #[should_panic(expected = "before the previous stop")]
+    fn stop_before_the_previous_one_panics() {
+        LinearGradient::new(GradientDirection::TopToBottom)
+            .with_stop(0.5, RED)
+            .with_stop(0.25, BLUE);
+    }
+}
@@ -2,11 +2,13 @@ use crate::animator::Interpolator;
 use crate::entity::Entity;
 
 #[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Length {
     Initial(f32),    // Default Value
     Auto,            // Let Layout System Decide
     Pixels(f32),     // Value in pixels
     Percentage(f32), // Percentage of parent
+    Stretch(f32),    // Share of the free space in the parent, like the css fr unit
 }
 
 impl Length {
@@ -23,6 +25,7 @@ impl Length {
             Length::Initial(value) => 0.0,
             Length::Pixels(value) => *value,
             Length::Percentage(value) => *value * 100.0,
+            Length::Stretch(value) => *value,
         }
     }
 
@@ -36,6 +39,10 @@ impl Length {
                 *val = value;
             }
 
+            Length::Stretch(val) => {
+                *val = value;
+            }
+
             _ => {}
         }
     }
@@ -47,20 +54,18 @@ impl Default for Length {
     }
 }
 
+// Lengths in the same units are interpolated, a change of units or to or from auto snaps to the end value
 impl Interpolator for Length {
     fn interpolate(start: &Self, end: &Self, t: f32) -> Self {
-        let s = match start {
-            Length::Pixels(val) => val,
-            Length::Percentage(val) => val,
-            Length::Auto => return end.clone(),
-            Length::Initial(val) => val,
-        };
-
-        match end {
-            Length::Pixels(e) => Length::Pixels(f32::interpolate(s, e, t)),
-            Length::Percentage(e) => Length::Percentage(f32::interpolate(s, e, t)),
-            Length::Auto => return end.clone(),
-            Length::Initial(e) => Length::Pixels(f32::interpolate(s, e, t)),
+        match (start, end) {
+            (Length::Pixels(s), Length::Pixels(e)) | (Length::Initial(s), Length::Pixels(e)) => {
+                Length::Pixels(f32::interpolate(s, e, t))
+            }
+            (Length::Percentage(s), Length::Percentage(e)) => {
+                Length::Percentage(f32::interpolate(s, e, t))
+            }
+            (Length::Stretch(s), Length::Stretch(e)) => Length::Stretch(f32::interpolate(s, e, t)),
+            _ => end.clone(),
         }
     }
 }
@@ -96,6 +101,7 @@ impl Interpolator for MaxHeight {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Position {
     Relative,
     Absolute,
@@ -150,6 +156,7 @@ impl Default for JustifySelf {
 // Not currently used
 
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Overflow {
     Visible,
     Hidden,
@@ -179,3 +186,46 @@ impl Default for Scroll {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lengths_in_the_same_units_are_interpolated() {
+        assert_eq!(
+            Length::interpolate(&Length::Pixels(0.0), &Length::Pixels(100.0), 0.25),
+            Length::Pixels(25.0)
+        );
+        assert_eq!(
+            Length::interpolate(&Length::Percentage(0.25), &Length::Percentage(0.75), 0.5),
+            Length::Percentage(0.5)
+        );
+        assert_eq!(
+            Length::interpolate(&Length::Stretch(1.0), &Length::Stretch(3.0), 0.5),
+            Length::Stretch(2.0)
+        );
+    }
+
+    #[test]
+    fn change_of_units_snaps_to_the_end_value() {
+        assert_eq!(
+            Length::interpolate(&Length::Pixels(100.0), &Length::Percentage(0.5), 0.25),
+            Length::Percentage(0.5)
+        );
+        assert_eq!(
+            Length::interpolate(&Length::Percentage(0.5), &Length::Pixels(100.0), 0.25),
+            Length::Pixels(100.0)
+        );
+
+        // As does animating to or from auto
+        assert_eq!(
+            Length::interpolate(&Length::Auto, &Length::Pixels(100.0), 0.25),
+            Length::Pixels(100.0)
+        );
+        assert_eq!(
+            Length::interpolate(&Length::Pixels(100.0), &Length::Auto, 0.25),
+            Length::Auto
+        );
+    }
+}
@@ -10,15 +10,16 @@ use crate::hierarchy::Hierarchy;
 use crate::state::storage::animatable_storage::AnimatableStorage;
 use crate::state::storage::dense_storage::DenseStorage;
 use crate::state::storage::style_storage::StyleStorage;
+use crate::{CursorIcon, Role};
 
-use crate::state::animator::AnimationState;
+use crate::state::animator::{AnimationState, Interpolator, Transition};
 
 pub mod themes;
 
 pub mod theme;
 
 pub mod prop;
-pub use prop::PropSet;
+pub use prop::{PropGet, PropSet};
 
 pub mod flexbox;
 pub use flexbox::*;
@@ -41,8 +42,11 @@ pub use display::*;
 pub mod property;
 pub use property::*;
 
-// pub mod stylesheet;
-// pub use stylesheet::StyleSheet;
+pub mod stylesheet;
+pub use stylesheet::*;
+
+pub mod computed;
+pub use computed::*;
 
 pub mod selector;
 pub use selector::*;
@@ -56,11 +60,18 @@ pub use rule::*;
 pub mod color;
 pub use color::Color;
 
+pub mod gradient;
+pub use gradient::*;
+
 pub mod trans;
-pub use trans::Scale;
+pub use trans::{Scale, TransformOrigin};
 
 use bimap::BiMap;
 
+// Colors of the selection highlight and caret of text inputs when selection-color and caret-color aren't set
+pub const DEFAULT_SELECTION_COLOR: Color = Color::rgba(0, 0, 0, 64);
+pub const DEFAULT_CARET_COLOR: Color = Color::rgba(247, 76, 0, 255);
+
 pub struct Style {
     //pub style_rules: Vec<StyleRule>,
 
@@ -71,6 +82,8 @@ pub struct Style {
     pub ids: BiMap<String, Entity>,
 
     pub elements: DenseStorage<u64>,
+    // The unhashed element names, for debugging
+    pub element_names: DenseStorage<String>,
 
     //replace with combinator storage at some point
     pub classes: DenseStorage<HashSet<String>>,
@@ -86,6 +99,10 @@ pub struct Style {
     // Transform
     pub rotate: AnimatableStorage<f32>,   // in degrees
     pub scaley: AnimatableStorage<Scale>, // TODO
+    // Offsets applied when drawing only, so the layout is unaffected
+    pub translate_x: AnimatableStorage<f32>,
+    pub translate_y: AnimatableStorage<f32>,
+    pub transform_origin: StyleStorage<TransformOrigin>,
 
     // General
     pub display: StyleStorage<Display>,
@@ -126,9 +143,24 @@ pub struct Style {
     pub padding_top: AnimatableStorage<Length>,
     pub padding_bottom: AnimatableStorage<Length>,
 
+    // Child Space
+    // The default space between the parent and its children on each side, overridden by child margins
+    pub child_left: AnimatableStorage<Length>,
+    pub child_right: AnimatableStorage<Length>,
+    pub child_top: AnimatableStorage<Length>,
+    pub child_bottom: AnimatableStorage<Length>,
+
     // Border
     pub border_width: AnimatableStorage<Length>,
     pub border_color: AnimatableStorage<Color>,
+    // Applies to all entities, percentage border widths resolve against this dimension of the parent
+    pub border_width_basis: BorderWidthBasis,
+
+    // Outline
+    // Drawn outside of the border box without affecting layout
+    pub outline_width: AnimatableStorage<Length>,
+    pub outline_color: AnimatableStorage<Color>,
+    pub outline_offset: AnimatableStorage<Length>,
 
     // Border Radius
     pub border_radius_top_left: AnimatableStorage<Length>,
@@ -137,9 +169,18 @@ pub struct Style {
     pub border_radius_bottom_right: AnimatableStorage<Length>,
 
     pub clip_widget: DenseStorage<Entity>,
+    // Overlays are positioned relative to the window and drawn on top of everything else, ignoring the clipping of their ancestors
+    pub overlay: DenseStorage<bool>,
 
     pub focus_order: DenseStorage<FocusOrder>,
 
+    // The mouse cursor shown while the entity is hovered
+    pub cursor: DenseStorage<CursorIcon>,
+
+    // Accessibility
+    pub role: DenseStorage<Role>,
+    pub accessible_label: DenseStorage<String>,
+
     // Flexbox
     pub align_self: StyleStorage<AlignSelf>,
     pub flex_grow: AnimatableStorage<f32>,
@@ -158,6 +199,7 @@ pub struct Style {
     // Background
     pub background_color: AnimatableStorage<Color>,
     pub background_image: StyleStorage<String>,
+    pub background_gradient: StyleStorage<LinearGradient>,
 
     // Box Shadow
     pub shadow_h_offset: AnimatableStorage<Length>,
@@ -173,6 +215,11 @@ pub struct Style {
 
     pub text_align: StyleStorage<Align>,
     pub text_justify: StyleStorage<Justify>,
+    pub text_wrap: StyleStorage<bool>,
+
+    // Text Input
+    pub selection_color: AnimatableStorage<Color>,
+    pub caret_color: AnimatableStorage<Color>,
 }
 
 impl Style {
@@ -186,6 +233,7 @@ impl Style {
             //ids: DenseStorage::new(),
             ids: BiMap::new(),
             elements: DenseStorage::new(),
+            element_names: DenseStorage::new(),
             classes: DenseStorage::new(),
             pseudo_classes: DenseStorage::new(),
 
@@ -199,6 +247,9 @@ impl Style {
             // Transform
             rotate: AnimatableStorage::new(),
             scaley: AnimatableStorage::new(),
+            translate_x: AnimatableStorage::new(),
+            translate_y: AnimatableStorage::new(),
+            transform_origin: StyleStorage::new(),
 
             // Positioning
             position: StyleStorage::new(),
@@ -229,9 +280,21 @@ impl Style {
             padding_top: AnimatableStorage::new(),
             padding_bottom: AnimatableStorage::new(),
 
+            child_left: AnimatableStorage::new(),
+            child_right: AnimatableStorage::new(),
+            child_top: AnimatableStorage::new(),
+            child_bottom: AnimatableStorage::new(),
+
             // Border
             border_width: AnimatableStorage::new(),
+            border_width_basis: BorderWidthBasis::default(),
             border_color: AnimatableStorage::new(),
+
+            // Outline
+            outline_width: AnimatableStorage::new(),
+            outline_color: AnimatableStorage::new(),
+            outline_offset: AnimatableStorage::new(),
+
             border_radius_top_left: AnimatableStorage::new(),
             border_radius_top_right: AnimatableStorage::new(),
             border_radius_bottom_left: AnimatableStorage::new(),
@@ -246,18 +309,27 @@ impl Style {
             // Text
             text_align: StyleStorage::new(),
             text_justify: StyleStorage::new(),
+            text_wrap: StyleStorage::new(),
 
             font_color: AnimatableStorage::new(),
             font_size: AnimatableStorage::new(),
 
+            // Text Input
+            selection_color: AnimatableStorage::new(),
+            caret_color: AnimatableStorage::new(),
+
             overflow: StyleStorage::new(),
             scroll: DenseStorage::new(),
+            cursor: DenseStorage::new(),
+            role: DenseStorage::new(),
+            accessible_label: DenseStorage::new(),
 
             // area_container: DenseStorage::new(),
             // area_item: DenseStorage::new(),
             display: StyleStorage::new(),
             visibility: StyleStorage::new(),
             clip_widget: DenseStorage::new(),
+            overlay: DenseStorage::new(),
             focus_order: DenseStorage::new(),
 
             // Box Shadow
@@ -268,6 +340,7 @@ impl Style {
 
             background_color: AnimatableStorage::new(),
             background_image: StyleStorage::new(),
+            background_gradient: StyleStorage::new(),
 
             //justification: DenseStorage::new(),
             //alignment: DenseStorage::new(),
@@ -420,6 +493,29 @@ impl Style {
                         self.padding_bottom.insert_rule(rule_id, value);
                     }
 
+                    Property::ChildSpace(value) => {
+                        self.child_left.insert_rule(rule_id, value);
+                        self.child_right.insert_rule(rule_id, value);
+                        self.child_top.insert_rule(rule_id, value);
+                        self.child_bottom.insert_rule(rule_id, value);
+                    }
+
+                    Property::ChildLeft(value) => {
+                        self.child_left.insert_rule(rule_id, value);
+                    }
+
+                    Property::ChildRight(value) => {
+                        self.child_right.insert_rule(rule_id, value);
+                    }
+
+                    Property::ChildTop(value) => {
+                        self.child_top.insert_rule(rule_id, value);
+                    }
+
+                    Property::ChildBottom(value) => {
+                        self.child_bottom.insert_rule(rule_id, value);
+                    }
+
                     // Border
                     Property::BorderWidth(value) => {
                         self.border_width.insert_rule(rule_id, value);
@@ -429,6 +525,19 @@ impl Style {
                         self.border_color.insert_rule(rule_id, value);
                     }
 
+                    // Outline
+                    Property::OutlineWidth(value) => {
+                        self.outline_width.insert_rule(rule_id, value);
+                    }
+
+                    Property::OutlineColor(value) => {
+                        self.outline_color.insert_rule(rule_id, value);
+                    }
+
+                    Property::OutlineOffset(value) => {
+                        self.outline_offset.insert_rule(rule_id, value);
+                    }
+
                     Property::BorderRadius(value) => {
                         self.border_radius_top_left.insert_rule(rule_id, value);
                         self.border_radius_top_right.insert_rule(rule_id, value);
@@ -460,6 +569,14 @@ impl Style {
                         self.font_color.insert_rule(rule_id, value);
                     }
 
+                    Property::SelectionColor(value) => {
+                        self.selection_color.insert_rule(rule_id, value);
+                    }
+
+                    Property::CaretColor(value) => {
+                        self.caret_color.insert_rule(rule_id, value);
+                    }
+
                     Property::BackgroundColor(value) => {
                         self.background_color.insert_rule(rule_id, value);
                     }
@@ -505,77 +622,130 @@ impl Style {
 
                     Property::Transition(transitions) => {
                         for transition in transitions {
-                            match transition.property.as_ref() {
-                                "background-color" => {
-                                    self.background_color.insert_transition(
-                                        rule_id,
-                                        AnimationState::new()
-                                            .with_duration(std::time::Duration::from_secs_f32(
-                                                transition.duration,
-                                            ))
-                                            .with_delay(std::time::Duration::from_secs_f32(
-                                                transition.delay,
-                                            ))
-                                            .with_keyframe((0.0, Default::default()))
-                                            .with_keyframe((1.0, Default::default())),
-                                    );
-                                }
-
-                                "left" => {
-                                    self.left.insert_transition(
-                                        rule_id,
-                                        AnimationState::new()
-                                            .with_duration(std::time::Duration::from_secs_f32(
-                                                transition.duration,
-                                            ))
-                                            .with_delay(std::time::Duration::from_secs_f32(
-                                                transition.delay,
-                                            ))
-                                            .with_keyframe((0.0, Default::default()))
-                                            .with_keyframe((1.0, Default::default())),
-                                    );
-                                }
-
-                                "width" => {
-                                    self.width.insert_transition(
-                                        rule_id,
-                                        AnimationState::new()
-                                            .with_duration(std::time::Duration::from_secs_f32(
-                                                transition.duration,
-                                            ))
-                                            .with_delay(std::time::Duration::from_secs_f32(
-                                                transition.delay,
-                                            ))
-                                            .with_keyframe((0.0, Default::default()))
-                                            .with_keyframe((1.0, Default::default())),
-                                    );
-                                }
-
-                                "opacity" => {
-                                    self.opacity.insert_transition(
-                                        rule_id,
-                                        AnimationState::new()
-                                            .with_duration(std::time::Duration::from_secs_f32(
-                                                transition.duration,
-                                            ))
-                                            .with_delay(std::time::Duration::from_secs_f32(
-                                                transition.delay,
-                                            ))
-                                            .with_keyframe((0.0, Default::default()))
-                                            .with_keyframe((1.0, Default::default())),
-                                    );
-                                }
-
-                                _ => {}
-                            }
+                            self.insert_transition(rule_id, &transition);
                         }
                     }
+
                     _ => {}
                 }
             }
         }
     }
 
+    // Links the animated properties named by a transition to the rule, so that the properties animate when an entity starts
+    // or stops matching the rule, such as when its pseudo-classes change
+    fn insert_transition(&mut self, rule_id: usize, transition: &Transition) {
+        match transition.property.as_ref() {
+            "background-color" => self
+                .background_color
+                .insert_transition(rule_id, transition_animation(transition)),
+            "color" => self
+                .font_color
+                .insert_transition(rule_id, transition_animation(transition)),
+            "font-size" => self
+                .font_size
+                .insert_transition(rule_id, transition_animation(transition)),
+            "opacity" => self
+                .opacity
+                .insert_transition(rule_id, transition_animation(transition)),
+
+            "left" => self
+                .left
+                .insert_transition(rule_id, transition_animation(transition)),
+            "right" => self
+                .right
+                .insert_transition(rule_id, transition_animation(transition)),
+            "top" => self
+                .top
+                .insert_transition(rule_id, transition_animation(transition)),
+            "bottom" => self
+                .bottom
+                .insert_transition(rule_id, transition_animation(transition)),
+
+            "width" => self
+                .width
+                .insert_transition(rule_id, transition_animation(transition)),
+            "height" => self
+                .height
+                .insert_transition(rule_id, transition_animation(transition)),
+
+            "margin-left" => self
+                .margin_left
+                .insert_transition(rule_id, transition_animation(transition)),
+            "margin-right" => self
+                .margin_right
+                .insert_transition(rule_id, transition_animation(transition)),
+            "margin-top" => self
+                .margin_top
+                .insert_transition(rule_id, transition_animation(transition)),
+            "margin-bottom" => self
+                .margin_bottom
+                .insert_transition(rule_id, transition_animation(transition)),
+
+            "padding-left" => self
+                .padding_left
+                .insert_transition(rule_id, transition_animation(transition)),
+            "padding-right" => self
+                .padding_right
+                .insert_transition(rule_id, transition_animation(transition)),
+            "padding-top" => self
+                .padding_top
+                .insert_transition(rule_id, transition_animation(transition)),
+            "padding-bottom" => self
+                .padding_bottom
+                .insert_transition(rule_id, transition_animation(transition)),
+
+            "border-width" => self
+                .border_width
+                .insert_transition(rule_id, transition_animation(transition)),
+            "border-color" => self
+                .border_color
+                .insert_transition(rule_id, transition_animation(transition)),
+
+            "outline-width" => self
+                .outline_width
+                .insert_transition(rule_id, transition_animation(transition)),
+            "outline-color" => self
+                .outline_color
+                .insert_transition(rule_id, transition_animation(transition)),
+            "outline-offset" => self
+                .outline_offset
+                .insert_transition(rule_id, transition_animation(transition)),
+
+            "selection-color" => self
+                .selection_color
+                .insert_transition(rule_id, transition_animation(transition)),
+            "caret-color" => self
+                .caret_color
+                .insert_transition(rule_id, transition_animation(transition)),
+
+            "border-radius" => {
+                self.border_radius_top_left
+                    .insert_transition(rule_id, transition_animation(transition));
+                self.border_radius_top_right
+                    .insert_transition(rule_id, transition_animation(transition));
+                self.border_radius_bottom_left
+                    .insert_transition(rule_id, transition_animation(transition));
+                self.border_radius_bottom_right
+                    .insert_transition(rule_id, transition_animation(transition));
+            }
+
+            "flex-grow" => self
+                .flex_grow
+                .insert_transition(rule_id, transition_animation(transition)),
+            "flex-shrink" => self
+                .flex_shrink
+                .insert_transition(rule_id, transition_animation(transition)),
+            "flex-basis" => self
+                .flex_basis
+                .insert_transition(rule_id, transition_animation(transition)),
+
+            property => {
+                eprintln!("WARNING: Transitions of {} are not supported", property);
+            }
+        }
+    }
+
     // Add style data to an entity
     pub fn add(&mut self, entity: Entity) {
         self.pseudo_classes.insert(entity, PseudoClasses::default());
@@ -590,7 +760,86 @@ impl Style {
         self.focus_order.insert(entity, Default::default());
     }
 
-    pub fn remove(&mut self, entity: Entity) {}
+    pub fn remove(&mut self, entity: Entity) {
+        self.ids.remove_by_right(&entity);
+
+        self.elements.remove(entity);
+        self.element_names.remove(entity);
+        self.classes.remove(entity);
+        self.pseudo_classes.remove(entity);
+        self.z_order.remove(entity);
+        self.rotate.remove(entity);
+        self.scaley.remove(entity);
+        self.translate_x.remove(entity);
+        self.translate_y.remove(entity);
+        self.transform_origin.remove(entity);
+        self.display.remove(entity);
+        self.visibility.remove(entity);
+        self.opacity.remove(entity);
+        self.overflow.remove(entity);
+        self.scroll.remove(entity);
+        self.cursor.remove(entity);
+        self.role.remove(entity);
+        self.accessible_label.remove(entity);
+        self.position.remove(entity);
+        self.left.remove(entity);
+        self.right.remove(entity);
+        self.top.remove(entity);
+        self.bottom.remove(entity);
+        self.width.remove(entity);
+        self.height.remove(entity);
+        self.max_width.remove(entity);
+        self.max_height.remove(entity);
+        self.min_width.remove(entity);
+        self.min_height.remove(entity);
+        self.margin_left.remove(entity);
+        self.margin_right.remove(entity);
+        self.margin_top.remove(entity);
+        self.margin_bottom.remove(entity);
+        self.padding_left.remove(entity);
+        self.padding_right.remove(entity);
+        self.padding_top.remove(entity);
+        self.padding_bottom.remove(entity);
+        self.child_left.remove(entity);
+        self.child_right.remove(entity);
+        self.child_top.remove(entity);
+        self.child_bottom.remove(entity);
+        self.border_width.remove(entity);
+        self.border_color.remove(entity);
+        self.outline_width.remove(entity);
+        self.outline_color.remove(entity);
+        self.outline_offset.remove(entity);
+        self.border_radius_top_left.remove(entity);
+        self.border_radius_top_right.remove(entity);
+        self.border_radius_bottom_left.remove(entity);
+        self.border_radius_bottom_right.remove(entity);
+        self.clip_widget.remove(entity);
+        self.overlay.remove(entity);
+        self.focus_order.remove(entity);
+        self.align_self.remove(entity);
+        self.flex_grow.remove(entity);
+        self.flex_shrink.remove(entity);
+        self.flex_basis.remove(entity);
+        self.flex_direction.remove(entity);
+        self.justify_content.remove(entity);
+        self.align_items.remove(entity);
+        self.align_content.remove(entity);
+        self.background_color.remove(entity);
+        self.background_image.remove(entity);
+        self.background_gradient.remove(entity);
+        self.shadow_h_offset.remove(entity);
+        self.shadow_v_offset.remove(entity);
+        self.shadow_blur.remove(entity);
+        self.shadow_color.remove(entity);
+        self.text.remove(entity);
+        self.font_color.remove(entity);
+        self.font_size.remove(entity);
+        self.text_align.remove(entity);
+        self.text_justify.remove(entity);
+        self.text_wrap.remove(entity);
+        self.selection_color.remove(entity);
+        self.caret_color.remove(entity);
+    }
 
     // pub fn insert_style_rule(&mut self, style_rule: StyleRule) -> &mut Self {
     //     self.style_rules.push(style_rule);
@@ -612,10 +861,18 @@ impl Style {
         let mut s = DefaultHasher::new();
         element.hash(&mut s);
         self.elements.insert(entity, s.finish());
+        self.element_names.insert(entity, element.to_string());
 
         self
     }
 
+    // Returns true if the entity has the given element name
+    pub fn has_element(&self, entity: Entity, element: &str) -> bool {
+        let mut s = DefaultHasher::new();
+        element.hash(&mut s);
+        self.elements.get(entity) == Some(&s.finish())
+    }
+
     pub fn insert_class(&mut self, entity: Entity, class: &str) -> &mut Self {
         if let Some(class_list) = self.classes.get_mut(entity) {
             class_list.insert(class.to_string());
@@ -628,3 +885,121 @@ impl Style {
         self
     }
 }
+
+// The animation played by a transition, the keyframes are filled in with the old and new values when it starts
+fn transition_animation<T: Interpolator + Default>(transition: &Transition) -> AnimationState<T> {
+    AnimationState::new()
+        .with_duration(std::time::Duration::from_secs_f32(transition.duration))
+        .with_delay(std::time::Duration::from_secs_f32(transition.delay))
+        .with_keyframe((0.0, Default::default()))
+        .with_keyframe((1.0, Default::default()))
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    fn round_trip<T>(value: &T) -> T
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        serde_json::from_str(&serde_json::to_string(value).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn layout_types_round_trip() {
+        for length in [
+            Length::Initial(1.0),
+            Length::Auto,
+            Length::Pixels(10.0),
+            Length::Percentage(0.5),
+            Length::Stretch(2.0),
+        ]
+        .iter()
+        {
+            assert_eq!(&round_trip(length), length);
+        }
+
+        for position in [Position::Relative, Position::Absolute].iter() {
+            assert_eq!(&round_trip(position), position);
+        }
+
+        for overflow in [Overflow::Visible, Overflow::Hidden].iter() {
+            assert_eq!(&round_trip(overflow), overflow);
+        }
+    }
+
+    #[test]
+    fn flexbox_types_round_trip() {
+        for direction in [FlexDirection::Row, FlexDirection::Column].iter() {
+            assert_eq!(&round_trip(direction), direction);
+        }
+
+        for justify in [
+            JustifyContent::FlexStart,
+            JustifyContent::FlexEnd,
+            JustifyContent::Center,
+            JustifyContent::Stretch,
+            JustifyContent::SpaceBetween,
+            JustifyContent::SpaceAround,
+            JustifyContent::SpaceEvenly,
+        ]
+        .iter()
+        {
+            assert_eq!(&round_trip(justify), justify);
+        }
+
+        for align in [
+            AlignItems::FlexStart,
+            AlignItems::FlexEnd,
+            AlignItems::Center,
+            AlignItems::Stretch,
+        ]
+        .iter()
+        {
+            assert_eq!(&round_trip(align), align);
+        }
+    }
+
+    #[test]
+    fn display_types_round_trip() {
+        for display in [
+            Display::None,
+            Display::Normal,
+            Display::Flexbox,
+            Display::Grid,
+        ]
+        .iter()
+        {
+            assert_eq!(&round_trip(display), display);
+        }
+
+        for visibility in [Visibility::Visible, Visibility::Invisible].iter() {
+            assert_eq!(&round_trip(visibility), visibility);
+        }
+    }
+
+    #[test]
+    fn text_round_trips() {
+        let text = Text {
+            text: "Hello".to_string(),
+            font: "roboto".to_string(),
+        };
+
+        let parsed = round_trip(&text);
+        assert_eq!(parsed.text, "Hello");
+        assert_eq!(parsed.font, "roboto");
+    }
+
+    #[test]
+    fn focus_order_round_trips() {
+        let focus_order = FocusOrder {
+            next: Entity::new(3, 0),
+            prev: Entity::null(),
+        };
+
+        let parsed = round_trip(&focus_order);
+        assert_eq!(parsed.next, Entity::new(3, 0));
+        assert_eq!(parsed.prev, Entity::null());
+    }
+}
@@ -2,7 +2,7 @@ use crate::entity::Entity;
 use crate::state::style::*;
 use crate::State;
 
-use crate::{Event, WindowEvent};
+use crate::{CursorIcon, Event, Role, WindowEvent};
 
 use crate::state::hierarchy::*;
 
@@ -49,6 +49,12 @@ pub trait PropSet {
     // Rotate
     fn set_rotate(self, state: &mut State, value: f32) -> Self;
 
+    // Translate
+    fn set_translate(self, state: &mut State, x: f32, y: f32) -> Self;
+
+    // Transform Origin
+    fn set_transform_origin(self, state: &mut State, value: TransformOrigin) -> Self;
+
     // Grid Container
     //fn set_grid_columns(self, state: &mut State, value: Vec<f32>) -> Self;
     //fn set_grid_rows(self, state: &mut State, value: Vec<f32>) -> Self;
@@ -88,23 +94,38 @@ pub trait PropSet {
 
     // Text
     fn set_text(self, state: &mut State, text: &str) -> Self;
+    // Sets the text and returns true if it differs from the current text
+    fn set_text_checked(self, state: &mut State, text: &str) -> bool;
 
     // Text Font
     fn set_font(self, state: &mut State, font: String) -> Self;
     fn set_font_size(self, state: &mut State, size: f32) -> Self;
     fn set_font_color(self, state: &mut State, color: Color) -> Self;
 
+    // Text Input
+    fn set_selection_color(self, state: &mut State, value: Color) -> Self;
+    fn set_caret_color(self, state: &mut State, value: Color) -> Self;
+
     // Text Alignment
     fn set_text_align(self, state: &mut State, align: Align) -> Self;
     fn set_text_justify(self, state: &mut State, justify: Justify) -> Self;
 
+    // Text Wrapping
+    fn set_text_wrap(self, state: &mut State, value: bool) -> Self;
+
     // Background
     fn set_background_color(self, state: &mut State, value: Color) -> Self;
+    fn set_background_gradient(self, state: &mut State, value: LinearGradient) -> Self;
 
     // Border
     fn set_border_width(self, state: &mut State, value: Length) -> Self;
     fn set_border_color(self, state: &mut State, value: Color) -> Self;
 
+    // Outline
+    fn set_outline_width(self, state: &mut State, value: Length) -> Self;
+    fn set_outline_color(self, state: &mut State, value: Color) -> Self;
+    fn set_outline_offset(self, state: &mut State, value: Length) -> Self;
+
     // Border Radius
     fn set_border_radius(self, state: &mut State, value: Length) -> Self;
     fn set_border_radius_top_left(self, state: &mut State, value: Length) -> Self;
@@ -126,14 +147,36 @@ pub trait PropSet {
     fn set_padding_top(self, state: &mut State, value: Length) -> Self;
     fn set_padding_bottom(self, state: &mut State, value: Length) -> Self;
 
+    // Child Space
+    // The space between the entity and its children on each side, used when a child has no margin on that side
+    // Along the flex direction it only separates the entity from its first and last children, not siblings from each other
+    fn set_child_space(self, state: &mut State, value: Length) -> Self;
+    fn set_child_left(self, state: &mut State, value: Length) -> Self;
+    fn set_child_right(self, state: &mut State, value: Length) -> Self;
+    fn set_child_top(self, state: &mut State, value: Length) -> Self;
+    fn set_child_bottom(self, state: &mut State, value: Length) -> Self;
+
     // Clipping
     fn set_clip_widget(self, state: &mut State, value: Entity) -> Self;
 
+    // Overlay
+    fn set_overlay(self, state: &mut State, value: bool) -> Self;
+
     fn set_z_order(self, state: &mut State, vaale: i32) -> Self;
 
     fn set_next_focus(self, state: &mut State, value: Entity) -> Self;
     fn set_prev_focus(self, state: &mut State, value: Entity) -> Self;
     fn set_focus_order(self, state: &mut State, next: Entity, prev: Entity) -> Self;
+
+    // Cursor
+    fn set_cursor(self, state: &mut State, value: CursorIcon) -> Self;
+
+    // Geometry
+    fn set_geometry_listener(self, state: &mut State, value: bool) -> Self;
+
+    // Accessibility
+    fn set_role(self, state: &mut State, value: Role) -> Self;
+    fn set_accessible_label(self, state: &mut State, value: &str) -> Self;
 }
 
 impl PropSet for Entity {
@@ -333,7 +376,10 @@ impl PropSet for Entity {
 
     //Opacity
     fn set_opacity(self, state: &mut State, value: f32) -> Self {
-        state.style.opacity.insert(self, Opacity(value));
+        state
+            .style
+            .opacity
+            .insert(self, Opacity(value.max(0.0).min(1.0)));
 
         state.insert_event(
             Event::new(WindowEvent::Relayout)
@@ -359,6 +405,26 @@ impl PropSet for Entity {
         self
     }
 
+    // Translate
+    // Only moves the entity when drawn so no relayout is needed
+    fn set_translate(self, state: &mut State, x: f32, y: f32) -> Self {
+        state.style.translate_x.insert(self, x);
+        state.style.translate_y.insert(self, y);
+
+        state.insert_event(Event::new(WindowEvent::Redraw));
+
+        self
+    }
+
+    // Transform Origin
+    fn set_transform_origin(self, state: &mut State, value: TransformOrigin) -> Self {
+        state.style.transform_origin.insert(self, value);
+
+        state.insert_event(Event::new(WindowEvent::Redraw));
+
+        self
+    }
+
     // Flex Container
     fn set_flex_direction(self, state: &mut State, value: FlexDirection) -> Self {
         state.style.flex_direction.insert(self, value);
@@ -622,8 +688,20 @@ impl PropSet for Entity {
 
     // Text
     fn set_text(self, state: &mut State, value: &str) -> Self {
+        self.set_text_checked(state, value);
+
+        self
+    }
+
+    // Unchanged text doesn't trigger a relayout or redraw
+    fn set_text_checked(self, state: &mut State, value: &str) -> bool {
         if let Some(data) = state.style.text.get_mut(self) {
+            if data.text == value {
+                return false;
+            }
+
             data.text = value.to_string();
+            state.text_layouts.remove(&self);
         } else {
             state.style.text.insert(
                 self,
@@ -641,11 +719,13 @@ impl PropSet for Entity {
         );
         state.insert_event(Event::new(WindowEvent::Redraw));
 
-        self
+        true
     }
 
     // Text Font
     fn set_font(self, state: &mut State, value: String) -> Self {
+        state.text_layouts.remove(&self);
+
         if let Some(data) = state.style.text.get_mut(self) {
             data.font = value;
         } else {
@@ -669,6 +749,8 @@ impl PropSet for Entity {
     }
 
     fn set_font_size(self, state: &mut State, value: f32) -> Self {
+        state.text_layouts.remove(&self);
+
         state.style.font_size.insert(self, value);
 
         state.insert_event(
@@ -689,6 +771,23 @@ impl PropSet for Entity {
         self
     }
 
+    // Text Input
+    fn set_selection_color(self, state: &mut State, value: Color) -> Self {
+        state.style.selection_color.insert(self, value);
+
+        state.insert_event(Event::new(WindowEvent::Redraw));
+
+        self
+    }
+
+    fn set_caret_color(self, state: &mut State, value: Color) -> Self {
+        state.style.caret_color.insert(self, value);
+
+        state.insert_event(Event::new(WindowEvent::Redraw));
+
+        self
+    }
+
     // Text Alignment
     fn set_text_justify(self, state: &mut State, value: Justify) -> Self {
         state.style.text_justify.insert(self, value);
@@ -706,6 +805,15 @@ impl PropSet for Entity {
         self
     }
 
+    // Text Wrapping
+    fn set_text_wrap(self, state: &mut State, value: bool) -> Self {
+        state.style.text_wrap.insert(self, value);
+
+        state.insert_event(Event::new(WindowEvent::Redraw));
+
+        self
+    }
+
     // Background
     fn set_background_color(self, state: &mut State, value: Color) -> Self {
         state.style.background_color.insert(self, value);
@@ -715,6 +823,15 @@ impl PropSet for Entity {
         self
     }
 
+    // Drawn instead of the background color
+    fn set_background_gradient(self, state: &mut State, value: LinearGradient) -> Self {
+        state.style.background_gradient.insert(self, value);
+
+        state.insert_event(Event::new(WindowEvent::Redraw));
+
+        self
+    }
+
     // Border
     fn set_border_width(self, state: &mut State, value: Length) -> Self {
         state.style.border_width.insert(self, value);
@@ -737,6 +854,31 @@ impl PropSet for Entity {
         self
     }
 
+    // Outline
+    fn set_outline_width(self, state: &mut State, value: Length) -> Self {
+        state.style.outline_width.insert(self, value);
+
+        state.insert_event(Event::new(WindowEvent::Redraw));
+
+        self
+    }
+
+    fn set_outline_color(self, state: &mut State, value: Color) -> Self {
+        state.style.outline_color.insert(self, value);
+
+        state.insert_event(Event::new(WindowEvent::Redraw));
+
+        self
+    }
+
+    fn set_outline_offset(self, state: &mut State, value: Length) -> Self {
+        state.style.outline_offset.insert(self, value);
+
+        state.insert_event(Event::new(WindowEvent::Redraw));
+
+        self
+    }
+
     // Border Radius
     fn set_border_radius(self, state: &mut State, value: Length) -> Self {
         state.style.border_radius_top_left.insert(self, value);
@@ -913,6 +1055,75 @@ impl PropSet for Entity {
         self
     }
 
+    // Child Space
+    fn set_child_space(self, state: &mut State, value: Length) -> Self {
+        state.style.child_left.insert(self, value);
+        state.style.child_right.insert(self, value);
+        state.style.child_top.insert(self, value);
+        state.style.child_bottom.insert(self, value);
+
+        state.insert_event(
+            Event::new(WindowEvent::Relayout)
+                .target(Entity::null())
+                .origin(self),
+        );
+        state.insert_event(Event::new(WindowEvent::Redraw));
+
+        self
+    }
+
+    fn set_child_left(self, state: &mut State, value: Length) -> Self {
+        state.style.child_left.insert(self, value);
+
+        state.insert_event(
+            Event::new(WindowEvent::Relayout)
+                .target(Entity::null())
+                .origin(self),
+        );
+        state.insert_event(Event::new(WindowEvent::Redraw));
+
+        self
+    }
+
+    fn set_child_right(self, state: &mut State, value: Length) -> Self {
+        state.style.child_right.insert(self, value);
+
+        state.insert_event(
+            Event::new(WindowEvent::Relayout)
+                .target(Entity::null())
+                .origin(self),
+        );
+        state.insert_event(Event::new(WindowEvent::Redraw));
+
+        self
+    }
+
+    fn set_child_top(self, state: &mut State, value: Length) -> Self {
+        state.style.child_top.insert(self, value);
+
+        state.insert_event(
+            Event::new(WindowEvent::Relayout)
+                .target(Entity::null())
+                .origin(self),
+        );
+        state.insert_event(Event::new(WindowEvent::Redraw));
+
+        self
+    }
+
+    fn set_child_bottom(self, state: &mut State, value: Length) -> Self {
+        state.style.child_bottom.insert(self, value);
+
+        state.insert_event(
+            Event::new(WindowEvent::Relayout)
+                .target(Entity::null())
+                .origin(self),
+        );
+        state.insert_event(Event::new(WindowEvent::Redraw));
+
+        self
+    }
+
     // Clipping
     fn set_clip_widget(self, state: &mut State, value: Entity) -> Self {
         state.style.clip_widget.insert(self, value);
@@ -927,6 +1138,20 @@ impl PropSet for Entity {
         self
     }
 
+    // Overlay
+    fn set_overlay(self, state: &mut State, value: bool) -> Self {
+        state.style.overlay.insert(self, value);
+
+        state.insert_event(
+            Event::new(WindowEvent::Relayout)
+                .target(Entity::null())
+                .origin(self),
+        );
+        state.insert_event(Event::new(WindowEvent::Redraw));
+
+        self
+    }
+
     fn set_z_order(self, state: &mut State, value: i32) -> Self {
         state.style.z_order.insert(self, value);
 
@@ -1006,4 +1231,123 @@ impl PropSet for Entity {
 
         self
     }
+
+    fn set_cursor(self, state: &mut State, value: CursorIcon) -> Self {
+        // The window cursor is updated by the backend when the hovered entity's cursor changes
+        state.style.cursor.insert(self, value);
+
+        self
+    }
+
+    fn set_geometry_listener(self, state: &mut State, value: bool) -> Self {
+        // Only listeners are compared before and after each layout so other entities cost nothing
+        if value {
+            state.geometry_listeners.insert(self);
+        } else {
+            state.geometry_listeners.remove(&self);
+        }
+
+        self
+    }
+
+    fn set_role(self, state: &mut State, value: Role) -> Self {
+        state.style.role.insert(self, value);
+
+        self
+    }
+
+    fn set_accessible_label(self, state: &mut State, value: &str) -> Self {
+        state.style.accessible_label.insert(self, value.to_string());
+
+        self
+    }
+}
+
+pub trait PropGet {
+    fn get_text(self, state: &mut State) -> String;
+
+    // Returns the name of the font, the regular font if none is set
+    fn get_font(self, state: &mut State) -> String;
+    // Returns the font size in pixels, 16.0 if none is set
+    fn get_font_size(self, state: &mut State) -> f32;
+}
+
+impl PropGet for Entity {
+    fn get_text(self, state: &mut State) -> String {
+        state
+            .style
+            .text
+            .get(self)
+            .map(|text| text.text.clone())
+            .unwrap_or_default()
+    }
+
+    fn get_font(self, state: &mut State) -> String {
+        state.style.text.get(self).cloned().unwrap_or_default().font
+    }
+
+    fn get_font_size(self, state: &mut State) -> f32 {
+        state.style.font_size.get(self).cloned().unwrap_or(16.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn font_round_trips() {
+        let mut state = State::new_headless();
+        let root = state.root;
+        let entity = state.add(root);
+
+        assert_eq!(entity.get_font(&mut state), "sans");
+
+        entity.set_font(&mut state, "Roboto-Bold".to_string());
+        assert_eq!(entity.get_font(&mut state), "Roboto-Bold");
+    }
+
+    #[test]
+    fn font_size_round_trips() {
+        let mut state = State::new_headless();
+        let root = state.root;
+        let entity = state.add(root);
+
+        assert_eq!(entity.get_font_size(&mut state), 16.0);
+
+        entity.set_font_size(&mut state, 24.0);
+        assert_eq!(entity.get_font_size(&mut state), 24.0);
+    }
+
+    #[test]
+    fn setting_font_keeps_text() {
+        let mut state = State::new_headless();
+        let root = state.root;
+        let entity = state.add(root);
+
+        entity
+            .set_text(&mut state, "Hello")
+            .set_font(&mut state, "Roboto-Bold".to_string());
+
+        assert_eq!(entity.get_text(&mut state), "Hello");
+        assert_eq!(entity.get_font(&mut state), "Roboto-Bold");
+    }
+
+    #[test]
+    fn unchanged_text_does_not_relayout() {
+        let mut state = State::new_headless();
+        let root = state.root;
+        let entity = state.add(root);
+
+        assert!(entity.set_text_checked(&mut state, "Hello"));
+        state.drain_events();
+
+        assert!(!entity.set_text_checked(&mut state, "Hello"));
+        assert!(state.pending_events().is_empty());
+
+        assert!(entity.set_text_checked(&mut state, "World"));
+        assert!(state
+            .pending_events()
+            .contains(&Event::new(WindowEvent::Relayout).origin(entity)));
+    }
 }
@@ -50,6 +50,13 @@ pub enum Property {
     PaddingTop(Length),
     PaddingBottom(Length),
 
+    // Child Space
+    ChildSpace(Length),
+    ChildLeft(Length),
+    ChildRight(Length),
+    ChildTop(Length),
+    ChildBottom(Length),
+
     // Flex Container
     FlexDirection(FlexDirection),
     JustifyContent(JustifyContent),
@@ -71,6 +78,11 @@ pub enum Property {
     BorderWidth(Length),
     BorderColor(Color),
 
+    // Outline
+    OutlineWidth(Length),
+    OutlineColor(Color),
+    OutlineOffset(Length),
+
     // Background
     BackgroundColor(Color),
     BackgroundImage(String),
@@ -81,6 +93,10 @@ pub enum Property {
     FontSize(f32),
     FontColor(Color),
 
+    // Text Input
+    SelectionColor(Color),
+    CaretColor(Color),
+
     Transition(Vec<Transition>),
 
     ZIndex(i32),
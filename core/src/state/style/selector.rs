@@ -26,10 +26,12 @@ use crate::Specificity;
 // 4 - Enabled
 // 5 - Disabled
 // 6 - Checked
-// 7 - Unassigned
+// 7 - FirstChild
+// 8 - LastChild
+// 9 to 15 - Unassigned
 
 #[derive(Debug, Clone)]
-pub struct PseudoClasses(u8);
+pub struct PseudoClasses(u16);
 
 impl Default for PseudoClasses {
     fn default() -> Self {
@@ -98,6 +100,22 @@ impl PseudoClasses {
         }
     }
 
+    pub fn set_first_child(&mut self, flag: bool) {
+        if flag {
+            self.0 |= (1 << 7);
+        } else {
+            self.0 &= !(1 << 7);
+        }
+    }
+
+    pub fn set_last_child(&mut self, flag: bool) {
+        if flag {
+            self.0 |= (1 << 8);
+        } else {
+            self.0 &= !(1 << 8);
+        }
+    }
+
     pub fn get_hover(&mut self) -> bool {
         (self.0 & 1) != 0
     }
@@ -125,6 +143,14 @@ impl PseudoClasses {
     pub fn get_checked(&mut self) -> bool {
         (self.0 & (1 << 6)) >> 6 != 0
     }
+
+    pub fn get_first_child(&mut self) -> bool {
+        (self.0 & (1 << 7)) >> 7 != 0
+    }
+
+    pub fn get_last_child(&mut self) -> bool {
+        (self.0 & (1 << 8)) >> 8 != 0
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -141,6 +167,8 @@ pub struct Selector {
     pub classes: HashSet<String>,
     //pub pseudo_classes: HashSet<PseudoClass>,
     pub pseudo_classes: PseudoClasses,
+    // The a and b of an nth-child(an+b) pseudo-class
+    pub nth_child: Option<(i32, i32)>,
     pub relation: Relation,
     pub asterisk: bool,
 }
@@ -153,6 +181,7 @@ impl Default for Selector {
             classes: HashSet::new(),
             //pseudo_classes: HashSet::new(),
             pseudo_classes: PseudoClasses::default(),
+            nth_child: None,
             relation: Relation::None,
             asterisk: false,
         }
@@ -167,6 +196,7 @@ impl Selector {
             classes: HashSet::new(),
             //pseudo_classes: HashSet::new(),
             pseudo_classes: PseudoClasses::default(),
+            nth_child: None,
             relation: Relation::None,
             asterisk: false,
         }
@@ -182,6 +212,7 @@ impl Selector {
             classes: HashSet::new(),
             //pseudo_classes: HashSet::new(),
             pseudo_classes: PseudoClasses::default(),
+            nth_child: None,
             relation: Relation::None,
             asterisk: false,
         }
@@ -221,11 +252,29 @@ impl Selector {
     pub fn specificity(&self) -> Specificity {
         Specificity([
             if self.id.is_some() { 1 } else { 0 },
-            (self.classes.len() + self.pseudo_classes.0.count_ones() as usize) as u8,
+            (self.classes.len()
+                + self.pseudo_classes.0.count_ones() as usize
+                + self.nth_child.is_some() as usize) as u8,
             if self.element.is_some() { 1 } else { 0 },
         ])
     }
 
+    // Returns true if an entity at the given position among its siblings, counting from 1, matches the nth-child pseudo-class
+    // Always true if the selector has no nth-child pseudo-class
+    pub fn matches_nth_child(&self, position: usize) -> bool {
+        let (a, b) = match self.nth_child {
+            Some(nth) => nth,
+            None => return true,
+        };
+
+        let offset = position as i32 - b;
+        if a == 0 {
+            offset == 0
+        } else {
+            offset % a == 0 && offset / a >= 0
+        }
+    }
+
     pub fn id(mut self, id: &str) -> Self {
         let mut s = DefaultHasher::new();
         id.hash(&mut s);
@@ -30,6 +30,20 @@ impl Default for BorderStyle {
 }
 */
 
+// Determines which dimension of the parent a percentage border width is resolved against
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BorderWidthBasis {
+    Width,
+    Height,
+    Min,
+}
+
+impl Default for BorderWidthBasis {
+    fn default() -> Self {
+        BorderWidthBasis::Width
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct BorderRadius {
     pub top_left: Length,
@@ -99,6 +113,7 @@ impl Default for ClipArea {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FocusOrder {
     pub next: Entity,
     pub prev: Entity,
@@ -0,0 +1,406 @@
+use std::io::{Error, ErrorKind};
+
+#[cfg(feature = "stylesheets")]
+use crate::style::{Color, Length, Property};
+
+// Rules of a JSON or RON stylesheet, mapping selectors to the properties they set
+// Selectors are written as they would be in CSS and values use the serialized style types, for example:
+// { "button:hover": { "background-color": "#ff505050", "width": { "Pixels": 100.0 } } }
+// Rules and properties are kept in the order they are written, so of two rules with the same specificity the last one wins
+#[cfg(feature = "stylesheets")]
+#[derive(Clone, Debug, Default)]
+pub struct StylesheetRules {
+    pub rules: Vec<(String, Vec<Property>)>,
+}
+
+// Reads a stylesheet file as CSS for the theme parser
+// Files ending in .json or .ron are converted from rules to CSS, which requires the stylesheets feature
+pub fn read_stylesheet(path: &str) -> Result<String, Error> {
+    let contents = std::fs::read_to_string(path)?;
+
+    if path.ends_with(".json") || path.ends_with(".ron") {
+        return rules_to_css(path, &contents);
+    }
+
+    Ok(contents)
+}
+
+#[cfg(feature = "stylesheets")]
+fn rules_to_css(path: &str, contents: &str) -> Result<String, Error> {
+    let rules: StylesheetRules = if path.ends_with(".json") {
+        serde_json::from_str(contents).map_err(|err| Error::new(ErrorKind::InvalidData, err))?
+    } else {
+        ron::de::from_str(contents).map_err(|err| Error::new(ErrorKind::InvalidData, err))?
+    };
+
+    rules.to_css()
+}
+
+#[cfg(not(feature = "stylesheets"))]
+fn rules_to_css(path: &str, _contents: &str) -> Result<String, Error> {
+    Err(Error::new(
+        ErrorKind::InvalidInput,
+        format!(
+            "{} can only be loaded with the stylesheets feature enabled",
+            path
+        ),
+    ))
+}
+
+#[cfg(feature = "stylesheets")]
+impl StylesheetRules {
+    // Writes the rules as CSS, in the order they were read
+    pub fn to_css(&self) -> Result<String, Error> {
+        let mut css = String::new();
+        for (selector, properties) in self.rules.iter() {
+            css += selector;
+            css += " {\n";
+            for property in properties.iter() {
+                css += &format!("    {};\n", property_css(property)?);
+            }
+            css += "}\n";
+        }
+
+        Ok(css)
+    }
+}
+
+// Reads the properties of a rule by name, deserializing each value as the type of the property
+#[cfg(feature = "stylesheets")]
+fn read_property<'de, A: serde::de::MapAccess<'de>>(
+    name: &str,
+    map: &mut A,
+) -> Result<Property, A::Error> {
+    Ok(match name {
+        "display" => Property::Display(map.next_value()?),
+        "visibility" => Property::Visibility(map.next_value()?),
+        "overflow" => Property::Overflow(map.next_value()?),
+        "opacity" => Property::Opacity(map.next_value()?),
+
+        "position" => Property::Position(map.next_value()?),
+        "left" => Property::Left(map.next_value()?),
+        "right" => Property::Right(map.next_value()?),
+        "top" => Property::Top(map.next_value()?),
+        "bottom" => Property::Bottom(map.next_value()?),
+
+        "width" => Property::Width(map.next_value()?),
+        "height" => Property::Height(map.next_value()?),
+        "min-width" => Property::MinWidth(map.next_value()?),
+        "min-height" => Property::MinHeight(map.next_value()?),
+        "max-width" => Property::MaxWidth(map.next_value()?),
+        "max-height" => Property::MaxHeight(map.next_value()?),
+
+        "margin" => Property::Margin(map.next_value()?),
+        "margin-left" => Property::MarginLeft(map.next_value()?),
+        "margin-right" => Property::MarginRight(map.next_value()?),
+        "margin-top" => Property::MarginTop(map.next_value()?),
+        "margin-bottom" => Property::MarginBottom(map.next_value()?),
+
+        "padding" => Property::Padding(map.next_value()?),
+        "padding-left" => Property::PaddingLeft(map.next_value()?),
+        "padding-right" => Property::PaddingRight(map.next_value()?),
+        "padding-top" => Property::PaddingTop(map.next_value()?),
+        "padding-bottom" => Property::PaddingBottom(map.next_value()?),
+
+        "child-space" => Property::ChildSpace(map.next_value()?),
+        "child-left" => Property::ChildLeft(map.next_value()?),
+        "child-right" => Property::ChildRight(map.next_value()?),
+        "child-top" => Property::ChildTop(map.next_value()?),
+        "child-bottom" => Property::ChildBottom(map.next_value()?),
+
+        "flex-direction" => Property::FlexDirection(map.next_value()?),
+        "justify-content" => Property::JustifyContent(map.next_value()?),
+        "align-items" => Property::AlignItems(map.next_value()?),
+        "flex-basis" => Property::FlexBasis(map.next_value()?),
+        "flex-grow" => Property::FlexGrow(map.next_value()?),
+        "flex-shrink" => Property::FlexShrink(map.next_value()?),
+
+        "border-radius" => Property::BorderRadius(map.next_value()?),
+        "border-top-left-radius" => Property::BorderTopLeftRadius(map.next_value()?),
+        "border-top-right-radius" => Property::BorderTopRightRadius(map.next_value()?),
+        "border-bottom-left-radius" => Property::BorderBottomLeftRadius(map.next_value()?),
+        "border-bottom-right-radius" => Property::BorderBottomRightRadius(map.next_value()?),
+        "border-width" => Property::BorderWidth(map.next_value()?),
+        "border-color" => Property::BorderColor(map.next_value()?),
+
+        "outline-width" => Property::OutlineWidth(map.next_value()?),
+        "outline-color" => Property::OutlineColor(map.next_value()?),
+        "outline-offset" => Property::OutlineOffset(map.next_value()?),
+
+        "background-color" => Property::BackgroundColor(map.next_value()?),
+        "color" => Property::FontColor(map.next_value()?),
+        "font-size" => Property::FontSize(map.next_value()?),
+        "selection-color" => Property::SelectionColor(map.next_value()?),
+        "caret-color" => Property::CaretColor(map.next_value()?),
+
+        _ => {
+            return Err(serde::de::Error::custom(format!(
+                "unsupported property `{}`",
+                name
+            )));
+        }
+    })
+}
+
+// Returns the CSS declaration of a property read from a stylesheet
+#[cfg(feature = "stylesheets")]
+fn property_css(property: &Property) -> Result<String, Error> {
+    use crate::style::{AlignItems, Display, FlexDirection, JustifyContent};
+    use crate::style::{Overflow, Position, Visibility};
+
+    let unsupported = |value: &str| {
+        Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("{} can't be used in a stylesheet", value),
+        ))
+    };
+
+    Ok(match property {
+        Property::Display(value) => match value {
+            Display::None => "display: none".to_string(),
+            Display::Flexbox => "display: flex".to_string(),
+            _ => return unsupported(&format!("display {:?}", value)),
+        },
+        Property::Visibility(value) => match value {
+            Visibility::Visible => "visibility: visible".to_string(),
+            Visibility::Invisible => "visibility: hidden".to_string(),
+        },
+        Property::Overflow(value) => match value {
+            Overflow::Visible => "overflow: visible".to_string(),
+            Overflow::Hidden => "overflow: hidden".to_string(),
+        },
+        Property::Opacity(value) => format!("opacity: {}", value),
+
+        Property::Position(value) => match value {
+            Position::Relative => "position: relative".to_string(),
+            Position::Absolute => "position: absolute".to_string(),
+        },
+        Property::Left(value) => format!("left: {}", length_css(value)),
+        Property::Right(value) => format!("right: {}", length_css(value)),
+        Property::Top(value) => format!("top: {}", length_css(value)),
+        Property::Bottom(value) => format!("bottom: {}", length_css(value)),
+
+        Property::Width(value) => format!("width: {}", length_css(value)),
+        Property::Height(value) => format!("height: {}", length_css(value)),
+        Property::MinWidth(value) => format!("min-width: {}", length_css(value)),
+        Property::MinHeight(value) => format!("min-height: {}", length_css(value)),
+        Property::MaxWidth(value) => format!("max-width: {}", length_css(value)),
+        Property::MaxHeight(value) => format!("max-height: {}", length_css(value)),
+
+        Property::Margin(value) => format!("margin: {}", length_css(value)),
+        Property::MarginLeft(value) => format!("margin-left: {}", length_css(value)),
+        Property::MarginRight(value) => format!("margin-right: {}", length_css(value)),
+        Property::MarginTop(value) => format!("margin-top: {}", length_css(value)),
+        Property::MarginBottom(value) => format!("margin-bottom: {}", length_css(value)),
+
+        Property::Padding(value) => format!("padding: {}", length_css(value)),
+        Property::PaddingLeft(value) => format!("padding-left: {}", length_css(value)),
+        Property::PaddingRight(value) => format!("padding-right: {}", length_css(value)),
+        Property::PaddingTop(value) => format!("padding-top: {}", length_css(value)),
+        Property::PaddingBottom(value) => format!("padding-bottom: {}", length_css(value)),
+
+        Property::ChildSpace(value) => format!("child-space: {}", length_css(value)),
+        Property::ChildLeft(value) => format!("child-left: {}", length_css(value)),
+        Property::ChildRight(value) => format!("child-right: {}", length_css(value)),
+        Property::ChildTop(value) => format!("child-top: {}", length_css(value)),
+        Property::ChildBottom(value) => format!("child-bottom: {}", length_css(value)),
+
+        Property::FlexDirection(value) => match value {
+            FlexDirection::Row => "flex-direction: row".to_string(),
+            FlexDirection::Column => "flex-direction: column".to_string(),
+        },
+        Property::JustifyContent(value) => match value {
+            JustifyContent::FlexStart => "justify-content: flex-start".to_string(),
+            JustifyContent::FlexEnd => "justify-content: flex-end".to_string(),
+            JustifyContent::Center => "justify-content: center".to_string(),
+            JustifyContent::SpaceBetween => "justify-content: space-between".to_string(),
+            JustifyContent::SpaceAround => "justify-content: space-around".to_string(),
+            JustifyContent::SpaceEvenly => "justify-content: space-evenly".to_string(),
+            _ => return unsupported(&format!("justify-content {:?}", value)),
+        },
+        Property::AlignItems(value) => match value {
+            AlignItems::FlexStart => "align-items: flex-start".to_string(),
+            AlignItems::FlexEnd => "align-items: flex-end".to_string(),
+            AlignItems::Center => "align-items: center".to_string(),
+            AlignItems::Stretch => "align-items: stretch".to_string(),
+        },
+        Property::FlexBasis(value) => format!("flex-basis: {}", value),
+        Property::FlexGrow(value) => format!("flex-grow: {}", value),
+        Property::FlexShrink(value) => format!("flex-shrink: {}", value),
+
+        Property::BorderRadius(value) => format!("border-radius: {}", length_css(value)),
+        Property::BorderTopLeftRadius(value) => {
+            format!("border-top-left-radius: {}", length_css(value))
+        }
+        Property::BorderTopRightRadius(value) => {
+            format!("border-top-right-radius: {}", length_css(value))
+        }
+        Property::BorderBottomLeftRadius(value) => {
+            format!("border-bottom-left-radius: {}", length_css(value))
+        }
+        Property::BorderBottomRightRadius(value) => {
+            format!("border-bottom-right-radius: {}", length_css(value))
+        }
+        Property::BorderWidth(value) => format!("border-width: {}", length_css(value)),
+        Property::BorderColor(value) => format!("border-color: {}", color_css(value)),
+
+        Property::OutlineWidth(value) => format!("outline-width: {}", length_css(value)),
+        Property::OutlineColor(value) => format!("outline-color: {}", color_css(value)),
+        Property::OutlineOffset(value) => format!("outline-offset: {}", length_css(value)),
+
+        Property::BackgroundColor(value) => format!("background-color: {}", color_css(value)),
+        Property::FontColor(value) => format!("color: {}", color_css(value)),
+        Property::FontSize(value) => format!("font-size: {}", value),
+        Property::SelectionColor(value) => format!("selection-color: {}", color_css(value)),
+        Property::CaretColor(value) => format!("caret-color: {}", color_css(value)),
+
+        _ => return unsupported(&format!("{:?}", property)),
+    })
+}
+
+#[cfg(feature = "stylesheets")]
+fn length_css(length: &Length) -> String {
+    match length {
+        Length::Auto => "auto".to_string(),
+        Length::Initial(value) | Length::Pixels(value) => format!("{}px", value),
+        Length::Percentage(value) => format!("{}%", value * 100.0),
+        Length::Stretch(value) => format!("{}fr", value),
+    }
+}
+
+// Written with the alpha first, which the theme parser reads the same way as the serialized color
+#[cfg(feature = "stylesheets")]
+fn color_css(color: &Color) -> String {
+    let data = color.data;
+    format!("#{:08x}", data)
+}
+
+#[cfg(feature = "stylesheets")]
+impl<'de> serde::Deserialize<'de> for StylesheetRules {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct RulesVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for RulesVisitor {
+            type Value = StylesheetRules;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a map of selectors to properties")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut rules = Vec::new();
+                while let Some(selector) = map.next_key::<String>()? {
+                    let properties: RuleProperties = map.next_value()?;
+                    rules.push((selector, properties.0));
+                }
+
+                Ok(StylesheetRules { rules })
+            }
+        }
+
+        deserializer.deserialize_map(RulesVisitor)
+    }
+}
+
+// The properties of a single rule, in the order they are written
+#[cfg(feature = "stylesheets")]
+struct RuleProperties(Vec<Property>);
+
+#[cfg(feature = "stylesheets")]
+impl<'de> serde::Deserialize<'de> for RuleProperties {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct PropertiesVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for PropertiesVisitor {
+            type Value = RuleProperties;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a map of property names to values")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut properties = Vec::new();
+                while let Some(name) = map.next_key::<String>()? {
+                    properties.push(read_property(&name, &mut map)?);
+                }
+
+                Ok(RuleProperties(properties))
+            }
+        }
+
+        deserializer.deserialize_map(PropertiesVisitor)
+    }
+}
+
+#[cfg(all(test, feature = "stylesheets"))]
+mod tests {
+    use super::*;
+
+    fn json_to_css(json: &str) -> Result<String, Error> {
+        rules_to_css("theme.json", json)
+    }
+
+    #[test]
+    fn rules_keep_their_order() {
+        let css = json_to_css(
+            r##"{
+                ".second": { "width": { "Pixels": 20.0 }, "height": { "Pixels": 10.0 } },
+                ".first": { "width": { "Pixels": 10.0 } }
+            }"##,
+        )
+        .unwrap();
+
+        assert_eq!(
+            css,
+            ".second {\n    width: 20px;\n    height: 10px;\n}\n.first {\n    width: 10px;\n}\n"
+        );
+    }
+
+    #[test]
+    fn values_use_the_style_types() {
+        let css = json_to_css(
+            r##"{
+                "button:hover": {
+                    "background-color": "#80ff0000",
+                    "width": { "Percentage": 0.5 },
+                    "height": "Auto",
+                    "flex-direction": "Column",
+                    "opacity": 0.5
+                }
+            }"##,
+        )
+        .unwrap();
+
+        assert_eq!(
+            css,
+            "button:hover {\n    background-color: #80ff0000;\n    width: 50%;\n    height: auto;\n    flex-direction: column;\n    opacity: 0.5;\n}\n"
+        );
+    }
+
+    #[test]
+    fn ron_rules_are_read() {
+        let css = rules_to_css(
+            "theme.ron",
+            r##"{ "#title": { "margin": Pixels(5.0), "color": "#ffe4e4e4" } }"##,
+        )
+        .unwrap();
+
+        assert_eq!(
+            css,
+            "#title {\n    margin: 5px;\n    color: #ffe4e4e4;\n}\n"
+        );
+    }
+
+    #[test]
+    fn untyped_or_unknown_values_are_errors() {
+        assert!(json_to_css(r#"{ "button": { "width": "100px" } }"#).is_err());
+        assert!(json_to_css(r#"{ "button": { "background-color": "red" } }"#).is_err());
+        assert!(json_to_css(r#"{ "button": { "transition": "width 1s" } }"#).is_err());
+    }
+}
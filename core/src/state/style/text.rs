@@ -5,6 +5,7 @@ use crate::layout::{Align, Justify};
 use crate::style::Color;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Text {
     pub text: String,
     pub font: String,
@@ -17,7 +18,7 @@ impl Default for Text {
     fn default() -> Self {
         Text {
             text: "".to_string(),
-            font: "Sans".to_string(),
+            font: "sans".to_string(),
             //font_size: 16.0,
             //font_color: Color::rgba(255, 255, 255, 255),
             //indent: 0.0,
@@ -24,6 +24,8 @@ pub enum CustomParseError {
     InvalidColorName(String),
     InvalidColorHex(String),
     InvalidStringName(String),
+    UnsupportedProperty(String),
+    UnsupportedPseudoClass(String),
 }
 
 impl<'t> From<CustomParseError> for ParseError<'t, CustomParseError> {
@@ -64,8 +66,15 @@ impl<'i> cssparser::QualifiedRuleParser<'i> for RuleParser {
     ) -> Result<Self::QualifiedRule, ParseError<'i, Self::Error>> {
         let decl_parser = DeclarationParser {};
 
+        // Declarations which fail to parse are skipped so the rest of the rule still applies
         let properties = DeclarationListParser::new(input, decl_parser)
-            .filter_map(|property| property.ok())
+            .filter_map(|property| match property {
+                Ok(property) => Some(property),
+                Err((_, declaration)) => {
+                    eprintln!("WARNING: Skipping declaration: {}", declaration.trim());
+                    None
+                }
+            })
             .collect::<Vec<_>>();
 
         Ok(StyleRule {
@@ -214,7 +223,24 @@ fn parse_selectors<'i, 't>(
 
             // Pseudo-class
             Token::Colon => {
-                let pseudo_class_str = input.expect_ident()?.to_owned();
+                let pseudo_class_str = match input.next_including_whitespace()?.clone() {
+                    Token::Ident(name) => name,
+
+                    // Pseudo-classes with an argument, such as nth-child(2n+1)
+                    Token::Function(name) => {
+                        if name.as_ref() == "nth-child" {
+                            let nth = input.parse_nested_block(|input| {
+                                cssparser::parse_nth(input)
+                                    .map_err(ParseError::<CustomParseError>::from)
+                            })?;
+                            selector.nth_child = Some(nth);
+                        }
+
+                        name
+                    }
+
+                    token => return Err(input.new_unexpected_token_error(token)),
+                };
 
                 match pseudo_class_str.as_ref() {
                     "hover" => selector.pseudo_classes.set_hover(true),
@@ -223,8 +249,21 @@ fn parse_selectors<'i, 't>(
                     "enabled" => selector.pseudo_classes.set_enabled(true),
                     "disabled" => selector.pseudo_classes.set_disabled(true),
                     "checked" => selector.pseudo_classes.set_checked(true),
-
-                    _ => {}
+                    "first-child" => selector.pseudo_classes.set_first_child(true),
+                    "last-child" => selector.pseudo_classes.set_last_child(true),
+                    "nth-child" if selector.nth_child.is_some() => {}
+
+                    // Ignoring the pseudo-class would apply the rule in every state
+                    _ => {
+                        eprintln!(
+                            "WARNING: Skipping rule with unsupported pseudo-class: {}",
+                            pseudo_class_str
+                        );
+                        return Err(CustomParseError::UnsupportedPseudoClass(
+                            pseudo_class_str.to_string(),
+                        )
+                        .into());
+                    }
                 }
 
                 // let pseudo_class = match pseudo_class_str.as_ref() {
@@ -347,6 +386,13 @@ impl<'i> cssparser::DeclarationParser<'i> for DeclarationParser {
             "padding-top" => Property::PaddingTop(parse_length(input)?),
             "padding-bottom" => Property::PaddingBottom(parse_length(input)?),
 
+            // Child Space
+            "child-space" => Property::ChildSpace(parse_length(input)?),
+            "child-left" => Property::ChildLeft(parse_length(input)?),
+            "child-right" => Property::ChildRight(parse_length(input)?),
+            "child-top" => Property::ChildTop(parse_length(input)?),
+            "child-bottom" => Property::ChildBottom(parse_length(input)?),
+
             "text-align" => Property::TextAlign(parse_alignment(input)?),
             "text-justify" => Property::TextJustify(parse_justification(input)?),
 
@@ -355,6 +401,15 @@ impl<'i> cssparser::DeclarationParser<'i> for DeclarationParser {
             // Border
             "border-width" => Property::BorderWidth(parse_length(input)?),
             "border-color" => Property::BorderColor(parse_color(input)?),
+
+            // Outline
+            "outline-width" => Property::OutlineWidth(parse_length(input)?),
+            "outline-color" => Property::OutlineColor(parse_color(input)?),
+            "outline-offset" => Property::OutlineOffset(parse_length(input)?),
+
+            // Text Input
+            "selection-color" => Property::SelectionColor(parse_color(input)?),
+            "caret-color" => Property::CaretColor(parse_color(input)?),
             // TODO - Support array for specifying each corner
             "border-radius" => Property::BorderRadius(parse_length(input)?),
 
@@ -393,11 +448,7 @@ impl<'i> cssparser::DeclarationParser<'i> for DeclarationParser {
             "z-index" => Property::ZIndex(parse_z_index(input)?),
 
             _ => {
-                let basic_error = BasicParseError {
-                    kind: BasicParseErrorKind::UnexpectedToken(input.next()?.to_owned()),
-                    location: SourceLocation { line: 0, column: 0 },
-                };
-                return Err(basic_error.into());
+                return Err(CustomParseError::UnsupportedProperty(name.to_string()).into());
             }
         })
     }
@@ -613,7 +664,14 @@ fn parse_length<'i, 't>(
         Token::Number { value: x, .. } => Length::Pixels(*x as f32),
         Token::Percentage { unit_value: x, .. } => Length::Percentage(*x as f32),
 
+        Token::Dimension {
+            value: x, ref unit, ..
+        } if unit.as_ref() == "fr" => Length::Stretch(*x as f32),
+
         Token::Dimension { value: x, .. } => Length::Pixels(*x as f32),
+
+        Token::Ident(name) if name.as_ref() == "auto" => Length::Auto,
+
         t => {
             let basic_error = BasicParseError {
                 kind: BasicParseErrorKind::UnexpectedToken(t.to_owned()),
@@ -1027,3 +1085,144 @@ pub fn parse(s: &str) -> Vec<StyleRule> {
 
     rules.into_iter().filter_map(|rule| rule.ok()).collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Parses the declarations as the body of a single rule
+    fn parse_declarations(declarations: &str) -> Vec<Property> {
+        let rules = parse(&format!(".a {{ {} }}", declarations));
+        assert_eq!(rules.len(), 1);
+        rules[0].properties.clone()
+    }
+
+    fn assert_parses_to(declaration: &str, expected: &str) {
+        let properties = parse_declarations(declaration);
+        assert_eq!(properties.len(), 1, "{} wasn't parsed", declaration);
+        assert_eq!(format!("{:?}", properties[0]), expected, "{}", declaration);
+    }
+
+    #[test]
+    fn parses_length_properties() {
+        for name in [
+            "left",
+            "right",
+            "top",
+            "bottom",
+            "width",
+            "height",
+            "min-width",
+            "min-height",
+            "max-width",
+            "max-height",
+            "margin",
+            "margin-left",
+            "margin-right",
+            "margin-top",
+            "margin-bottom",
+            "padding",
+            "padding-left",
+            "padding-right",
+            "padding-top",
+            "padding-bottom",
+            "child-space",
+            "child-left",
+            "child-right",
+            "child-top",
+            "child-bottom",
+            "border-width",
+            "border-radius",
+            "border-top-left-radius",
+            "border-top-right-radius",
+            "border-bottom-left-radius",
+            "border-bottom-right-radius",
+            "outline-width",
+            "outline-offset",
+        ]
+        .iter()
+        {
+            let properties = parse_declarations(&format!("{}: 10px", name));
+            assert_eq!(properties.len(), 1, "{} wasn't parsed", name);
+            assert!(
+                format!("{:?}", properties[0]).ends_with("(Pixels(10.0))"),
+                "{} parsed to {:?}",
+                name,
+                properties[0]
+            );
+        }
+
+        assert_parses_to("width: 10", "Width(Pixels(10.0))");
+        assert_parses_to("width: 50%", "Width(Percentage(0.5))");
+        assert_parses_to("width: 2fr", "Width(Stretch(2.0))");
+        assert_parses_to("width: auto", "Width(Auto)");
+    }
+
+    #[test]
+    fn parses_color_properties() {
+        let red = "(r: 255, g: 0, b: 0 a: 255)";
+
+        assert_parses_to(
+            "background-color: #ff0000",
+            &format!("BackgroundColor({})", red),
+        );
+        assert_parses_to("color: red", &format!("FontColor({})", red));
+        assert_parses_to("border-color: #ff0000", &format!("BorderColor({})", red));
+        assert_parses_to("outline-color: #ff0000", &format!("OutlineColor({})", red));
+        assert_parses_to(
+            "selection-color: #ff0000",
+            &format!("SelectionColor({})", red),
+        );
+        assert_parses_to("caret-color: #ff0000", &format!("CaretColor({})", red));
+    }
+
+    #[test]
+    fn parses_keyword_properties() {
+        assert_parses_to("display: none", "Display(None)");
+        assert_parses_to("display: flex", "Display(Flexbox)");
+        assert_parses_to("visibility: hidden", "Visibility(Invisible)");
+        assert_parses_to("overflow: hidden", "Overflow(Hidden)");
+        assert_parses_to("position: absolute", "Position(Absolute)");
+        assert_parses_to("flex-direction: column", "FlexDirection(Column)");
+        assert_parses_to(
+            "justify-content: space-between",
+            "JustifyContent(SpaceBetween)",
+        );
+        assert_parses_to("align-content: stretch", "AlignContent(Stretch)");
+        assert_parses_to("align-items: center", "AlignItems(Center)");
+        assert_parses_to("align-self: flex-end", "AlignSelf(FlexEnd)");
+        assert_parses_to("text-align: center", "TextAlign(Center)");
+        assert_parses_to("text-justify: right", "TextJustify(End)");
+    }
+
+    #[test]
+    fn parses_number_and_string_properties() {
+        assert_parses_to("opacity: 0.5", "Opacity(0.5)");
+        assert_parses_to("flex-grow: 2", "FlexGrow(2.0)");
+        assert_parses_to("flex-shrink: 0", "FlexShrink(0.0)");
+        assert_parses_to("flex-basis: 30", "FlexBasis(30.0)");
+        assert_parses_to("font-size: 20px", "FontSize(20.0)");
+        assert_parses_to("z-index: 3", "ZIndex(3)");
+        assert_parses_to(
+            "background-image: \"icon.png\"",
+            "BackgroundImage(\"icon.png\")",
+        );
+    }
+
+    #[test]
+    fn unsupported_and_invalid_declarations_are_skipped() {
+        let properties = parse_declarations("width: 10px; float: left; height: tall; margin: 5px");
+
+        assert_eq!(properties.len(), 2);
+        assert_eq!(format!("{:?}", properties[0]), "Width(Pixels(10.0))");
+        assert_eq!(format!("{:?}", properties[1]), "Margin(Pixels(5.0))");
+    }
+
+    #[test]
+    fn rule_with_unsupported_pseudo_class_is_skipped() {
+        let rules = parse(".a:visited { width: 10px } .b:hover { width: 20px }");
+
+        assert_eq!(rules.len(), 1);
+        assert!(rules[0].selectors[0].classes.contains("b"));
+    }
+}
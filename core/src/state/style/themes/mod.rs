@@ -1 +1,90 @@
+use std::collections::HashMap;
+
 pub const DEFAULT_THEME: &str = include_str!("default_theme.css");
+
+// A named set of variables which themes and stylesheets reference with var(--name)
+// Switching to another theme with State::set_theme restyles the tree with the new values
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub name: String,
+    pub variables: HashMap<String, String>,
+}
+
+impl Theme {
+    pub fn new(name: &str) -> Self {
+        Theme {
+            name: name.to_owned(),
+            variables: HashMap::new(),
+        }
+    }
+
+    // The name can be given with or without the leading dashes
+    pub fn with_variable(mut self, name: &str, value: &str) -> Self {
+        self.variables
+            .insert(name.trim_start_matches("--").to_owned(), value.to_owned());
+
+        self
+    }
+
+    pub fn get_variable(&self, name: &str) -> Option<&str> {
+        self.variables
+            .get(name.trim_start_matches("--"))
+            .map(|value| value.as_str())
+    }
+
+    // Dark text on light backgrounds, the values used by the default theme
+    pub fn light() -> Self {
+        Theme::new("light")
+            .with_variable("background", "#ffffff")
+            .with_variable("text", "#000000")
+            .with_variable("border", "#000000")
+            .with_variable("muted", "#646464")
+            .with_variable("accent", "#ff5e1a")
+            .with_variable("accent-hover", "#ff7033")
+    }
+
+    // Light text on dark backgrounds
+    pub fn dark() -> Self {
+        Theme::new("dark")
+            .with_variable("background", "#2b2b2b")
+            .with_variable("text", "#e6e6e6")
+            .with_variable("border", "#6e6e6e")
+            .with_variable("muted", "#4a4a4a")
+            .with_variable("accent", "#ff5e1a")
+            .with_variable("accent-hover", "#ff7033")
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::light()
+    }
+}
+
+// Replaces each var(--name) in a stylesheet with the value of the variable in the theme
+// References to variables the theme doesn't define are left in place so the declarations using them are skipped
+pub fn resolve_variables(stylesheet: &str, theme: &Theme) -> String {
+    let mut resolved = String::with_capacity(stylesheet.len());
+    let mut rest = stylesheet;
+
+    while let Some(start) = rest.find("var(--") {
+        resolved += &rest[..start];
+        rest = &rest[start..];
+
+        let end = match rest.find(')') {
+            Some(end) => end,
+            None => break,
+        };
+
+        match theme.get_variable(rest["var(".len()..end].trim()) {
+            Some(value) => resolved += value,
+            None => resolved += &rest[..=end],
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    resolved += rest;
+
+    resolved
+}
@@ -21,3 +21,29 @@ impl Interpolator for Scale {
         return Scale(start.0 + (end.0 - start.0) * t);
     }
 }
+
+// The point which rotate and scale transforms are applied around
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum TransformOrigin {
+    TopLeft,
+    Center,
+    // Offset in pixels from the top left corner of the entity
+    Point(f32, f32),
+}
+
+impl TransformOrigin {
+    // Returns the offset of the origin from the top left corner of an entity with the given size
+    pub fn resolve(&self, width: f32, height: f32) -> (f32, f32) {
+        match self {
+            TransformOrigin::TopLeft => (0.0, 0.0),
+            TransformOrigin::Center => (width / 2.0, height / 2.0),
+            TransformOrigin::Point(x, y) => (*x, *y),
+        }
+    }
+}
+
+impl Default for TransformOrigin {
+    fn default() -> Self {
+        TransformOrigin::Center
+    }
+}
@@ -0,0 +1,84 @@
+// Watching stylesheets for changes uses the notify crate and requires the hot-reload feature
+// Watching is only done in debug builds, in release builds watched stylesheets are loaded once
+
+use std::time::Duration;
+
+// How long a file must go unchanged before it is reloaded, so an editor saving in several steps triggers one reload
+pub const STYLESHEET_DEBOUNCE: Duration = Duration::from_millis(200);
+
+#[cfg(all(feature = "hot-reload", debug_assertions))]
+pub use watcher::StylesheetWatcher;
+
+#[cfg(all(feature = "hot-reload", debug_assertions))]
+mod watcher {
+    use super::STYLESHEET_DEBOUNCE;
+
+    use notify::{DebouncedEvent, RecommendedWatcher, RecursiveMode, Watcher};
+
+    use std::io;
+    use std::path::{Path, PathBuf};
+    use std::sync::mpsc::{channel, Receiver};
+
+    pub struct StylesheetWatcher {
+        watcher: RecommendedWatcher,
+        receiver: Receiver<DebouncedEvent>,
+        paths: Vec<PathBuf>,
+    }
+
+    impl StylesheetWatcher {
+        pub fn new() -> Result<Self, io::Error> {
+            let (sender, receiver) = channel();
+            let watcher = notify::watcher(sender, STYLESHEET_DEBOUNCE).map_err(to_io_error)?;
+
+            Ok(StylesheetWatcher {
+                watcher,
+                receiver,
+                paths: Vec::new(),
+            })
+        }
+
+        pub fn watch(&mut self, path: &str) -> Result<(), io::Error> {
+            let path = Path::new(path).canonicalize()?;
+            if self.paths.contains(&path) {
+                return Ok(());
+            }
+
+            // The directory is watched because editors often save by replacing the file, which ends a watch on the file itself
+            let directory = path.parent().unwrap_or(&path).to_owned();
+            self.watcher
+                .watch(&directory, RecursiveMode::NonRecursive)
+                .map_err(to_io_error)?;
+
+            self.paths.push(path);
+
+            Ok(())
+        }
+
+        // Returns true if any of the watched stylesheets changed since the last call
+        pub fn changed(&mut self) -> bool {
+            let mut changed = false;
+            while let Ok(event) = self.receiver.try_recv() {
+                let path = match event {
+                    DebouncedEvent::Create(path)
+                    | DebouncedEvent::Write(path)
+                    | DebouncedEvent::Rename(_, path) => path,
+                    _ => continue,
+                };
+
+                let path = path.canonicalize().unwrap_or(path);
+                if self.paths.contains(&path) {
+                    changed = true;
+                }
+            }
+
+            changed
+        }
+    }
+
+    fn to_io_error(error: notify::Error) -> io::Error {
+        match error {
+            notify::Error::Io(error) => error,
+            error => io::Error::new(io::ErrorKind::Other, error.to_string()),
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use crate::Entity;
+
+use femtovg::{Align, Baseline, TextMetrics};
+use fnv::FnvHashMap;
+
+use std::ops::Range;
+
+// What the layout of some text depends on
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextLayoutKey {
+    pub text: String,
+    pub font: String,
+    pub font_size: f32,
+    // The available width, infinite for text which doesn't wrap
+    pub width: f32,
+    pub align: Align,
+    pub baseline: Baseline,
+}
+
+// The lines of an entity's text with their shaped glyphs, reused between draws while the key is unchanged
+#[derive(Clone, Debug)]
+pub struct TextLayout {
+    pub key: TextLayoutKey,
+
+    pub lines: Vec<TextLine>,
+    pub line_height: f32,
+}
+
+// A line of text and its glyphs, positioned relative to the point the line is drawn at
+#[derive(Clone, Debug)]
+pub struct TextLine {
+    pub range: Range<usize>,
+    pub metrics: TextMetrics,
+}
+
+impl TextLine {
+    // Returns the metrics of the line drawn at the given position
+    pub fn metrics_at(&self, x: f32, y: f32) -> TextMetrics {
+        let mut metrics = self.metrics.clone();
+        metrics.x += x;
+        metrics.y += y;
+
+        for glyph in metrics.glyphs.iter_mut() {
+            glyph.x += x;
+            glyph.y += y;
+        }
+
+        metrics
+    }
+}
+
+// Returns the layout of an entity's text, only calling shape to break and shape the text again when the key
+// differs from the one of the cached layout
+// shape returns the lines of the text and the line height
+pub fn cached_text_layout<F>(
+    text_layouts: &mut FnvHashMap<Entity, TextLayout>,
+    entity: Entity,
+    key: TextLayoutKey,
+    shape: F,
+) -> Option<&TextLayout>
+where
+    F: FnOnce() -> Option<(Vec<TextLine>, f32)>,
+{
+    let cached = text_layouts
+        .get(&entity)
+        .map(|layout| layout.key == key)
+        .unwrap_or(false);
+
+    if !cached {
+        let (lines, line_height) = shape()?;
+
+        text_layouts.insert(
+            entity,
+            TextLayout {
+                key,
+                lines,
+                line_height,
+            },
+        );
+    }
+
+    text_layouts.get(&entity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Lays out the text as a single line, counting the number of times it is shaped
+    fn layout(
+        text_layouts: &mut FnvHashMap<Entity, TextLayout>,
+        text: &str,
+        width: f32,
+        shaped: &mut usize,
+    ) {
+        let key = TextLayoutKey {
+            text: text.to_string(),
+            font: "sans".to_string(),
+            font_size: 16.0,
+            width,
+            align: Align::Left,
+            baseline: Baseline::Top,
+        };
+
+        cached_text_layout(text_layouts, Entity::new(1, 0), key, || {
+            *shaped += 1;
+            Some((
+                vec![TextLine {
+                    range: 0..text.len(),
+                    metrics: TextMetrics::default(),
+                }],
+                20.0,
+            ))
+        });
+    }
+
+    #[test]
+    fn second_draw_reuses_cached_layout() {
+        let mut text_layouts = FnvHashMap::default();
+        let mut shaped = 0;
+
+        layout(&mut text_layouts, "Hello", 100.0, &mut shaped);
+        layout(&mut text_layouts, "Hello", 100.0, &mut shaped);
+
+        assert_eq!(shaped, 1);
+    }
+
+    #[test]
+    fn changed_text_or_width_is_shaped_again() {
+        let mut text_layouts = FnvHashMap::default();
+        let mut shaped = 0;
+
+        layout(&mut text_layouts, "Hello", 100.0, &mut shaped);
+        layout(&mut text_layouts, "Hello world", 100.0, &mut shaped);
+        layout(&mut text_layouts, "Hello world", 50.0, &mut shaped);
+
+        assert_eq!(shaped, 3);
+    }
+}
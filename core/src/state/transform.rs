@@ -10,6 +10,44 @@ pub struct Rect {
     pub h: f32,
 }
 
+impl Rect {
+    pub fn new(x: f32, y: f32, w: f32, h: f32) -> Self {
+        Rect { x, y, w, h }
+    }
+
+    pub fn area(&self) -> f32 {
+        self.w * self.h
+    }
+
+    // Returns the smallest rect containing both rects
+    pub fn union(&self, other: &Rect) -> Rect {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        Rect {
+            x,
+            y,
+            w: (self.x + self.w).max(other.x + other.w) - x,
+            h: (self.y + self.h).max(other.y + other.h) - y,
+        }
+    }
+
+    // Returns the overlap of both rects, which has no area if they don't overlap
+    pub fn intersection(&self, other: &Rect) -> Rect {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        Rect {
+            x,
+            y,
+            w: ((self.x + self.w).min(other.x + other.w) - x).max(0.0),
+            h: ((self.y + self.h).min(other.y + other.h) - y).max(0.0),
+        }
+    }
+
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.intersection(other).area() > 0.0
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct Pos {
     pub x: f32,
@@ -55,6 +93,8 @@ pub struct Transform {
     pub hoverability: Vec<bool>,
     pub z_order: Vec<i32>,
     pub clip_widget: Vec<Entity>,
+    // True for overlays and their descendants, which are drawn and hit-tested after the rest of the tree
+    pub overlay: Vec<bool>,
     // Holds the child_width_sum and then the free_width_space
     pub child_sum: Vec<f32>, // Sum of child widths
     pub child_max: Vec<f32>, // Max child width
@@ -80,6 +120,7 @@ impl Transform {
             opacity: Vec::new(),
             z_order: Vec::new(),
             clip_widget: Vec::new(),
+            overlay: Vec::new(),
         }
     }
 
@@ -99,6 +140,7 @@ impl Transform {
             self.opacity.resize(key + 1, 0.0);
             self.z_order.resize(key + 1, 0);
             self.clip_widget.resize(key + 1, Entity::new(0, 0));
+            self.overlay.resize(key + 1, false);
         }
 
         // Are these needed?
@@ -115,7 +157,28 @@ impl Transform {
         }
     }
 
-    pub fn remove(&mut self, _entity: Entity) {}
+    // Resets the transform data of a removed entity
+    pub fn remove(&mut self, entity: Entity) {
+        let key = entity.index();
+
+        if key >= self.position.len() {
+            return;
+        }
+
+        self.position[key] = Default::default();
+        self.size[key] = Default::default();
+        self.visibility[key] = Default::default();
+        self.hoverability[key] = true;
+        self.child_sum[key] = 0.0;
+        self.child_max[key] = 0.0;
+        self.child_pos[key] = 0.0;
+        self.child_grow_sum[key] = 0.0;
+        self.child_shrink_sum[key] = 0.0;
+        self.opacity[key] = 0.0;
+        self.z_order[key] = 0;
+        self.clip_widget[key] = Entity::new(0, 0);
+        self.overlay[key] = false;
+    }
 
     // For getters and setters it's safe to use unwrap because every entity must have a position and size.
     // Event if the position and size are 0.0, or the entity is invisible.
@@ -128,6 +191,10 @@ impl Transform {
         self.z_order.get(entity.index()).cloned().unwrap()
     }
 
+    pub fn is_overlay(&self, entity: Entity) -> bool {
+        self.overlay.get(entity.index()).cloned().unwrap()
+    }
+
     pub fn get_child_sum(&self, entity: Entity) -> f32 {
         self.child_sum.get(entity.index()).cloned().unwrap()
     }
@@ -176,6 +243,12 @@ impl Transform {
         }
     }
 
+    pub fn set_overlay(&mut self, entity: Entity, val: bool) {
+        if let Some(overlay) = self.overlay.get_mut(entity.index()) {
+            *overlay = val;
+        }
+    }
+
     pub fn set_z_order(&mut self, entity: Entity, val: i32) {
         if let Some(z_order) = self.z_order.get_mut(entity.index()) {
             *z_order = val;
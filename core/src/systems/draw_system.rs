@@ -0,0 +1,701 @@
+use crate::{
+    cached_text_layout, BorderWidthBasis, Entity, Justify, Length, Rect, State, TextLayoutKey,
+    TextLine, Visibility,
+};
+
+use femtovg::{
+    renderer::OpenGl, Align, Baseline, Canvas, FontId, ImageFlags, ImageId, Paint, Path,
+    TextMetrics,
+};
+
+// Border width and radii of an entity resolved to pixels
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct BorderShape {
+    pub width: f32,
+    pub radius_top_left: f32,
+    pub radius_top_right: f32,
+    pub radius_bottom_left: f32,
+    pub radius_bottom_right: f32,
+}
+
+// Resolves the border width and radii of an entity, with percentages relative to the parent
+pub fn resolve_border(state: &State, entity: Entity) -> BorderShape {
+    // The root has no parent so is its own basis
+    let parent = state.hierarchy.get_parent(entity).unwrap_or(entity);
+
+    let parent_width = state.transform.get_width(parent);
+    let parent_height = state.transform.get_height(parent);
+
+    let border_basis = match state.style.border_width_basis {
+        BorderWidthBasis::Width => parent_width,
+        BorderWidthBasis::Height => parent_height,
+        BorderWidthBasis::Min => parent_width.min(parent_height),
+    };
+
+    let resolve = |length: Option<&Length>, basis: f32| match length.cloned().unwrap_or_default() {
+        Length::Pixels(val) => val,
+        Length::Percentage(val) => basis * val,
+        _ => 0.0,
+    };
+
+    BorderShape {
+        width: resolve(state.style.border_width.get(entity), border_basis),
+        radius_top_left: resolve(state.style.border_radius_top_left.get(entity), parent_width),
+        radius_top_right: resolve(
+            state.style.border_radius_top_right.get(entity),
+            parent_width,
+        ),
+        radius_bottom_left: resolve(
+            state.style.border_radius_bottom_left.get(entity),
+            parent_width,
+        ),
+        radius_bottom_right: resolve(
+            state.style.border_radius_bottom_right.get(entity),
+            parent_width,
+        ),
+    }
+}
+
+// Rounds a coordinate to the nearest device pixel when pixel snapping is enabled
+pub fn snap_to_pixel(state: &State, value: f32) -> f32 {
+    if state.pixel_snapping {
+        (value * state.scale_factor).round() / state.scale_factor
+    } else {
+        value
+    }
+}
+
+// Rounds the bounds of an entity to device pixels when pixel snapping is enabled, as (x, y, width, height)
+// The edges are rounded rather than the size so neighbouring entities stay touching
+pub fn snap_bounds(state: &State, x: f32, y: f32, width: f32, height: f32) -> (f32, f32, f32, f32) {
+    let left = snap_to_pixel(state, x);
+    let top = snap_to_pixel(state, y);
+    let right = snap_to_pixel(state, x + width);
+    let bottom = snap_to_pixel(state, y + height);
+
+    (left, top, right - left, bottom - top)
+}
+
+// Returns the padding of an entity in pixels as (left, right, top, bottom)
+// Percentages are of the width or height of the parent, as in the layout
+pub fn resolve_padding(state: &State, entity: Entity) -> (f32, f32, f32, f32) {
+    let parent = state.hierarchy.get_parent(entity).unwrap_or(entity);
+
+    let parent_width = state.transform.get_width(parent);
+    let parent_height = state.transform.get_height(parent);
+
+    let resolve = |length: Option<&Length>, basis: f32| match length.cloned().unwrap_or_default() {
+        Length::Pixels(val) => val,
+        Length::Percentage(val) => basis * val,
+        _ => 0.0,
+    };
+
+    (
+        resolve(state.style.padding_left.get(entity), parent_width),
+        resolve(state.style.padding_right.get(entity), parent_width),
+        resolve(state.style.padding_top.get(entity), parent_height),
+        resolve(state.style.padding_bottom.get(entity), parent_height),
+    )
+}
+
+// Returns the bounds of an entity inset by its padding and border, which its text is drawn within
+pub fn get_content_bounds(state: &State, entity: Entity) -> Rect {
+    let (padding_left, padding_right, padding_top, padding_bottom) = resolve_padding(state, entity);
+    let border_width = resolve_border(state, entity).width;
+
+    Rect::new(
+        state.transform.get_posx(entity) + padding_left + border_width,
+        state.transform.get_posy(entity) + padding_top + border_width,
+        (state.transform.get_width(entity) - padding_left - padding_right - 2.0 * border_width)
+            .max(0.0),
+        (state.transform.get_height(entity) - padding_top - padding_bottom - 2.0 * border_width)
+            .max(0.0),
+    )
+}
+
+// Returns the font registered with a font name, falling back to the regular font
+pub fn resolve_font(state: &State, name: &str) -> FontId {
+    match state.fonts.font_ids.get(name) {
+        Some(font_id) => *font_id,
+        None => state.fonts.regular.unwrap(),
+    }
+}
+
+// Loads any fonts added to state since the last call into the canvas
+pub fn load_fonts(state: &mut State, canvas: &mut Canvas<OpenGl>) {
+    for (name, data) in state.resource_manager.fonts.drain(..) {
+        match canvas.add_font_mem(&data) {
+            Ok(font_id) => {
+                state.fonts.font_ids.insert(name, font_id);
+            }
+
+            Err(e) => eprintln!("WARNING: Failed to load font {}: {:?}", name, e),
+        }
+    }
+}
+
+// Returns the image loaded from a file path, loading it the first time it's used
+// An image which failed to load is only tried once and returns None
+pub fn load_image(state: &mut State, canvas: &mut Canvas<OpenGl>, path: &str) -> Option<ImageId> {
+    if let Some(image_id) = state.images.get(path) {
+        return *image_id;
+    }
+
+    let image_id = match canvas.load_image_file(path, ImageFlags::empty()) {
+        Ok(image_id) => Some(image_id),
+
+        Err(e) => {
+            println!("Error loading image {}: {:?}", path, e);
+            None
+        }
+    };
+
+    state.images.insert(path.to_owned(), image_id);
+
+    image_id
+}
+
+// Narrows the scissor of the canvas to a rectangle, keeping it within the region being repainted
+pub fn intersect_scissor(state: &State, canvas: &mut Canvas<OpenGl>, rect: Rect) {
+    let rect = match state.draw_region {
+        Some(region) => rect.intersection(&region),
+        None => rect,
+    };
+
+    canvas.intersect_scissor(rect.x, rect.y, rect.w, rect.h);
+}
+
+// Draws the background and border of an entity
+// Returns false if the entity is not drawn, otherwise the canvas is left saved with the
+// transform and scissor of the entity applied and the caller must restore it
+pub fn draw_background(state: &mut State, entity: Entity, canvas: &mut Canvas<OpenGl>) -> bool {
+    // Skip window
+    if entity == Entity::new(0, 0) {
+        return false;
+    }
+
+    // Skip invisible widgets
+    if state.transform.get_visibility(entity) == Visibility::Invisible {
+        return false;
+    }
+
+    if state.transform.get_opacity(entity) == 0.0 {
+        return false;
+    }
+
+    let (posx, posy, width, height) = snap_bounds(
+        state,
+        state.transform.get_posx(entity),
+        state.transform.get_posy(entity),
+        state.transform.get_width(entity),
+        state.transform.get_height(entity),
+    );
+
+    let (padding_left, padding_right, padding_top, padding_bottom) = resolve_padding(state, entity);
+
+    let background_color = state
+        .style
+        .background_color
+        .get(entity)
+        .cloned()
+        .unwrap_or_default();
+
+    let border_color = state
+        .style
+        .border_color
+        .get(entity)
+        .cloned()
+        .unwrap_or_default();
+
+    let mut border = resolve_border(state, entity);
+
+    // A border thinner than a device pixel is kept at one pixel wide rather than disappearing
+    if state.pixel_snapping && border.width > 0.0 {
+        border.width = snap_to_pixel(state, border.width).max(1.0 / state.scale_factor);
+    }
+
+    let opacity = state.transform.get_opacity(entity);
+
+    let mut background_color: femtovg::Color = background_color.into();
+    background_color.set_alphaf(background_color.a * opacity);
+
+    let mut border_color: femtovg::Color = border_color.into();
+    border_color.set_alphaf(border_color.a * opacity);
+
+    // Skip widgets with no width or no height
+    if width + 2.0 * border.width + padding_left + padding_right == 0.0
+        || height + 2.0 * border.width + padding_top + padding_bottom == 0.0
+    {
+        return false;
+    }
+
+    // Apply transformations
+    let rotate = state.style.rotate.get(entity).cloned().unwrap_or_default();
+    let scaley = state.style.scaley.get(entity).cloned().unwrap_or_default();
+    let translate_x = state
+        .style
+        .translate_x
+        .get(entity)
+        .cloned()
+        .unwrap_or_default();
+    let translate_y = state
+        .style
+        .translate_y
+        .get(entity)
+        .cloned()
+        .unwrap_or_default();
+
+    let (origin_x, origin_y) = state
+        .style
+        .transform_origin
+        .get(entity)
+        .cloned()
+        .unwrap_or_default()
+        .resolve(width, height);
+
+    canvas.save();
+    // The translation is applied first so it isn't rotated or scaled
+    canvas.translate(translate_x, translate_y);
+    canvas.translate(posx + origin_x, posy + origin_y);
+    canvas.rotate(rotate.to_radians());
+    canvas.scale(1.0, scaley.0);
+    canvas.translate(-(posx + origin_x), -(posy + origin_y));
+
+    // Apply Scissor
+    let clip_entity = state.transform.get_clip_widget(entity);
+
+    let clip_posx = state.transform.get_posx(clip_entity);
+    let clip_posy = state.transform.get_posy(clip_entity);
+    let clip_width = state.transform.get_width(clip_entity);
+    let clip_height = state.transform.get_height(clip_entity);
+
+    // Drawing is also limited to the region being repainted when only part of the window is redrawn
+    let clip = Rect::new(clip_posx, clip_posy, clip_width, clip_height);
+    let clip = match state.draw_region {
+        Some(region) => clip.intersection(&region),
+        None => clip,
+    };
+
+    canvas.scissor(clip.x, clip.y, clip.w, clip.h);
+
+    // Draw rounded rect
+    let mut path = Path::new();
+    path.rounded_rect_varying(
+        posx + (border.width / 2.0),
+        posy + (border.width / 2.0),
+        width - border.width,
+        height - border.width,
+        border.radius_top_left,
+        border.radius_top_right,
+        border.radius_bottom_right,
+        border.radius_bottom_left,
+    );
+    let paint = match state.style.background_gradient.get(entity) {
+        Some(gradient) => {
+            let (start_x, start_y, end_x, end_y) =
+                gradient.direction.points(posx, posy, width, height);
+
+            let stops: Vec<(f32, femtovg::Color)> = gradient
+                .get_stops()
+                .iter()
+                .map(|(position, color)| {
+                    let mut color: femtovg::Color = (*color).into();
+                    color.set_alphaf(color.a * opacity);
+                    (*position, color)
+                })
+                .collect();
+
+            Paint::linear_gradient_stops(start_x, start_y, end_x, end_y, &stops)
+        }
+
+        None => Paint::color(background_color),
+    };
+    canvas.fill_path(&mut path, &paint);
+
+    // The background image is stretched over the background
+    if let Some(image) = state.style.background_image.get(entity).cloned() {
+        if let Some(image_id) = load_image(state, canvas, &image) {
+            let paint = Paint::image(image_id, posx, posy, width, height, 0.0, opacity);
+            canvas.fill_path(&mut path, &paint);
+        }
+    }
+
+    // Draw border
+    let mut paint = Paint::color(border_color);
+    paint.set_line_width(border.width);
+    canvas.stroke_path(&mut path, &paint);
+
+    draw_outline(state, entity, canvas, &border);
+
+    true
+}
+
+// Draws the outline of an entity as a stroke around the outside of its border box
+// The outline doesn't take up any space so it can overlap neighbouring widgets
+// Returns the area an entity can paint into, which includes any outline outside of its bounds
+pub fn get_draw_bounds(state: &State, entity: Entity) -> Rect {
+    let resolve = |length: Option<&Length>| match length.cloned().unwrap_or_default() {
+        Length::Pixels(val) => val,
+        _ => 0.0,
+    };
+
+    let outline_width = resolve(state.style.outline_width.get(entity));
+    let outset = if outline_width > 0.0 {
+        resolve(state.style.outline_offset.get(entity)) + outline_width
+    } else {
+        0.0
+    };
+
+    // Extra pixel for antialiasing at the edges
+    let outset = outset + 1.0;
+
+    // Translated entities are drawn away from their layout position
+    let translate_x = state
+        .style
+        .translate_x
+        .get(entity)
+        .cloned()
+        .unwrap_or_default();
+    let translate_y = state
+        .style
+        .translate_y
+        .get(entity)
+        .cloned()
+        .unwrap_or_default();
+
+    Rect::new(
+        state.transform.get_posx(entity) + translate_x - outset,
+        state.transform.get_posy(entity) + translate_y - outset,
+        state.transform.get_width(entity) + 2.0 * outset,
+        state.transform.get_height(entity) + 2.0 * outset,
+    )
+}
+
+fn draw_outline(state: &State, entity: Entity, canvas: &mut Canvas<OpenGl>, border: &BorderShape) {
+    let resolve = |length: Option<&Length>| match length.cloned().unwrap_or_default() {
+        Length::Pixels(val) => val,
+        _ => 0.0,
+    };
+
+    let outline_width = resolve(state.style.outline_width.get(entity));
+
+    if outline_width <= 0.0 {
+        return;
+    }
+
+    let outline_offset = resolve(state.style.outline_offset.get(entity));
+
+    let mut outline_color: femtovg::Color = state
+        .style
+        .outline_color
+        .get(entity)
+        .cloned()
+        .unwrap_or_default()
+        .into();
+    outline_color.set_alphaf(outline_color.a * state.transform.get_opacity(entity));
+
+    // Distance from the border box to the middle of the stroke
+    let outset = outline_offset + outline_width / 2.0;
+
+    let posx = state.transform.get_posx(entity) - outset;
+    let posy = state.transform.get_posy(entity) - outset;
+    let width = state.transform.get_width(entity) + 2.0 * outset;
+    let height = state.transform.get_height(entity) + 2.0 * outset;
+
+    // Grow the corner radii with the outline so it follows the shape of the border
+    let radius = |r: f32| if r > 0.0 { r + outset } else { 0.0 };
+
+    let mut path = Path::new();
+    path.rounded_rect_varying(
+        posx,
+        posy,
+        width,
+        height,
+        radius(border.radius_top_left),
+        radius(border.radius_top_right),
+        radius(border.radius_bottom_right),
+        radius(border.radius_bottom_left),
+    );
+
+    let mut paint = Paint::color(outline_color);
+    paint.set_line_width(outline_width);
+    canvas.stroke_path(&mut path, &paint);
+}
+
+// Draws the text of an entity aligned within its content box, wrapping it onto multiple lines if text_wrap is set
+// Returns the position the text was drawn at and its metrics
+pub fn draw_text(
+    state: &mut State,
+    entity: Entity,
+    canvas: &mut Canvas<OpenGl>,
+) -> Option<(f32, f32, TextMetrics)> {
+    draw_text_offset(state, entity, canvas, 0.0)
+}
+
+// Draws the text of an entity shifted horizontally, used to scroll text wider than its content box
+pub fn draw_text_offset(
+    state: &mut State,
+    entity: Entity,
+    canvas: &mut Canvas<OpenGl>,
+    offset_x: f32,
+) -> Option<(f32, f32, TextMetrics)> {
+    let text = state.style.text.get(entity).cloned()?;
+
+    let content = state.content_box(entity);
+
+    let opacity = state.transform.get_opacity(entity);
+
+    let font_color = state
+        .style
+        .font_color
+        .get(entity)
+        .cloned()
+        .unwrap_or(crate::Color::rgb(255, 255, 255));
+
+    let font_id = resolve_font(state, &text.font);
+
+    let mut x = content.x + offset_x;
+    let mut y = content.y;
+
+    let text_align = state
+        .style
+        .text_align
+        .get(entity)
+        .cloned()
+        .unwrap_or_default();
+    let text_justify = state
+        .style
+        .text_justify
+        .get(entity)
+        .cloned()
+        .unwrap_or_default();
+
+    let align = match text_justify {
+        Justify::Start => Align::Left,
+        Justify::Center => {
+            x += 0.5 * content.w;
+            Align::Center
+        }
+        Justify::End => {
+            x += content.w;
+            Align::Right
+        }
+    };
+
+    let baseline = match text_align {
+        crate::Align::Start => Baseline::Top,
+        crate::Align::Center => {
+            y += 0.5 * content.h;
+            Baseline::Middle
+        }
+        crate::Align::End => {
+            y += content.h;
+            Baseline::Bottom
+        }
+    };
+
+    // Text starting between device pixels is blurred
+    x = snap_to_pixel(state, x);
+    y = snap_to_pixel(state, y);
+
+    let mut font_color: femtovg::Color = font_color.into();
+    font_color.set_alphaf(font_color.a * opacity);
+
+    let font_size = state.style.font_size.get(entity).cloned().unwrap_or(16.0);
+
+    let mut paint = Paint::color(font_color);
+    paint.set_font_size(font_size);
+    paint.set_font(&[font_id]);
+    paint.set_text_align(align);
+    paint.set_text_baseline(baseline);
+    paint.set_anti_alias(false);
+
+    let text_wrap = state
+        .style
+        .text_wrap
+        .get(entity)
+        .cloned()
+        .unwrap_or_default();
+
+    // Wrapped lines are positioned from the top so the vertical alignment applies to the whole block
+    if text_wrap {
+        paint.set_text_baseline(Baseline::Top);
+    }
+
+    // Text which doesn't wrap isn't affected by the available width
+    let max_width = if text_wrap {
+        content.w
+    } else {
+        std::f32::INFINITY
+    };
+
+    // The text is only broken into lines and shaped again when the text, font, available width or alignment changes
+    // Drawing the lines still goes through fill_text, which looks up the glyphs in the font atlas
+    let key = TextLayoutKey {
+        text: text.text.clone(),
+        font: text.font.clone(),
+        font_size,
+        width: max_width,
+        align,
+        baseline: if text_wrap { Baseline::Top } else { baseline },
+    };
+
+    let layout = cached_text_layout(&mut state.text_layouts, entity, key, || {
+        let ranges = if text_wrap {
+            canvas.break_text_vec(max_width, &text.text, &paint).ok()?
+        } else {
+            vec![0..text.text.len()]
+        };
+
+        let lines = ranges
+            .into_iter()
+            .map(|range| {
+                canvas
+                    .measure_text(0.0, 0.0, &text.text[range.clone()], &paint)
+                    .ok()
+                    .map(|metrics| TextLine { range, metrics })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let line_height = canvas.measure_font(&paint).ok()?.height();
+
+        Some((lines, line_height))
+    })?;
+
+    if text_wrap {
+        let text_height = layout.line_height * layout.lines.len() as f32;
+
+        y = match text_align {
+            crate::Align::Start => content.y,
+            crate::Align::Center => content.y + 0.5 * (content.h - text_height),
+            crate::Align::End => content.y + content.h - text_height,
+        };
+    }
+
+    let mut first_line = None;
+    for line in layout.lines.iter() {
+        if canvas
+            .fill_text(x, y, &text.text[line.range.clone()], &paint)
+            .is_ok()
+            && first_line.is_none()
+        {
+            first_line = Some((x, y, line.metrics_at(x, y)));
+        }
+
+        y += layout.line_height;
+    }
+
+    first_line
+}
+
+// Outlines the hovered entity and labels it with its id, element name and size
+pub fn draw_inspector(state: &State, canvas: &mut Canvas<OpenGl>) {
+    let entity = state.hovered;
+    if entity == Entity::null() || !state.is_alive(entity) {
+        return;
+    }
+
+    let posx = state.transform.get_posx(entity);
+    let posy = state.transform.get_posy(entity);
+    let width = state.transform.get_width(entity);
+    let height = state.transform.get_height(entity);
+
+    canvas.save();
+    canvas.reset();
+    canvas.scale(state.scale_factor, state.scale_factor);
+
+    let mut path = Path::new();
+    path.rect(posx + 0.5, posy + 0.5, width - 1.0, height - 1.0);
+    let mut paint = Paint::color(femtovg::Color::rgba(0, 170, 255, 60));
+    canvas.fill_path(&mut path, &paint);
+    paint = Paint::color(femtovg::Color::rgb(0, 170, 255));
+    paint.set_line_width(1.0);
+    canvas.stroke_path(&mut path, &paint);
+
+    let font_id = match state.fonts.regular {
+        Some(font_id) => font_id,
+        None => {
+            canvas.restore();
+            return;
+        }
+    };
+
+    let element = state
+        .style
+        .element_names
+        .get(entity)
+        .cloned()
+        .unwrap_or_default();
+    let label = format!("#{} {} {:.0} x {:.0}", entity, element, width, height);
+
+    let mut text_paint = Paint::color(femtovg::Color::rgb(255, 255, 255));
+    text_paint.set_font(&[font_id]);
+    text_paint.set_font_size(12.0);
+    text_paint.set_text_baseline(Baseline::Top);
+
+    let label_width = canvas
+        .measure_text(0.0, 0.0, &label, &text_paint)
+        .map(|metrics| metrics.width())
+        .unwrap_or(0.0);
+    let label_height = 18.0;
+
+    // The label sits above the entity unless that would be outside of the window
+    let label_x = posx.max(0.0);
+    let label_y = if posy >= label_height {
+        posy - label_height
+    } else {
+        posy + height
+    };
+
+    let mut path = Path::new();
+    path.rect(label_x, label_y, label_width + 8.0, label_height);
+    canvas.fill_path(&mut path, &Paint::color(femtovg::Color::rgba(0, 0, 0, 200)));
+    let _ = canvas.fill_text(label_x + 4.0, label_y + 3.0, &label, &text_paint);
+
+    canvas.restore();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A 200 by 100 parent with a child whose border width is 10% of the basis
+    fn border_width(basis: BorderWidthBasis) -> f32 {
+        let mut state = State::new_headless();
+        let root = state.root;
+
+        let parent = state.add(root);
+        state.transform.set_width(parent, 200.0);
+        state.transform.set_height(parent, 100.0);
+
+        let child = state.add(parent);
+        state
+            .style
+            .border_width
+            .insert(child, Length::Percentage(0.1));
+
+        state.style.border_width_basis = basis;
+
+        resolve_border(&state, child).width
+    }
+
+    #[test]
+    fn border_width_relative_to_width() {
+        assert_eq!(border_width(BorderWidthBasis::Width), 20.0);
+    }
+
+    #[test]
+    fn border_width_relative_to_height() {
+        assert_eq!(border_width(BorderWidthBasis::Height), 10.0);
+    }
+
+    #[test]
+    fn border_width_relative_to_min() {
+        assert_eq!(border_width(BorderWidthBasis::Min), 10.0);
+    }
+
+    #[test]
+    fn border_width_basis_defaults_to_width() {
+        assert_eq!(border_width(BorderWidthBasis::default()), 20.0);
+    }
+}
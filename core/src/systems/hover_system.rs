@@ -0,0 +1,189 @@
+use crate::{Entity, Event, State, WindowEvent};
+
+// Determines the entity under the cursor and updates the hovered entity
+// Sends MouseOut to the previously hovered entity and MouseOver to the new one
+pub fn apply_hover(state: &mut State) {
+    let cursorx = state.mouse.cursorx;
+    let cursory = state.mouse.cursory;
+
+    // The root is hovered when the cursor is outside of the window
+    let hovered_widget = state
+        .hit_test(cursorx, cursory)
+        .unwrap_or(Entity::new(0, 0));
+
+    if hovered_widget != state.hovered {
+        let prev_hovered = state.hovered;
+
+        // The over and hover pseudo-classes follow the hovered entity
+        if let Some(pseudo_classes) = state.style.pseudo_classes.get_mut(prev_hovered) {
+            pseudo_classes.set_over(false);
+            pseudo_classes.set_hover(false);
+        }
+
+        if let Some(pseudo_classes) = state.style.pseudo_classes.get_mut(hovered_widget) {
+            pseudo_classes.set_over(true);
+            pseudo_classes.set_hover(true);
+        }
+
+        state.insert_event(Event::new(WindowEvent::MouseOut(prev_hovered)).target(prev_hovered));
+        state.insert_event(
+            Event::new(WindowEvent::MouseOver(hovered_widget)).target(hovered_widget),
+        );
+
+        state.insert_event(Event::new(WindowEvent::Restyle).origin(hovered_widget));
+        state.insert_event(Event::new(WindowEvent::Restyle).origin(prev_hovered));
+
+        state.hovered = hovered_widget;
+        state.active = Entity::null();
+
+        // Hovering only changes appearance so the layout isn't recomputed
+        state.request_redraw(hovered_widget);
+        state.request_redraw(prev_hovered);
+    }
+
+    state.update_tooltip();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        BuildHandler, Button, ButtonEvent, Element, EventManager, Length, MouseButton, Position,
+        PropSet,
+    };
+
+    // Returns the MouseOver and MouseOut events in the queue, in order
+    fn hover_events(state: &mut State) -> Vec<WindowEvent> {
+        state
+            .drain_events()
+            .into_iter()
+            .filter_map(|mut event| match event.message.downcast::<WindowEvent>() {
+                Some(WindowEvent::MouseOver(entity)) => Some(WindowEvent::MouseOver(*entity)),
+                Some(WindowEvent::MouseOut(entity)) => Some(WindowEvent::MouseOut(*entity)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn moving_across_adjacent_entities_sends_out_then_over() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let root = state.root;
+
+        // Stacked in a column, the first from 0 to 100 and the second from 100 to 200
+        let first = Element::new().build(&mut state, root, |builder| {
+            builder
+                .set_width(Length::Pixels(100.0))
+                .set_height(Length::Pixels(100.0))
+        });
+        let second = Element::new().build(&mut state, root, |builder| {
+            builder
+                .set_width(Length::Pixels(100.0))
+                .set_height(Length::Pixels(100.0))
+        });
+
+        // Nothing queues a layout without a window so one is requested before flushing
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        while !state.event_queue.is_empty() {
+            event_manager.flush_events(&mut state);
+        }
+
+        state.mouse.cursorx = 50.0;
+        state.mouse.cursory = 50.0;
+        apply_hover(&mut state);
+
+        assert_eq!(state.hovered, first);
+        assert_eq!(
+            hover_events(&mut state),
+            vec![WindowEvent::MouseOut(root), WindowEvent::MouseOver(first)]
+        );
+
+        state.mouse.cursory = 150.0;
+        apply_hover(&mut state);
+
+        assert_eq!(state.hovered, second);
+        assert_eq!(
+            hover_events(&mut state),
+            vec![WindowEvent::MouseOut(first), WindowEvent::MouseOver(second)]
+        );
+
+        // The over pseudo-class follows the hovered entity
+        assert!(!state
+            .style
+            .pseudo_classes
+            .get_mut(first)
+            .unwrap()
+            .get_over());
+        assert!(state
+            .style
+            .pseudo_classes
+            .get_mut(second)
+            .unwrap()
+            .get_over());
+
+        // Moving within the same entity sends nothing
+        state.mouse.cursory = 160.0;
+        apply_hover(&mut state);
+        assert!(hover_events(&mut state).is_empty());
+    }
+
+    #[test]
+    fn transparent_overlay_lets_clicks_through() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let root = state.root;
+
+        let button = Button::with_label("Beneath").build(&mut state, root, |builder| {
+            builder
+                .set_width(Length::Pixels(100.0))
+                .set_height(Length::Pixels(30.0))
+        });
+
+        // Covers the button and is drawn above it
+        let overlay = Element::new().build(&mut state, root, |builder| {
+            builder
+                .set_position(Position::Absolute)
+                .set_left(Length::Pixels(0.0))
+                .set_top(Length::Pixels(0.0))
+                .set_width(Length::Pixels(200.0))
+                .set_height(Length::Pixels(200.0))
+                .set_z_order(1)
+        });
+        let overlay_child = Element::new().build(&mut state, overlay, |builder| {
+            builder
+                .set_width(Length::Pixels(50.0))
+                .set_height(Length::Pixels(50.0))
+        });
+
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        while !state.event_queue.is_empty() {
+            event_manager.flush_events(&mut state);
+        }
+
+        state.mouse.cursorx = 20.0;
+        state.mouse.cursory = 10.0;
+        apply_hover(&mut state);
+        assert_eq!(state.hovered, overlay_child);
+
+        // A transparent overlay and its children let the click through to the button
+        overlay.set_opacity(&mut state, 0.0);
+        while !state.event_queue.is_empty() {
+            event_manager.flush_events(&mut state);
+        }
+        assert_eq!(state.hit_test(20.0, 10.0), Some(button));
+
+        apply_hover(&mut state);
+        assert_eq!(state.hovered, button);
+
+        state.insert_event(
+            Event::new(WindowEvent::MouseDown(MouseButton::Left)).target(state.hovered),
+        );
+        event_manager.flush_events(&mut state);
+
+        assert!(state
+            .pending_events()
+            .iter()
+            .any(|event| event.target == button && event.message.equals_a(&ButtonEvent::Pressed)));
+    }
+}
@@ -1,10 +1,12 @@
-use crate::{Entity, State};
+use crate::{resolve_padding, Entity, State};
 
 use crate::hierarchy::*;
 use crate::style::*;
 
 use crate::flexbox::AlignItems;
 
+use fnv::FnvHashMap;
+
 pub fn apply_z_ordering(state: &mut State, hierarchy: &Hierarchy) {
     for entity in hierarchy.into_iter() {
         if entity == Entity::new(0, 0) {
@@ -1399,10 +1401,193 @@ pub fn layout_fun(state: &mut State, hierarchy: &Hierarchy) {
 }
 */
 
+// Overlays are always absolutely positioned
+fn resolve_position(state: &State, entity: Entity) -> Position {
+    if state.style.overlay.get(entity).cloned().unwrap_or_default() {
+        return Position::Absolute;
+    }
+
+    state
+        .style
+        .position
+        .get(entity)
+        .cloned()
+        .unwrap_or_default()
+}
+
+// Returns the margin of a child on one side, using the child space of the parent on that side if the margin isn't set
+// A margin set on the child always takes precedence over the child space of its parent
+fn child_margin(margin: Option<&Length>, child_space: Option<Length>) -> Length {
+    match margin {
+        Some(Length::Auto) | None => child_space.unwrap_or_default(),
+        Some(margin) => *margin,
+    }
+}
+
+// The child space of a parent, with its first and last children in the flow
+// Along the main axis the child space only separates the parent from its first and last children, so it
+// isn't added between siblings. Across the main axis, and for children out of the flow, it applies on both sides.
+#[derive(Clone, Copy)]
+struct ChildSpace {
+    left: Option<Length>,
+    right: Option<Length>,
+    top: Option<Length>,
+    bottom: Option<Length>,
+    flex_direction: FlexDirection,
+    first: Option<Entity>,
+    last: Option<Entity>,
+}
+
+// Returns the child space of a parent, or None if it has none so its children can skip resolving it
+fn resolve_child_space(state: &State, hierarchy: &Hierarchy, parent: Entity) -> Option<ChildSpace> {
+    let left = state.style.child_left.get(parent).cloned();
+    let right = state.style.child_right.get(parent).cloned();
+    let top = state.style.child_top.get(parent).cloned();
+    let bottom = state.style.child_bottom.get(parent).cloned();
+
+    if left.is_none() && right.is_none() && top.is_none() && bottom.is_none() {
+        return None;
+    }
+
+    let mut flow_children = parent
+        .child_iter(hierarchy)
+        .filter(|child| in_flow(state, *child));
+    let first = flow_children.next();
+    let last = flow_children.next_back().or(first);
+
+    Some(ChildSpace {
+        left,
+        right,
+        top,
+        bottom,
+        flex_direction: state
+            .style
+            .flex_direction
+            .get(parent)
+            .cloned()
+            .unwrap_or_default(),
+        first,
+        last,
+    })
+}
+
+impl ChildSpace {
+    // Returns the child space which applies to the left, right, top and bottom of a child
+    fn for_child(
+        &self,
+        state: &State,
+        child: Entity,
+    ) -> (
+        Option<Length>,
+        Option<Length>,
+        Option<Length>,
+        Option<Length>,
+    ) {
+        let is_first = !in_flow(state, child) || self.first == Some(child);
+        let is_last = !in_flow(state, child) || self.last == Some(child);
+
+        match self.flex_direction {
+            FlexDirection::Row => (
+                self.left.filter(|_| is_first),
+                self.right.filter(|_| is_last),
+                self.top,
+                self.bottom,
+            ),
+            FlexDirection::Column => (
+                self.left,
+                self.right,
+                self.top.filter(|_| is_first),
+                self.bottom.filter(|_| is_last),
+            ),
+        }
+    }
+}
+
+// Non-displayed and absolutely positioned entities don't take part in the flow of their parent
+fn in_flow(state: &State, entity: Entity) -> bool {
+    state.style.display.get(entity).cloned().unwrap_or_default() != Display::None
+        && resolve_position(state, entity) != Position::Absolute
+}
+
+// Returns the share of the free space along the main axis taken by a stretch margin
+fn stretch_share(margin: Length, grow_sum: f32, free_space: f32) -> f32 {
+    match margin {
+        Length::Stretch(val) if free_space > 0.0 => free_space * val / grow_sum,
+        _ => 0.0,
+    }
+}
+
+// Splits the free space on the cross axis between two stretch margins by their stretch factors
+fn split_stretch(before: Length, after: Length, free_space: f32) -> (f32, f32) {
+    let before = match before {
+        Length::Stretch(val) => val,
+        _ => 0.0,
+    };
+
+    let after = match after {
+        Length::Stretch(val) => val,
+        _ => 0.0,
+    };
+
+    if before + after <= 0.0 || free_space <= 0.0 {
+        return (0.0, 0.0);
+    }
+
+    (
+        free_space * before / (before + after),
+        free_space * after / (before + after),
+    )
+}
+
+// Returns the size of the text of an entity with its width or height set to auto, or zero for other entities
+// The text is measured with the metrics of its font so it can be laid out before anything is drawn
+fn fit_text_size(state: &mut State, entity: Entity) -> (f32, f32) {
+    let fit_width = state.style.width.get(entity) == Some(&Length::Auto);
+    let fit_height = state.style.height.get(entity) == Some(&Length::Auto);
+    if !fit_width && !fit_height {
+        return (0.0, 0.0);
+    }
+
+    let text = match state.style.text.get(entity) {
+        Some(text) => text,
+        None => return (0.0, 0.0),
+    };
+
+    let font_size = state.style.font_size.get(entity).cloned().unwrap_or(16.0);
+
+    // Unknown fonts fall back to the regular font, as when drawing
+    let font = if state.font_metrics.contains_key(&text.font) {
+        text.font.as_str()
+    } else {
+        "sans"
+    };
+
+    state
+        .font_metrics
+        .get_mut(font)
+        .map(|metrics| metrics.measure(&text.text, font_size))
+        .unwrap_or_default()
+}
+
 pub fn apply_layout(state: &mut State, hierarchy: &Hierarchy) {
     // Reset
     for entity in hierarchy.entities.iter() {
-        state.transform.set_child_sum(*entity, 0.0);
+        // An entity sized to fit its text starts with its text as content, so an auto width or height
+        // covers both the text and any children
+        let (text_width, text_height) = fit_text_size(state, *entity);
+        let (main, cross) = match state
+            .style
+            .flex_direction
+            .get(*entity)
+            .cloned()
+            .unwrap_or_default()
+        {
+            FlexDirection::Row => (text_width, text_height),
+            FlexDirection::Column => (text_height, text_width),
+        };
+
+        state.transform.set_child_sum(*entity, main);
+        state.transform.set_child_max(*entity, cross);
         state.transform.set_child_pos(*entity, 0.0);
         state.transform.set_child_grow_sum(*entity, 0.0);
         state.transform.set_child_shrink_sum(*entity, 0.0);
@@ -1410,6 +1595,9 @@ pub fn apply_layout(state: &mut State, hierarchy: &Hierarchy) {
 
     let mut hierarchy_up_iterator = hierarchy.entities.iter();
 
+    // Resolved once per parent rather than for each of its children
+    let mut child_spaces: FnvHashMap<Entity, Option<ChildSpace>> = FnvHashMap::default();
+
     //////////////////////
     // Walk up the tree //
     //////////////////////
@@ -1483,49 +1671,42 @@ pub fn apply_layout(state: &mut State, hierarchy: &Hierarchy) {
             _ => std::f32::INFINITY,
         };
 
-        let child_margin_left = match state
-            .style
-            .margin_left
-            .get(*entity)
-            .cloned()
-            .unwrap_or_default()
-        {
+        // Margins not set on the child fall back to the child space of the parent
+        let (child_space_left, child_space_right, child_space_top, child_space_bottom) =
+            child_spaces
+                .entry(parent)
+                .or_insert_with(|| resolve_child_space(state, hierarchy, parent))
+                .map(|child_space| child_space.for_child(state, *entity))
+                .unwrap_or_default();
+
+        let margin_left = child_margin(state.style.margin_left.get(*entity), child_space_left);
+
+        let child_margin_left = match margin_left {
             Length::Pixels(val) => val,
             Length::Percentage(val) => parent_width * val,
             _ => 0.0,
         };
 
-        let child_margin_right = match state
-            .style
-            .margin_right
-            .get(*entity)
-            .cloned()
-            .unwrap_or_default()
-        {
+        let margin_right = child_margin(state.style.margin_right.get(*entity), child_space_right);
+
+        let child_margin_right = match margin_right {
             Length::Pixels(val) => val,
             Length::Percentage(val) => parent_width * val,
             _ => 0.0,
         };
 
-        let child_margin_top = match state
-            .style
-            .margin_top
-            .get(*entity)
-            .cloned()
-            .unwrap_or_default()
-        {
+        let margin_top = child_margin(state.style.margin_top.get(*entity), child_space_top);
+
+        let child_margin_top = match margin_top {
             Length::Pixels(val) => val,
             Length::Percentage(val) => parent_height * val,
             _ => 0.0,
         };
 
-        let child_margin_bottom = match state
-            .style
-            .margin_bottom
-            .get(*entity)
-            .cloned()
-            .unwrap_or_default()
-        {
+        let margin_bottom =
+            child_margin(state.style.margin_bottom.get(*entity), child_space_bottom);
+
+        let child_margin_bottom = match margin_bottom {
             Length::Pixels(val) => val,
             Length::Percentage(val) => parent_height * val,
             _ => 0.0,
@@ -1603,53 +1784,9 @@ pub fn apply_layout(state: &mut State, hierarchy: &Hierarchy) {
             _ => 0.0,
         };
 
-        let parent_padding_left = match state
-            .style
-            .padding_left
-            .get(parent)
-            .cloned()
-            .unwrap_or_default()
-        {
-            Length::Pixels(val) => val,
-            Length::Percentage(val) => parent_width * val,
-            _ => 0.0,
-        };
-
-        let parent_padding_right = match state
-            .style
-            .padding_right
-            .get(parent)
-            .cloned()
-            .unwrap_or_default()
-        {
-            Length::Pixels(val) => val,
-            Length::Percentage(val) => parent_width * val,
-            _ => 0.0,
-        };
-
-        let parent_padding_top = match state
-            .style
-            .padding_top
-            .get(parent)
-            .cloned()
-            .unwrap_or_default()
-        {
-            Length::Pixels(val) => val,
-            Length::Percentage(val) => parent_height * val,
-            _ => 0.0,
-        };
-
-        let parent_padding_bottom = match state
-            .style
-            .padding_bottom
-            .get(parent)
-            .cloned()
-            .unwrap_or_default()
-        {
-            Length::Pixels(val) => val,
-            Length::Percentage(val) => parent_height * val,
-            _ => 0.0,
-        };
+        // Resolved against the parent's parent, as when the parent was laid out as a child
+        let (parent_padding_left, parent_padding_right, parent_padding_top, parent_padding_bottom) =
+            resolve_padding(state, parent);
 
         let parent_flex_direction = state
             .style
@@ -1705,6 +1842,11 @@ pub fn apply_layout(state: &mut State, hierarchy: &Hierarchy) {
                             * val;
                     }
 
+                    // Stretched widgets only take up space when the free space is distributed
+                    Length::Stretch(_) => {
+                        new_width = 0.0;
+                    }
+
                     _ => {}
                 };
 
@@ -1751,12 +1893,7 @@ pub fn apply_layout(state: &mut State, hierarchy: &Hierarchy) {
                     + child_padding_bottom
                     + 2.0 * child_border_width;
 
-                let position = state
-                    .style
-                    .position
-                    .get(*entity)
-                    .cloned()
-                    .unwrap_or_default();
+                let position = resolve_position(state, *entity);
 
                 match position {
                     Position::Relative => {
@@ -1788,6 +1925,11 @@ pub fn apply_layout(state: &mut State, hierarchy: &Hierarchy) {
                             - 2.0 * parent_border_width)
                             * val;
                     }
+
+                    Length::Stretch(_) => {
+                        new_height = 0.0;
+                    }
+
                     _ => {}
                 };
 
@@ -1830,12 +1972,7 @@ pub fn apply_layout(state: &mut State, hierarchy: &Hierarchy) {
                     + child_padding_bottom
                     + 2.0 * child_border_width;
 
-                let position = state
-                    .style
-                    .position
-                    .get(*entity)
-                    .cloned()
-                    .unwrap_or_default();
+                let position = resolve_position(state, *entity);
 
                 match position {
                     Position::Relative => {
@@ -1855,6 +1992,11 @@ pub fn apply_layout(state: &mut State, hierarchy: &Hierarchy) {
             }
         }
 
+        // Absolutely positioned widgets take no part in sharing the free space of the parent
+        if resolve_position(state, *entity) == Position::Absolute {
+            continue;
+        }
+
         if let Some(flex_grow) = state.style.flex_grow.get(*entity) {
             state.transform.set_child_grow_sum(
                 parent,
@@ -1862,6 +2004,33 @@ pub fn apply_layout(state: &mut State, hierarchy: &Hierarchy) {
             );
         }
 
+        // Stretch sizes along the main axis share the free space along with flex grow
+        let main_length = match parent_flex_direction {
+            FlexDirection::Row => width,
+            FlexDirection::Column => height,
+        };
+
+        if let Length::Stretch(stretch) = main_length {
+            state
+                .transform
+                .set_child_grow_sum(parent, state.transform.get_child_grow_sum(parent) + stretch);
+        }
+
+        // As do stretch margins along the main axis
+        let (main_before, main_after) = match parent_flex_direction {
+            FlexDirection::Row => (margin_left, margin_right),
+            FlexDirection::Column => (margin_top, margin_bottom),
+        };
+
+        for margin in [main_before, main_after].iter() {
+            if let Length::Stretch(stretch) = margin {
+                state.transform.set_child_grow_sum(
+                    parent,
+                    state.transform.get_child_grow_sum(parent) + stretch,
+                );
+            }
+        }
+
         if let Some(flex_shrink) = state.style.flex_shrink.get(*entity) {
             state.transform.set_child_shrink_sum(
                 parent,
@@ -1897,53 +2066,9 @@ pub fn apply_layout(state: &mut State, hierarchy: &Hierarchy) {
             _ => 0.0,
         };
 
-        let parent_padding_left = match state
-            .style
-            .padding_left
-            .get(parent)
-            .cloned()
-            .unwrap_or_default()
-        {
-            Length::Pixels(val) => val,
-            Length::Percentage(val) => parent_width * val,
-            _ => 0.0,
-        };
-
-        let parent_padding_right = match state
-            .style
-            .padding_right
-            .get(parent)
-            .cloned()
-            .unwrap_or_default()
-        {
-            Length::Pixels(val) => val,
-            Length::Percentage(val) => parent_width * val,
-            _ => 0.0,
-        };
-
-        let parent_padding_top = match state
-            .style
-            .padding_top
-            .get(parent)
-            .cloned()
-            .unwrap_or_default()
-        {
-            Length::Pixels(val) => val,
-            Length::Percentage(val) => parent_height * val,
-            _ => 0.0,
-        };
-
-        let parent_padding_bottom = match state
-            .style
-            .padding_bottom
-            .get(parent)
-            .cloned()
-            .unwrap_or_default()
-        {
-            Length::Pixels(val) => val,
-            Length::Percentage(val) => parent_height * val,
-            _ => 0.0,
-        };
+        // Resolved against the parent's parent, as when the parent was laid out as a child
+        let (parent_padding_left, parent_padding_right, parent_padding_top, parent_padding_bottom) =
+            resolve_padding(state, parent);
 
         let parent_posx =
             state.transform.get_posx(parent) + parent_padding_left + parent_border_width;
@@ -1951,11 +2076,19 @@ pub fn apply_layout(state: &mut State, hierarchy: &Hierarchy) {
             state.transform.get_posy(parent) + parent_padding_top + parent_border_width;
 
         //TEMP - Move to the walk up phase
+        // Non-displayed and absolutely positioned children don't take part in the layout so aren't counted
         let mut num_children = 0;
-        for _ in parent.child_iter(&hierarchy) {
-            num_children += 1;
+        for child in parent.child_iter(&hierarchy) {
+            if in_flow(state, child) {
+                num_children += 1;
+            }
         }
 
+        let child_space = child_spaces
+            .get(&parent)
+            .cloned()
+            .unwrap_or_else(|| resolve_child_space(state, hierarchy, parent));
+
         let parent_flex_direction = state
             .style
             .flex_direction
@@ -2010,10 +2143,12 @@ pub fn apply_layout(state: &mut State, hierarchy: &Hierarchy) {
         }
 
         for child in parent.child_iter(&hierarchy) {
-            // Skip non-displayed widgets
+            // Non-displayed widgets take up no space, unlike invisible widgets
             let display = state.style.display.get(child).cloned().unwrap_or_default();
 
             if display == Display::None {
+                state.transform.set_width(child, 0.0);
+                state.transform.set_height(child, 0.0);
                 continue;
             }
 
@@ -2080,49 +2215,40 @@ pub fn apply_layout(state: &mut State, hierarchy: &Hierarchy) {
                 _ => std::f32::INFINITY,
             };
 
-            let child_margin_left = match state
-                .style
-                .margin_left
-                .get(child)
-                .cloned()
-                .unwrap_or_default()
-            {
+            // Margins not set on the child fall back to the child space of the parent
+            let (child_space_left, child_space_right, child_space_top, child_space_bottom) =
+                child_space
+                    .map(|child_space| child_space.for_child(state, child))
+                    .unwrap_or_default();
+
+            let margin_left = child_margin(state.style.margin_left.get(child), child_space_left);
+
+            let child_margin_left = match margin_left {
                 Length::Pixels(val) => val,
                 Length::Percentage(val) => parent_width * val,
                 _ => 0.0,
             };
 
-            let child_margin_right = match state
-                .style
-                .margin_right
-                .get(child)
-                .cloned()
-                .unwrap_or_default()
-            {
+            let margin_right = child_margin(state.style.margin_right.get(child), child_space_right);
+
+            let child_margin_right = match margin_right {
                 Length::Pixels(val) => val,
                 Length::Percentage(val) => parent_width * val,
                 _ => 0.0,
             };
 
-            let child_margin_top = match state
-                .style
-                .margin_top
-                .get(child)
-                .cloned()
-                .unwrap_or_default()
-            {
+            let margin_top = child_margin(state.style.margin_top.get(child), child_space_top);
+
+            let child_margin_top = match margin_top {
                 Length::Pixels(val) => val,
                 Length::Percentage(val) => parent_height * val,
                 _ => 0.0,
             };
 
-            let child_margin_bottom = match state
-                .style
-                .margin_bottom
-                .get(child)
-                .cloned()
-                .unwrap_or_default()
-            {
+            let margin_bottom =
+                child_margin(state.style.margin_bottom.get(child), child_space_bottom);
+
+            let child_margin_bottom = match margin_bottom {
                 Length::Pixels(val) => val,
                 Length::Percentage(val) => parent_height * val,
                 _ => 0.0,
@@ -2214,10 +2340,20 @@ pub fn apply_layout(state: &mut State, hierarchy: &Hierarchy) {
                 child_shrink_sum = 1.0;
             }
 
-            let flex_grow_fraction = child_flex_grow / child_grow_sum;
+            let child_stretch = match parent_flex_direction {
+                FlexDirection::Row => width,
+                FlexDirection::Column => height,
+            };
+
+            let child_stretch = match child_stretch {
+                Length::Stretch(val) => val,
+                _ => 0.0,
+            };
+
+            let flex_grow_fraction = (child_flex_grow + child_stretch) / child_grow_sum;
             let flex_shrink_fraction = child_flex_shrink / child_shrink_sum;
 
-            let position = state.style.position.get(child).cloned().unwrap_or_default();
+            let position = resolve_position(state, child);
 
             match flex_direction {
                 FlexDirection::Row => {
@@ -2268,6 +2404,13 @@ pub fn apply_layout(state: &mut State, hierarchy: &Hierarchy) {
                                         + child_padding_right
                                         + 2.0 * child_border_width;
                                 }
+
+                                Length::Stretch(_) => {
+                                    new_width = child_padding_left
+                                        + child_padding_right
+                                        + 2.0 * child_border_width;
+                                }
+
                                 _ => {}
                             };
 
@@ -2291,6 +2434,12 @@ pub fn apply_layout(state: &mut State, hierarchy: &Hierarchy) {
                                 new_width += flex_shrink_fraction * parent_free_space;
                             }
 
+                            // Stretch margins take their share of the free space
+                            let child_margin_left = child_margin_left
+                                + stretch_share(margin_left, child_grow_sum, parent_free_space);
+                            let child_margin_right = child_margin_right
+                                + stretch_share(margin_right, child_grow_sum, parent_free_space);
+
                             let align_items = state
                                 .style
                                 .align_items
@@ -2359,6 +2508,21 @@ pub fn apply_layout(state: &mut State, hierarchy: &Hierarchy) {
                                 new_height = child_max_height;
                             }
 
+                            // Stretch margins on the cross axis split the space left beside the child
+                            let (stretch_top, stretch_bottom) = split_stretch(
+                                margin_top,
+                                margin_bottom,
+                                parent_height
+                                    - parent_padding_top
+                                    - parent_padding_bottom
+                                    - 2.0 * parent_border_width
+                                    - new_height
+                                    - child_margin_top
+                                    - child_margin_bottom,
+                            );
+                            let child_margin_top = child_margin_top + stretch_top;
+                            let child_margin_bottom = child_margin_bottom + stretch_bottom;
+
                             match left {
                                 Length::Pixels(val) => {
                                     new_posx = current_pos + val;
@@ -2455,6 +2619,13 @@ pub fn apply_layout(state: &mut State, hierarchy: &Hierarchy) {
                                         + child_padding_bottom
                                         + 2.0 * child_border_width;
                                 }
+
+                                Length::Stretch(_) => {
+                                    new_height = child_padding_top
+                                        + child_padding_bottom
+                                        + 2.0 * child_border_width;
+                                }
+
                                 _ => {}
                             };
 
@@ -2478,6 +2649,12 @@ pub fn apply_layout(state: &mut State, hierarchy: &Hierarchy) {
                                 new_height += flex_shrink_fraction * parent_free_space;
                             }
 
+                            // Stretch margins take their share of the free space
+                            let child_margin_top = child_margin_top
+                                + stretch_share(margin_top, child_grow_sum, parent_free_space);
+                            let child_margin_bottom = child_margin_bottom
+                                + stretch_share(margin_bottom, child_grow_sum, parent_free_space);
+
                             let align_items = state
                                 .style
                                 .align_items
@@ -2549,6 +2726,21 @@ pub fn apply_layout(state: &mut State, hierarchy: &Hierarchy) {
                             //state.transform.set_width(child, new_width);
                             //state.transform.set_height(child, new_height);
 
+                            // Stretch margins on the cross axis split the space left beside the child
+                            let (stretch_left, stretch_right) = split_stretch(
+                                margin_left,
+                                margin_right,
+                                parent_width
+                                    - parent_padding_left
+                                    - parent_padding_right
+                                    - 2.0 * parent_border_width
+                                    - new_width
+                                    - child_margin_left
+                                    - child_margin_right,
+                            );
+                            let child_margin_left = child_margin_left + stretch_left;
+                            let child_margin_right = child_margin_right + stretch_right;
+
                             match top {
                                 Length::Pixels(val) => {
                                     new_posy = current_pos + val;
@@ -2631,6 +2823,31 @@ pub fn apply_layout(state: &mut State, hierarchy: &Hierarchy) {
                 }
 
                 Position::Absolute => {
+                    // Absolutely positioned children are placed within the content box of the parent
+                    // Overlays are positioned relative to the window instead of their parent
+                    let (parent_posx, parent_posy, content_width, content_height) =
+                        if state.style.overlay.get(child).cloned().unwrap_or_default() {
+                            (
+                                0.0,
+                                0.0,
+                                state.transform.get_width(state.root),
+                                state.transform.get_height(state.root),
+                            )
+                        } else {
+                            (
+                                parent_posx,
+                                parent_posy,
+                                parent_width
+                                    - parent_padding_left
+                                    - parent_padding_right
+                                    - 2.0 * parent_border_width,
+                                parent_height
+                                    - parent_padding_top
+                                    - parent_padding_bottom
+                                    - 2.0 * parent_border_width,
+                            )
+                        };
+
                     let width = state.style.width.get(child).cloned().unwrap_or_default();
                     let height = state.style.height.get(child).cloned().unwrap_or_default();
 
@@ -2639,120 +2856,48 @@ pub fn apply_layout(state: &mut State, hierarchy: &Hierarchy) {
                     let top = state.style.top.get(child).cloned().unwrap_or_default();
                     let bottom = state.style.bottom.get(child).cloned().unwrap_or_default();
 
-                    new_posx = parent_posx;
-                    new_posy = parent_posy;
-
-                    let r = match right {
-                        Length::Pixels(val) => val,
-                        Length::Percentage(val) => val * parent_width,
-                        Length::Initial(val) => val,
-                        Length::Auto => 0.0,
+                    // There is no free space to share so stretch lengths are treated as auto
+                    let resolve = |length: Length, basis: f32| match length {
+                        Length::Pixels(val) => Some(val),
+                        Length::Percentage(val) => Some(val * basis),
+                        Length::Initial(val) => Some(val),
+                        _ => None,
                     };
 
-                    let l = match left {
-                        Length::Pixels(val) => val,
-                        Length::Percentage(val) => val * parent_width,
-                        Length::Initial(val) => val,
-                        Length::Auto => 0.0,
-                    };
+                    let l = resolve(left, content_width);
+                    let r = resolve(right, content_width);
+                    let t = resolve(top, content_height);
+                    let b = resolve(bottom, content_height);
 
-                    if !right.is_auto() && !left.is_auto() {
-                        new_width = parent_width - l - r;
+                    // Setting both sides stretches the child between them
+                    if let (Some(l), Some(r)) = (l, r) {
+                        new_width = content_width - l - r;
                     }
 
-                    let b = match bottom {
-                        Length::Pixels(val) => val,
-                        Length::Percentage(val) => val * parent_height,
-                        Length::Initial(val) => val,
-                        Length::Auto => 0.0,
-                    };
-
-                    let t = match top {
-                        Length::Pixels(val) => val,
-                        Length::Percentage(val) => val * parent_height,
-                        Length::Initial(val) => val,
-                        Length::Auto => 0.0,
-                    };
-
-                    if !bottom.is_auto() && !top.is_auto() {
-                        new_height = parent_height - t - b;
+                    if let (Some(t), Some(b)) = (t, b) {
+                        new_height = content_height - t - b;
                     }
 
-                    match width {
-                        Length::Auto => {}
-                        Length::Pixels(val) => new_width = val,
-                        Length::Initial(val) => new_width = val,
-                        Length::Percentage(val) => new_width = val * parent_width,
-                    }
-
-                    match height {
-                        Length::Auto => {}
-                        Length::Pixels(val) => new_height = val,
-                        Length::Initial(val) => new_height = val,
-                        Length::Percentage(val) => new_height = val * parent_height,
-                    }
-
-                    //state.transform.set_width(child, new_width);
-                    //state.transform.set_height(child, new_height);
-
-                    match right {
-                        Length::Pixels(val) => {
-                            new_posx = parent_posx + parent_width - new_width - val;
-                        }
-
-                        Length::Percentage(val) => {
-                            new_posx = parent_posx + parent_width
-                                - new_width
-                                - (val
-                                    * (parent_width - parent_padding_left - parent_padding_right));
-                        }
-
-                        _ => {}
-                    }
-
-                    match left {
-                        Length::Pixels(val) => {
-                            new_posx = parent_posx + val;
-                        }
-
-                        Length::Percentage(val) => {
-                            new_posx = parent_posx
-                                + (val
-                                    * (parent_width - parent_padding_left - parent_padding_right));
-                        }
-
-                        _ => {}
+                    if let Some(val) = resolve(width, content_width) {
+                        new_width = val;
                     }
 
-                    match bottom {
-                        Length::Pixels(val) => {
-                            new_posy = parent_posy + parent_height - new_height - val;
-                        }
-
-                        Length::Percentage(val) => {
-                            new_posy = parent_posy + parent_height
-                                - new_height
-                                - (val
-                                    * (parent_height - parent_padding_top - parent_padding_bottom));
-                        }
-
-                        _ => {}
+                    if let Some(val) = resolve(height, content_height) {
+                        new_height = val;
                     }
 
-                    match top {
-                        Length::Pixels(val) => {
-                            new_posy = parent_posy + val;
-                        }
-
-                        Length::Percentage(val) => {
-                            new_posy = parent_posy
-                                + (val
-                                    * (parent_height - parent_padding_top - parent_padding_bottom));
-                        }
-
-                        _ => {}
-                    }
+                    // Left and top take precedence over right and bottom
+                    new_posx = match (l, r) {
+                        (Some(l), _) => parent_posx + l,
+                        (None, Some(r)) => parent_posx + content_width - new_width - r,
+                        (None, None) => parent_posx,
+                    };
 
+                    new_posy = match (t, b) {
+                        (Some(t), _) => parent_posy + t,
+                        (None, Some(b)) => parent_posy + content_height - new_height - b,
+                        (None, None) => parent_posy,
+                    };
                     //state.transform.set_posx(child, new_posx);
                     //state.transform.set_posy(child, new_posy);
                 }
@@ -2791,3 +2936,126 @@ pub fn apply_layout(state: &mut State, hierarchy: &Hierarchy) {
         // Set the transform properties
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Event, EventManager, PropSet, WindowEvent};
+
+    #[test]
+    fn child_space_is_not_added_between_siblings() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let root = state.root;
+
+        let parent = state
+            .add(root)
+            .set_width(&mut state, Length::Pixels(200.0))
+            .set_height(&mut state, Length::Pixels(200.0))
+            .set_child_space(&mut state, Length::Pixels(10.0));
+
+        let children: Vec<Entity> = (0..3)
+            .map(|_| {
+                state
+                    .add(parent)
+                    .set_width(&mut state, Length::Pixels(50.0))
+                    .set_height(&mut state, Length::Pixels(20.0))
+            })
+            .collect();
+
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        while !state.event_queue.is_empty() {
+            event_manager.flush_events(&mut state);
+        }
+
+        let parent_posx = state.transform.get_posx(parent);
+        let parent_posy = state.transform.get_posy(parent);
+
+        // Children are stacked in a column, so the child space only comes before the first child
+        for (index, child) in children.iter().enumerate() {
+            assert_eq!(state.transform.get_posx(*child) - parent_posx, 10.0);
+            assert_eq!(
+                state.transform.get_posy(*child) - parent_posy,
+                10.0 + 20.0 * index as f32
+            );
+        }
+    }
+
+    #[test]
+    fn auto_width_fits_text_on_the_first_layout() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let root = state.root;
+
+        let row = state
+            .add(root)
+            .set_flex_direction(&mut state, FlexDirection::Row)
+            .set_width(&mut state, Length::Pixels(400.0))
+            .set_height(&mut state, Length::Pixels(100.0));
+
+        let fit = |state: &mut State, text: &str| {
+            state
+                .add(row)
+                .set_text(state, text)
+                .set_width(state, Length::Auto)
+                .set_height(state, Length::Auto)
+                .set_padding(state, Length::Pixels(5.0))
+        };
+        let short = fit(&mut state, "Fit");
+        let long = fit(&mut state, "A button with a longer label");
+
+        // Laid out without drawing, so the text is measured with the font metrics alone
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        while !state.event_queue.is_empty() {
+            event_manager.flush_events(&mut state);
+        }
+
+        let (short_text, _) = state
+            .font_metrics
+            .get_mut("sans")
+            .unwrap()
+            .measure("Fit", 16.0);
+        assert!(short_text > 0.0);
+        assert_eq!(state.transform.get_width(short), short_text + 10.0);
+        assert!(state.transform.get_width(long) > state.transform.get_width(short));
+    }
+
+    #[test]
+    fn percentage_padding_insets_children() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let root = state.root;
+
+        let container = state
+            .add(root)
+            .set_width(&mut state, Length::Pixels(200.0))
+            .set_height(&mut state, Length::Pixels(100.0));
+
+        // Percentages are of the width or height of the parent, so 20px left and right and 10px top and bottom
+        let panel = state
+            .add(container)
+            .set_width(&mut state, Length::Pixels(100.0))
+            .set_height(&mut state, Length::Pixels(50.0))
+            .set_padding(&mut state, Length::Percentage(0.1));
+
+        let child = state
+            .add(panel)
+            .set_width(&mut state, Length::Pixels(10.0))
+            .set_height(&mut state, Length::Pixels(10.0));
+
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        while !state.event_queue.is_empty() {
+            event_manager.flush_events(&mut state);
+        }
+
+        let child_x = state.transform.get_posx(child);
+        let child_y = state.transform.get_posy(child);
+
+        assert_eq!(child_x - state.transform.get_posx(panel), 20.0);
+        assert_eq!(child_y - state.transform.get_posy(panel), 10.0);
+
+        // The layout and drawing agree on where the content starts
+        let content = state.content_box(panel);
+        assert_eq!((content.x, content.y), (child_x, child_y));
+    }
+}
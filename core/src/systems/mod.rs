@@ -5,3 +5,9 @@ pub use style_system::*;
 
 pub mod layout_system;
 pub use layout_system::*;
+
+pub mod draw_system;
+pub use draw_system::*;
+
+pub mod hover_system;
+pub use hover_system::*;
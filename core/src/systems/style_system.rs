@@ -14,20 +14,35 @@ pub fn apply_clipping(state: &mut State, hierarchy: &Hierarchy) {
 
         let parent = hierarchy.get_parent(entity).unwrap();
 
+        // Overlays escape the clipping of their ancestors
+        let overlay = state.style.overlay.get(entity).cloned().unwrap_or_default();
+
         if let Some(clip_widget) = state.style.clip_widget.get(entity) {
             state.transform.set_clip_widget(entity, *clip_widget);
+        } else if overlay {
+            state.transform.set_clip_widget(entity, Entity::new(0, 0));
+        } else if state.style.overflow.get(parent) == Some(&Overflow::Hidden) {
+            // Children of a parent with hidden overflow are clipped to it
+            state.transform.set_clip_widget(entity, parent);
         } else {
             let parent_clip_widget = state.transform.get_clip_widget(parent);
             state.transform.set_clip_widget(entity, parent_clip_widget);
         }
+
+        // Descendants of an overlay are part of the overlay layer
+        let parent_overlay = state.transform.is_overlay(parent);
+        state
+            .transform
+            .set_overlay(entity, overlay || parent_overlay);
     }
 }
 
+// Resolves the visibility and opacity used for drawing and hit testing
+// An entity is invisible if it or any of its ancestors is invisible or not displayed, a child can't be shown
+// inside a hidden parent by setting it to visible
 pub fn apply_visibility(state: &mut State, hierarchy: &Hierarchy) {
-    let mut draw_hierarchy: Vec<Entity> = hierarchy.into_iter().collect();
-    draw_hierarchy.sort_by_cached_key(|entity| state.transform.get_z_order(*entity));
-
-    for widget in draw_hierarchy.into_iter() {
+    // Iterate in tree order so that parents are resolved before their children
+    for widget in hierarchy.into_iter() {
         let visibility = state
             .style
             .visibility
@@ -36,9 +51,17 @@ pub fn apply_visibility(state: &mut State, hierarchy: &Hierarchy) {
             .unwrap_or_default();
         state.transform.set_visibility(widget, visibility);
 
-        let opacity = state.style.opacity.get(widget).cloned().unwrap_or_default();
+        let opacity = state
+            .style
+            .opacity
+            .get(widget)
+            .cloned()
+            .unwrap_or_default()
+            .0
+            .max(0.0)
+            .min(1.0);
 
-        state.transform.set_opacity(widget, opacity.0);
+        state.transform.set_opacity(widget, opacity);
 
         let display = state.style.display.get(widget).cloned().unwrap_or_default();
 
@@ -62,13 +85,12 @@ pub fn apply_visibility(state: &mut State, hierarchy: &Hierarchy) {
                     .set_visibility(widget, Visibility::Invisible);
             }
 
+            // Opacity is inherited multiplicatively, like group opacity in CSS
             let parent_opacity = state.transform.get_opacity(parent);
 
-            let opacity = state.style.opacity.get(widget).cloned().unwrap_or_default();
-
             state
                 .transform
-                .set_opacity(widget, opacity.0 * parent_opacity);
+                .set_opacity(widget, opacity * parent_opacity);
         }
     }
 }
@@ -102,333 +124,408 @@ fn check_match(state: &State, widget: Entity, selector: &Selector) -> bool {
         .cloned()
         .unwrap_or_default();
 
+    // Structural pseudo-classes are worked out from the hierarchy each time the entity is restyled
+    let hierarchy = &state.hierarchy;
+    widget_selector
+        .pseudo_classes
+        .set_first_child(hierarchy.is_first_child(widget));
+    widget_selector
+        .pseudo_classes
+        .set_last_child(hierarchy.is_last_child(widget));
+
+    if selector.nth_child.is_some() {
+        match hierarchy.sibling_index(widget) {
+            Some(index) if selector.matches_nth_child(index + 1) => {}
+            _ => return false,
+        }
+    }
+
     return selector.matches(&widget_selector);
 }
 
 pub fn apply_styles(state: &mut State, hierarchy: &Hierarchy) {
     // Loop through all entities
     for entity in hierarchy.into_iter() {
-        if entity == Entity::new(0, 0) {
-            continue;
-        }
+        apply_entity_styles(state, hierarchy, entity);
+    }
+}
 
-        // Possible point to add Cascading
+// Restyles an entity and its descendants, which are the only entities affected by a change to its
+// classes or pseudo-classes because selectors only relate entities to their ancestors
+pub fn apply_styles_from(state: &mut State, hierarchy: &Hierarchy, entity: Entity) {
+    for entity in entity.branch_iter(hierarchy) {
+        apply_entity_styles(state, hierarchy, entity);
+    }
+}
 
-        // Create a list of style rules that match this widget
-        let mut matched_rules: Vec<usize> = Vec::new();
+fn apply_entity_styles(state: &mut State, hierarchy: &Hierarchy, entity: Entity) {
+    if entity == Entity::new(0, 0) {
+        return;
+    }
 
-        // Loop through all of the style rules
-        'rule_loop: for (index, selectors) in state.style.rule_selectors.iter().enumerate() {
-            let mut relation_entity = entity;
-            // Loop through selectors (Should be from right to left)
-            // All the selectors need to match for the rule to apply
-            'selector_loop: for rule_selector in selectors.iter().rev() {
-                // Get the relation of the selector
-                match rule_selector.relation {
-                    Relation::None => {
-                        if !check_match(state, entity, rule_selector) {
-                            continue 'rule_loop;
-                        }
+    // Possible point to add Cascading
+
+    // Create a list of style rules that match this widget
+    let mut matched_rules: Vec<usize> = Vec::new();
+
+    // Loop through all of the style rules
+    'rule_loop: for (index, selectors) in state.style.rule_selectors.iter().enumerate() {
+        let mut relation_entity = entity;
+        // Loop through selectors (Should be from right to left)
+        // All the selectors need to match for the rule to apply
+        'selector_loop: for rule_selector in selectors.iter().rev() {
+            // Get the relation of the selector
+            match rule_selector.relation {
+                Relation::None => {
+                    if !check_match(state, entity, rule_selector) {
+                        continue 'rule_loop;
                     }
+                }
 
-                    Relation::Parent => {
-                        // Get the parent
-                        // Contrust the selector for the parent
-                        // Check if the parent selector matches the rule_seletor
-                        if let Some(parent) = relation_entity.parent(hierarchy) {
-                            if !check_match(state, parent, rule_selector) {
-                                continue 'rule_loop;
-                            }
-
-                            relation_entity = parent;
-                        } else {
+                Relation::Parent => {
+                    // Get the parent
+                    // Contrust the selector for the parent
+                    // Check if the parent selector matches the rule_seletor
+                    if let Some(parent) = relation_entity.parent(hierarchy) {
+                        if !check_match(state, parent, rule_selector) {
                             continue 'rule_loop;
                         }
+
+                        relation_entity = parent;
+                    } else {
+                        continue 'rule_loop;
                     }
+                }
 
-                    Relation::Ancestor => {
-                        // Walk up the hierarchy
-                        // Check if each entity matches the selector
-                        // If any of them match, move on to the next selector
-                        // If none of them do, move on to the next rule
-                        for ancestor in relation_entity.parent_iter(hierarchy) {
-                            if ancestor == relation_entity {
-                                continue;
-                            }
+                Relation::Ancestor => {
+                    // Walk up the hierarchy
+                    // Check if each entity matches the selector
+                    // If any of them match, move on to the next selector
+                    // If none of them do, move on to the next rule
+                    for ancestor in relation_entity.parent_iter(hierarchy) {
+                        if ancestor == relation_entity {
+                            continue;
+                        }
 
-                            if check_match(state, ancestor, rule_selector) {
-                                relation_entity = ancestor;
+                        if check_match(state, ancestor, rule_selector) {
+                            relation_entity = ancestor;
 
-                                continue 'selector_loop;
-                            }
+                            continue 'selector_loop;
                         }
-
-                        continue 'rule_loop;
                     }
+
+                    continue 'rule_loop;
                 }
             }
-
-            // If all the selectors match then add the rule to the matched rules list
-            matched_rules.push(index);
         }
 
-        //println!("Entity: {}, Matched Rules: {:?}", entity, &matched_rules);
+        // If all the selectors match then add the rule to the matched rules list
+        matched_rules.push(index);
+    }
 
-        if matched_rules.len() == 0 {
-            continue;
-        }
+    //println!("Entity: {}, Matched Rules: {:?}", entity, &matched_rules);
 
-        // Display
-        if state.style.display.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
-        if state.style.visibility.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if matched_rules.len() == 0 {
+        return;
+    }
 
-        if state.style.z_order.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    // Display
+    if state.style.display.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
+    if state.style.visibility.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        // Currently doesn't do anything - TODO
-        state.style.overflow.link_rule(entity, &matched_rules);
+    if state.style.z_order.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        // Opacity
-        if state.style.opacity.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    // Currently doesn't do anything - TODO
+    state.style.overflow.link_rule(entity, &matched_rules);
 
-        // Positioning
-        if state.style.position.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    // Opacity
+    if state.style.opacity.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state.style.left.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    // Positioning
+    if state.style.position.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state.style.right.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state.style.left.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state.style.top.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state.style.right.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state.style.bottom.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state.style.top.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        // Size
-        if state.style.width.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state.style.bottom.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state.style.height.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    // Size
+    if state.style.width.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        // Size Constraints
-        if state.style.max_width.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state.style.height.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state.style.min_width.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    // Size Constraints
+    if state.style.max_width.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state.style.max_height.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state.style.min_width.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state.style.min_height.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state.style.max_height.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        // Margin
-        if state.style.margin_left.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state.style.min_height.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state.style.margin_right.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    // Margin
+    if state.style.margin_left.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state.style.margin_top.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state.style.margin_right.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state.style.margin_bottom.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state.style.margin_top.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        // Padding
-        if state.style.padding_left.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state.style.margin_bottom.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state.style.padding_right.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    // Padding
+    if state.style.padding_left.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state.style.padding_top.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state.style.padding_right.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state.style.padding_bottom.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state.style.padding_top.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        // Border
-        if state.style.border_width.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state.style.padding_bottom.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state.style.border_color.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    // Child Space
+    if state.style.child_left.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state
-            .style
-            .border_radius_top_left
-            .link_rule(entity, &matched_rules)
-        {
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state.style.child_right.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state
-            .style
-            .border_radius_top_right
-            .link_rule(entity, &matched_rules)
-        {
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state.style.child_top.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state
-            .style
-            .border_radius_bottom_left
-            .link_rule(entity, &matched_rules)
-        {
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state.style.child_bottom.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state
-            .style
-            .border_radius_bottom_right
-            .link_rule(entity, &matched_rules)
-        {
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    // Border
+    if state.style.border_width.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        // Flex Container
-        if state.style.flex_direction.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state.style.border_color.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state
-            .style
-            .justify_content
-            .link_rule(entity, &matched_rules)
-        {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    // Outline
+    if state.style.outline_width.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state.style.align_content.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state.style.outline_color.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state.style.align_items.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state.style.outline_offset.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state.style.align_self.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state
+        .style
+        .border_radius_top_left
+        .link_rule(entity, &matched_rules)
+    {
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        // Flex Item
-        if state.style.flex_basis.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state
+        .style
+        .border_radius_top_right
+        .link_rule(entity, &matched_rules)
+    {
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state.style.flex_grow.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state
+        .style
+        .border_radius_bottom_left
+        .link_rule(entity, &matched_rules)
+    {
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state.style.flex_shrink.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state
+        .style
+        .border_radius_bottom_right
+        .link_rule(entity, &matched_rules)
+    {
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state.style.align_self.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    // Flex Container
+    if state.style.flex_direction.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        // Text Alignment
-        if state.style.text_align.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state
+        .style
+        .justify_content
+        .link_rule(entity, &matched_rules)
+    {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state.style.text_justify.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state.style.align_content.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        // Background
-        if state
-            .style
-            .background_color
-            .link_rule(entity, &matched_rules)
-        {
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state.style.align_items.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state
-            .style
-            .background_image
-            .link_rule(entity, &matched_rules)
-        {
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state.style.align_self.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        // Font
-        if state.style.font_color.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    // Flex Item
+    if state.style.flex_basis.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
 
-        if state.style.font_size.link_rule(entity, &matched_rules) {
-            state.insert_event(Event::new(WindowEvent::Redraw));
-        }
+    if state.style.flex_grow.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
+
+    if state.style.flex_shrink.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
+
+    if state.style.align_self.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
+
+    // Text Alignment
+    if state.style.text_align.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
+
+    if state.style.text_justify.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
+
+    // Background
+    if state
+        .style
+        .background_color
+        .link_rule(entity, &matched_rules)
+    {
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
+
+    if state
+        .style
+        .background_image
+        .link_rule(entity, &matched_rules)
+    {
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
+
+    // Font
+    if state.style.font_color.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
+
+    // Text Input
+    if state
+        .style
+        .selection_color
+        .link_rule(entity, &matched_rules)
+    {
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
+
+    if state.style.caret_color.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+    }
+
+    if state.style.font_size.link_rule(entity, &matched_rules) {
+        state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
     }
 }
 
@@ -631,6 +728,27 @@ pub fn apply_styles2(state: &mut State, hierarchy: &Hierarchy, mut style_entity:
             state.insert_event(Event::new(WindowEvent::Redraw));
         }
 
+        // Child Space
+        if state.style.child_left.link_rule(entity, &matched_rules) {
+            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+            state.insert_event(Event::new(WindowEvent::Redraw));
+        }
+
+        if state.style.child_right.link_rule(entity, &matched_rules) {
+            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+            state.insert_event(Event::new(WindowEvent::Redraw));
+        }
+
+        if state.style.child_top.link_rule(entity, &matched_rules) {
+            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+            state.insert_event(Event::new(WindowEvent::Redraw));
+        }
+
+        if state.style.child_bottom.link_rule(entity, &matched_rules) {
+            state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+            state.insert_event(Event::new(WindowEvent::Redraw));
+        }
+
         // Border
         if state.style.border_width.link_rule(entity, &matched_rules) {
             state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
@@ -641,6 +759,19 @@ pub fn apply_styles2(state: &mut State, hierarchy: &Hierarchy, mut style_entity:
             state.insert_event(Event::new(WindowEvent::Redraw));
         }
 
+        // Outline
+        if state.style.outline_width.link_rule(entity, &matched_rules) {
+            state.insert_event(Event::new(WindowEvent::Redraw));
+        }
+
+        if state.style.outline_color.link_rule(entity, &matched_rules) {
+            state.insert_event(Event::new(WindowEvent::Redraw));
+        }
+
+        if state.style.outline_offset.link_rule(entity, &matched_rules) {
+            state.insert_event(Event::new(WindowEvent::Redraw));
+        }
+
         if state.style.border_radius_top_left.link_rule(entity, &matched_rules) {
             state.insert_event(Event::new(WindowEvent::Redraw));
         }
@@ -739,6 +870,15 @@ pub fn apply_styles2(state: &mut State, hierarchy: &Hierarchy, mut style_entity:
             state.insert_event(Event::new(WindowEvent::Redraw));
         }
 
+        // Text Input
+        if state.style.selection_color.link_rule(entity, &matched_rules) {
+            state.insert_event(Event::new(WindowEvent::Redraw));
+        }
+
+        if state.style.caret_color.link_rule(entity, &matched_rules) {
+            state.insert_event(Event::new(WindowEvent::Redraw));
+        }
+
         if state.style.font_size.link_rule(entity, &matched_rules) {
             state.insert_event(Event::new(WindowEvent::Redraw));
         }
@@ -748,3 +888,161 @@ pub fn apply_styles2(state: &mut State, hierarchy: &Hierarchy, mut style_entity:
 
 }
 */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BuildHandler, Color, Element, EventManager, Length, PropSet};
+
+    static LIST_THEME: &'static str = r#"
+        .item { border-width: 1px; }
+        .item:last-child { border-width: 0px; }
+        .item:first-child { background-color: #ff0000; }
+        .item:nth-child(2n) { height: 30px; }
+    "#;
+
+    fn flush(state: &mut State, event_manager: &mut EventManager) {
+        while !state.event_queue.is_empty() {
+            event_manager.flush_events(state);
+        }
+    }
+
+    // A list of three items styled by their position
+    fn list(state: &mut State, event_manager: &mut EventManager) -> (Entity, Vec<Entity>) {
+        state.insert_theme(LIST_THEME);
+
+        let root = state.root;
+        let list = Element::new().build(state, root, |builder| builder);
+        let items = (0..3)
+            .map(|_| Element::new().build(state, list, |builder| builder.class("item")))
+            .collect();
+
+        flush(state, event_manager);
+
+        (list, items)
+    }
+
+    fn border(state: &State, entity: Entity) -> Length {
+        state.computed_style(entity).border_width
+    }
+
+    #[test]
+    fn children_are_clipped_to_a_parent_with_hidden_overflow() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let root = state.root;
+
+        let hidden = Element::new().build(&mut state, root, |builder| {
+            builder
+                .set_height(Length::Pixels(50.0))
+                .set_overflow(Overflow::Hidden)
+        });
+        let clipped = Element::new().build(&mut state, hidden, |builder| builder);
+        let grandchild = Element::new().build(&mut state, clipped, |builder| builder);
+
+        let visible = Element::new().build(&mut state, root, |builder| {
+            builder.set_height(Length::Pixels(50.0))
+        });
+        let unclipped = Element::new().build(&mut state, visible, |builder| builder);
+
+        state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+        flush(&mut state, &mut event_manager);
+
+        assert_eq!(state.transform.get_clip_widget(clipped), hidden);
+        // Deeper descendants keep the clip of their parent
+        assert_eq!(state.transform.get_clip_widget(grandchild), hidden);
+        assert_eq!(
+            state.transform.get_clip_widget(unclipped),
+            state.transform.get_clip_widget(visible)
+        );
+    }
+
+    #[test]
+    fn opacity_is_clamped() {
+        let mut state = State::new_headless();
+        let root = state.root;
+
+        let entity = state.add(root);
+
+        entity.set_opacity(&mut state, 2.0);
+        assert_eq!(state.style.opacity.get(entity).cloned().unwrap().0, 1.0);
+
+        entity.set_opacity(&mut state, -1.0);
+        assert_eq!(state.style.opacity.get(entity).cloned().unwrap().0, 0.0);
+    }
+
+    #[test]
+    fn opacity_is_inherited_multiplicatively() {
+        let mut state = State::new_headless();
+        let root = state.root;
+
+        let parent = state.add(root);
+        let child = state.add(parent);
+
+        parent.set_opacity(&mut state, 0.5);
+        child.set_opacity(&mut state, 0.5);
+
+        let hierarchy = state.hierarchy.clone();
+        apply_visibility(&mut state, &hierarchy);
+
+        assert_eq!(state.transform.get_opacity(parent), 0.5);
+        assert_eq!(state.transform.get_opacity(child), 0.25);
+    }
+
+    #[test]
+    fn last_child_rule_applies_only_to_last_child() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let (_, items) = list(&mut state, &mut event_manager);
+
+        assert_eq!(border(&state, items[0]), Length::Pixels(1.0));
+        assert_eq!(border(&state, items[1]), Length::Pixels(1.0));
+        assert_eq!(border(&state, items[2]), Length::Pixels(0.0));
+    }
+
+    #[test]
+    fn first_and_nth_child_rules_apply_by_position() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let (_, items) = list(&mut state, &mut event_manager);
+
+        let background = |entity: Entity| state.computed_style(entity).background_color;
+        assert_eq!(background(items[0]), Color::rgb(255, 0, 0));
+        assert_eq!(background(items[1]), Color::default());
+
+        // Only the even items match nth-child(2n)
+        assert_eq!(state.computed_style(items[1]).height, Length::Pixels(30.0));
+        assert_ne!(state.computed_style(items[2]).height, Length::Pixels(30.0));
+    }
+
+    #[test]
+    fn structural_styles_follow_hierarchy_changes() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let (list, items) = list(&mut state, &mut event_manager);
+
+        // Adding an item makes it the last child instead
+        let added = Element::new().build(&mut state, list, |builder| builder.class("item"));
+        flush(&mut state, &mut event_manager);
+
+        assert_eq!(border(&state, items[2]), Length::Pixels(1.0));
+        assert_eq!(border(&state, added), Length::Pixels(0.0));
+
+        // Reordering restyles the moved items
+        state.move_to_back(items[0]);
+        flush(&mut state, &mut event_manager);
+
+        assert_eq!(border(&state, items[0]), Length::Pixels(0.0));
+        assert_eq!(border(&state, added), Length::Pixels(1.0));
+        assert_eq!(
+            state.computed_style(items[1]).background_color,
+            Color::rgb(255, 0, 0)
+        );
+
+        // Removing the last item makes the one before it last
+        state.remove(items[0]);
+        flush(&mut state, &mut event_manager);
+
+        assert_eq!(border(&state, added), Length::Pixels(0.0));
+    }
+}
@@ -168,7 +168,7 @@ impl EventHandler for ControlKnob {
                     }
                 }
 
-                WindowEvent::KeyDown(keycode, _) => {
+                WindowEvent::KeyDown(keycode, _, _) => {
                     if *keycode == keyboard_types::Code::ShiftLeft {
                         if !self.shift_pressed {
                             self.shift_pressed = true;
@@ -4,13 +4,25 @@ use crate::entity::Entity;
 use crate::mouse::*;
 
 use crate::{BuildHandler, Event, EventHandler, Propagation, WindowEvent};
-use crate::{PropSet, State};
+use crate::{PropSet, Role, State};
+
+// Events sent by a button to itself, propagating up to its ancestors
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ButtonEvent {
+    // The left mouse button was pressed while over the button
+    Pressed,
+    // The left mouse button was released after a press
+    Released,
+    // The left mouse button was released while still over the button
+    Clicked,
+}
 
 pub struct Button {
     pub id: Entity,
 
     on_press: Option<Event>,
     on_release: Option<Event>,
+    on_press_callback: Option<Box<dyn Fn(&mut State, Entity)>>,
     text: Option<String>,
 }
 
@@ -20,6 +32,7 @@ impl Button {
             id: Entity::default(),
             on_press: None,
             on_release: None,
+            on_press_callback: None,
             text: None,
         }
     }
@@ -30,19 +43,43 @@ impl Button {
             id: Entity::default(),
             on_press: None,
             on_release: None,
+            on_press_callback: None,
             text: Some(text.to_string()),
         }
     }
 
+    // Event sent when the button is clicked, released while the cursor is still over it
     pub fn on_press(mut self, event: Event) -> Self {
         self.on_press = Some(event);
         self
     }
 
+    // Event sent when the left mouse button goes down on the button
     pub fn on_release(mut self, event: Event) -> Self {
         self.on_release = Some(event);
         self
     }
+
+    // Closure called when the button is clicked, along with the on_press event
+    pub fn on_press_with<F>(mut self, callback: F) -> Self
+    where
+        F: 'static + Fn(&mut State, Entity),
+    {
+        self.on_press_callback = Some(Box::new(callback));
+        self
+    }
+
+    fn send_event(state: &mut State, entity: Entity, event: &Option<Event>) {
+        if let Some(mut event) = event.clone() {
+            if event.target == Entity::null() {
+                event.target = entity;
+            }
+
+            event.origin = entity;
+            event.propagation = Propagation::Down;
+            state.insert_event(event);
+        }
+    }
 }
 
 impl BuildHandler for Button {
@@ -53,6 +90,7 @@ impl BuildHandler for Button {
         }
 
         state.style.insert_element(entity, "button");
+        entity.set_role(state, Role::Button);
 
         entity
     }
@@ -64,18 +102,21 @@ impl EventHandler for Button {
             match window_event {
                 WindowEvent::MouseDown(button) => match button {
                     MouseButton::Left => {
-                        if entity == event.target {
+                        if entity == event.target && state.hovered == entity {
                             state.focused = entity;
 
-                            if let Some(mut on_release) = self.on_release.clone() {
-                                if on_release.target == Entity::null() {
-                                    on_release.target = entity;
-                                }
+                            // Capture the mouse so the release is received outside the button
+                            state.capture(entity);
+                            entity.set_active(state, true);
 
-                                on_release.origin = entity;
-                                on_release.propagation = Propagation::Down;
-                                state.insert_event(on_release);
-                            }
+                            state.insert_event(
+                                Event::new(ButtonEvent::Pressed)
+                                    .target(entity)
+                                    .origin(entity)
+                                    .propagate(Propagation::Up),
+                            );
+
+                            Button::send_event(state, entity, &self.on_release);
                         }
                     }
 
@@ -84,15 +125,30 @@ impl EventHandler for Button {
 
                 WindowEvent::MouseUp(button) => match button {
                     MouseButton::Left => {
-                        if entity == event.target && entity == state.focused {
-                            if let Some(mut on_press) = self.on_press.clone() {
-                                if on_press.target == Entity::null() {
-                                    on_press.target = entity;
+                        if entity == event.target && state.captured() == entity {
+                            state.release(entity);
+                            entity.set_active(state, false);
+
+                            state.insert_event(
+                                Event::new(ButtonEvent::Released)
+                                    .target(entity)
+                                    .origin(entity)
+                                    .propagate(Propagation::Up),
+                            );
+
+                            if state.hovered == entity {
+                                state.insert_event(
+                                    Event::new(ButtonEvent::Clicked)
+                                        .target(entity)
+                                        .origin(entity)
+                                        .propagate(Propagation::Up),
+                                );
+
+                                Button::send_event(state, entity, &self.on_press);
+
+                                if let Some(callback) = &self.on_press_callback {
+                                    (callback)(state, entity);
                                 }
-
-                                on_press.origin = entity;
-                                on_press.propagation = Propagation::Down;
-                                state.insert_event(on_press);
                             }
                         }
                     }
@@ -107,3 +163,69 @@ impl EventHandler for Button {
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EventManager;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum TestEvent {
+        Press,
+    }
+
+    fn sent(state: &mut State, message: &TestEvent) -> bool {
+        state
+            .pending_events()
+            .iter()
+            .any(|event| event.message.equals_a(message))
+    }
+
+    #[test]
+    fn on_press_is_sent_when_the_button_is_released() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let root = state.root;
+
+        let button = Button::with_label("Press")
+            .on_press(Event::new(TestEvent::Press))
+            .build(&mut state, root, |builder| builder);
+        while !state.event_queue.is_empty() {
+            event_manager.flush_events(&mut state);
+        }
+
+        state.hovered = button;
+        state.insert_event(Event::new(WindowEvent::MouseDown(MouseButton::Left)).target(button));
+        event_manager.flush_events(&mut state);
+        assert!(!sent(&mut state, &TestEvent::Press));
+
+        state.insert_event(Event::new(WindowEvent::MouseUp(MouseButton::Left)).target(button));
+        event_manager.flush_events(&mut state);
+        assert!(sent(&mut state, &TestEvent::Press));
+    }
+
+    #[test]
+    fn on_press_is_not_sent_when_released_outside_the_button() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let root = state.root;
+
+        let button = Button::with_label("Press")
+            .on_press(Event::new(TestEvent::Press))
+            .build(&mut state, root, |builder| builder);
+        while !state.event_queue.is_empty() {
+            event_manager.flush_events(&mut state);
+        }
+
+        state.hovered = button;
+        state.insert_event(Event::new(WindowEvent::MouseDown(MouseButton::Left)).target(button));
+        event_manager.flush_events(&mut state);
+
+        // The button captured the mouse so it still receives the release
+        state.hovered = root;
+        state.insert_event(Event::new(WindowEvent::MouseUp(MouseButton::Left)).target(button));
+        event_manager.flush_events(&mut state);
+        assert!(!sent(&mut state, &TestEvent::Press));
+        assert_eq!(state.captured(), Entity::null());
+    }
+}
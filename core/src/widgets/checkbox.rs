@@ -3,7 +3,7 @@
 use crate::entity::Entity;
 use crate::mouse::*;
 use crate::{BuildHandler, Event, EventHandler, Propagation, WindowEvent};
-use crate::{PropSet, State};
+use crate::{PropSet, Role, State};
 
 use crate::style::layout::{Align, Justify};
 
@@ -98,7 +98,7 @@ impl BuildHandler for Checkbox {
     type Ret = Entity;
     fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
         entity
-            .set_font(state, "Icons".to_string())
+            .set_font(state, "icons".to_string())
             .set_text_justify(state, Justify::Center)
             .set_text_align(state, Align::Center);
 
@@ -117,6 +117,7 @@ impl BuildHandler for Checkbox {
         }
 
         state.style.insert_element(entity, "checkbox");
+        entity.set_role(state, Role::Checkbox);
 
         entity
     }
@@ -48,6 +48,50 @@ impl BuildHandler for VBox {
 
 impl EventHandler for VBox {}
 
+// An element which lays out its children horizontally
+pub struct Row {}
+
+impl Row {
+    pub fn new() -> Self {
+        Row {}
+    }
+}
+
+impl BuildHandler for Row {
+    type Ret = Entity;
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity.set_flex_direction(state, FlexDirection::Row);
+
+        entity.set_element(state, "row");
+
+        entity
+    }
+}
+
+impl EventHandler for Row {}
+
+// An element which lays out its children vertically
+pub struct Column {}
+
+impl Column {
+    pub fn new() -> Self {
+        Column {}
+    }
+}
+
+impl BuildHandler for Column {
+    type Ret = Entity;
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity.set_flex_direction(state, FlexDirection::Column);
+
+        entity.set_element(state, "column");
+
+        entity
+    }
+}
+
+impl EventHandler for Column {}
+
 pub struct ResizableVBox {
     resizing: bool,
     previous_width: f32,
@@ -112,7 +156,7 @@ impl EventHandler for ResizableVBox {
                 }
 
                 // Occurs when the cursor leaves the entity
-                WindowEvent::MouseOut => {
+                WindowEvent::MouseOut(_) => {
                     if !self.resizing {
                         state.insert_event(Event::new(WindowEvent::SetCursor(CursorIcon::Arrow)));
                     }
@@ -0,0 +1,43 @@
+use crate::draw_background;
+use crate::widgets::*;
+
+use femtovg::{renderer::OpenGl, Canvas};
+
+// A widget drawn by a closure, for charts, game graphics and other shapes without writing a widget
+// The closure is called after the background is drawn, within the clipping and transform of the widget,
+// and is styled with the "custom" selector
+pub struct CustomWidget {
+    on_draw: Box<dyn Fn(&mut State, Entity, &mut Canvas<OpenGl>)>,
+}
+
+impl CustomWidget {
+    pub fn new<F>(on_draw: F) -> Self
+    where
+        F: 'static + Fn(&mut State, Entity, &mut Canvas<OpenGl>),
+    {
+        CustomWidget {
+            on_draw: Box::new(on_draw),
+        }
+    }
+}
+
+impl BuildHandler for CustomWidget {
+    type Ret = Entity;
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity.set_element(state, "custom");
+
+        entity
+    }
+}
+
+impl EventHandler for CustomWidget {
+    fn on_draw(&mut self, state: &mut State, entity: Entity, canvas: &mut Canvas<OpenGl>) {
+        if !draw_background(state, entity, canvas) {
+            return;
+        }
+
+        (self.on_draw)(state, entity, canvas);
+
+        canvas.restore();
+    }
+}
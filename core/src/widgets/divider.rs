@@ -0,0 +1,74 @@
+use crate::widgets::*;
+use crate::{Color, Length};
+
+// A thin line separating groups of widgets, styled with the "divider" selector
+// A horizontal divider fills the width of its parent and a vertical divider fills the height
+// The line is drawn as the background of the divider so it can be colored with background-color
+pub struct Divider {
+    orientation: Direction,
+    thickness: f32,
+    color: Option<Color>,
+}
+
+impl Divider {
+    pub fn new() -> Self {
+        Divider {
+            orientation: Direction::Horizontal,
+            thickness: 1.0,
+            color: None,
+        }
+    }
+
+    pub fn with_orientation(mut self, orientation: Direction) -> Self {
+        self.orientation = orientation;
+
+        self
+    }
+
+    // Thickness of the line in pixels, 1.0 by default
+    pub fn with_thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+
+        self
+    }
+}
+
+impl BuildHandler for Divider {
+    type Ret = Entity;
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity.set_element(state, "divider");
+
+        // Dividers keep their thickness in flex layouts and can't be hovered or clicked
+        entity.set_flex_grow(state, 0.0).set_flex_shrink(state, 0.0);
+        state.transform.set_hoverability(entity, false);
+
+        match self.orientation {
+            Direction::Horizontal => {
+                entity
+                    .set_width(state, Length::Percentage(1.0))
+                    .set_height(state, Length::Pixels(self.thickness));
+            }
+
+            Direction::Vertical => {
+                entity
+                    .set_width(state, Length::Pixels(self.thickness))
+                    .set_height(state, Length::Percentage(1.0));
+            }
+        }
+
+        if let Some(color) = self.color {
+            entity.set_background_color(state, color);
+        }
+
+        entity
+    }
+}
+
+// Dividers don't respond to events and use the default on_draw
+impl EventHandler for Divider {}
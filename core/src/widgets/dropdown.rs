@@ -8,7 +8,7 @@ use crate::{PropSet, State};
 use crate::state::style::*;
 use crate::widgets::{Button, Checkbox, CheckboxEvent, Element, HBox, Label, RadioList};
 
-use crate::state::hierarchy::HierarchyTree;
+use crate::state::hierarchy::{HierarchyTree, IntoChildIterator};
 
 const ICON_DOWN_OPEN: &str = "\u{e75c}";
 
@@ -16,7 +16,10 @@ const ICON_DOWN_DIR: &str = "\u{25be}";
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum DropdownEvent {
+    // Sent by an item when it is clicked
     SetText(String, String),
+    // Sent by the dropdown when an item is selected with the index and text of the item
+    Selected(usize, String),
 }
 
 pub struct Item {
@@ -102,6 +105,8 @@ pub struct Dropdown {
     label: Entity,
     //options: Vec<(Entity, String, String)>,
     text: String,
+    // Options added as items when the dropdown is built
+    options: Vec<String>,
 
     open: bool,
 
@@ -122,6 +127,7 @@ impl Dropdown {
             label: Entity::null(),
             //options: Vec::new(),
             text: text.to_string(),
+            options: Vec::new(),
             open: false,
             //other_container: Entity::null(),
             expand_animation: std::usize::MAX,
@@ -132,6 +138,13 @@ impl Dropdown {
         }
     }
 
+    // Adds an item to the list for each option
+    pub fn with_options(mut self, options: Vec<String>) -> Self {
+        self.options = options;
+
+        self
+    }
+
     // pub fn add_item(mut self, name: &str, proxy: &str) -> Self {
     //     self.options.push((Entity::null(), name.to_string(), proxy.to_string()));
 
@@ -202,6 +215,10 @@ impl BuildHandler for Dropdown {
         //     *id = Item::new(name, proxy).build(state, self.other_container, |builder| builder.set_flex_direction(FlexDirection::Row).class("item").class("other"));
         // }
 
+        for option in self.options.iter() {
+            Item::new(option, option).build(state, self.container, |builder| builder);
+        }
+
         state.style.insert_element(entity, "dropdown");
 
         // let container_expand_animation = AnimationState::new()
@@ -255,6 +272,21 @@ impl EventHandler for Dropdown {
                     //println!("Set Text");
                     //Check here if it's an event from a child (TODO)
                     self.label.set_text(state, proxy);
+
+                    // The index of an item is its position in the list
+                    let item = event.target;
+                    if let Some(index) = self
+                        .container
+                        .child_iter(&state.hierarchy)
+                        .position(|child| child == item)
+                    {
+                        state.insert_event(
+                            Event::new(DropdownEvent::Selected(index, text.clone()))
+                                .target(entity)
+                                .origin(entity)
+                                .propagate(Propagation::Up),
+                        );
+                    }
                     //self.container.set_visibility(state, Visibility::Invisible);
                     self.open = false;
                     //state.style.height.play_animation(self.container, self.collapse_animation);
@@ -1,7 +1,10 @@
 use crate::entity::Entity;
 
-use crate::{BuildHandler, EventHandler, State};
+use crate::{BuildHandler, EventHandler, PropSet, State};
 
+// A generic container with no behaviour of its own
+// Drawn with the default background, border and text rendering and styled with the "element" selector
+// Use it as the building block for composite widgets such as panels, rows and columns
 pub struct Element {}
 
 impl Element {
@@ -13,8 +16,11 @@ impl Element {
 impl BuildHandler for Element {
     type Ret = Entity;
     fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity.set_element(state, "element");
+
         entity
     }
 }
 
+// Elements don't respond to events and use the default on_draw
 impl EventHandler for Element {}
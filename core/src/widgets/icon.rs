@@ -0,0 +1,53 @@
+use crate::widgets::*;
+use crate::{Align, Justify, Role};
+
+// A glyph of an icon font looked up by name, styled with the "icon" selector
+// Names are looked up in the icons registered with the state, see State::register_icon
+pub struct Icon {
+    name: String,
+    font: String,
+}
+
+impl Icon {
+    pub fn new(name: &str) -> Self {
+        Icon {
+            name: name.to_string(),
+            font: "icons".to_string(),
+        }
+    }
+
+    // The font containing the glyph, the built in "icons" font by default
+    pub fn with_font(mut self, font: &str) -> Self {
+        self.font = font.to_string();
+
+        self
+    }
+}
+
+impl BuildHandler for Icon {
+    type Ret = Entity;
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        let text = match state.icon(&self.name) {
+            Some(codepoint) => codepoint.to_string(),
+
+            None => {
+                eprintln!("WARNING: No icon is registered with the name {}", self.name);
+                String::new()
+            }
+        };
+
+        entity
+            .set_text(state, &text)
+            .set_font(state, self.font.clone())
+            .set_text_justify(state, Justify::Center)
+            .set_text_align(state, Align::Center)
+            .set_element(state, "icon")
+            .set_role(state, Role::Image)
+            .set_accessible_label(state, &self.name);
+
+        entity
+    }
+}
+
+// Icons use the default on_draw
+impl EventHandler for Icon {}
@@ -0,0 +1,128 @@
+use crate::widgets::*;
+use crate::{draw_background, intersect_scissor, load_image, Rect, Role};
+
+use femtovg::{renderer::OpenGl, Canvas, Paint, Path};
+
+// How an image is sized within the content box of an Image widget
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageFit {
+    // Stretched to fill the content box, ignoring the aspect ratio of the image
+    Fill,
+    // Scaled to fit within the content box, keeping the aspect ratio of the image
+    Contain,
+    // Scaled to cover the content box, keeping the aspect ratio of the image, with the excess clipped
+    Cover,
+    // Drawn at the size of the image
+    None,
+}
+
+impl Default for ImageFit {
+    fn default() -> Self {
+        ImageFit::Contain
+    }
+}
+
+// Returns where an image of the given size is drawn within the bounds
+// Every mode except Fill centers the image within the bounds
+pub fn fit_image(fit: ImageFit, image_width: f32, image_height: f32, bounds: Rect) -> Rect {
+    if image_width <= 0.0 || image_height <= 0.0 {
+        return bounds;
+    }
+
+    let scale = match fit {
+        ImageFit::Fill => return bounds,
+        ImageFit::Contain => (bounds.w / image_width).min(bounds.h / image_height),
+        ImageFit::Cover => (bounds.w / image_width).max(bounds.h / image_height),
+        ImageFit::None => 1.0,
+    };
+
+    let width = image_width * scale;
+    let height = image_height * scale;
+
+    Rect::new(
+        bounds.x + (bounds.w - width) / 2.0,
+        bounds.y + (bounds.h - height) / 2.0,
+        width,
+        height,
+    )
+}
+
+// Displays an image loaded from a file, styled with the "image" selector
+// An image which fails to load is drawn as a crossed out box
+pub struct Image {
+    path: String,
+    fit: ImageFit,
+}
+
+impl Image {
+    pub fn new(path: &str) -> Self {
+        Image {
+            path: path.to_string(),
+            fit: ImageFit::default(),
+        }
+    }
+
+    pub fn with_fit(mut self, fit: ImageFit) -> Self {
+        self.fit = fit;
+
+        self
+    }
+}
+
+impl BuildHandler for Image {
+    type Ret = Entity;
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity
+            .set_element(state, "image")
+            .set_role(state, Role::Image);
+
+        entity
+    }
+}
+
+impl EventHandler for Image {
+    fn on_draw(&mut self, state: &mut State, entity: Entity, canvas: &mut Canvas<OpenGl>) {
+        if !draw_background(state, entity, canvas) {
+            return;
+        }
+
+        let content = state.content_box(entity);
+        let opacity = state.transform.get_opacity(entity);
+
+        let image = load_image(state, canvas, &self.path).and_then(|image_id| {
+            canvas
+                .image_size(image_id)
+                .ok()
+                .map(|(width, height)| (image_id, width as f32, height as f32))
+        });
+
+        match image {
+            Some((image_id, width, height)) => {
+                let rect = fit_image(self.fit, width, height, content);
+
+                // A covering or unscaled image can be larger than the content box
+                intersect_scissor(state, canvas, content);
+
+                let mut path = Path::new();
+                path.rect(rect.x, rect.y, rect.w, rect.h);
+                let paint = Paint::image(image_id, rect.x, rect.y, rect.w, rect.h, 0.0, opacity);
+                canvas.fill_path(&mut path, &paint);
+            }
+
+            None => {
+                let mut path = Path::new();
+                path.rect(content.x, content.y, content.w, content.h);
+                path.move_to(content.x, content.y);
+                path.line_to(content.x + content.w, content.y + content.h);
+                path.move_to(content.x + content.w, content.y);
+                path.line_to(content.x, content.y + content.h);
+
+                let mut paint = Paint::color(femtovg::Color::rgbaf(0.5, 0.5, 0.5, opacity));
+                paint.set_line_width(1.0);
+                canvas.stroke_path(&mut path, &paint);
+            }
+        }
+
+        canvas.restore();
+    }
+}
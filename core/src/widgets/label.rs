@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 
 use crate::widgets::*;
+use crate::{Color, Role};
 
 // Default style for labels
 const label_style: &str = r#"
@@ -12,14 +13,39 @@ const label_style: &str = r#"
 
 pub struct Label {
     text: String,
+    font: Option<String>,
+    color: Option<Color>,
 }
 
 impl Label {
     pub fn new(text: &str) -> Self {
         Label {
             text: text.to_string(),
+            font: None,
+            color: None,
         }
     }
+
+    pub fn with_font(mut self, font: &str) -> Self {
+        self.font = Some(font.to_string());
+
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+
+        self
+    }
+}
+
+impl Bindable for Label {
+    type Data = String;
+
+    fn on_update(&mut self, state: &mut State, entity: Entity, data: &Self::Data) {
+        self.text = data.clone();
+        entity.set_text(state, data);
+    }
 }
 
 impl BuildHandler for Label {
@@ -27,7 +53,17 @@ impl BuildHandler for Label {
     fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
         entity.set_text(state, &self.text);
 
-        entity.set_element(state, "label");
+        entity
+            .set_element(state, "label")
+            .set_role(state, Role::Label);
+
+        if let Some(font) = self.font.clone() {
+            entity.set_font(state, font);
+        }
+
+        if let Some(color) = self.color {
+            entity.set_font_color(state, color);
+        }
 
         //state.insert_style(label_style);
 
@@ -35,4 +71,5 @@ impl BuildHandler for Label {
     }
 }
 
+// Labels are not interactive and use the default text rendering
 impl EventHandler for Label {}
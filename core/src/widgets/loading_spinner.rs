@@ -0,0 +1,120 @@
+#![allow(dead_code)]
+
+use crate::entity::Entity;
+use crate::{AnimationState, BuildHandler, Event, EventHandler, LoopMode, WindowEvent};
+use crate::{PropSet, State};
+
+use crate::style::layout::{Align, Justify};
+
+use std::time::Duration;
+
+const ICON_CW: &str = "\u{27f3}";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LoadingSpinnerEvent {
+    Start,
+    Stop,
+}
+
+// An icon which rotates continuously to show that something is in progress
+// Named to avoid clashing with the numeric Spinner widget
+pub struct LoadingSpinner {
+    // Rotation speed in degrees per second
+    speed: f32,
+    spinning: bool,
+}
+
+impl LoadingSpinner {
+    pub fn new() -> Self {
+        LoadingSpinner {
+            speed: 360.0,
+            spinning: true,
+        }
+    }
+
+    pub fn with_speed(mut self, degrees_per_sec: f32) -> Self {
+        self.speed = degrees_per_sec;
+
+        self
+    }
+
+    // Builds the spinner without starting it
+    pub fn stopped(mut self) -> Self {
+        self.spinning = false;
+
+        self
+    }
+
+    fn start(&mut self, state: &mut State, entity: Entity) {
+        self.spinning = true;
+
+        if self.speed <= 0.0 {
+            return;
+        }
+
+        // One full turn per loop
+        let animation = AnimationState::new()
+            .with_duration(Duration::from_secs_f32(360.0 / self.speed))
+            .with_keyframe((0.0, 0.0))
+            .with_keyframe((1.0, 360.0))
+            .with_loop_mode(LoopMode::Loop);
+
+        state.style.rotate.play(entity, animation);
+
+        // The application loop keeps redrawing while the animation is active
+        state.insert_event(Event::new(WindowEvent::Redraw));
+    }
+
+    fn stop(&mut self, state: &mut State, entity: Entity) {
+        self.spinning = false;
+
+        // Stopping the animation stops the redraws
+        state.style.rotate.stop(entity);
+
+        state.insert_event(Event::new(WindowEvent::Redraw));
+    }
+}
+
+impl BuildHandler for LoadingSpinner {
+    type Ret = Entity;
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity
+            .set_text(state, ICON_CW)
+            .set_font(state, "icons".to_string())
+            .set_text_justify(state, Justify::Center)
+            .set_text_align(state, Align::Center)
+            .set_rotate(state, 0.0);
+
+        if self.spinning {
+            self.start(state, entity);
+        }
+
+        entity.set_element(state, "loading_spinner")
+    }
+}
+
+impl EventHandler for LoadingSpinner {
+    fn on_event(&mut self, state: &mut State, entity: Entity, event: &mut Event) -> bool {
+        if let Some(spinner_event) = event.message.downcast::<LoadingSpinnerEvent>() {
+            if event.target == entity {
+                match spinner_event {
+                    LoadingSpinnerEvent::Start => {
+                        if !self.spinning {
+                            self.start(state, entity);
+                        }
+                    }
+
+                    LoadingSpinnerEvent::Stop => {
+                        if self.spinning {
+                            self.stop(state, entity);
+                        }
+                    }
+                }
+
+                return true;
+            }
+        }
+
+        false
+    }
+}
@@ -12,6 +12,8 @@ use crate::widgets::Button;
 
 use crate::state::hierarchy::IntoChildIterator;
 
+use crate::Key;
+
 // Notes:
 // When user clicks menu, the container should appear
 // When container is visible, clicking on a menu item activates the item
@@ -23,6 +25,8 @@ pub enum MenuEvent {
     Close(Entity),
     CloseAll(Entity),
     OpenHover(bool),
+    // Emitted by a menu item when it is chosen
+    Selected(String),
 }
 
 //impl Message for MenuEvent {}
@@ -43,6 +47,8 @@ pub struct Menu {
     menu_position: MenuPosition,
     open_on_hover: bool,
     open: bool,
+    // The item highlighted using the keyboard
+    highlighted: Entity,
 }
 
 impl Menu {
@@ -55,7 +61,75 @@ impl Menu {
             menu_position: menu_position,
             open_on_hover: false,
             open: false,
+            highlighted: Entity::null(),
+        }
+    }
+
+    fn open_menu(&mut self, state: &mut State, entity: Entity) {
+        let posx = state.transform.get_posx(entity);
+        let posy = state.transform.get_posy(entity);
+        let width = state.transform.get_width(entity);
+        let height = state.transform.get_height(entity);
+
+        // The container is an overlay so is positioned in window coordinates
+        let (left, top) = match self.menu_position {
+            MenuPosition::Right => (posx + width, posy),
+            _ => (posx, posy + height),
+        };
+
+        self.container
+            .set_left(state, Length::Pixels(left))
+            .set_top(state, Length::Pixels(top))
+            .set_display(state, Display::Flexbox);
+
+        entity.set_checked(state, true);
+        state.focused = entity;
+        self.open = true;
+    }
+
+    fn close_menu(&mut self, state: &mut State, entity: Entity) {
+        self.container.set_display(state, Display::None);
+        self.set_highlighted(state, Entity::null());
+
+        entity.set_checked(state, false);
+        if state.focused == entity {
+            state.focused = state.root;
         }
+        self.open = false;
+    }
+
+    fn set_highlighted(&mut self, state: &mut State, item: Entity) {
+        if self.highlighted != Entity::null() {
+            self.highlighted.set_focus(state, false);
+        }
+
+        if item != Entity::null() {
+            item.set_focus(state, true);
+        }
+
+        self.highlighted = item;
+    }
+
+    // Moves the highlight to the next or previous enabled item, wrapping around at the ends
+    fn move_highlight(&mut self, state: &mut State, forward: bool) {
+        let items: Vec<Entity> = self.container.child_iter(&state.hierarchy).collect();
+        let items: Vec<Entity> = items
+            .into_iter()
+            .filter(|item| !item.is_disabled(state))
+            .collect();
+
+        if items.is_empty() {
+            return;
+        }
+
+        let next = match items.iter().position(|item| *item == self.highlighted) {
+            Some(index) if forward => (index + 1) % items.len(),
+            Some(index) => (index + items.len() - 1) % items.len(),
+            None if forward => 0,
+            None => items.len() - 1,
+        };
+
+        self.set_highlighted(state, items[next]);
     }
 
     // pub fn add_item(mut self, name: &str, event: Option<Event>) -> Self {
@@ -80,17 +154,10 @@ impl BuildHandler for Menu {
                 .class("container")
         });
 
-        match self.menu_position {
-            MenuPosition::Down => {
-                self.container.set_top(state, Length::Percentage(1.0));
-            }
-
-            MenuPosition::Right => {
-                self.container.set_left(state, Length::Percentage(1.0));
-            }
-
-            _ => {}
-        }
+        // The container is drawn above the rest of the tree and hidden until the menu is opened
+        self.container
+            .set_overlay(state, true)
+            .set_display(state, Display::None);
 
         //state.style.checked.set(entity, false);
         entity.set_checked(state, false);
@@ -131,8 +198,7 @@ impl EventHandler for Menu {
                 MenuEvent::Open(_id) => {
                     if event.target == entity {
                         state.capture(entity);
-                        entity.set_checked(state, true);
-                        self.open = true;
+                        self.open_menu(state, entity);
                     } else {
                         self.open_on_hover = true;
                     }
@@ -140,12 +206,9 @@ impl EventHandler for Menu {
 
                 MenuEvent::Close(id) => {
                     if *id == entity {
-                        if entity.parent(&state.hierarchy).unwrap() == state.root {
-                            state.release(entity);
-                        }
+                        state.release(entity);
                         //state.style.checked.set(entity, false);
-                        entity.set_checked(state, false);
-                        self.open = false;
+                        self.close_menu(state, entity);
                     }
                     // else {
                     //     state.capture(entity);
@@ -158,8 +221,7 @@ impl EventHandler for Menu {
                     }
 
                     //state.style.checked.set(entity, false);
-                    entity.set_checked(state, false);
-                    self.open = false;
+                    self.close_menu(state, entity);
 
                     state.release(entity);
                 }
@@ -167,6 +229,8 @@ impl EventHandler for Menu {
                 MenuEvent::OpenHover(val) => {
                     self.open_on_hover = *val;
                 }
+
+                MenuEvent::Selected(_) => {}
             }
 
             state.insert_event(Event::new(WindowEvent::Restyle));
@@ -222,8 +286,15 @@ impl EventHandler for Menu {
                             // );
 
                             if event.target == entity {
-                                for entity in self.container.child_iter(&state.hierarchy) {
+                                let items: Vec<Entity> =
+                                    self.container.child_iter(&state.hierarchy).collect();
+                                for entity in items {
                                     if entity == state.hovered {
+                                        // Disabled items ignore the click and keep the menu open
+                                        if entity.is_disabled(state) {
+                                            return false;
+                                        }
+
                                         // Forward the mouse down event to the option that's hovered
                                         state.insert_event(
                                             Event::new(WindowEvent::MouseDown(*button))
@@ -248,8 +319,14 @@ impl EventHandler for Menu {
                 WindowEvent::MouseUp(button) => {
                     match button {
                         MouseButton::Left => {
-                            for entity in self.container.child_iter(&state.hierarchy) {
+                            let items: Vec<Entity> =
+                                self.container.child_iter(&state.hierarchy).collect();
+                            for entity in items {
                                 if entity == state.hovered {
+                                    if entity.is_disabled(state) {
+                                        return false;
+                                    }
+
                                     // Forward the mouse down event to the option that's hovered
                                     state.insert_event(
                                         Event::new(WindowEvent::MouseUp(*button))
@@ -283,10 +360,10 @@ impl EventHandler for Menu {
                     }
                 }
 
-                WindowEvent::MouseOver => {
+                WindowEvent::MouseOver(hovered) => {
                     if event.origin == Entity::null() {
                         state.insert_event(
-                            Event::new(WindowEvent::MouseOver)
+                            Event::new(WindowEvent::MouseOver(*hovered))
                                 .origin(event.target)
                                 .target(self.container)
                                 .propagate(Propagation::Fall),
@@ -308,7 +385,7 @@ impl EventHandler for Menu {
                     } else if event.origin.is_descendant_of(&state.hierarchy, entity) {
                         //if event.target != self.container {
                         // state.insert_event(
-                        //     Event::new(WindowEvent::MouseOver)
+                        //     Event::new(WindowEvent::MouseOver(*hovered))
                         //         .origin(event.target)
                         //         .target(self.container)
                         //         .propagate(Propagation::Fall),
@@ -349,7 +426,52 @@ impl EventHandler for Menu {
                     //println!("Mouse over menu");
                 }
 
-                WindowEvent::MouseOut => {
+                WindowEvent::KeyDown(code, key, repeat) => {
+                    if self.open && event.target == entity {
+                        match key {
+                            Some(Key::ArrowDown) => {
+                                self.move_highlight(state, true);
+                            }
+
+                            Some(Key::ArrowUp) => {
+                                self.move_highlight(state, false);
+                            }
+
+                            Some(Key::Enter) if !*repeat => {
+                                if self.highlighted != Entity::null() {
+                                    // Forward the key press to the highlighted item
+                                    state.insert_event(
+                                        Event::new(WindowEvent::KeyDown(
+                                            *code,
+                                            key.clone(),
+                                            *repeat,
+                                        ))
+                                        .target(self.highlighted)
+                                        .propagate(Propagation::Direct),
+                                    );
+
+                                    state.insert_event(
+                                        Event::new(MenuEvent::CloseAll(entity)).target(entity),
+                                    );
+                                }
+                            }
+
+                            Some(Key::Escape) => {
+                                state.insert_event(
+                                    Event::new(MenuEvent::CloseAll(entity)).target(entity),
+                                );
+                            }
+
+                            _ => {}
+                        }
+
+                        state.insert_event(Event::new(WindowEvent::Restyle));
+
+                        return true;
+                    }
+                }
+
+                WindowEvent::MouseOut(_) => {
                     //println!("Mouse over menu");
                     // state.insert_event(
                     //     Event::new(MenuEvent::Close(entity))
@@ -419,3 +541,118 @@ impl EventHandler for Menu {
         false
     }
 }
+
+// An entry in a menu which emits MenuEvent::Selected with its id when chosen
+// Disabled items (the disabled pseudo-class) can't be chosen
+pub struct MenuItem {
+    id: String,
+    label: String,
+}
+
+impl MenuItem {
+    pub fn new(id: &str, label: &str) -> Self {
+        MenuItem {
+            id: id.to_string(),
+            label: label.to_string(),
+        }
+    }
+}
+
+impl BuildHandler for MenuItem {
+    type Ret = Entity;
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity.set_text(state, &self.label);
+
+        state.style.insert_element(entity, "menu_item");
+
+        entity
+    }
+}
+
+impl EventHandler for MenuItem {
+    fn on_event(&mut self, state: &mut State, entity: Entity, event: &mut Event) -> bool {
+        if let Some(window_event) = event.message.downcast::<WindowEvent>() {
+            let chosen = match window_event {
+                WindowEvent::MouseUp(MouseButton::Left) => true,
+                // Holding enter only chooses the item once
+                WindowEvent::KeyDown(_, Some(Key::Enter), false) => true,
+                _ => false,
+            };
+
+            if chosen && event.target == entity && !entity.is_disabled(state) {
+                state.insert_event(
+                    Event::new(MenuEvent::Selected(self.id.clone()))
+                        .target(entity)
+                        .propagate(Propagation::Up),
+                );
+
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+// A horizontal bar of menus
+// While a menu is open the left and right arrow keys move to the neighbouring menu
+pub struct MenuBar {}
+
+impl MenuBar {
+    pub fn new() -> Self {
+        MenuBar {}
+    }
+}
+
+impl BuildHandler for MenuBar {
+    type Ret = Entity;
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity.set_flex_direction(state, FlexDirection::Row);
+
+        state.style.insert_element(entity, "menu_bar");
+
+        entity
+    }
+}
+
+impl EventHandler for MenuBar {
+    fn on_event(&mut self, state: &mut State, entity: Entity, event: &mut Event) -> bool {
+        if let Some(window_event) = event.message.downcast::<WindowEvent>() {
+            match window_event {
+                WindowEvent::KeyDown(_, key, _) => {
+                    let forward = match key {
+                        Some(Key::ArrowRight) => true,
+                        Some(Key::ArrowLeft) => false,
+                        _ => return false,
+                    };
+
+                    // Key presses are sent to the open menu, which is focused
+                    let menus: Vec<Entity> = entity.child_iter(&state.hierarchy).collect();
+                    if let Some(index) = menus.iter().position(|menu| *menu == event.target) {
+                        let next = if forward {
+                            (index + 1) % menus.len()
+                        } else {
+                            (index + menus.len() - 1) % menus.len()
+                        };
+
+                        state.insert_event(
+                            Event::new(MenuEvent::CloseAll(event.target)).target(event.target),
+                        );
+
+                        state.insert_event(
+                            Event::new(MenuEvent::Open(menus[next]))
+                                .target(menus[next])
+                                .propagate(Propagation::Fall),
+                        );
+
+                        return true;
+                    }
+                }
+
+                _ => {}
+            }
+        }
+
+        false
+    }
+}
@@ -21,6 +21,9 @@ pub use switch::*;
 pub mod tab;
 pub use tab::*;
 
+pub mod tree_view;
+pub use tree_view::*;
+
 pub mod textbox;
 pub use textbox::*;
 
@@ -36,6 +39,12 @@ pub use scroll_container::*;
 pub mod spinner;
 pub use spinner::*;
 
+pub mod spinbox;
+pub use spinbox::*;
+
+pub mod loading_spinner;
+pub use loading_spinner::*;
+
 pub mod value_slider;
 pub use value_slider::ValueSlider;
 
@@ -51,14 +60,38 @@ pub use radio::*;
 pub mod label;
 pub use label::*;
 
+pub mod divider;
+pub use divider::Divider;
+
+pub mod tooltip;
+pub use tooltip::*;
+
 pub mod containers;
 pub use containers::*;
 
+pub mod custom_widget;
+pub use custom_widget::CustomWidget;
+
+pub mod image_widget;
+pub use image_widget::*;
+
+pub mod icon;
+pub use icon::Icon;
+
+// Drawing SVG files requires the svg feature
+#[cfg(feature = "svg")]
+pub mod svg_widget;
+#[cfg(feature = "svg")]
+pub use svg_widget::Svg;
+
 pub mod vector_edit;
 pub use vector_edit::*;
 
 pub mod window;
-pub use window::WindowWidget;
+pub use window::{WindowPropSet, WindowWidget};
+
+pub mod model;
+pub use model::*;
 
 // Audio Widgets
 pub mod audio_widgets;
@@ -0,0 +1,421 @@
+#![allow(dead_code)]
+
+use crate::widgets::*;
+use crate::{Event, GeometryChanged, Propagation};
+
+use std::any::TypeId;
+use std::fmt::Debug;
+
+use femtovg::{renderer::OpenGl, Canvas};
+
+// Selects a part (usually a field) of some data
+pub trait Lens: 'static {
+    type Source: 'static + Clone + Debug + PartialEq;
+    type Target;
+
+    fn view<'a>(&self, source: &'a Self::Source) -> &'a Self::Target;
+    fn view_mut<'a>(&self, source: &'a mut Self::Source) -> &'a mut Self::Target;
+}
+
+// Implemented by widgets which can display, and optionally edit, bound data
+// Both methods default to doing nothing so widgets only override what they need
+pub trait Bindable {
+    type Data;
+
+    // Called when the bound data changes
+    fn on_update(&mut self, _state: &mut State, _entity: Entity, _data: &Self::Data) {}
+
+    // Returns the new value of the bound data if the event is a change made by the widget
+    fn on_change(
+        &mut self,
+        _state: &mut State,
+        _entity: Entity,
+        _event: &mut Event,
+    ) -> Option<Self::Data> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BindEvent<D> {
+    // Sent to a model by an observer to receive updates when the part of the data selected by a lens changes
+    // The type id is the type of the lens
+    Bind(Entity, TypeId),
+    // Sent to a model to replace its data after the part selected by the lens with the given type id changed
+    Set(D, TypeId),
+    // Sent by a model to an observer with the current data and the type id of the lens whose part of it changed
+    Update(D, TypeId),
+}
+
+// Holds some data and sends it to the observers bound to it when it changes
+pub struct Model<D> {
+    data: D,
+    observers: Vec<Entity>,
+}
+
+impl<D: 'static + Clone + Debug + PartialEq> Model<D> {
+    pub fn new(data: D) -> Self {
+        Model {
+            data,
+            observers: Vec::new(),
+        }
+    }
+
+    fn send_update(&self, state: &mut State, entity: Entity, observer: Entity, lens: TypeId) {
+        let mut event = Event::new(BindEvent::Update(self.data.clone(), lens))
+            .target(observer)
+            .origin(entity)
+            .propagate(Propagation::Direct);
+
+        // Every observer gets its own copy of the same update
+        event.unique = false;
+
+        state.insert_event(event);
+    }
+}
+
+impl<D: 'static + Clone + Debug + PartialEq> BuildHandler for Model<D> {
+    type Ret = Entity;
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity.set_element(state, "model");
+
+        entity
+    }
+}
+
+impl<D: 'static + Clone + Debug + PartialEq> EventHandler for Model<D> {
+    fn on_event(&mut self, state: &mut State, entity: Entity, event: &mut Event) -> bool {
+        if let Some(bind_event) = event.message.downcast::<BindEvent<D>>() {
+            match bind_event {
+                BindEvent::Bind(observer, lens) => {
+                    self.observers.push(*observer);
+
+                    self.send_update(state, entity, *observer, *lens);
+
+                    return true;
+                }
+
+                BindEvent::Set(data, lens) => {
+                    self.data = data.clone();
+
+                    // Every observer is sent the new data, skipping the one that changed it, so that the
+                    // data they write back later includes this change
+                    for observer in self.observers.iter() {
+                        if *observer != event.origin {
+                            self.send_update(state, entity, *observer, *lens);
+                        }
+                    }
+
+                    return true;
+                }
+
+                _ => {}
+            }
+        }
+
+        false
+    }
+}
+
+// Binds a widget to the part of the data in an ancestor model selected by a lens
+// Changes to the data are passed to the widget and changes made by the widget are written back to the model
+pub struct Wrapper<L: Lens, W> {
+    lens: L,
+    widget: W,
+    data: Option<L::Source>,
+}
+
+impl<L, W> Wrapper<L, W>
+where
+    L: Lens,
+    W: Bindable<Data = L::Target>,
+{
+    pub fn new(lens: L, widget: W) -> Self {
+        Wrapper {
+            lens,
+            widget,
+            data: None,
+        }
+    }
+}
+
+impl<L, W> BuildHandler for Wrapper<L, W>
+where
+    L: Lens,
+    W: BuildHandler<Ret = Entity> + Bindable<Data = L::Target>,
+{
+    type Ret = Entity;
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        let entity = self.widget.on_build(state, entity);
+
+        state.insert_event(
+            Event::new(BindEvent::<L::Source>::Bind(entity, TypeId::of::<L>()))
+                .target(entity)
+                .origin(entity)
+                .propagate(Propagation::Up),
+        );
+
+        entity
+    }
+}
+
+impl<L, W> EventHandler for Wrapper<L, W>
+where
+    L: Lens,
+    W: EventHandler + Bindable<Data = L::Target>,
+{
+    fn on_event(&mut self, state: &mut State, entity: Entity, event: &mut Event) -> bool {
+        if let Some(bind_event) = event.message.downcast::<BindEvent<L::Source>>() {
+            match bind_event {
+                BindEvent::Update(data, lens) => {
+                    if event.target == entity {
+                        // Only widgets bound to the changed part of the data are updated
+                        if *lens == TypeId::of::<L>() {
+                            self.widget.on_update(state, entity, self.lens.view(data));
+                        }
+
+                        self.data = Some(data.clone());
+                    }
+
+                    return false;
+                }
+
+                _ => {}
+            }
+        }
+
+        let consumed = self.widget.on_event(state, entity, event);
+
+        // Write changes made by the widget back to the model
+        if let Some(value) = self.widget.on_change(state, entity, event) {
+            if let Some(data) = &mut self.data {
+                *self.lens.view_mut(data) = value;
+
+                state.insert_event(
+                    Event::new(BindEvent::Set(data.clone(), TypeId::of::<L>()))
+                        .target(entity)
+                        .origin(entity)
+                        .propagate(Propagation::Up),
+                );
+            }
+        }
+
+        consumed
+    }
+
+    fn on_geometry_changed(
+        &mut self,
+        state: &mut State,
+        entity: Entity,
+        geometry_changed: GeometryChanged,
+    ) {
+        self.widget
+            .on_geometry_changed(state, entity, geometry_changed);
+    }
+
+    fn on_draw(&mut self, state: &mut State, entity: Entity, canvas: &mut Canvas<OpenGl>) {
+        self.widget.on_draw(state, entity, canvas);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EventManager;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Person {
+        name: String,
+        city: String,
+    }
+
+    struct NameLens;
+
+    impl Lens for NameLens {
+        type Source = Person;
+        type Target = String;
+
+        fn view<'a>(&self, source: &'a Person) -> &'a String {
+            &source.name
+        }
+
+        fn view_mut<'a>(&self, source: &'a mut Person) -> &'a mut String {
+            &mut source.name
+        }
+    }
+
+    struct CityLens;
+
+    impl Lens for CityLens {
+        type Source = Person;
+        type Target = String;
+
+        fn view<'a>(&self, source: &'a Person) -> &'a String {
+            &source.city
+        }
+
+        fn view_mut<'a>(&self, source: &'a mut Person) -> &'a mut String {
+            &mut source.city
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum ProbeEvent {
+        Change(String),
+    }
+
+    // Records the values it is updated with and changes the bound data when sent a ProbeEvent
+    struct Probe {
+        updates: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl Probe {
+        fn new(updates: &Rc<RefCell<Vec<String>>>) -> Self {
+            Probe {
+                updates: updates.clone(),
+            }
+        }
+    }
+
+    impl Bindable for Probe {
+        type Data = String;
+
+        fn on_update(&mut self, _state: &mut State, _entity: Entity, data: &Self::Data) {
+            self.updates.borrow_mut().push(data.clone());
+        }
+
+        fn on_change(
+            &mut self,
+            _state: &mut State,
+            entity: Entity,
+            event: &mut Event,
+        ) -> Option<Self::Data> {
+            if event.target == entity {
+                if let Some(ProbeEvent::Change(value)) = event.message.downcast::<ProbeEvent>() {
+                    return Some(value.clone());
+                }
+            }
+
+            None
+        }
+    }
+
+    impl BuildHandler for Probe {
+        type Ret = Entity;
+        fn on_build(&mut self, _state: &mut State, entity: Entity) -> Self::Ret {
+            entity
+        }
+    }
+
+    impl EventHandler for Probe {}
+
+    fn flush(state: &mut State, event_manager: &mut EventManager) {
+        while !state.event_queue.is_empty() {
+            event_manager.flush_events(state);
+        }
+    }
+
+    fn build_model(state: &mut State) -> Entity {
+        let root = state.root;
+        Model::new(Person {
+            name: "Alice".to_string(),
+            city: "Paris".to_string(),
+        })
+        .build(state, root, |builder| builder)
+    }
+
+    #[test]
+    fn only_observers_of_changed_part_are_updated() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let model = build_model(&mut state);
+
+        let name_updates = Rc::new(RefCell::new(Vec::new()));
+        let other_name_updates = Rc::new(RefCell::new(Vec::new()));
+        let city_updates = Rc::new(RefCell::new(Vec::new()));
+
+        let name =
+            Wrapper::new(NameLens, Probe::new(&name_updates))
+                .build(&mut state, model, |builder| builder);
+        Wrapper::new(NameLens, Probe::new(&other_name_updates)).build(
+            &mut state,
+            model,
+            |builder| builder,
+        );
+        Wrapper::new(CityLens, Probe::new(&city_updates))
+            .build(&mut state, model, |builder| builder);
+        flush(&mut state, &mut event_manager);
+
+        state.insert_event(Event::new(ProbeEvent::Change("Bob".to_string())).target(name));
+        flush(&mut state, &mut event_manager);
+
+        // The observer which made the change is not sent it back
+        assert_eq!(*name_updates.borrow(), vec!["Alice".to_string()]);
+        assert_eq!(
+            *other_name_updates.borrow(),
+            vec!["Alice".to_string(), "Bob".to_string()]
+        );
+        assert_eq!(*city_updates.borrow(), vec!["Paris".to_string()]);
+    }
+
+    #[test]
+    fn changes_through_different_lenses_are_kept() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let model = build_model(&mut state);
+
+        let updates = Rc::new(RefCell::new(Vec::new()));
+
+        let name =
+            Wrapper::new(NameLens, Probe::new(&updates))
+                .build(&mut state, model, |builder| builder);
+        let city =
+            Wrapper::new(CityLens, Probe::new(&updates))
+                .build(&mut state, model, |builder| builder);
+        flush(&mut state, &mut event_manager);
+
+        state.insert_event(Event::new(ProbeEvent::Change("Bob".to_string())).target(name));
+        flush(&mut state, &mut event_manager);
+
+        // The city observer must write back the new name along with its own change
+        state.insert_event(Event::new(ProbeEvent::Change("London".to_string())).target(city));
+        flush(&mut state, &mut event_manager);
+
+        let name_updates = Rc::new(RefCell::new(Vec::new()));
+        let city_updates = Rc::new(RefCell::new(Vec::new()));
+        Wrapper::new(NameLens, Probe::new(&name_updates))
+            .build(&mut state, model, |builder| builder);
+        Wrapper::new(CityLens, Probe::new(&city_updates))
+            .build(&mut state, model, |builder| builder);
+        flush(&mut state, &mut event_manager);
+
+        assert_eq!(*name_updates.borrow(), vec!["Bob".to_string()]);
+        assert_eq!(*city_updates.borrow(), vec!["London".to_string()]);
+    }
+
+    #[test]
+    fn bound_label_shows_data_when_model_changes() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let model = build_model(&mut state);
+
+        let updates = Rc::new(RefCell::new(Vec::new()));
+        let name =
+            Wrapper::new(NameLens, Probe::new(&updates))
+                .build(&mut state, model, |builder| builder);
+        // Label only overrides on_update
+        let label =
+            Wrapper::new(NameLens, Label::new("")).build(&mut state, model, |builder| builder);
+        flush(&mut state, &mut event_manager);
+
+        assert_eq!(state.style.text.get(label).unwrap().text, "Alice");
+
+        state.insert_event(Event::new(ProbeEvent::Change("Bob".to_string())).target(name));
+        flush(&mut state, &mut event_manager);
+
+        assert_eq!(state.style.text.get(label).unwrap().text, "Bob");
+    }
+}
@@ -73,7 +73,7 @@ impl BuildHandler for Panel {
         self.checkbox = Element::new().build(state, self.header, |builder| {
             builder
                 .set_text(ICON_DOWN_OPEN_BIG)
-                .set_font("Icons".to_string())
+                .set_font("icons".to_string())
                 .set_text_justify(Justify::Center)
                 .set_text_align(Align::Center)
                 .set_width(Length::Pixels(20.0))
@@ -4,7 +4,7 @@ use crate::entity::Entity;
 use crate::mouse::*;
 
 use crate::{BuildHandler, Event, EventHandler, Propagation, Visibility, WindowEvent};
-use crate::{JustifyContent, Length, PropSet, State};
+use crate::{JustifyContent, Length, PropSet, Role, State};
 
 use crate::widgets::Button;
 
@@ -100,6 +100,7 @@ impl BuildHandler for RadioBox {
         self.marker.set_visibility(state, Visibility::Invisible);
 
         state.style.insert_element(entity, "radio");
+        entity.set_role(state, Role::RadioButton);
 
         entity
     }
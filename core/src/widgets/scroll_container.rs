@@ -14,11 +14,24 @@ pub enum ScrollEvent {
     ScrollV(f32),
 }
 
+// Number of pixels scrolled per line of mouse wheel movement
+const SCROLL_LINE_HEIGHT: f32 = 40.0;
+
+// Adds a mouse wheel delta, scaled by the scroll sensitivity, to the accumulated lines
+// and returns the whole number of lines to scroll, keeping the fractional part for next time
+fn accumulate_lines(accumulated: &mut f32, delta: f32, sensitivity: f32) -> f32 {
+    *accumulated += delta * sensitivity;
+    let lines = accumulated.trunc();
+    *accumulated -= lines;
+    lines
+}
+
 pub struct ScrollContainerH {
     container: Entity,
     horizontal_scroll: Entity,
     //vertical_scroll: Entity,
     scrollx: f32,
+    lines_x: f32,
 
     pressedx: f32,
     pressedy: f32,
@@ -36,6 +49,7 @@ impl ScrollContainerH {
             horizontal_scroll: Entity::null(),
             //vertical_scroll: Entity::null(),
             scrollx: 0.0,
+            lines_x: 0.0,
 
             pressedx: 0.0,
             pressedy: 0.0,
@@ -188,34 +202,22 @@ impl EventHandler for ScrollContainerH {
                     }
                 }
 
-                /*
-                WindowEvent::MouseScroll(_, y) => {
-                    //println!("Mouse Scroll Event");
-                    // Forward mouse scroll event to the scrollbar
-                    // state.insert_event(
-                    //     Event::new(WindowEvent::MouseScroll(*x, *y))
-                    //         .target(self.vertical_scroll)
-                    //         .propagate(Propagation::None),
-                    // );
-
-                    //if event.target == entity {
-
-                    let overflow = state.transform.get_width(entity)
-                        - state.transform.get_width(self.horizontal_scroll);
+                // Scroll the content with the horizontal movement of the mouse wheel
+                WindowEvent::MouseScroll(x, _) => {
+                    let overflow = state.transform.get_width(self.container)
+                        - state.transform.get_width(entity);
 
-                    if overflow == 0.0 {
+                    if overflow <= 0.0 {
                         return false;
                     }
 
-                    // Need better names for these
-                    let overflow = 1.0
-                        - (state.transform.get_width(self.container)
-                            / state.transform.get_width(entity));
-                    let overflow2 = 1.0
-                        - (state.transform.get_width(entity)
-                            / state.transform.get_width(self.container));
+                    let lines = accumulate_lines(&mut self.lines_x, *x, state.scroll_sensitivity);
+
+                    if lines == 0.0 {
+                        return true;
+                    }
 
-                    self.scrollx += (40.0 * *y) / (state.transform.get_width(entity) * overflow);
+                    self.scrollx -= (SCROLL_LINE_HEIGHT * lines) / overflow;
 
                     if self.scrollx < 0.0 {
                         self.scrollx = 0.0;
@@ -225,61 +227,30 @@ impl EventHandler for ScrollContainerH {
                         self.scrollx = 1.0;
                     }
 
-                    //println!("Scroll: {}", self.scrolly);
-
-                    // let mut scrollh = state.transform.get_height(entity) / state.transform.get_height(self.container);
-                    // if scrollh > 1.0 {
-                    //     scrollh = 1.0;
-                    // }
-
-                    let current_scroll_left = state
-                        .style
-                        .left
-                        .get(self.horizontal_scroll)
-                        .cloned()
-                        .unwrap_or_default();
-                    let current_container_left = state
-                        .style
-                        .left
-                        .get(self.container)
-                        .cloned()
-                        .unwrap_or_default();
+                    let overflow = 1.0
+                        - (state.transform.get_width(self.container)
+                            / state.transform.get_width(entity));
+                    let overflow2 = 1.0
+                        - (state.transform.get_width(entity)
+                            / state.transform.get_width(self.container));
 
                     self.container
                         .set_left(state, Length::Percentage(self.scrollx * overflow));
                     self.horizontal_scroll
                         .set_left(state, Length::Percentage(self.scrollx * overflow2));
 
-                    /*
-                    if let Some(animation) = state.style.top.get_animation_mut(self.vertical_scroll_animation) {
-                        *animation.keyframes.first_mut().unwrap() = (0.0, current_scroll_top);
-                        *animation.keyframes.last_mut().unwrap() = (1.0, Length::Percentage(self.scrolly * overflow2));
+                    if let Some(scroll) = state.style.scroll.get_mut(entity) {
+                        scroll.x = self.scrollx;
                     }
 
-                    state.style.top.play_animation(self.vertical_scroll, self.vertical_scroll_animation);
-
+                    state.insert_event(Event::new(WindowEvent::Restyle));
+                    state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+                    state.insert_event(Event::new(WindowEvent::Redraw));
 
-
-
-
-                    if let Some(animation) = state.style.top.get_animation_mut(self.vertical_container_animation) {
-                        *animation.keyframes.first_mut().unwrap() = (0.0, current_container_top);
-                        *animation.keyframes.last_mut().unwrap() = (1.0, Length::Percentage(self.scrolly * overflow));
-                    }
-
-                    state.style.top.play_animation(self.container, self.vertical_container_animation);
-                    */
-
-                    //println!("A: {:?}  B: {:?}", current_container_top, self.scrolly * overflow);
-
-                    //state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-                    //state.insert_event(Event::new(WindowEvent::Redraw));
-                    //}
-
-                    // Capture the event to stop it triggering twice
-                    //return true;
+                    // Consume the event so that any parent scroll containers don't also scroll
+                    return true;
                 }
-                */
+
                 WindowEvent::WindowResize(_, _) => {
                     // let scroll = state
                     //     .style
@@ -394,6 +365,7 @@ pub struct ScrollContainer {
     horizontal_scroll: Entity,
     vertical_scroll: Entity,
     scrolly: f32,
+    lines_y: f32,
 
     pressedx: f32,
     pressedy: f32,
@@ -411,6 +383,7 @@ impl ScrollContainer {
             horizontal_scroll: Entity::null(),
             vertical_scroll: Entity::null(),
             scrolly: 0.0,
+            lines_y: 0.0,
 
             pressedx: 0.0,
             pressedy: 0.0,
@@ -423,6 +396,28 @@ impl ScrollContainer {
     }
 }
 
+impl ScrollContainer {
+    // Moves the content and the scroll indicator to the current scroll position
+    fn update_scroll(&mut self, state: &mut State, entity: Entity) {
+        let overflow =
+            1.0 - (state.transform.get_height(self.container) / state.transform.get_height(entity));
+        let overflow2 =
+            1.0 - (state.transform.get_height(entity) / state.transform.get_height(self.container));
+
+        self.container
+            .set_top(state, Length::Percentage(self.scrolly * overflow));
+        self.vertical_scroll
+            .set_top(state, Length::Percentage(self.scrolly * overflow2));
+
+        if let Some(scroll) = state.style.scroll.get_mut(entity) {
+            scroll.y = self.scrolly;
+        }
+
+        state
+            .insert_event(Event::new(ScrollEvent::ScrollV(self.scrolly * overflow)).target(entity));
+    }
+}
+
 impl BuildHandler for ScrollContainer {
     type Ret = Entity;
     fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
@@ -565,6 +560,12 @@ impl EventHandler for ScrollContainer {
                             Length::Percentage(self.scrolly * overflow2),
                         );
 
+                        // Publish the scroll position and visible ratio for any bound scrollbars
+                        if let Some(scroll) = state.style.scroll.get_mut(entity) {
+                            scroll.y = self.scrolly;
+                            scroll.h = scrollh;
+                        }
+
                         // Relayout and Redraw wont get called automatically so need to manually trigger them
                         state.insert_event(Event::new(WindowEvent::Relayout).origin(entity));
                         //state.insert_event(Event::new(WindowEvent::Redraw));
@@ -572,36 +573,22 @@ impl EventHandler for ScrollContainer {
                     }
                 }
 
-                /*
+                // Scroll the content with the mouse wheel
                 WindowEvent::MouseScroll(_, y) => {
-                    //println!("Mouse Scroll Event");
-                    // Forward mouse scroll event to the scrollbar
-                    // state.insert_event(
-                    //     Event::new(WindowEvent::MouseScroll(*x, *y))
-                    //         .target(self.vertical_scroll)
-                    //         .propagate(Propagation::None),
-                    // );
-
-                    //if event.target == entity {
-
-                    //println!("Height: {}", state.transform.get_height(entity));
+                    let overflow = state.transform.get_height(self.container)
+                        - state.transform.get_height(entity);
 
-                    let overflow = state.transform.get_height(entity)
-                        - state.transform.get_height(self.vertical_scroll);
-
-                    if overflow == 0.0 {
+                    if overflow <= 0.0 {
                         return false;
                     }
 
-                    // Need better names for these
-                    let overflow = 1.0
-                        - (state.transform.get_height(self.container)
-                            / state.transform.get_height(entity));
-                    let overflow2 = 1.0
-                        - (state.transform.get_height(entity)
-                            / state.transform.get_height(self.container));
+                    let lines = accumulate_lines(&mut self.lines_y, *y, state.scroll_sensitivity);
 
-                    self.scrolly += (40.0 * *y) / (state.transform.get_height(entity) * overflow);
+                    if lines == 0.0 {
+                        return true;
+                    }
+
+                    self.scrolly -= (SCROLL_LINE_HEIGHT * lines) / overflow;
 
                     if self.scrolly < 0.0 {
                         self.scrolly = 0.0;
@@ -611,63 +598,16 @@ impl EventHandler for ScrollContainer {
                         self.scrolly = 1.0;
                     }
 
-                    //println!("Scroll: {}", self.scrolly);
+                    self.update_scroll(state, entity);
 
-                    // let mut scrollh = state.transform.get_height(entity) / state.transform.get_height(self.container);
-                    // if scrollh > 1.0 {
-                    //     scrollh = 1.0;
-                    // }
-
-                    let current_scroll_top = state
-                        .style
-                        .top
-                        .get(self.vertical_scroll)
-                        .cloned()
-                        .unwrap_or_default();
-                    let current_container_top = state
-                        .style
-                        .top
-                        .get(self.container)
-                        .cloned()
-                        .unwrap_or_default();
-
-                    self.container
-                        .set_top(state, Length::Percentage(self.scrolly * overflow));
-                    self.vertical_scroll
-                        .set_top(state, Length::Percentage(self.scrolly * overflow2));
-
-                    state.insert_event(Event::new(ScrollEvent::ScrollV(self.scrolly * overflow)).target(entity));
-
-                    /*
-                    if let Some(animation) = state.style.top.get_animation_mut(self.vertical_scroll_animation) {
-                        *animation.keyframes.first_mut().unwrap() = (0.0, current_scroll_top);
-                        *animation.keyframes.last_mut().unwrap() = (1.0, Length::Percentage(self.scrolly * overflow2));
-                    }
-
-                    state.style.top.play_animation(self.vertical_scroll, self.vertical_scroll_animation);
-
-
-
-
-
-                    if let Some(animation) = state.style.top.get_animation_mut(self.vertical_container_animation) {
-                        *animation.keyframes.first_mut().unwrap() = (0.0, current_container_top);
-                        *animation.keyframes.last_mut().unwrap() = (1.0, Length::Percentage(self.scrolly * overflow));
-                    }
+                    state.insert_event(Event::new(WindowEvent::Restyle));
+                    state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+                    state.insert_event(Event::new(WindowEvent::Redraw));
 
-                    state.style.top.play_animation(self.container, self.vertical_container_animation);
-                    */
-
-                    //println!("A: {:?}  B: {:?}", current_container_top, self.scrolly * overflow);
-
-                    //state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
-                    //state.insert_event(Event::new(WindowEvent::Redraw));
-                    //}
-
-                    // Capture the event to stop it triggering twice
-                    //return true;
+                    // Consume the event so that any parent scroll containers don't also scroll
+                    return true;
                 }
-                */
+
                 WindowEvent::WindowResize(_, _) => {
                     // let scroll = state
                     //     .style
@@ -748,22 +688,7 @@ impl EventHandler for ScrollContainer {
                         //self.vertical_scroll
                         //    .set_top(state, Length::Pixels(self.position + dist_y));
 
-                        let overflow = 1.0
-                            - (state.transform.get_height(self.container)
-                                / state.transform.get_height(entity));
-                        let overflow2 = 1.0
-                            - (state.transform.get_height(entity)
-                                / state.transform.get_height(self.container));
-
-                        self.container
-                            .set_top(state, Length::Percentage(self.scrolly * overflow));
-                        self.vertical_scroll
-                            .set_top(state, Length::Percentage(self.scrolly * overflow2));
-
-                        state.insert_event(
-                            Event::new(ScrollEvent::ScrollV(self.scrolly * overflow))
-                                .target(entity),
-                        );
+                        self.update_scroll(state, entity);
 
                         state.insert_event(Event::new(WindowEvent::Restyle));
                         state
@@ -1151,7 +1076,7 @@ impl EventHandler for ScrollContainerHV {
                 },
 
                 WindowEvent::MouseMove(x, y) => {
-                    if self.moving && state.captured == self.vertical_scroll {
+                    if self.moving && state.captured() == self.vertical_scroll {
                         let dist_y = *y - self.pressedy;
                         let overflow = state.transform.get_height(entity)
                             - state.transform.get_height(self.vertical_scroll);
@@ -1206,7 +1131,7 @@ impl EventHandler for ScrollContainerHV {
                         //println!("overflow: {}, dist: {}, ratio: {}, scrolly: {}", overflow, dist_y, r, self.scrolly);
                     }
 
-                    if self.moving && state.captured == self.horizontal_scroll {
+                    if self.moving && state.captured() == self.horizontal_scroll {
                         let dist_x = *x - self.pressedx;
                         let overflow = state.transform.get_width(entity)
                             - state.transform.get_width(self.vertical_scroll);
@@ -1268,3 +1193,107 @@ impl EventHandler for ScrollContainerHV {
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accumulate_lines_keeps_fractional_remainder() {
+        let mut accumulated = 0.0;
+        assert_eq!(accumulate_lines(&mut accumulated, 0.4, 1.0), 0.0);
+        assert_eq!(accumulate_lines(&mut accumulated, 0.4, 1.0), 0.0);
+        assert_eq!(accumulate_lines(&mut accumulated, 0.4, 1.0), 1.0);
+        assert!((accumulated - 0.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn scroll_events_accumulate_with_sensitivity() {
+        let mut state = State::new_headless();
+        let root = state.root;
+        state.scroll_sensitivity = 0.5;
+
+        let entity = state.add(root);
+        let mut scroll_container = ScrollContainer::new();
+        scroll_container.on_build(&mut state, entity);
+
+        // 400px of overflow, so each line scrolls by a tenth
+        state.transform.set_height(entity, 100.0);
+        state
+            .transform
+            .set_height(scroll_container.container, 500.0);
+
+        let scroll = |state: &mut State, scroll_container: &mut ScrollContainer| {
+            let mut event = Event::new(WindowEvent::MouseScroll(0.0, -1.0)).target(entity);
+            scroll_container.on_event(state, entity, &mut event)
+        };
+
+        // Half a line is not enough to scroll
+        assert!(scroll(&mut state, &mut scroll_container));
+        assert_eq!(scroll_container.scrolly, 0.0);
+
+        assert!(scroll(&mut state, &mut scroll_container));
+        assert!((scroll_container.scrolly - 0.1).abs() < 1e-6);
+
+        assert!(scroll(&mut state, &mut scroll_container));
+        assert!(scroll(&mut state, &mut scroll_container));
+        assert!((scroll_container.scrolly - 0.2).abs() < 1e-6);
+        assert_eq!(scroll_container.lines_y, 0.0);
+    }
+
+    #[test]
+    fn shift_turns_vertical_wheel_into_horizontal_delta() {
+        let mut state = State::new_headless();
+        assert_eq!(state.scroll_delta(0.0, -1.0), (0.0, -1.0));
+
+        state.modifiers.shift = true;
+        assert_eq!(state.scroll_delta(0.0, -1.0), (-1.0, 0.0));
+
+        // Horizontal deltas are left alone
+        assert_eq!(state.scroll_delta(1.0, 0.0), (1.0, 0.0));
+
+        // Swapping can be turned off
+        state.shift_scroll = false;
+        assert_eq!(state.scroll_delta(0.0, -1.0), (0.0, -1.0));
+    }
+
+    #[test]
+    fn shift_scroll_moves_horizontal_container() {
+        let mut state = State::new_headless();
+        let mut event_manager = crate::EventManager::new();
+        let root = state.root;
+
+        let flush = |state: &mut State, event_manager: &mut crate::EventManager| {
+            while !state.event_queue.is_empty() {
+                event_manager.flush_events(state);
+            }
+        };
+
+        let frame = Element::new().build(&mut state, root, |builder| {
+            builder
+                .set_width(Length::Pixels(200.0))
+                .set_height(Length::Pixels(100.0))
+        });
+
+        // The content is wider than the scroll container
+        let container = ScrollContainerH::new().build(&mut state, frame, |builder| builder);
+        container.set_width(&mut state, Length::Pixels(600.0));
+
+        flush(&mut state, &mut event_manager);
+
+        let left = |state: &State| state.style.left.get(container).cloned().unwrap_or_default();
+        let start = left(&state);
+
+        // Without shift a vertical wheel doesn't scroll horizontally
+        let (x, y) = state.scroll_delta(0.0, -1.0);
+        state.insert_event(Event::new(WindowEvent::MouseScroll(x, y)).target(container));
+        flush(&mut state, &mut event_manager);
+        assert_eq!(left(&state), start);
+
+        state.modifiers.shift = true;
+        let (x, y) = state.scroll_delta(0.0, -1.0);
+        state.insert_event(Event::new(WindowEvent::MouseScroll(x, y)).target(container));
+        flush(&mut state, &mut event_manager);
+        assert_ne!(left(&state), start);
+    }
+}
@@ -136,7 +136,30 @@ impl EventHandler for Scrollbar {
         if let Some(window_event) = event.message.downcast::<WindowEvent>() {
             match window_event {
                 // When a relayout occurs, determine the new height of the scroll bar
-                WindowEvent::Relayout => {}
+                // from the scroll published by the bound entity (e.g. a scroll container)
+                WindowEvent::Relayout => {
+                    if event.origin != entity {
+                        let scroll = state
+                            .style
+                            .scroll
+                            .get(self.entity)
+                            .cloned()
+                            .unwrap_or_default();
+
+                        let top = Length::Percentage(scroll.y * (1.0 - scroll.h));
+                        let height = Length::Percentage(scroll.h);
+
+                        // Setting it this way avoids calling Restyle automatically
+                        if state.style.top.get(self.front) != Some(&top)
+                            || state.style.height.get(self.front) != Some(&height)
+                        {
+                            state.style.top.insert(self.front, top);
+                            state.style.height.insert(self.front, height);
+
+                            state.insert_event(Event::new(WindowEvent::Relayout).origin(entity));
+                        }
+                    }
+                }
 
                 WindowEvent::WindowResize(_, _) => {
                     let scroll = state
@@ -3,7 +3,7 @@
 use crate::entity::Entity;
 use crate::mouse::*;
 use crate::{BuildHandler, Event, EventHandler, Length, WindowEvent};
-use crate::{PropSet, State};
+use crate::{PropSet, Role, State};
 
 use crate::state::style::*;
 
@@ -57,6 +57,7 @@ impl BuildHandler for Slider {
         });
 
         state.style.insert_element(entity, "slider");
+        entity.set_role(state, Role::Slider);
 
         entity
     }
@@ -302,6 +303,7 @@ impl BuildHandler for Slider2 {
         );
 
         state.style.insert_element(entity, "slider2");
+        entity.set_role(state, Role::Slider);
 
         entity
     }
@@ -0,0 +1,183 @@
+#![allow(dead_code)]
+
+const ICON_DOWN_OPEN_MINI: &str = "\u{e760}";
+const ICON_UP_OPEN_MINI: &str = "\u{e763}";
+
+use crate::entity::Entity;
+use crate::events::*;
+use crate::state::style::*;
+use crate::{PropSet, State, WindowEvent};
+
+use crate::layout::{Align, Justify};
+
+use crate::widgets::{Button, ButtonEvent, Element, Textbox, TextboxEvent};
+
+// Sent by a spinbox to itself, propagating up, when its value changes
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpinboxEvent {
+    Changed(f64),
+}
+
+// A numeric textbox with buttons to step the value up and down
+// Scrolling over the spinbox also steps the value
+pub struct Spinbox {
+    value: f64,
+    step: f64,
+    min: f64,
+    max: f64,
+    // Number of digits shown after the decimal point, 0 shows the value as an integer
+    decimals: usize,
+
+    textbox: Entity,
+    increment: Entity,
+    decrement: Entity,
+}
+
+impl Spinbox {
+    pub fn new(value: f64) -> Self {
+        Spinbox {
+            value,
+            step: 1.0,
+            min: std::f64::MIN,
+            max: std::f64::MAX,
+            decimals: 0,
+
+            textbox: Entity::null(),
+            increment: Entity::null(),
+            decrement: Entity::null(),
+        }
+    }
+
+    // Amount added or subtracted by the buttons and the scroll wheel
+    pub fn with_step(mut self, step: f64) -> Self {
+        self.step = step;
+
+        self
+    }
+
+    // The value is clamped between min and max
+    pub fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.min = min;
+        self.max = max;
+
+        self
+    }
+
+    pub fn with_decimals(mut self, decimals: usize) -> Self {
+        self.decimals = decimals;
+
+        self
+    }
+
+    fn format(&self, value: f64) -> String {
+        format!("{:.*}", self.decimals, value)
+    }
+
+    // Clamps and rounds the value to the shown precision, sending Changed if it is different
+    fn set_value(&mut self, state: &mut State, entity: Entity, value: f64) {
+        let value = self
+            .format(value.max(self.min).min(self.max))
+            .parse::<f64>()
+            .unwrap_or(self.value);
+
+        // The textbox is updated even if the value hasn't changed to replace any invalid text
+        state.insert_event(
+            Event::new(TextboxEvent::SetValue(self.format(value)))
+                .target(self.textbox)
+                .propagate(Propagation::Direct),
+        );
+
+        if value != self.value {
+            self.value = value;
+
+            state.insert_event(
+                Event::new(SpinboxEvent::Changed(value))
+                    .target(entity)
+                    .origin(entity),
+            );
+        }
+    }
+}
+
+impl BuildHandler for Spinbox {
+    type Ret = Entity;
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity
+            .set_display(state, Display::Flexbox)
+            .set_flex_direction(state, FlexDirection::Row);
+
+        self.value = self.value.max(self.min).min(self.max);
+
+        self.textbox = Textbox::new(&self.format(self.value))
+            .build(state, entity, |builder| builder.set_flex_grow(1.0));
+
+        let arrow_container = Element::new().build(state, entity, |builder| {
+            builder
+                .set_width(Length::Pixels(19.0))
+                .set_flex_grow(0.0)
+                .class("arrow_container")
+        });
+
+        self.increment = Button::new().build(state, arrow_container, |builder| {
+            builder
+                .set_font("icons".to_string())
+                .set_text_justify(Justify::Center)
+                .set_text_align(Align::Center)
+                .set_text(ICON_UP_OPEN_MINI)
+                .set_flex_grow(1.0)
+                .class("increment")
+        });
+
+        self.decrement = Button::new().build(state, arrow_container, |builder| {
+            builder
+                .set_font("icons".to_string())
+                .set_text_justify(Justify::Center)
+                .set_text_align(Align::Center)
+                .set_text(ICON_DOWN_OPEN_MINI)
+                .set_flex_grow(1.0)
+                .class("decrement")
+        });
+
+        state.style.insert_element(entity, "spinbox");
+
+        entity
+    }
+}
+
+impl EventHandler for Spinbox {
+    fn on_event(&mut self, state: &mut State, entity: Entity, event: &mut Event) -> bool {
+        if let Some(ButtonEvent::Pressed) = event.message.downcast::<ButtonEvent>() {
+            if event.target == self.increment {
+                self.set_value(state, entity, self.value + self.step);
+                return true;
+            }
+
+            if event.target == self.decrement {
+                self.set_value(state, entity, self.value - self.step);
+                return true;
+            }
+        }
+
+        if let Some(WindowEvent::MouseScroll(_, y)) = event.message.downcast::<WindowEvent>() {
+            if *y > 0.0 {
+                self.set_value(state, entity, self.value + self.step);
+            } else if *y < 0.0 {
+                self.set_value(state, entity, self.value - self.step);
+            }
+
+            return true;
+        }
+
+        if let Some(TextboxEvent::ValueChanged(text)) = event.message.downcast::<TextboxEvent>() {
+            if event.target == self.textbox {
+                // Invalid text is replaced with the current value
+                let value = text.trim().parse::<f64>().unwrap_or(self.value);
+                self.set_value(state, entity, value);
+
+                return true;
+            }
+        }
+
+        false
+    }
+}
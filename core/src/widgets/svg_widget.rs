@@ -0,0 +1,185 @@
+use crate::widgets::*;
+use crate::{draw_background, fit_image, ImageFit, Rect, Role};
+
+use femtovg::{renderer::OpenGl, Canvas, FillRule, Paint, Path};
+
+use usvg::NodeExt;
+
+// A path of an SVG converted for drawing with femtovg
+struct SvgPath {
+    path: Path,
+    fill: Option<Paint>,
+    stroke: Option<Paint>,
+}
+
+// Draws an SVG file scaled to fit the content box, keeping its aspect ratio, styled with the "svg" selector
+// The SVG is drawn as paths so it stays sharp at any size and scale factor
+// Requires the svg feature
+pub struct Svg {
+    tree: Option<usvg::Tree>,
+    // The paths for the last size and opacity drawn, converted again when either changes
+    cache: Option<(f32, f32, f32, Vec<SvgPath>)>,
+}
+
+impl Svg {
+    pub fn new(path: &str) -> Self {
+        let tree = match usvg::Tree::from_file(path, &usvg::Options::default()) {
+            Ok(tree) => Some(tree),
+
+            Err(e) => {
+                println!("Error loading svg {}: {:?}", path, e);
+                None
+            }
+        };
+
+        Svg { tree, cache: None }
+    }
+}
+
+impl BuildHandler for Svg {
+    type Ret = Entity;
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity
+            .set_element(state, "svg")
+            .set_role(state, Role::Image);
+
+        entity
+    }
+}
+
+impl EventHandler for Svg {
+    fn on_draw(&mut self, state: &mut State, entity: Entity, canvas: &mut Canvas<OpenGl>) {
+        if !draw_background(state, entity, canvas) {
+            return;
+        }
+
+        let content = state.content_box(entity);
+        let opacity = state.transform.get_opacity(entity);
+
+        if let Some(tree) = &self.tree {
+            let cached = match &self.cache {
+                Some((width, height, cached_opacity, _)) => {
+                    *width == content.w && *height == content.h && *cached_opacity == opacity
+                }
+
+                None => false,
+            };
+
+            if !cached {
+                let paths = convert_paths(tree, content.w, content.h, opacity);
+                self.cache = Some((content.w, content.h, opacity, paths));
+            }
+        }
+
+        if let Some((_, _, _, paths)) = &mut self.cache {
+            // The paths are converted relative to the content box so only moving the widget doesn't convert them again
+            canvas.translate(content.x, content.y);
+
+            for svg_path in paths.iter_mut() {
+                if let Some(fill) = &svg_path.fill {
+                    canvas.fill_path(&mut svg_path.path, fill);
+                }
+
+                if let Some(stroke) = &svg_path.stroke {
+                    canvas.stroke_path(&mut svg_path.path, stroke);
+                }
+            }
+        }
+
+        canvas.restore();
+    }
+}
+
+// Converts the paths of an SVG, scaled and centered to fit within a box of the given size
+fn convert_paths(tree: &usvg::Tree, width: f32, height: f32, opacity: f32) -> Vec<SvgPath> {
+    let view_box = tree.svg_node().view_box.rect;
+    let bounds = fit_image(
+        ImageFit::Contain,
+        view_box.width() as f32,
+        view_box.height() as f32,
+        Rect::new(0.0, 0.0, width, height),
+    );
+    let scale = bounds.w / view_box.width() as f32;
+
+    let point = |transform: &usvg::Transform, x: f64, y: f64| {
+        let (x, y) = transform.apply(x, y);
+        (
+            bounds.x + (x - view_box.x()) as f32 * scale,
+            bounds.y + (y - view_box.y()) as f32 * scale,
+        )
+    };
+
+    // Gradients and patterns aren't supported and are drawn as nothing
+    let color = |paint: &usvg::Paint, alpha: f64| match paint {
+        usvg::Paint::Color(color) => {
+            let mut color = femtovg::Color::rgb(color.red, color.green, color.blue);
+            color.set_alphaf(alpha as f32 * opacity);
+            Some(color)
+        }
+
+        _ => None,
+    };
+
+    let mut paths = Vec::new();
+
+    for node in tree.root().descendants() {
+        if let usvg::NodeKind::Path(svg_path) = &*node.borrow() {
+            let transform = node.abs_transform();
+
+            let mut path = Path::new();
+            for segment in svg_path.data.0.iter() {
+                match *segment {
+                    usvg::PathSegment::MoveTo { x, y } => {
+                        let (x, y) = point(&transform, x, y);
+                        path.move_to(x, y);
+                    }
+
+                    usvg::PathSegment::LineTo { x, y } => {
+                        let (x, y) = point(&transform, x, y);
+                        path.line_to(x, y);
+                    }
+
+                    usvg::PathSegment::CurveTo {
+                        x1,
+                        y1,
+                        x2,
+                        y2,
+                        x,
+                        y,
+                    } => {
+                        let (x1, y1) = point(&transform, x1, y1);
+                        let (x2, y2) = point(&transform, x2, y2);
+                        let (x, y) = point(&transform, x, y);
+                        path.bezier_to(x1, y1, x2, y2, x, y);
+                    }
+
+                    usvg::PathSegment::ClosePath => path.close(),
+                }
+            }
+
+            let fill = svg_path.fill.as_ref().and_then(|fill| {
+                color(&fill.paint, fill.opacity.value()).map(|color| {
+                    let mut paint = Paint::color(color);
+                    paint.set_anti_alias(true);
+                    if fill.rule == usvg::FillRule::EvenOdd {
+                        paint.set_fill_rule(FillRule::EvenOdd);
+                    }
+                    paint
+                })
+            });
+
+            let stroke = svg_path.stroke.as_ref().and_then(|stroke| {
+                color(&stroke.paint, stroke.opacity.value()).map(|color| {
+                    let mut paint = Paint::color(color);
+                    paint.set_anti_alias(true);
+                    paint.set_line_width(stroke.width.value() as f32 * scale);
+                    paint
+                })
+            });
+
+            paths.push(SvgPath { path, fill, stroke });
+        }
+    }
+
+    paths
+}
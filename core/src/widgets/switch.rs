@@ -3,47 +3,65 @@
 use crate::entity::Entity;
 use crate::mouse::*;
 
-use crate::{BuildHandler, Event, EventHandler, WindowEvent};
-use crate::{PropSet, State};
+use crate::{BuildHandler, Event, EventHandler, Propagation, WindowEvent};
+use crate::{PropSet, Role, State};
 
 use crate::state::style::*;
 
 use crate::widgets::Element;
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SwitchEvent {
+    // Sent up the tree from the switch when it is toggled, with the new state
+    Toggled(bool),
+    // Received by the switch to change its state without sending Toggled
+    Check,
+    Uncheck,
+}
+
+// An on/off switch with a thumb which slides between the ends of a track
+// The track is styled with the checked and disabled pseudo-classes of the "switch" selector, and the thumb with "switch>.front"
+// The default theme animates the thumb with a transition on left
 #[derive(Clone)]
 pub struct Switch {
     front: Entity,
-    //on_press: Option<Event>,
     checked: bool,
+    disabled: bool,
 }
 
 impl Switch {
     pub fn new(checked: bool) -> Self {
         Switch {
             front: Entity::null(),
-            //on_press: None,
             checked,
+            disabled: false,
         }
     }
 
-    // pub fn on_press(mut self, message: Event) -> Self {
-    //     self.on_press = Some(message);
-    //     self
-    // }
+    // A disabled switch ignores clicks, it can still be changed with SwitchEvent::Check and SwitchEvent::Uncheck
+    pub fn disabled(mut self, flag: bool) -> Self {
+        self.disabled = flag;
+
+        self
+    }
+
+    fn set_checked(&mut self, state: &mut State, entity: Entity, checked: bool) {
+        self.checked = checked;
+        entity.set_checked(state, checked);
+    }
 }
 
 impl BuildHandler for Switch {
     type Ret = Entity;
     fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
-        self.front = Element::new().build(state, entity, |builder| {
-            builder.class("front")
-            //.set_height(Length::Percentage(1.0))
-            //.set_width(Length::Percentage(0.5))
-            //.set_left(Length::Pixels(0.0))
-            //.set_top(Length::Pixels(0.0))
-        });
+        self.front = Element::new().build(state, entity, |builder| builder.class("front"));
 
         state.style.insert_element(entity, "switch");
+        entity.set_role(state, Role::Switch);
+
+        entity
+            .set_checked(state, self.checked)
+            .set_disabled(state, self.disabled);
 
         entity
     }
@@ -51,39 +69,38 @@ impl BuildHandler for Switch {
 
 impl EventHandler for Switch {
     fn on_event(&mut self, state: &mut State, entity: Entity, event: &mut Event) -> bool {
+        if let Some(switch_event) = event.message.downcast::<SwitchEvent>() {
+            if event.target == entity {
+                match switch_event {
+                    SwitchEvent::Check => self.set_checked(state, entity, true),
+                    SwitchEvent::Uncheck => self.set_checked(state, entity, false),
+                    SwitchEvent::Toggled(_) => {}
+                }
+            }
+        }
+
         if let Some(window_event) = event.message.downcast::<WindowEvent>() {
-            //println!("received window event: {:?}", window_event);
             match window_event {
-                WindowEvent::MouseUp(button) => match button {
-                    MouseButton::Left => {
-                        if entity == event.target || self.front == event.target {
-                            if self.checked {
-                                //self.front.set_justification(state, Justification::Start);
-                                //self.front.set_left(state, Length::Percentage(0.0));
-                                self.checked = false;
-                                entity.set_checked(state, false);
-                            } else {
-                                //self.front.set_justification(state, Justification::End);
-                                //self.front.set_left(state, Length::Percentage(0.5));
-                                self.checked = true;
-                                entity.set_checked(state, true);
-                            }
-
-                            // state.insert_event(
-                            //     Event::new(WindowEvent::Restyle).target(Entity::new(0, 0)),
-                            // );
-
-                            // if let Some(mut on_press) = self.on_press.clone() {
-                            //     on_press.target = entity;
-                            //     state.insert_event(on_press);
-                            // }
-
+                WindowEvent::MouseUp(MouseButton::Left) => {
+                    if entity == event.target || self.front == event.target {
+                        // The disabled pseudo-class can also be set directly on the entity
+                        if entity.is_disabled(state) {
                             return true;
                         }
-                    }
 
-                    _ => {}
-                },
+                        let checked = !self.checked;
+                        self.set_checked(state, entity, checked);
+
+                        state.insert_event(
+                            Event::new(SwitchEvent::Toggled(checked))
+                                .target(entity)
+                                .origin(entity)
+                                .propagate(Propagation::Up),
+                        );
+
+                        return true;
+                    }
+                }
 
                 _ => {}
             }
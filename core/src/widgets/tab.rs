@@ -2,17 +2,19 @@
 
 use crate::state::{Entity, State};
 
-use crate::events::{BuildHandler, Event, EventHandler};
+use crate::events::{BuildHandler, Event, EventHandler, Propagation};
 
-use crate::widgets::Element;
+use crate::widgets::{Button, ButtonEvent, Element};
 
-use crate::{IntoChildIterator, WindowEvent};
+use crate::{HierarchyTree, IntoChildIterator, PropSet, WindowEvent};
 
 use crate::state::style::*;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum TabEvent {
     SwitchTab(usize),
+    // Emitted by a tab view when the selected tab changes
+    TabSelected(usize),
 }
 
 //impl Message for TabEvent {}
@@ -93,6 +95,8 @@ impl EventHandler for TabBar {
                         state.insert_event(Event::new(WindowEvent::Relayout));
                     }
                 }
+
+                _ => {}
             }
         }
 
@@ -193,9 +197,159 @@ impl EventHandler for TabContainer {
                         tab.set_display(state, Display::Flexbox);
                     }
                 }
+
+                _ => {}
+            }
+        }
+
+        false
+    }
+}
+
+// A header row of tab buttons above a content area with one panel per tab
+// Only the panel of the selected tab is displayed and the selected tab button is checked
+pub struct TabView {
+    header: Entity,
+    content: Entity,
+}
+
+impl TabView {
+    pub fn new() -> Self {
+        TabView {
+            header: Entity::null(),
+            content: Entity::null(),
+        }
+    }
+
+    // Adds a tab to a built tab view and returns the panel to add the tab contents to
+    // The first tab added is selected
+    pub fn add_tab(state: &mut State, tab_view: Entity, name: &str) -> Entity {
+        let (header, content) = match Self::parts(state, tab_view) {
+            Some(parts) => parts,
+            None => return Entity::null(),
+        };
+
+        Button::with_label(name).build(state, header, |builder| builder.class("tab"));
+
+        let panel = Element::new().build(state, content, |builder| {
+            builder.set_flex_grow(1.0).class("tab_panel")
+        });
+
+        if state.hierarchy.get_num_children(content) == 1 {
+            select_tab(state, header, content, 0);
+        } else {
+            panel.set_display(state, Display::None);
+        }
+
+        panel
+    }
+
+    // Removes the tab with the given index, selecting a neighbouring tab if it was selected
+    pub fn remove_tab(state: &mut State, tab_view: Entity, index: usize) {
+        let (header, content) = match Self::parts(state, tab_view) {
+            Some(parts) => parts,
+            None => return,
+        };
+
+        if let (Some(tab), Some(panel)) = (
+            state.hierarchy.get_child(header, index),
+            state.hierarchy.get_child(content, index),
+        ) {
+            let was_selected = tab.is_checked(state);
+
+            state.remove(tab);
+            state.remove(panel);
+
+            let num_tabs = state.hierarchy.get_num_children(header) as usize;
+            if was_selected && num_tabs > 0 {
+                select_tab(state, header, content, index.min(num_tabs - 1));
+            }
+        }
+    }
+
+    fn parts(state: &State, tab_view: Entity) -> Option<(Entity, Entity)> {
+        Some((
+            state.hierarchy.get_child(tab_view, 0)?,
+            state.hierarchy.get_child(tab_view, 1)?,
+        ))
+    }
+}
+
+fn select_tab(state: &mut State, header: Entity, content: Entity, index: usize) {
+    let tabs: Vec<Entity> = header.child_iter(&state.hierarchy).collect();
+    for (i, tab) in tabs.into_iter().enumerate() {
+        tab.set_checked(state, i == index);
+    }
+
+    let panels: Vec<Entity> = content.child_iter(&state.hierarchy).collect();
+    for (i, panel) in panels.into_iter().enumerate() {
+        if i == index {
+            panel.set_display(state, Display::Flexbox);
+        } else {
+            panel.set_display(state, Display::None);
+        }
+    }
+
+    state.insert_event(Event::new(WindowEvent::Restyle));
+    state.insert_event(Event::new(WindowEvent::Relayout));
+}
+
+impl BuildHandler for TabView {
+    type Ret = Entity;
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity.set_flex_direction(state, FlexDirection::Column);
+
+        self.header = Element::new().build(state, entity, |builder| {
+            builder
+                .set_flex_direction(FlexDirection::Row)
+                .class("tab_bar")
+        });
+
+        self.content = Element::new().build(state, entity, |builder| {
+            builder.set_flex_grow(1.0).class("tab_content")
+        });
+
+        state.style.insert_element(entity, "tab_view");
+
+        entity
+    }
+}
+
+impl EventHandler for TabView {
+    fn on_event(&mut self, state: &mut State, entity: Entity, event: &mut Event) -> bool {
+        let mut selected = None;
+
+        if let Some(TabEvent::SwitchTab(index)) = event.message.downcast::<TabEvent>() {
+            if event.target == entity {
+                selected = Some(*index);
             }
         }
 
+        // Pressing a tab button selects its tab
+        if let Some(ButtonEvent::Pressed) = event.message.downcast::<ButtonEvent>() {
+            if event.target.parent(&state.hierarchy) == Some(self.header) {
+                let tab = event.target;
+                selected = self
+                    .header
+                    .child_iter(&state.hierarchy)
+                    .position(|child| child == tab);
+            }
+        }
+
+        if let Some(index) = selected {
+            if index < state.hierarchy.get_num_children(self.header) as usize {
+                select_tab(state, self.header, self.content, index);
+
+                state.insert_event(
+                    Event::new(TabEvent::TabSelected(index))
+                        .target(entity)
+                        .propagate(Propagation::Up),
+                );
+            }
+
+            return true;
+        }
+
         false
     }
 }
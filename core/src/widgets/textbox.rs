@@ -3,7 +3,10 @@
 use crate::entity::Entity;
 use crate::events::*;
 use crate::mouse::*;
-use crate::{BuildHandler, Justify, Length, PropSet, State, Visibility, WindowEvent};
+use crate::{
+    draw_background, draw_text_offset, intersect_scissor, BuildHandler, CursorIcon, PropSet, Rect,
+    Role, State, WindowEvent, DEFAULT_CARET_COLOR, DEFAULT_SELECTION_COLOR,
+};
 
 use glutin::event::VirtualKeyCode;
 
@@ -14,6 +17,9 @@ use femtovg::{
 
 use crate::Key;
 
+// Character drawn in place of each character of a password
+pub const PASSWORD_MASK: char = '\u{2022}';
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum TextboxEvent {
     SetValue(String),
@@ -38,6 +44,22 @@ pub struct Textbox {
     edit: bool,
     hitx: f32,
     dragx: f32,
+
+    // Set by a double click to select the word under the cursor on the next draw
+    select_word: bool,
+
+    // Horizontal offset of the text which keeps the caret visible when the text is wider than the textbox
+    scroll_x: f32,
+
+    // Drawn in place of each character when set, the real text is still used for the value
+    password: Option<char>,
+
+    // Maximum number of characters which can be typed into the textbox
+    max_length: Option<usize>,
+
+    // Text being composed by an input method, drawn at the caret until it is committed
+    preedit: String,
+    preedit_cursor: Option<usize>,
 }
 
 impl Textbox {
@@ -61,6 +83,12 @@ impl Textbox {
             edit: false,
             hitx: -1.0,
             dragx: -1.0,
+            select_word: false,
+            scroll_x: 0.0,
+            password: None,
+            max_length: None,
+            preedit: String::new(),
+            preedit_cursor: None,
         }
     }
 
@@ -70,6 +98,45 @@ impl Textbox {
         self
     }
 
+    // Hides the text by drawing the mask character, such as PASSWORD_MASK, in place of each character
+    pub fn with_password(mut self, mask: char) -> Self {
+        self.password = Some(mask);
+
+        self
+    }
+
+    // Limits the number of characters, input which would exceed the limit is ignored
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+
+        self
+    }
+
+    // Replaces the selection with the text, moving the caret to the end of the inserted text
+    fn insert_text(&mut self, state: &mut State, entity: Entity, input: &str) {
+        let start = std::cmp::min(self.select_pos, self.cursor_pos) as usize;
+        let end = std::cmp::max(self.select_pos, self.cursor_pos) as usize;
+        let count = input.chars().count();
+
+        if let Some(txt) = state.style.text.get_mut(entity) {
+            // Ignore the input if it would take the text over the maximum length,
+            // taking into account the selected characters which it replaces
+            if let Some(max_length) = self.max_length {
+                if txt.text.chars().count().saturating_sub(end - start) + count > max_length {
+                    return;
+                }
+            }
+
+            let range = byte_index(&txt.text, start)..byte_index(&txt.text, end);
+            txt.text.replace_range(range, input);
+        }
+
+        self.cursor_pos = (start + count) as u32;
+        self.select_pos = self.cursor_pos;
+
+        state.insert_event(Event::new(WindowEvent::Redraw));
+    }
+
     // pub fn set_enabled(&self, state: &mut WidgetState, val: bool) {
     //     if val {
     //         self.id
@@ -89,11 +156,40 @@ impl BuildHandler for Textbox {
         self.entity = entity;
 
         state.style.insert_element(entity, "textbox");
+        entity.set_role(state, Role::Textbox);
+
+        entity.set_cursor(state, CursorIcon::Text);
 
         entity
     }
 }
 
+impl Bindable for Textbox {
+    type Data = String;
+
+    fn on_update(&mut self, state: &mut State, entity: Entity, data: &Self::Data) {
+        entity.set_text(state, &(data.to_owned() + &self.units));
+
+        state.insert_event(Event::new(WindowEvent::Redraw));
+    }
+
+    fn on_change(
+        &mut self,
+        _state: &mut State,
+        entity: Entity,
+        event: &mut Event,
+    ) -> Option<Self::Data> {
+        if event.target == entity {
+            if let Some(TextboxEvent::ValueChanged(text)) = event.message.downcast::<TextboxEvent>()
+            {
+                return Some(text.clone());
+            }
+        }
+
+        None
+    }
+}
+
 impl EventHandler for Textbox {
     fn on_event(&mut self, state: &mut State, entity: Entity, event: &mut Event) -> bool {
         if let Some(textbox_event) = event.message.downcast::<TextboxEvent>() {
@@ -137,8 +233,13 @@ impl EventHandler for Textbox {
 
                 WindowEvent::MouseDown(button) => {
                     if entity == state.hovered {
+                        // Only the left button edits the text, other buttons are left to propagate
+                        if *button != MouseButton::Left {
+                            return false;
+                        }
+
                         if self.edit == false && !entity.is_disabled(state) {
-                            self.cursor_pos = text_data.text.len() as u32;
+                            self.cursor_pos = text_data.text.chars().count() as u32;
                             self.select_pos = 0;
                             self.buffer = text_data.text.clone();
                             state.focused = entity;
@@ -150,6 +251,7 @@ impl EventHandler for Textbox {
                         if self.edit == true {
                             self.hitx = state.mouse.cursorx;
                             self.dragx = state.mouse.cursorx;
+                            state.reset_caret_blink();
                         }
                         //self.edit = true;
 
@@ -159,7 +261,12 @@ impl EventHandler for Textbox {
 
                         state.insert_event(Event::new(WindowEvent::Redraw));
                     } else {
+                        if self.edit {
+                            state.stop_caret_blink();
+                        }
+
                         self.edit = false;
+                        self.preedit.clear();
                         entity.set_active(state, false);
 
                         state.insert_event(
@@ -173,7 +280,7 @@ impl EventHandler for Textbox {
 
                         state.insert_event(Event::new(WindowEvent::Redraw));
 
-                        if state.captured == entity {
+                        if state.captured() == entity {
                             state.insert_event(
                                 Event::new(WindowEvent::MouseDown(*button)).target(state.hovered),
                             );
@@ -188,12 +295,25 @@ impl EventHandler for Textbox {
                     }
                 }
 
+                WindowEvent::MouseDoubleClick(button) => {
+                    if *button == MouseButton::Left && entity == state.hovered && self.edit {
+                        self.select_word = true;
+                        state.insert_event(Event::new(WindowEvent::Redraw));
+                    }
+                }
+
                 WindowEvent::MouseUp(_) => {
                     self.hitx = -1.0;
                 }
 
-                WindowEvent::KeyDown(code, key) => {
+                // Held arrows and backspace repeat, while enter and escape only act on the first press
+                WindowEvent::KeyDown(code, key, repeat) => {
                     println!("Code: {:?} Key: {:?}", code, key);
+                    // The caret stays visible while typing
+                    if self.edit {
+                        state.reset_caret_blink();
+                    }
+
                     if *key == Some(Key::ArrowLeft) {
                         if self.edit {
                             self.hitx = -1.0;
@@ -215,7 +335,7 @@ impl EventHandler for Textbox {
                     if *key == Some(Key::ArrowRight) {
                         if self.edit {
                             self.hitx = -1.0;
-                            if self.cursor_pos < text_data.text.len() as u32 {
+                            if self.cursor_pos < text_data.text.chars().count() as u32 {
                                 self.cursor_pos += 1;
                             }
                             if !state.modifiers.shift {
@@ -237,14 +357,17 @@ impl EventHandler for Textbox {
                             //let end = text_data.cursor_pos as usize;
                             if start == end && self.cursor_pos > 0 {
                                 if let Some(txt) = state.style.text.get_mut(entity) {
-                                    txt.text.remove((self.cursor_pos - 1) as usize);
+                                    let index = byte_index(&txt.text, self.cursor_pos as usize - 1);
+                                    txt.text.remove(index);
                                 }
 
                                 self.cursor_pos -= 1;
                                 self.select_pos -= 1;
                             } else {
                                 if let Some(txt) = state.style.text.get_mut(entity) {
-                                    txt.text.replace_range(start..end, "");
+                                    let range =
+                                        byte_index(&txt.text, start)..byte_index(&txt.text, end);
+                                    txt.text.replace_range(range, "");
                                 }
                                 self.cursor_pos = start as u32;
                                 self.select_pos = start as u32;
@@ -257,7 +380,7 @@ impl EventHandler for Textbox {
                             state.insert_event(Event::new(WindowEvent::Redraw));
                         }
                     }
-                    if *key == Some(Key::Enter) {
+                    if *key == Some(Key::Enter) && !*repeat {
                         if self.edit {
                             //text_data.buffer = text_data.text.clone();
                             state.insert_event(
@@ -266,9 +389,11 @@ impl EventHandler for Textbox {
                             );
 
                             self.edit = false;
+                            self.preedit.clear();
                             entity.set_active(state, false);
                             state.focused = Entity::new(0, 0);
-                            state.captured = Entity::null();
+                            state.release(entity);
+                            state.stop_caret_blink();
 
                             // state.insert_event(
                             //     Event::new(WindowEvent::Restyle).target(Entity::new(0, 0)),
@@ -277,11 +402,13 @@ impl EventHandler for Textbox {
                             state.insert_event(Event::new(WindowEvent::Redraw));
                         }
                     }
-                    if *key == Some(Key::Escape) {
+                    if *key == Some(Key::Escape) && !*repeat {
                         if self.edit {
                             self.text = self.buffer.clone();
                             self.edit = false;
+                            self.preedit.clear();
                             entity.set_active(state, false);
+                            state.stop_caret_blink();
 
                             // state.insert_event(
                             //     Event::new(WindowEvent::Restyle).target(Entity::new(0, 0)),
@@ -295,32 +422,27 @@ impl EventHandler for Textbox {
                 WindowEvent::CharInput(input) => {
                     if *input as u8 != 8 && *input as u8 != 13 {
                         if self.edit {
-                            let start = std::cmp::min(self.select_pos, self.cursor_pos) as usize;
-                            let end = std::cmp::max(self.select_pos, self.cursor_pos) as usize;
-                            //let start = text_data.select_pos as usize;
-                            //let end = text_data.cursor_pos as usize;
-                            if start == end {
-                                if let Some(txt) = state.style.text.get_mut(entity) {
-                                    txt.text.insert(start, *input);
-                                }
+                            state.reset_caret_blink();
+                            self.insert_text(state, entity, &input.to_string());
+                        }
+                    }
+                }
 
-                                //text_data.text.remove((text_data.cursor_pos - 1) as usize);
-                                self.cursor_pos += 1;
-                                self.select_pos += 1;
-                            } else {
-                                if let Some(txt) = state.style.text.get_mut(entity) {
-                                    txt.text.replace_range(start..end, &input.to_string());
-                                }
-                                self.cursor_pos = (start + 1) as u32;
-                                self.select_pos = (start + 1) as u32;
-                            }
+                WindowEvent::ImePreedit(text, cursor) => {
+                    if self.edit {
+                        state.reset_caret_blink();
+                        self.preedit = text.clone();
+                        self.preedit_cursor = *cursor;
 
-                            // state.insert_event(
-                            //     Event::new(WindowEvent::Restyle).target(Entity::new(0, 0)),
-                            // );
+                        state.insert_event(Event::new(WindowEvent::Redraw));
+                    }
+                }
 
-                            state.insert_event(Event::new(WindowEvent::Redraw));
-                        }
+                WindowEvent::ImeCommit(text) => {
+                    if self.edit {
+                        state.reset_caret_blink();
+                        self.preedit.clear();
+                        self.insert_text(state, entity, text);
                     }
                 }
 
@@ -331,433 +453,258 @@ impl EventHandler for Textbox {
         false
     }
 
-    fn on_draw(
-        &mut self,
-        state: &mut State,
-        entity: Entity,
-        canvas: &mut Canvas<OpenGl>,
-        //images: &HashMap<String, nanovg::Image>,
-    ) {
-        // Skip window
-        if entity == Entity::new(0, 0) {
-            return;
-        }
-
-        // Skip invisible widgets
-        if state.transform.get_visibility(entity) == Visibility::Invisible {
-            return;
-        }
-
-        if state.transform.get_opacity(entity) == 0.0 {
+    fn on_draw(&mut self, state: &mut State, entity: Entity, canvas: &mut Canvas<OpenGl>) {
+        if !draw_background(state, entity, canvas) {
             return;
         }
 
         let posx = state.transform.get_posx(entity);
         let posy = state.transform.get_posy(entity);
         let width = state.transform.get_width(entity);
-        let height = state.transform.get_height(entity);
-
-        //println!("entity: {} posx: {} posy: {} width: {} height: {}", entity, posx, posy, width, height);
-
-        let padding_left = match state
-            .style
-            .padding_left
-            .get(entity)
-            .unwrap_or(&Length::Auto)
-        {
-            Length::Pixels(val) => val,
-            _ => &0.0,
-        };
-
-        let padding_right = match state
-            .style
-            .padding_right
-            .get(entity)
-            .unwrap_or(&Length::Auto)
-        {
-            Length::Pixels(val) => val,
-            _ => &0.0,
-        };
-
-        let padding_top = match state.style.padding_top.get(entity).unwrap_or(&Length::Auto) {
-            Length::Pixels(val) => val,
-            _ => &0.0,
-        };
+        let content = state.content_box(entity);
+        let text = state.style.text.get(entity).cloned().unwrap_or_default();
+
+        // Scrolled text is clipped to the textbox
+        intersect_scissor(
+            state,
+            canvas,
+            Rect::new(posx, posy, width, state.transform.get_height(entity)),
+        );
 
-        let padding_bottom = match state
-            .style
-            .padding_bottom
-            .get(entity)
-            .unwrap_or(&Length::Auto)
-        {
-            Length::Pixels(val) => val,
-            _ => &0.0,
-        };
+        if !self.edit {
+            self.scroll_x = 0.0;
+        }
 
-        let background_color = state
-            .style
-            .background_color
-            .get(entity)
-            .cloned()
-            .unwrap_or_default();
-
-        let font_color = state
-            .style
-            .font_color
-            .get(entity)
-            .cloned()
-            .unwrap_or(crate::Color::rgb(255, 255, 255));
-
-        let border_color = state
-            .style
-            .border_color
-            .get(entity)
-            .cloned()
-            .unwrap_or_default();
-
-        let shadow_color = state
-            .style
-            .shadow_color
-            .get(entity)
-            .cloned()
-            .unwrap_or_default();
-
-        let parent = state
-            .hierarchy
-            .get_parent(entity)
-            .expect("Failed to find parent somehow");
-
-        let parent_width = state.transform.get_width(parent);
-
-        let border_radius_top_left = match state
-            .style
-            .border_radius_top_left
-            .get(entity)
-            .cloned()
-            .unwrap_or_default()
-        {
-            Length::Pixels(val) => val,
-            Length::Percentage(val) => parent_width * val,
-            _ => 0.0,
+        // The masked text and the text being composed by an input method are only swapped in
+        // while drawing so the caret and selection are positioned from the drawn glyphs
+        let composing = self.edit && !self.preedit.is_empty();
+        let mut display_text = match self.password {
+            Some(mask) => text.text.chars().map(|_| mask).collect(),
+            None => text.text.clone(),
         };
 
-        let border_radius_top_right = match state
-            .style
-            .border_radius_top_right
-            .get(entity)
-            .cloned()
-            .unwrap_or_default()
-        {
-            Length::Pixels(val) => val,
-            Length::Percentage(val) => parent_width * val,
-            _ => 0.0,
-        };
+        if composing {
+            let index = byte_index(&display_text, self.cursor_pos as usize);
+            display_text.insert_str(index, &self.preedit);
+        }
 
-        let border_radius_bottom_left = match state
-            .style
-            .border_radius_bottom_left
-            .get(entity)
-            .cloned()
-            .unwrap_or_default()
-        {
-            Length::Pixels(val) => val,
-            Length::Percentage(val) => parent_width * val,
-            _ => 0.0,
-        };
+        let real_text = match state.style.text.get_mut(entity) {
+            Some(data) if data.text != display_text => {
+                Some(std::mem::replace(&mut data.text, display_text.clone()))
+            }
 
-        let border_radius_bottom_right = match state
-            .style
-            .border_radius_bottom_right
-            .get(entity)
-            .cloned()
-            .unwrap_or_default()
-        {
-            Length::Pixels(val) => val,
-            Length::Percentage(val) => parent_width * val,
-            _ => 0.0,
+            _ => None,
         };
 
-        let opacity = state.transform.get_opacity(entity);
-
-        let mut background_color: femtovg::Color = background_color.into();
-        background_color.set_alphaf(background_color.a * opacity);
-
-        let mut border_color: femtovg::Color = border_color.into();
-        border_color.set_alphaf(border_color.a * opacity);
-
-        let mut shadow_color: femtovg::Color = shadow_color.into();
-        shadow_color.set_alphaf(shadow_color.a * opacity);
+        // Glyph indices of the composed text and of the caret within it
+        let preedit_start = self.cursor_pos as usize;
+        let preedit_end = preedit_start + self.preedit.chars().count();
+        let preedit_caret = preedit_start + self.preedit_cursor.unwrap_or(0);
+        let text_len = display_text.chars().count();
 
-        let border_width = match state
-            .style
-            .border_width
-            .get(entity)
-            .cloned()
-            .unwrap_or_default()
-        {
-            Length::Pixels(val) => val,
-            Length::Percentage(val) => parent_width * val,
-            _ => 0.0,
-        };
-
-        //println!("Border Width: {}", border_width);
+        let drawn = draw_text_offset(state, entity, canvas, self.scroll_x);
 
-        // Skip widgets with no width or no height
-        if width + 2.0 * border_width + padding_left + padding_right == 0.0
-            || height + 2.0 * border_width + padding_top + padding_bottom == 0.0
-        {
-            return;
+        if let (Some(real_text), Some(data)) = (real_text, state.style.text.get_mut(entity)) {
+            data.text = real_text;
         }
 
-        // Apply transformations
-        let rotate = state.style.rotate.get(entity).unwrap_or(&0.0);
-        let scaley = state.style.scaley.get(entity).cloned().unwrap_or_default();
-
-        canvas.save();
-        // canvas.translate(posx + width / 2.0, posy + height / 2.0);
-        // canvas.rotate(rotate.to_radians());
-        // canvas.translate(-(posx + width / 2.0), -(posy + height / 2.0));
-
-        let pt = canvas
-            .transform()
-            .inverse()
-            .transform_point(posx + width / 2.0, posy + height / 2.0);
-        //canvas.translate(posx + width / 2.0, posy + width / 2.0);
-        canvas.translate(pt.0, pt.1);
-        canvas.scale(1.0, scaley.0);
-        canvas.translate(-pt.0, -pt.1);
-
-        // Apply Scissor
-        let clip_entity = state.transform.get_clip_widget(entity);
-
-        let clip_posx = state.transform.get_posx(clip_entity);
-        let clip_posy = state.transform.get_posy(clip_entity);
-        let clip_width = state.transform.get_width(clip_entity);
-        let clip_height = state.transform.get_height(clip_entity);
-
-        canvas.scissor(clip_posx, clip_posy, clip_width, clip_height);
-
-        let shadow_h_offset = state
-            .style
-            .shadow_h_offset
-            .get(entity)
-            .cloned()
-            .unwrap_or_default();
-
-        // Draw shadow
-        // let mut path = Path::new();
-        // path.rounded_rect_varying(posx, posy, width, height, border_radius_top_left, border_radius_top_right, border_radius_bottom_right, border_radius_bottom_left);
-        // let mut paint = Paint::color(background_color);
-        // canvas.fill_path(&mut path, paint);
-
-        // Draw rounded rect
-        let mut path = Path::new();
-        path.rounded_rect_varying(
-            posx + (border_width / 2.0),
-            posy + (border_width / 2.0),
-            width - border_width,
-            height - border_width,
-            border_radius_top_left,
-            border_radius_top_right,
-            border_radius_bottom_right,
-            border_radius_bottom_left,
-        );
-        let mut paint = Paint::color(background_color);
-        canvas.fill_path(&mut path, &paint);
-
-        // Draw border
-        let mut paint = Paint::color(border_color);
-        paint.set_line_width(border_width);
-        //paint.set_anti_alias(false);
-        canvas.stroke_path(&mut path, &paint);
-        //println!("posx: {}", posx);
-
-        let mut font_color: femtovg::Color = font_color.into();
-        font_color.set_alphaf(font_color.a * opacity);
-
-        if let Some(text) = state.style.text.get_mut(entity) {
-            let font_id = match text.font.as_ref() {
-                "Sans" => state.fonts.regular.unwrap(),
-                "Icons" => state.fonts.icons.unwrap(),
-                _ => state.fonts.regular.unwrap(),
-            };
-
-            let mut x = posx;
-            let mut y = posy;
-
-            let text_string = text.text.to_owned();
-
-            let text_align = state
-                .style
-                .text_align
-                .get(entity)
-                .cloned()
-                .unwrap_or_default();
-            let text_justify = state
-                .style
-                .text_justify
-                .get(entity)
-                .cloned()
-                .unwrap_or_default();
-
-            let align = match text_justify {
-                Justify::Start => {
-                    x += padding_left;
-                    Align::Left
-                }
-                Justify::Center => {
-                    x += 0.5 * width;
-                    Align::Center
-                }
-                Justify::End => {
-                    x += width - padding_right;
-                    Align::Right
-                }
-            };
+        if let Some((x, y, res)) = drawn {
+            let text_width = res.width();
+            let mut glyph_positions = res.glyphs.iter().peekable();
 
-            let baseline = match text_align {
-                crate::Align::Start => {
-                    y += padding_top;
-                    Baseline::Top
-                }
-                crate::Align::Center => {
-                    y += 0.5 * height;
-                    Baseline::Middle
-                }
-                crate::Align::End => {
-                    y += height - padding_bottom;
-                    Baseline::Bottom
-                }
-            };
+            let mut caretx = content.x;
 
-            let font_size = state.style.font_size.get(entity).cloned().unwrap_or(16.0);
+            let mut selectx = caretx;
 
-            let mut paint = Paint::color(font_color);
-            paint.set_font_size(font_size);
-            paint.set_font(&[font_id]);
-            paint.set_text_align(align);
-            paint.set_text_baseline(baseline);
+            if self.edit {
+                let startx = x - text_width / 2.0;
+                let endx = x + text_width / 2.0;
+                // Mouse selection is ignored while composing
+                if self.hitx != -1.0 && !composing {
+                    //let endx = res.glyphs.last().unwrap().x + res.glyphs.last().unwrap().w;
 
-            if let Ok(res) = canvas.fill_text(x, y, &text_string, &paint) {
-                let text_width = res.width();
-                let mut glyph_positions = res.glyphs.iter().peekable();
-
-                let mut caretx = posx + padding_left;
+                    selectx = if self.hitx < startx + text_width / 2.0 {
+                        self.select_pos = 0;
+                        startx
+                    } else {
+                        self.select_pos = text_len as u32;
+                        endx
+                    };
 
-                let mut selectx = caretx;
+                    caretx = if self.dragx < startx + text_width / 2.0 {
+                        self.cursor_pos = 0;
+                        startx
+                    } else {
+                        self.cursor_pos = text_len as u32;
+                        endx
+                    };
+
+                    let mut n = 0;
+                    let mut px = content.x;
+
+                    for glyph in res.glyphs.iter() {
+                        let left_edge = glyph.x;
+                        let right_edge = left_edge + glyph.width;
+                        let gx = left_edge * 0.3 + right_edge * 0.7;
+
+                        // if n == 0 && self.hitx <= glyph.x {
+                        //     selectx = left_edge;
+                        //     self.select_pos = 0;
+                        // }
+
+                        // if n == res.glyphs.len() as u32 && self.hitx >= glyph.x + glyph.width {
+                        //     selectx = right_edge;
+                        //     self.select_pos = n;
+                        // }
+
+                        // if n == 0 && self.dragx <= glyph.x {
+                        //     caretx = left_edge;
+                        //     self.cursor_pos = 0;
+                        // }
+
+                        // if n == res.glyphs.len() as u32 && self.hitx >= glyph.x + glyph.width {
+                        //     caretx = right_edge;
+                        //     self.cursor_pos = n;
+                        // }
+
+                        if self.hitx >= px && self.hitx < gx {
+                            selectx = left_edge;
+
+                            self.select_pos = n;
+                        }
 
-                if self.edit {
-                    let startx = x - text_width / 2.0;
-                    let endx = x + text_width / 2.0;
-                    if self.hitx != -1.0 {
-                        //let endx = res.glyphs.last().unwrap().x + res.glyphs.last().unwrap().w;
+                        if self.dragx >= px && self.dragx < gx {
+                            caretx = left_edge;
 
-                        selectx = if self.hitx < startx + text_width / 2.0 {
-                            self.select_pos = 0;
-                            startx
-                        } else {
-                            self.select_pos = text.text.len() as u32;
-                            endx
-                        };
-
-                        caretx = if self.dragx < startx + text_width / 2.0 {
-                            self.cursor_pos = 0;
-                            startx
-                        } else {
-                            self.cursor_pos = text.text.len() as u32;
-                            endx
-                        };
-
-                        let mut n = 0;
-                        let mut px = x + padding_left;
-
-                        for glyph in res.glyphs.iter() {
-                            let left_edge = glyph.x;
-                            let right_edge = left_edge + glyph.width;
-                            let gx = left_edge * 0.3 + right_edge * 0.7;
-
-                            // if n == 0 && self.hitx <= glyph.x {
-                            //     selectx = left_edge;
-                            //     self.select_pos = 0;
-                            // }
-
-                            // if n == res.glyphs.len() as u32 && self.hitx >= glyph.x + glyph.width {
-                            //     selectx = right_edge;
-                            //     self.select_pos = n;
-                            // }
-
-                            // if n == 0 && self.dragx <= glyph.x {
-                            //     caretx = left_edge;
-                            //     self.cursor_pos = 0;
-                            // }
-
-                            // if n == res.glyphs.len() as u32 && self.hitx >= glyph.x + glyph.width {
-                            //     caretx = right_edge;
-                            //     self.cursor_pos = n;
-                            // }
-
-                            if self.hitx >= px && self.hitx < gx {
-                                selectx = left_edge;
-
-                                self.select_pos = n;
-                            }
+                            self.cursor_pos = n;
+                        }
 
-                            if self.dragx >= px && self.dragx < gx {
-                                caretx = left_edge;
+                        px = gx;
+                        n += 1;
+                    }
 
-                                self.cursor_pos = n;
-                            }
+                    if self.select_word {
+                        let (start, end) = word_bounds(&text.text, self.cursor_pos as usize);
+                        self.select_pos = start as u32;
+                        self.cursor_pos = end as u32;
+                        self.select_word = false;
+                        // Stop dragging so the selection is positioned from the glyphs below
+                        self.hitx = -1.0;
+                    }
+                }
 
-                            px = gx;
-                            n += 1;
-                        }
+                if self.hitx == -1.0 || composing {
+                    // The selection is hidden while composing and the caret is placed within the composed text
+                    let (cursor_pos, select_pos) = if composing {
+                        (preedit_caret, preedit_caret)
                     } else {
-                        let mut n = 0;
+                        (self.cursor_pos as usize, self.select_pos as usize)
+                    };
 
-                        //let mut start_x = 0.0;
+                    let mut n = 0;
 
-                        for glyph in res.glyphs.iter() {
-                            if n == self.cursor_pos {
-                                caretx = glyph.x;
-                            }
+                    //let mut start_x = 0.0;
 
-                            if n == self.select_pos {
-                                selectx = glyph.x;
-                            }
-
-                            n += 1;
+                    for glyph in res.glyphs.iter() {
+                        if n == cursor_pos {
+                            caretx = glyph.x;
                         }
 
-                        if self.cursor_pos as usize == text.text.len() {
-                            caretx = endx;
+                        if n == select_pos {
+                            selectx = glyph.x;
                         }
 
-                        if self.select_pos as usize == text.text.len() {
-                            selectx = endx;
-                        }
+                        n += 1;
                     }
 
-                    //Draw selection
-                    let select_width = (caretx - selectx).abs();
-                    if selectx > caretx {
-                        let mut path = Path::new();
-                        path.rect(
-                            caretx,
-                            y - 1.2 * res.height() / 2.0,
-                            select_width,
-                            1.3 * res.height(),
-                        );
-                        canvas.fill_path(&mut path, &Paint::color(Color::rgba(0, 0, 0, 64)));
-                    } else if caretx > selectx {
+                    if cursor_pos == text_len {
+                        caretx = endx;
+                    }
+
+                    if select_pos == text_len {
+                        selectx = endx;
+                    }
+                }
+
+                // Underline the text being composed
+                if composing {
+                    let glyphs = res
+                        .glyphs
+                        .get(preedit_start..preedit_end.min(res.glyphs.len()));
+                    if let Some(glyphs) = glyphs.filter(|glyphs| !glyphs.is_empty()) {
+                        let startx = glyphs[0].x;
+                        let endx = glyphs[glyphs.len() - 1].x + glyphs[glyphs.len() - 1].width;
+                        let font_color = state
+                            .style
+                            .font_color
+                            .get(entity)
+                            .cloned()
+                            .unwrap_or_default();
+
                         let mut path = Path::new();
-                        path.rect(
-                            selectx,
-                            y - 1.2 * res.height() / 2.0,
-                            select_width,
-                            1.3 * res.height(),
-                        );
-                        canvas.fill_path(&mut path, &Paint::color(Color::rgba(0, 0, 0, 64)));
+                        path.rect(startx, y + 0.6 * res.height(), endx - startx, 1.0);
+                        canvas.fill_path(&mut path, &Paint::color(font_color.into()));
                     }
+                }
+
+                //Draw selection
+                let selection_color = state
+                    .style
+                    .selection_color
+                    .get(entity)
+                    .cloned()
+                    .unwrap_or(DEFAULT_SELECTION_COLOR);
+                let select_width = (caretx - selectx).abs();
+                if selectx > caretx {
+                    let mut path = Path::new();
+                    path.rect(
+                        caretx,
+                        y - 1.2 * res.height() / 2.0,
+                        select_width,
+                        1.3 * res.height(),
+                    );
+                    canvas.fill_path(&mut path, &Paint::color(selection_color.into()));
+                } else if caretx > selectx {
+                    let mut path = Path::new();
+                    path.rect(
+                        selectx,
+                        y - 1.2 * res.height() / 2.0,
+                        select_width,
+                        1.3 * res.height(),
+                    );
+                    canvas.fill_path(&mut path, &Paint::color(selection_color.into()));
+                }
+
+                // Scroll the text so the caret is within the content box, and back again when
+                // the text shrinks, drawing again with the new offset
+                let content_left = content.x;
+                let content_right = content.x + content.w;
+                let text_end = res
+                    .glyphs
+                    .last()
+                    .map(|glyph| glyph.x + glyph.width)
+                    .unwrap_or(x);
+                let mut scroll_x = self.scroll_x;
+                if caretx > content_right {
+                    scroll_x -= caretx - content_right;
+                } else if caretx < content_left {
+                    scroll_x += content_left - caretx;
+                } else if scroll_x < 0.0 && text_end < content_right {
+                    scroll_x = (scroll_x + content_right - text_end).min(0.0);
+                }
+
+                if scroll_x != self.scroll_x {
+                    self.scroll_x = scroll_x;
+                    state.insert_event(Event::new(WindowEvent::Redraw).origin(entity));
+                }
+
+                if state.caret_visible() && (!composing || self.preedit_cursor.is_some()) {
+                    let caret_color = state
+                        .style
+                        .caret_color
+                        .get(entity)
+                        .cloned()
+                        .unwrap_or(DEFAULT_CARET_COLOR);
 
                     let mut path = Path::new();
                     path.rect(
@@ -766,13 +713,257 @@ impl EventHandler for Textbox {
                         2.0,
                         1.3 * res.height(),
                     );
-                    canvas.fill_path(&mut path, &Paint::color(Color::rgba(247, 76, 0, 255)));
-
-                    // let mut path = Path::new();
-                    // path.rect(endx, y - 0.25 * height, 1.0, height * 0.5);
-                    // canvas.fill_path(&mut path, Paint::color(Color::rgba(255, 0, 0, 255)));
+                    canvas.fill_path(&mut path, &Paint::color(caret_color.into()));
                 }
+
+                // let mut path = Path::new();
+                // path.rect(endx, y - 0.25 * height, 1.0, height * 0.5);
+                // canvas.fill_path(&mut path, Paint::color(Color::rgba(255, 0, 0, 255)));
             }
         }
+
+        canvas.restore();
+    }
+}
+
+// Returns the byte index of the character with the given index, or the length of the text if past the end
+fn byte_index(text: &str, index: usize) -> usize {
+    text.char_indices()
+        .nth(index)
+        .map(|(byte, _)| byte)
+        .unwrap_or(text.len())
+}
+
+// Returns the start and end index of the word containing the given index
+fn word_bounds(text: &str, index: usize) -> (usize, usize) {
+    let chars: Vec<char> = text.chars().collect();
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+
+    let index = index.min(chars.len());
+
+    let mut start = index;
+    while start > 0 && is_word(chars[start - 1]) {
+        start -= 1;
+    }
+
+    let mut end = index;
+    while end < chars.len() && is_word(chars[end]) {
+        end += 1;
+    }
+
+    (start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Code;
+
+    // Builds a textbox being edited with the caret at the end of the text
+    fn editing(state: &mut State, textbox: Textbox) -> (Textbox, Entity) {
+        let root = state.root;
+        let entity = state.add(root);
+
+        let mut textbox = textbox;
+        textbox.on_build(state, entity);
+        textbox.edit = true;
+        textbox.cursor_pos = textbox.text.chars().count() as u32;
+        textbox.select_pos = textbox.cursor_pos;
+
+        (textbox, entity)
+    }
+
+    fn text(state: &State, entity: Entity) -> String {
+        state.style.text.get(entity).unwrap().text.clone()
+    }
+
+    fn type_char(state: &mut State, textbox: &mut Textbox, entity: Entity, input: char) {
+        let mut event = Event::new(WindowEvent::CharInput(input)).target(entity);
+        textbox.on_event(state, entity, &mut event);
+    }
+
+    fn key_down(
+        state: &mut State,
+        textbox: &mut Textbox,
+        entity: Entity,
+        code: Code,
+        key: Key,
+        repeat: bool,
+    ) {
+        let mut event = Event::new(WindowEvent::KeyDown(code, Some(key), repeat)).target(entity);
+        textbox.on_event(state, entity, &mut event);
+    }
+
+    fn press(state: &mut State, textbox: &mut Textbox, entity: Entity, code: Code, key: Key) {
+        key_down(state, textbox, entity, code, key, false);
+    }
+
+    #[test]
+    fn backspace_removes_whole_multi_byte_character() {
+        let mut state = State::new_headless();
+        let (mut textbox, entity) = editing(&mut state, Textbox::new("héllo"));
+
+        textbox.cursor_pos = 2;
+        textbox.select_pos = 2;
+
+        press(
+            &mut state,
+            &mut textbox,
+            entity,
+            Code::Backspace,
+            Key::Backspace,
+        );
+        assert_eq!(text(&state, entity), "hllo");
+        assert_eq!(textbox.cursor_pos, 1);
+    }
+
+    #[test]
+    fn typing_after_multi_byte_characters_inserts_at_caret() {
+        let mut state = State::new_headless();
+        let (mut textbox, entity) = editing(&mut state, Textbox::new("日本"));
+
+        press(
+            &mut state,
+            &mut textbox,
+            entity,
+            Code::ArrowLeft,
+            Key::ArrowLeft,
+        );
+        type_char(&mut state, &mut textbox, entity, 'x');
+        assert_eq!(text(&state, entity), "日x本");
+
+        press(
+            &mut state,
+            &mut textbox,
+            entity,
+            Code::ArrowRight,
+            Key::ArrowRight,
+        );
+        press(
+            &mut state,
+            &mut textbox,
+            entity,
+            Code::ArrowRight,
+            Key::ArrowRight,
+        );
+        assert_eq!(textbox.cursor_pos, 3);
+    }
+
+    #[test]
+    fn typing_stops_at_max_length() {
+        let mut state = State::new_headless();
+        let (mut textbox, entity) = editing(&mut state, Textbox::new("abc").with_max_length(4));
+
+        type_char(&mut state, &mut textbox, entity, 'd');
+        assert_eq!(text(&state, entity), "abcd");
+
+        type_char(&mut state, &mut textbox, entity, 'e');
+        assert_eq!(text(&state, entity), "abcd");
+        assert_eq!(textbox.cursor_pos, 4);
+    }
+
+    #[test]
+    fn paste_over_max_length_is_ignored() {
+        let mut state = State::new_headless();
+        let (mut textbox, entity) = editing(&mut state, Textbox::new("abc").with_max_length(4));
+
+        textbox.insert_text(&mut state, entity, "de");
+        assert_eq!(text(&state, entity), "abc");
+        assert_eq!(textbox.cursor_pos, 3);
+
+        textbox.insert_text(&mut state, entity, "d");
+        assert_eq!(text(&state, entity), "abcd");
+    }
+
+    #[test]
+    fn paste_replacing_selection_can_reach_max_length() {
+        let mut state = State::new_headless();
+        let (mut textbox, entity) = editing(&mut state, Textbox::new("abcd").with_max_length(4));
+
+        // Select "ab"
+        textbox.select_pos = 0;
+        textbox.cursor_pos = 2;
+
+        textbox.insert_text(&mut state, entity, "xyz");
+        assert_eq!(text(&state, entity), "abcd");
+
+        textbox.insert_text(&mut state, entity, "xy");
+        assert_eq!(text(&state, entity), "xycd");
+        assert_eq!(textbox.cursor_pos, 2);
+    }
+
+    #[test]
+    fn preedit_is_not_inserted_until_committed() {
+        let mut state = State::new_headless();
+        let (mut textbox, entity) = editing(&mut state, Textbox::new("ab"));
+
+        let mut event =
+            Event::new(WindowEvent::ImePreedit("にほ".to_string(), Some(2))).target(entity);
+        textbox.on_event(&mut state, entity, &mut event);
+        assert_eq!(text(&state, entity), "ab");
+        assert_eq!(textbox.preedit, "にほ");
+
+        let mut event = Event::new(WindowEvent::ImeCommit("日本".to_string())).target(entity);
+        textbox.on_event(&mut state, entity, &mut event);
+        assert_eq!(text(&state, entity), "ab日本");
+        assert!(textbox.preedit.is_empty());
+        assert_eq!(textbox.cursor_pos, 4);
+    }
+
+    #[test]
+    fn repeated_backspace_deletes_a_character_each_time() {
+        let mut state = State::new_headless();
+        let (mut textbox, entity) = editing(&mut state, Textbox::new("hello"));
+
+        // A press followed by two presses repeated by the OS
+        for repeat in [false, true, true].iter() {
+            key_down(
+                &mut state,
+                &mut textbox,
+                entity,
+                Code::Backspace,
+                Key::Backspace,
+                *repeat,
+            );
+        }
+
+        assert_eq!(text(&state, entity), "he");
+        assert_eq!(textbox.cursor_pos, 2);
+    }
+
+    #[test]
+    fn repeated_enter_does_not_finish_editing() {
+        let mut state = State::new_headless();
+        let (mut textbox, entity) = editing(&mut state, Textbox::new("hello"));
+
+        let value_changed = |events: Vec<Event>| {
+            events.into_iter().any(|event| {
+                event
+                    .message
+                    .equals_a(&TextboxEvent::ValueChanged("hello".to_string()))
+            })
+        };
+
+        key_down(
+            &mut state,
+            &mut textbox,
+            entity,
+            Code::Enter,
+            Key::Enter,
+            true,
+        );
+        assert!(textbox.edit);
+        assert!(!value_changed(state.drain_events()));
+
+        key_down(
+            &mut state,
+            &mut textbox,
+            entity,
+            Code::Enter,
+            Key::Enter,
+            false,
+        );
+        assert!(!textbox.edit);
+        assert!(value_changed(state.drain_events()));
     }
 }
@@ -0,0 +1,278 @@
+use crate::widgets::*;
+use crate::{Display, Event, GeometryChanged, Length, Rect, Role};
+
+// Space between a tooltip and the widget or cursor it is placed next to
+pub const TOOLTIP_GAP: f32 = 4.0;
+
+// Space left below the cursor for the pointer when a tooltip follows the cursor
+const CURSOR_HEIGHT: f32 = 16.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TooltipEvent {
+    // Sent by the state to a tooltip once the cursor has rested over its widget for the tooltip delay
+    Show,
+    // Sent by the state to the shown tooltip when the cursor leaves its widget
+    Hide,
+    // Sent by the state to the shown tooltip when the cursor moves over its widget
+    Move,
+}
+
+// Returns the position of a tooltip with the given size placed below the anchor and aligned with its left side,
+// flipped above the anchor if it would go past the bottom of the window and aligned with the right side of the
+// anchor if it would go past the right of the window
+pub fn tooltip_position(anchor: Rect, width: f32, height: f32, window: Rect) -> (f32, f32) {
+    let mut x = anchor.x;
+    if x + width > window.x + window.w {
+        x = anchor.x + anchor.w - width;
+    }
+
+    let mut y = anchor.y + anchor.h + TOOLTIP_GAP;
+    if y + height > window.y + window.h {
+        y = anchor.y - TOOLTIP_GAP - height;
+    }
+
+    // Tooltips larger than the space on either side are kept inside the window at the top left
+    (x.max(window.x), y.max(window.y))
+}
+
+// A label shown next to its parent widget when the cursor rests over it, styled with the "tooltip" selector
+// The tooltip is shown after the delay set by State::tooltip_delay and hidden when the cursor leaves the widget
+pub struct Tooltip {
+    text: String,
+    follow_cursor: bool,
+}
+
+impl Tooltip {
+    pub fn new(text: &str) -> Self {
+        Tooltip {
+            text: text.to_string(),
+            follow_cursor: false,
+        }
+    }
+
+    // When true the tooltip trails the cursor instead of staying below its widget
+    pub fn follow_cursor(mut self, flag: bool) -> Self {
+        self.follow_cursor = flag;
+
+        self
+    }
+
+    fn place(&self, state: &mut State, entity: Entity) {
+        let anchor = if self.follow_cursor {
+            Rect::new(state.mouse.cursorx, state.mouse.cursory, 0.0, CURSOR_HEIGHT)
+        } else {
+            match entity.get_parent(state) {
+                Some(parent) => Rect::new(
+                    state.transform.get_posx(parent),
+                    state.transform.get_posy(parent),
+                    state.transform.get_width(parent),
+                    state.transform.get_height(parent),
+                ),
+                None => return,
+            }
+        };
+
+        let window = Rect::new(
+            0.0,
+            0.0,
+            state.transform.get_width(state.root),
+            state.transform.get_height(state.root),
+        );
+
+        // Uses the size from the last layout, the tooltip is placed again if its size changes when shown
+        let (left, top) = tooltip_position(
+            anchor,
+            state.transform.get_width(entity),
+            state.transform.get_height(entity),
+            window,
+        );
+
+        entity
+            .set_left(state, Length::Pixels(left))
+            .set_top(state, Length::Pixels(top));
+    }
+}
+
+impl BuildHandler for Tooltip {
+    type Ret = Entity;
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity
+            .set_text(state, &self.text)
+            .set_element(state, "tooltip")
+            .set_role(state, Role::Tooltip);
+
+        // The tooltip is drawn above the rest of the tree and hidden until shown
+        // It can't be hovered so it doesn't take the hover from its widget
+        entity
+            .set_overlay(state, true)
+            .set_z_order(state, 2)
+            .set_display(state, Display::None)
+            .set_geometry_listener(state, true);
+        state.transform.set_hoverability(entity, false);
+
+        if let Some(parent) = entity.get_parent(state) {
+            state.tooltips.insert(parent, entity);
+        }
+
+        entity
+    }
+}
+
+impl EventHandler for Tooltip {
+    fn on_event(&mut self, state: &mut State, entity: Entity, event: &mut Event) -> bool {
+        if let Some(tooltip_event) = event.message.downcast::<TooltipEvent>() {
+            if event.target == entity {
+                match tooltip_event {
+                    TooltipEvent::Show => {
+                        self.place(state, entity);
+                        entity.set_display(state, Display::Flexbox);
+                    }
+
+                    TooltipEvent::Hide => {
+                        entity.set_display(state, Display::None);
+                    }
+
+                    TooltipEvent::Move => {
+                        if self.follow_cursor {
+                            self.place(state, entity);
+                        }
+                    }
+                }
+
+                return true;
+            }
+        }
+
+        false
+    }
+
+    // Place the tooltip again once its size is known
+    fn on_geometry_changed(
+        &mut self,
+        state: &mut State,
+        entity: Entity,
+        geometry_changed: GeometryChanged,
+    ) {
+        if geometry_changed.width || geometry_changed.height {
+            self.place(state, entity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{apply_hover, EventManager};
+
+    use std::time::Duration;
+
+    const WINDOW: Rect = Rect {
+        x: 0.0,
+        y: 0.0,
+        w: 800.0,
+        h: 600.0,
+    };
+
+    #[test]
+    fn tooltip_is_placed_below_and_left_aligned() {
+        let anchor = Rect::new(100.0, 100.0, 80.0, 30.0);
+        assert_eq!(
+            tooltip_position(anchor, 150.0, 24.0, WINDOW),
+            (100.0, 130.0 + TOOLTIP_GAP)
+        );
+    }
+
+    #[test]
+    fn tooltip_near_right_edge_is_right_aligned() {
+        let anchor = Rect::new(700.0, 100.0, 80.0, 30.0);
+        assert_eq!(
+            tooltip_position(anchor, 150.0, 24.0, WINDOW),
+            (630.0, 130.0 + TOOLTIP_GAP)
+        );
+    }
+
+    #[test]
+    fn tooltip_near_bottom_edge_is_flipped_above() {
+        let anchor = Rect::new(100.0, 560.0, 80.0, 30.0);
+        assert_eq!(
+            tooltip_position(anchor, 150.0, 24.0, WINDOW),
+            (100.0, 560.0 - TOOLTIP_GAP - 24.0)
+        );
+
+        // In the bottom right corner both apply
+        let anchor = Rect::new(700.0, 560.0, 80.0, 30.0);
+        assert_eq!(
+            tooltip_position(anchor, 150.0, 24.0, WINDOW),
+            (630.0, 560.0 - TOOLTIP_GAP - 24.0)
+        );
+    }
+
+    #[test]
+    fn oversize_tooltip_stays_inside_the_window() {
+        // Wider than the space on either side of the anchor
+        let anchor = Rect::new(10.0, 100.0, 20.0, 30.0);
+        assert_eq!(
+            tooltip_position(anchor, 900.0, 24.0, WINDOW),
+            (0.0, 130.0 + TOOLTIP_GAP)
+        );
+
+        // Taller than the space above and below the anchor
+        let anchor = Rect::new(100.0, 100.0, 80.0, 30.0);
+        assert_eq!(tooltip_position(anchor, 150.0, 700.0, WINDOW), (100.0, 0.0));
+    }
+
+    #[test]
+    fn tooltip_is_shown_while_hovering_its_widget() {
+        let mut state = State::new_headless();
+        let mut event_manager = EventManager::new();
+        let root = state.root;
+
+        let flush = |state: &mut State, event_manager: &mut EventManager| {
+            while !state.event_queue.is_empty() {
+                event_manager.flush_events(state);
+            }
+        };
+
+        state.tooltip_delay = Duration::from_millis(0);
+
+        let button = Element::new().build(&mut state, root, |builder| {
+            builder
+                .set_width(Length::Pixels(100.0))
+                .set_height(Length::Pixels(30.0))
+        });
+        let tooltip = Tooltip::new("Tip").build(&mut state, button, |builder| {
+            builder
+                .set_width(Length::Pixels(50.0))
+                .set_height(Length::Pixels(20.0))
+        });
+
+        flush(&mut state, &mut event_manager);
+
+        let display = |state: &State| {
+            state
+                .style
+                .display
+                .get(tooltip)
+                .cloned()
+                .unwrap_or_default()
+        };
+        assert_eq!(display(&state), Display::None);
+
+        // Hovering the button shows the tooltip once the delay has passed
+        state.mouse.cursorx = 50.0;
+        state.mouse.cursory = 15.0;
+        apply_hover(&mut state);
+        assert!(state.tooltip_due());
+        flush(&mut state, &mut event_manager);
+        assert_eq!(display(&state), Display::Flexbox);
+        assert_eq!(state.transform.get_posy(tooltip), 30.0 + TOOLTIP_GAP);
+
+        // Leaving the button hides it
+        state.mouse.cursorx = 400.0;
+        state.mouse.cursory = 300.0;
+        apply_hover(&mut state);
+        flush(&mut state, &mut event_manager);
+        assert_eq!(display(&state), Display::None);
+        assert!(!state.tooltip_due());
+    }
+}
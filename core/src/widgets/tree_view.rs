@@ -0,0 +1,204 @@
+#![allow(dead_code)]
+
+use crate::entity::Entity;
+use crate::state::style::*;
+use crate::widgets::{Button, ButtonEvent, Element};
+use crate::{
+    BuildHandler, Event, EventHandler, IntoBranchIterator, IntoParentIterator, Propagation,
+    WindowEvent,
+};
+use crate::{PropSet, State};
+
+const ICON_EXPANDED: &str = "\u{25be}";
+const ICON_COLLAPSED: &str = "\u{25b8}";
+
+// Indentation of a node row for each level of depth within the tree
+const INDENT: f32 = 16.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TreeEvent {
+    // Emitted by a node when it is expanded or collapsed
+    Toggled(Entity),
+    // Emitted by a lazy node the first time it is expanded so its children can be built
+    Populate(Entity),
+}
+
+// A container for a hierarchy of tree nodes
+pub struct TreeView {}
+
+impl TreeView {
+    pub fn new() -> Self {
+        TreeView {}
+    }
+}
+
+impl BuildHandler for TreeView {
+    type Ret = Entity;
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity.set_flex_direction(state, FlexDirection::Column);
+
+        state.style.insert_element(entity, "tree_view");
+
+        entity
+    }
+}
+
+impl EventHandler for TreeView {}
+
+// A node in a tree view with a row containing a disclosure triangle and a label
+// Child nodes are built into the returned entity and are hidden while the node is collapsed
+// Expanded nodes have the checked pseudo-class
+pub struct TreeNode {
+    label: String,
+    disclosure: Entity,
+    expanded: bool,
+    lazy: bool,
+    populated: bool,
+}
+
+impl TreeNode {
+    pub fn new(label: &str) -> Self {
+        TreeNode {
+            label: label.to_string(),
+            disclosure: Entity::null(),
+            expanded: true,
+            lazy: false,
+            populated: false,
+        }
+    }
+
+    // Starts collapsed and emits TreeEvent::Populate when first expanded
+    pub fn lazy(mut self) -> Self {
+        self.lazy = true;
+        self.expanded = false;
+
+        self
+    }
+}
+
+// Returns the depth of a node below the tree view containing it
+fn depth_in_tree(state: &State, node: Entity) -> usize {
+    let depth = state.hierarchy.get_depth(node);
+    for ancestor in node.parent_iter(&state.hierarchy) {
+        if state.style.has_element(ancestor, "tree_view") {
+            return depth - state.hierarchy.get_depth(ancestor) - 1;
+        }
+    }
+
+    0
+}
+
+// Shows the descendant nodes of a node whose ancestors up to the node are all expanded and hides the rest
+fn update_branch(state: &mut State, node: Entity) {
+    let descendants: Vec<Entity> = node
+        .branch_iter(&state.hierarchy)
+        .skip(1)
+        .filter(|descendant| state.style.has_element(*descendant, "tree_node"))
+        .collect();
+
+    for descendant in descendants {
+        let ancestors: Vec<Entity> = descendant.parent_iter(&state.hierarchy).skip(1).collect();
+
+        let mut visible = true;
+        for ancestor in ancestors {
+            if state.style.has_element(ancestor, "tree_node") {
+                visible &= ancestor.is_checked(state);
+            }
+
+            if ancestor == node {
+                break;
+            }
+        }
+
+        if visible {
+            descendant.set_display(state, Display::Flexbox);
+        } else {
+            descendant.set_display(state, Display::None);
+        }
+    }
+}
+
+impl BuildHandler for TreeNode {
+    type Ret = Entity;
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity.set_flex_direction(state, FlexDirection::Column);
+
+        let row = Element::new().build(state, entity, |builder| {
+            builder
+                .set_flex_direction(FlexDirection::Row)
+                .set_height(Length::Pixels(24.0))
+                .class("tree_row")
+        });
+
+        let depth = depth_in_tree(state, entity);
+        row.set_padding_left(state, Length::Pixels(depth as f32 * INDENT));
+
+        let icon = if self.expanded {
+            ICON_EXPANDED
+        } else {
+            ICON_COLLAPSED
+        };
+
+        self.disclosure = Button::with_label(icon).build(state, row, |builder| {
+            builder
+                .set_width(Length::Pixels(20.0))
+                .set_text_justify(Justify::Center)
+                .class("disclosure")
+        });
+
+        Element::new()
+            .build(state, row, |builder| {
+                builder.set_flex_grow(1.0).class("label")
+            })
+            .set_text(state, &self.label);
+
+        entity.set_checked(state, self.expanded);
+
+        state.style.insert_element(entity, "tree_node");
+
+        entity
+    }
+}
+
+impl EventHandler for TreeNode {
+    fn on_event(&mut self, state: &mut State, entity: Entity, event: &mut Event) -> bool {
+        if let Some(ButtonEvent::Pressed) = event.message.downcast::<ButtonEvent>() {
+            if event.target == self.disclosure {
+                self.expanded = !self.expanded;
+
+                let icon = if self.expanded {
+                    ICON_EXPANDED
+                } else {
+                    ICON_COLLAPSED
+                };
+
+                self.disclosure.set_text(state, icon);
+                entity.set_checked(state, self.expanded);
+
+                update_branch(state, entity);
+
+                if self.expanded && self.lazy && !self.populated {
+                    self.populated = true;
+
+                    state.insert_event(
+                        Event::new(TreeEvent::Populate(entity))
+                            .target(entity)
+                            .propagate(Propagation::Up),
+                    );
+                }
+
+                state.insert_event(
+                    Event::new(TreeEvent::Toggled(entity))
+                        .target(entity)
+                        .propagate(Propagation::Up),
+                );
+
+                state.insert_event(Event::new(WindowEvent::Restyle));
+
+                return true;
+            }
+        }
+
+        false
+    }
+}
@@ -1,8 +1,6 @@
-use crate::{Entity, Event, EventHandler, State, WindowEvent};
+use crate::{Entity, Event, EventHandler, Length, State, WindowEvent};
 
-use crate::systems::{
-    apply_clipping, apply_layout, apply_styles, apply_visibility, apply_z_ordering,
-};
+use femtovg::{renderer::OpenGl, Canvas};
 
 #[derive(Clone)]
 pub struct WindowWidget {}
@@ -17,26 +15,80 @@ impl WindowWidget {
     }
 }
 
+// Changes the properties of the window, applied by the backend when the events are flushed
+// These can be called while building the application or at any time after
+pub trait WindowPropSet {
+    // Sets the title shown by the OS for the window
+    // This is separate from PropSet::set_text, which only stores text for an entity to draw and isn't drawn for the root
+    fn set_window_title(self, state: &mut State, title: &str) -> Self;
+    fn set_inner_size(self, state: &mut State, width: u32, height: u32) -> Self;
+    fn set_min_size(self, state: &mut State, width: u32, height: u32) -> Self;
+    fn set_resizable(self, state: &mut State, resizable: bool) -> Self;
+}
+
+impl WindowPropSet for Entity {
+    fn set_window_title(self, state: &mut State, title: &str) -> Self {
+        state.insert_event(Event::new(WindowEvent::SetTitle(title.to_string())).target(self));
+
+        self
+    }
+
+    fn set_inner_size(self, state: &mut State, width: u32, height: u32) -> Self {
+        state.insert_event(Event::new(WindowEvent::SetInnerSize(width, height)).target(self));
+
+        self
+    }
+
+    fn set_min_size(self, state: &mut State, width: u32, height: u32) -> Self {
+        state.insert_event(Event::new(WindowEvent::SetMinSize(width, height)).target(self));
+
+        self
+    }
+
+    fn set_resizable(self, state: &mut State, resizable: bool) -> Self {
+        state.insert_event(Event::new(WindowEvent::SetResizable(resizable)).target(self));
+
+        self
+    }
+}
+
 impl EventHandler for WindowWidget {
-    fn on_event(&mut self, state: &mut State, _entity: Entity, event: &mut Event) -> bool {
+    fn on_event(&mut self, state: &mut State, entity: Entity, event: &mut Event) -> bool {
         if let Some(window_event) = event.message.downcast::<WindowEvent>() {
             match window_event {
                 WindowEvent::WindowClose => {
                     println!("Window Close Event");
                 }
 
+                WindowEvent::WindowResize(width, height) => {
+                    state.style.width.insert(entity, Length::Pixels(*width));
+                    state.style.height.insert(entity, Length::Pixels(*height));
+
+                    state.transform.set_width(entity, *width);
+                    state.transform.set_height(entity, *height);
+
+                    state.insert_event(Event::new(WindowEvent::Restyle).origin(entity));
+                    state.insert_event(Event::new(WindowEvent::Relayout).target(Entity::null()));
+                    state.insert_event(Event::new(WindowEvent::Redraw));
+                }
+
+                // Restyling and layout are deferred until the event queue has been flushed
+                // so that many changes in one frame only cause a single pass
                 WindowEvent::Restyle => {
                     //println!("Restyle");
                     //apply_styles2(state, &state.hierarchy.clone(), event.origin);
-                    apply_styles(state, &state.hierarchy.clone());
+                    // Restyles caused by a change to a single entity, such as a pseudo-class setter,
+                    // only restyle the branch of that entity
+                    if event.origin == Entity::null() {
+                        state.needs_restyle = true;
+                    } else if !state.restyle_roots.contains(&event.origin) {
+                        state.restyle_roots.push(event.origin);
+                    }
                     //apply_visibility(state, &state.hierarchy.clone());
                 }
 
                 WindowEvent::Relayout => {
-                    apply_z_ordering(state, &state.hierarchy.clone());
-                    apply_visibility(state, &state.hierarchy.clone());
-                    apply_clipping(state, &state.hierarchy.clone());
-                    apply_layout(state, &state.hierarchy.clone());
+                    state.needs_relayout = true;
                 }
 
                 _ => {}
@@ -45,4 +97,8 @@ impl EventHandler for WindowWidget {
 
         false
     }
+
+    // The background of the root is drawn by clearing the canvas and its text isn't drawn,
+    // so text set on the root isn't drawn over the whole window
+    fn on_draw(&mut self, _state: &mut State, _entity: Entity, _canvas: &mut Canvas<OpenGl>) {}
 }
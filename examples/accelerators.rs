@@ -0,0 +1,84 @@
+extern crate tuix;
+
+use tuix::*;
+
+#[derive(Debug, Clone, PartialEq)]
+enum AppEvent {
+    Save,
+    SaveAs,
+    DisableSave,
+}
+
+// Handles the events sent by the accelerators, which work even while the textbox is being edited
+struct App {}
+
+impl BuildHandler for App {
+    type Ret = Entity;
+
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity.set_flex_grow(state, 1.0);
+
+        Textbox::new("Ctrl+S saves, Ctrl+Shift+S saves as, Ctrl+D disables Ctrl+S").build(
+            state,
+            entity,
+            |builder| {
+                builder
+                    .set_height(Length::Pixels(30.0))
+                    .set_margin(Length::Pixels(10.0))
+                    .set_background_color(Color::rgb(50, 50, 50))
+            },
+        );
+
+        state.add_accelerator(
+            Accelerator::new(Key::Character("s".to_string())).with_ctrl(),
+            Event::new(AppEvent::Save),
+        );
+
+        state.add_accelerator(
+            Accelerator::new(Key::Character("s".to_string()))
+                .with_ctrl()
+                .with_shift(),
+            Event::new(AppEvent::SaveAs),
+        );
+
+        state.add_accelerator(
+            Accelerator::new(Key::Character("d".to_string())).with_ctrl(),
+            Event::new(AppEvent::DisableSave),
+        );
+
+        entity
+    }
+}
+
+impl EventHandler for App {
+    fn on_event(&mut self, state: &mut State, _entity: Entity, event: &mut Event) -> bool {
+        if let Some(app_event) = event.message.downcast::<AppEvent>() {
+            match app_event {
+                AppEvent::Save => println!("Save"),
+
+                AppEvent::SaveAs => println!("Save as"),
+
+                AppEvent::DisableSave => {
+                    state.remove_accelerator(
+                        &Accelerator::new(Key::Character("s".to_string())).with_ctrl(),
+                    );
+                    println!("Ctrl+S disabled");
+                }
+            }
+
+            return true;
+        }
+
+        false
+    }
+}
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        App {}.build(state, window, |builder| builder);
+
+        win_desc.with_title("accelerators")
+    });
+
+    app.run();
+}
@@ -0,0 +1,110 @@
+extern crate tuix;
+
+use tuix::*;
+
+use tuix::style::themes::DEFAULT_THEME;
+
+use std::time::Duration;
+
+static THEME: &'static str = r#"
+.accordion {
+    width: 300px;
+    padding: 10px;
+}
+
+.section>button {
+    height: 30px;
+}
+
+.content {
+    padding-left: 10px;
+}
+
+.content>label {
+    height: 30px;
+}
+"#;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SectionEvent {
+    Toggle,
+}
+
+// A section with a header which collapses and expands its content when pressed
+pub struct Section {
+    title: String,
+    items: Vec<String>,
+    content: Entity,
+    expanded: bool,
+}
+
+impl Section {
+    pub fn new(title: &str, items: &[&str]) -> Self {
+        Section {
+            title: title.to_string(),
+            items: items.iter().map(|item| item.to_string()).collect(),
+            content: Entity::null(),
+            expanded: true,
+        }
+    }
+}
+
+impl BuildHandler for Section {
+    type Ret = Entity;
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity.class(state, "section");
+
+        Button::with_label(&self.title)
+            .on_press(Event::new(SectionEvent::Toggle))
+            .build(state, entity, |builder| builder);
+
+        self.content = VBox::new().build(state, entity, |builder| builder.class("content"));
+
+        for item in self.items.iter() {
+            Label::new(item).build(state, self.content, |builder| builder);
+        }
+
+        entity
+    }
+}
+
+impl EventHandler for Section {
+    fn on_event(&mut self, state: &mut State, _entity: Entity, event: &mut Event) -> bool {
+        if let Some(SectionEvent::Toggle) = event.message.downcast::<SectionEvent>() {
+            // Toggling part way through reverses the animation from the current height
+            if self.expanded {
+                self.content.collapse(state, Duration::from_millis(200));
+            } else {
+                self.content.expand(state, Duration::from_millis(200));
+            }
+
+            self.expanded = !self.expanded;
+
+            return true;
+        }
+
+        false
+    }
+}
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.insert_theme(DEFAULT_THEME);
+        state.insert_theme(THEME);
+
+        let accordion = VBox::new().build(state, window, |builder| builder.class("accordion"));
+
+        Section::new("Fruit", &["Apple", "Banana", "Cherry"])
+            .build(state, accordion, |builder| builder);
+        Section::new("Vegetables", &["Carrot", "Leek"]).build(state, accordion, |builder| builder);
+        Section::new("Grains", &["Barley", "Oats", "Rice", "Wheat"]).build(
+            state,
+            accordion,
+            |builder| builder,
+        );
+
+        win_desc.with_title("Accordion")
+    });
+
+    app.run();
+}
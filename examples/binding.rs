@@ -0,0 +1,72 @@
+extern crate tuix;
+
+use tuix::*;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Person {
+    name: String,
+    city: String,
+}
+
+// Selects the name of a person
+struct NameLens;
+
+impl Lens for NameLens {
+    type Source = Person;
+    type Target = String;
+
+    fn view<'a>(&self, source: &'a Person) -> &'a String {
+        &source.name
+    }
+
+    fn view_mut<'a>(&self, source: &'a mut Person) -> &'a mut String {
+        &mut source.name
+    }
+}
+
+// Selects the city of a person
+struct CityLens;
+
+impl Lens for CityLens {
+    type Source = Person;
+    type Target = String;
+
+    fn view<'a>(&self, source: &'a Person) -> &'a String {
+        &source.city
+    }
+
+    fn view_mut<'a>(&self, source: &'a mut Person) -> &'a mut String {
+        &mut source.city
+    }
+}
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        let person = Person {
+            name: "Alice".to_string(),
+            city: "Paris".to_string(),
+        };
+
+        let model = Model::new(person).build(state, window, |builder| builder.set_flex_grow(1.0));
+
+        // Editing either of the name textboxes updates the other one
+        // The city textbox is only updated when the city changes
+        for _ in 0..2 {
+            Wrapper::new(NameLens, Textbox::new("")).build(state, model, |builder| {
+                builder
+                    .set_width(Length::Pixels(200.0))
+                    .set_height(Length::Pixels(30.0))
+            });
+        }
+
+        Wrapper::new(CityLens, Textbox::new("")).build(state, model, |builder| {
+            builder
+                .set_width(Length::Pixels(200.0))
+                .set_height(Length::Pixels(30.0))
+        });
+
+        win_desc.with_title("binding")
+    });
+
+    app.run();
+}
@@ -0,0 +1,62 @@
+extern crate tuix;
+
+use tuix::*;
+
+use std::time::Instant;
+
+const NUM_ITEMS: usize = 500;
+
+// Builds a list of labels, each of which queues a restyle, relayout and redraw
+fn build_list(state: &mut State, parent: Entity) {
+    for i in 0..NUM_ITEMS {
+        Label::new(&format!("Item {}", i)).build(state, parent, |builder| {
+            builder
+                .set_height(Length::Pixels(20.0))
+                .set_background_color(Color::rgb(50, 50, 50))
+        });
+    }
+}
+
+// Times building a list of 500 entities with and without batching, without opening a window
+fn main() {
+    let mut state = State::new_headless();
+    let mut event_manager = EventManager::new();
+    let root = state.root;
+
+    // Before: every change queues its own events
+    let start = Instant::now();
+    let list = Element::new().build(&mut state, root, |builder| builder);
+    build_list(&mut state, list);
+    let queued = state.event_queue.len();
+    while !state.event_queue.is_empty() {
+        event_manager.flush_events(&mut state);
+    }
+    let before = start.elapsed();
+
+    state.remove(list);
+    while !state.event_queue.is_empty() {
+        event_manager.flush_events(&mut state);
+    }
+
+    // After: the events are queued once at the end of the batch
+    let start = Instant::now();
+    let list = state.build_batch(|state| {
+        let list = Element::new().build(state, root, |builder| builder);
+        build_list(state, list);
+        list
+    });
+    let batched_queued = state.event_queue.len();
+    while !state.event_queue.is_empty() {
+        event_manager.flush_events(&mut state);
+    }
+    let after = start.elapsed();
+
+    assert_eq!(list.child_iter(&state.hierarchy).count(), NUM_ITEMS);
+
+    println!("Building a list of {} labels", NUM_ITEMS);
+    println!("Without batching: {:?} ({} events queued)", before, queued);
+    println!(
+        "With batching: {:?} ({} events queued)",
+        after, batched_queued
+    );
+}
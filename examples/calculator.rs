@@ -388,7 +388,7 @@ impl EventHandler for Calculator {
 
         if let Some(window_event) = event.message.downcast::<WindowEvent>() {
             match window_event {
-                WindowEvent::KeyDown(code, key) => {
+                WindowEvent::KeyDown(code, key, _) => {
                     match code {
                         Code::Digit0 => {
                             state.active = self.zero;
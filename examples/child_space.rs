@@ -0,0 +1,23 @@
+extern crate tuix;
+
+use tuix::*;
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        // Stretch child space on every side of the window centers its children
+        // A margin set on a child would take precedence over the child space on that side
+        window.set_child_space(state, Length::Stretch(1.0));
+
+        Button::with_label("Centered").build(state, window, |builder| {
+            builder
+                .set_width(Length::Pixels(120.0))
+                .set_height(Length::Pixels(30.0))
+                .set_background_color(Color::rgb(80, 80, 200))
+                .set_text_justify(Justify::Center)
+        });
+
+        win_desc.with_title("child space")
+    });
+
+    app.run();
+}
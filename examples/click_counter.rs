@@ -0,0 +1,72 @@
+extern crate tuix;
+
+use tuix::*;
+
+// Counts the clicks of its button and shows the total in a label
+struct ClickCounter {
+    count: u32,
+    button: Entity,
+    label: Entity,
+}
+
+impl ClickCounter {
+    pub fn new() -> Self {
+        ClickCounter {
+            count: 0,
+            button: Entity::null(),
+            label: Entity::null(),
+        }
+    }
+}
+
+impl BuildHandler for ClickCounter {
+    type Ret = Entity;
+
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        self.button = Button::with_label("Click me")
+            .on_press_with(|_, button| println!("Button {} pressed", button))
+            .build(state, entity, |builder| {
+                builder
+                    .set_width(Length::Pixels(100.0))
+                    .set_height(Length::Pixels(30.0))
+                    .set_background_color(Color::rgb(80, 80, 200))
+            });
+
+        self.label = Label::new("0").build(state, entity, |builder| builder);
+
+        entity
+    }
+}
+
+impl EventHandler for ClickCounter {
+    fn on_event(&mut self, state: &mut State, _entity: Entity, event: &mut Event) -> bool {
+        if let Some(button_event) = event.message.downcast::<ButtonEvent>() {
+            match button_event {
+                ButtonEvent::Clicked => {
+                    if event.target == self.button {
+                        self.count += 1;
+                        self.label.set_text(state, &self.count.to_string());
+                    }
+                }
+
+                _ => {}
+            }
+        }
+
+        false
+    }
+}
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        ClickCounter::new().build(state, window, |builder| {
+            builder
+                .set_flex_grow(1.0)
+                .set_flex_direction(FlexDirection::Row)
+        });
+
+        win_desc.with_title("click counter")
+    });
+
+    app.run();
+}
@@ -0,0 +1,35 @@
+extern crate tuix;
+
+use tuix::*;
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        let panel = Element::new().build(state, window, |builder| {
+            builder
+                .set_width(Length::Pixels(300.0))
+                .set_height(Length::Pixels(200.0))
+                .set_margin(Length::Pixels(50.0))
+                .set_padding(Length::Pixels(10.0))
+                .set_background_color(Color::rgb(60, 60, 60))
+        });
+
+        Label::new("Panel content").build(state, panel, |builder| builder);
+
+        // Pinned to the top-right corner of the content box of the panel
+        // Absolutely positioned widgets don't affect the layout of their siblings
+        Button::with_label("X").build(state, panel, |builder| {
+            builder
+                .set_position(Position::Absolute)
+                .set_top(Length::Pixels(0.0))
+                .set_right(Length::Pixels(0.0))
+                .set_width(Length::Pixels(20.0))
+                .set_height(Length::Pixels(20.0))
+                .set_background_color(Color::rgb(200, 80, 80))
+                .set_text_justify(Justify::Center)
+        });
+
+        win_desc.with_title("close button")
+    });
+
+    app.run();
+}
@@ -0,0 +1,55 @@
+extern crate tuix;
+
+use tuix::*;
+
+static THEME: &'static str = r#"
+.panel {
+    width: 120px;
+    height: 80px;
+    background-color: #ff0000;
+}
+
+.panel:hover {
+    background-color: #00ff00;
+}
+"#;
+
+// Prints the style values resolved for an entity styled by a class
+fn main() {
+    let mut state = State::new_headless();
+    let mut event_manager = EventManager::new();
+
+    state.insert_theme(THEME);
+
+    let root = state.root;
+    let panel = Element::new().build(&mut state, root, |builder| builder.class("panel"));
+
+    while !state.event_queue.is_empty() {
+        event_manager.flush_events(&mut state);
+    }
+
+    let computed = state.computed_style(panel);
+
+    assert_eq!(computed.classes, vec!["panel".to_string()]);
+    assert_eq!(computed.background_color, Color::rgb(255, 0, 0));
+    assert_eq!(computed.width, Length::Pixels(120.0));
+    assert_eq!(computed.bounds.w, 120.0);
+
+    // Properties which aren't set report their defaults
+    assert_eq!(computed.border_color, Color::default());
+    assert_eq!(computed.font_size, 16.0);
+
+    // Pseudo-classes are taken into account
+    panel.set_hover(&mut state, true);
+
+    while !state.event_queue.is_empty() {
+        event_manager.flush_events(&mut state);
+    }
+
+    assert_eq!(
+        state.computed_style(panel).background_color,
+        Color::rgb(0, 255, 0)
+    );
+
+    println!("{:#?}", computed);
+}
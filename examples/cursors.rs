@@ -0,0 +1,41 @@
+extern crate tuix;
+
+use tuix::*;
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        window.set_flex_direction(state, FlexDirection::Column);
+
+        // Textboxes show the text cursor when hovered
+        Textbox::new("Hover to see the text cursor").build(state, window, |builder| {
+            builder
+                .set_width(Length::Pixels(250.0))
+                .set_height(Length::Pixels(30.0))
+                .set_margin(Length::Pixels(10.0))
+                .set_background_color(Color::rgb(50, 50, 50))
+        });
+
+        for (label, cursor) in [
+            ("Hand", CursorIcon::Hand),
+            ("Move", CursorIcon::Move),
+            ("Resize", CursorIcon::EwResize),
+            ("Not allowed", CursorIcon::NotAllowed),
+        ]
+        .iter()
+        {
+            Button::with_label(label).build(state, window, |builder| {
+                builder
+                    .set_cursor(*cursor)
+                    .set_width(Length::Pixels(250.0))
+                    .set_height(Length::Pixels(30.0))
+                    .set_margin(Length::Pixels(10.0))
+                    .set_background_color(Color::rgb(80, 80, 120))
+                    .set_text_justify(Justify::Center)
+            });
+        }
+
+        win_desc.with_title("cursors")
+    });
+
+    app.run();
+}
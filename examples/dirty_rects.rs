@@ -0,0 +1,69 @@
+extern crate tuix;
+
+use tuix::*;
+
+const NUM_ROWS: usize = 40;
+const NUM_COLUMNS: usize = 40;
+
+static THEME: &'static str = r#"
+    .cell {
+        width: 18px;
+        height: 12px;
+        margin: 1px;
+        background-color: #3a3a3a;
+        border-radius: 2px;
+    }
+
+    .cell:hover {
+        background-color: #5a9bd5;
+    }
+"#;
+
+// A grid of cells which highlight when hovered
+// Pressing D toggles dirty rectangles so that hovering only repaints the cells which changed
+struct Grid {}
+
+impl BuildHandler for Grid {
+    type Ret = Entity;
+
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity.set_flex_direction(state, FlexDirection::Column);
+
+        for _ in 0..NUM_ROWS {
+            let row = Element::new().build(state, entity, |builder| {
+                builder.set_flex_direction(FlexDirection::Row)
+            });
+
+            for _ in 0..NUM_COLUMNS {
+                Element::new().build(state, row, |builder| builder.class("cell"));
+            }
+        }
+
+        entity
+    }
+}
+
+impl EventHandler for Grid {
+    fn on_event(&mut self, state: &mut State, _entity: Entity, event: &mut Event) -> bool {
+        if let Some(WindowEvent::KeyDown(Code::KeyD, _, false)) =
+            event.message.downcast::<WindowEvent>()
+        {
+            state.dirty_rects = !state.dirty_rects;
+            println!("Dirty rectangles: {}", state.dirty_rects);
+        }
+
+        false
+    }
+}
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.insert_theme(THEME);
+
+        Grid {}.build(state, window, |builder| builder.set_flex_grow(1.0));
+
+        win_desc.with_title("dirty rectangles")
+    });
+
+    app.run();
+}
@@ -0,0 +1,145 @@
+extern crate tuix;
+
+use tuix::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToggleEvent {
+    ToggleDisplay,
+    ToggleVisibility,
+}
+
+// Toggles the display or visibility of the middle of three items and logs the sibling positions
+// A non-displayed item gives up its space while an invisible item keeps it reserved
+struct DisplayToggle {
+    items: Vec<Entity>,
+    displayed: bool,
+    visible: bool,
+    changed: bool,
+}
+
+impl DisplayToggle {
+    pub fn new() -> Self {
+        DisplayToggle {
+            items: Vec::new(),
+            displayed: true,
+            visible: true,
+            changed: false,
+        }
+    }
+}
+
+impl BuildHandler for DisplayToggle {
+    type Ret = Entity;
+
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        let controls = Element::new().build(state, entity, |builder| {
+            builder
+                .set_flex_direction(FlexDirection::Row)
+                .set_height(Length::Pixels(30.0))
+        });
+
+        Button::with_label("Toggle Display")
+            .on_press(Event::new(ToggleEvent::ToggleDisplay).target(entity))
+            .build(state, controls, |builder| {
+                builder
+                    .set_width(Length::Pixels(150.0))
+                    .set_background_color(Color::rgb(100, 100, 100))
+            });
+
+        Button::with_label("Toggle Visibility")
+            .on_press(Event::new(ToggleEvent::ToggleVisibility).target(entity))
+            .build(state, controls, |builder| {
+                builder
+                    .set_width(Length::Pixels(150.0))
+                    .set_background_color(Color::rgb(100, 100, 100))
+            });
+
+        let row = Element::new().build(state, entity, |builder| {
+            builder
+                .set_flex_grow(1.0)
+                .set_flex_direction(FlexDirection::Row)
+        });
+
+        for color in [
+            Color::rgb(200, 80, 80),
+            Color::rgb(80, 200, 80),
+            Color::rgb(80, 80, 200),
+        ]
+        .iter()
+        {
+            let item = Element::new().build(state, row, |builder| {
+                builder
+                    .set_width(Length::Pixels(100.0))
+                    .set_background_color(*color)
+            });
+
+            self.items.push(item);
+        }
+
+        entity
+    }
+}
+
+impl EventHandler for DisplayToggle {
+    fn on_event(&mut self, state: &mut State, _entity: Entity, event: &mut Event) -> bool {
+        if let Some(toggle_event) = event.message.downcast::<ToggleEvent>() {
+            match toggle_event {
+                ToggleEvent::ToggleDisplay => {
+                    self.displayed = !self.displayed;
+                    let display = if self.displayed {
+                        Display::Normal
+                    } else {
+                        Display::None
+                    };
+                    self.items[1].set_display(state, display);
+                    self.changed = true;
+                }
+
+                ToggleEvent::ToggleVisibility => {
+                    self.visible = !self.visible;
+                    let visibility = if self.visible {
+                        Visibility::Visible
+                    } else {
+                        Visibility::Invisible
+                    };
+                    self.items[1].set_visibility(state, visibility);
+                    self.changed = true;
+                }
+            }
+        }
+
+        if let Some(window_event) = event.message.downcast::<WindowEvent>() {
+            match window_event {
+                // The new positions are only known once the relayout has happened
+                WindowEvent::Relayout => {
+                    if self.changed {
+                        for item in self.items.iter() {
+                            println!(
+                                "Item {}: posx {} width {}",
+                                item,
+                                state.transform.get_posx(*item),
+                                state.transform.get_width(*item)
+                            );
+                        }
+
+                        self.changed = false;
+                    }
+                }
+
+                _ => {}
+            }
+        }
+
+        false
+    }
+}
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        DisplayToggle::new().build(state, window, |builder| builder.set_flex_grow(1.0));
+
+        win_desc.with_title("display none")
+    });
+
+    app.run();
+}
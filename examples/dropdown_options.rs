@@ -0,0 +1,61 @@
+extern crate tuix;
+
+use tuix::*;
+
+use tuix::style::themes::DEFAULT_THEME;
+
+// Prints the option selected in a dropdown
+struct Selector {
+    options: Vec<String>,
+}
+
+impl Selector {
+    pub fn new(options: Vec<String>) -> Self {
+        Selector { options }
+    }
+}
+
+impl BuildHandler for Selector {
+    type Ret = Entity;
+
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        Dropdown::new("Choose a fruit")
+            .with_options(self.options.clone())
+            .build(state, entity, |builder| {
+                builder
+                    .set_width(Length::Pixels(150.0))
+                    .set_height(Length::Pixels(30.0))
+            });
+
+        entity
+    }
+}
+
+impl EventHandler for Selector {
+    fn on_event(&mut self, _state: &mut State, _entity: Entity, event: &mut Event) -> bool {
+        if let Some(DropdownEvent::Selected(index, text)) =
+            event.message.downcast::<DropdownEvent>()
+        {
+            println!("Selected option {}: {}", index, text);
+        }
+
+        false
+    }
+}
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.insert_theme(DEFAULT_THEME);
+
+        let fruits = vec!["Apple", "Banana", "Cherry", "Damson"]
+            .into_iter()
+            .map(|fruit| fruit.to_string())
+            .collect();
+
+        Selector::new(fruits).build(state, window, |builder| builder);
+
+        win_desc.with_title("dropdown options")
+    });
+
+    app.run();
+}
@@ -0,0 +1,40 @@
+extern crate tuix;
+
+use tuix::*;
+
+const STYLE: &str = r#"
+    element.box {
+        width: 100px;
+        height: 100px;
+        margin-left: 10px;
+    }
+"#;
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.insert_theme(STYLE);
+
+        let row = Element::new().build(state, window, |builder| {
+            builder
+                .set_flex_direction(FlexDirection::Row)
+                .set_align_items(AlignItems::Center)
+                .set_flex_grow(1.0)
+        });
+
+        for color in [
+            Color::rgb(200, 80, 80),
+            Color::rgb(80, 200, 80),
+            Color::rgb(80, 80, 200),
+        ]
+        .iter()
+        {
+            Element::new().build(state, row, |builder| {
+                builder.class("box").set_background_color(*color)
+            });
+        }
+
+        win_desc.with_title("element row")
+    });
+
+    app.run();
+}
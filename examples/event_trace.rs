@@ -0,0 +1,31 @@
+extern crate tuix;
+
+use tuix::*;
+
+// Prints the events dispatched when a button is clicked
+fn main() {
+    let mut state = State::new_headless();
+    let mut event_manager = EventManager::new();
+    let root = state.root;
+
+    let button = Button::with_label("Press")
+        .on_press(Event::new(WindowEvent::Redraw))
+        .build(&mut state, root, |builder| builder);
+
+    while !state.event_queue.is_empty() {
+        event_manager.flush_events(&mut state);
+    }
+
+    state.trace_events(true);
+
+    // Click the button
+    state.hovered = button;
+    state.insert_event(Event::new(WindowEvent::MouseDown(MouseButton::Left)).target(button));
+    state.insert_event(Event::new(WindowEvent::MouseUp(MouseButton::Left)).target(button));
+
+    while !state.event_queue.is_empty() {
+        event_manager.flush_events(&mut state);
+    }
+
+    state.trace_events(false);
+}
@@ -0,0 +1,68 @@
+extern crate tuix;
+
+use tuix::*;
+
+use std::time::Duration;
+
+// A panel which fades in when hovered and fades out again when the mouse leaves
+pub struct FadePanel {}
+
+impl FadePanel {
+    pub fn new() -> Self {
+        FadePanel {}
+    }
+}
+
+impl BuildHandler for FadePanel {
+    type Ret = Entity;
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity
+            .set_width(state, Length::Pixels(200.0))
+            .set_height(state, Length::Pixels(200.0))
+            .set_background_color(state, Color::rgb(80, 80, 200))
+            .set_opacity(state, 0.3);
+
+        entity
+    }
+}
+
+impl EventHandler for FadePanel {
+    fn on_event(&mut self, state: &mut State, entity: Entity, event: &mut Event) -> bool {
+        if let Some(window_event) = event.message.downcast::<WindowEvent>() {
+            // Start from the current opacity so reversing part way through doesn't jump
+            let opacity = state
+                .style
+                .opacity
+                .get(entity)
+                .cloned()
+                .unwrap_or_default()
+                .0;
+
+            match window_event {
+                WindowEvent::MouseOver(target) if *target == entity => {
+                    entity.animate_opacity(state, opacity, 1.0, Duration::from_millis(200));
+                }
+
+                WindowEvent::MouseOut(target) if *target == entity => {
+                    entity.animate_opacity(state, opacity, 0.3, Duration::from_millis(200));
+                }
+
+                _ => {}
+            }
+        }
+
+        false
+    }
+}
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        FadePanel::new().build(state, window, |builder| {
+            builder.set_margin(Length::Pixels(50.0))
+        });
+
+        win_desc.with_title("fade")
+    });
+
+    app.run();
+}
@@ -0,0 +1,61 @@
+extern crate tuix;
+
+use tuix::*;
+
+use tuix::style::themes::DEFAULT_THEME;
+
+static THEME: &'static str = r#"
+.row {
+    flex-direction: row;
+    padding: 10px;
+}
+
+.row>button {
+    margin-right: 10px;
+}
+
+/* An explicit auto size fits the text, padding is added around it */
+button.fit {
+    width: auto;
+    height: auto;
+    padding-left: 10px;
+    padding-right: 10px;
+    padding-top: 5px;
+    padding-bottom: 5px;
+}
+"#;
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.insert_theme(DEFAULT_THEME);
+        state.insert_theme(THEME);
+
+        let row = HBox::new().build(state, window, |builder| builder.class("row"));
+
+        // Fixed size, the label is clipped or leaves empty space
+        Button::with_label("Fixed size").build(state, row, |builder| {
+            builder
+                .set_width(Length::Pixels(150.0))
+                .set_height(Length::Pixels(30.0))
+        });
+
+        // Shrinks to fit the label
+        Button::with_label("Fit").build(state, row, |builder| builder.class("fit"));
+
+        // Grows to fit the label
+        Button::with_label("A button with a longer label")
+            .build(state, row, |builder| builder.class("fit"));
+
+        // The same set inline
+        Button::with_label("Inline").build(state, row, |builder| {
+            builder
+                .set_width(Length::Auto)
+                .set_height(Length::Auto)
+                .set_padding(Length::Pixels(5.0))
+        });
+
+        win_desc.with_title("Fit Content")
+    });
+
+    app.run();
+}
@@ -0,0 +1,38 @@
+extern crate tuix;
+
+use tuix::*;
+
+// Labels in a row share the free space according to their flex-grow
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        let row = Element::new().build(state, window, |builder| {
+            builder
+                .set_flex_direction(FlexDirection::Row)
+                .set_height(Length::Pixels(50.0))
+        });
+
+        Label::new("flex-grow: 1").build(state, row, |builder| {
+            builder
+                .set_flex_grow(1.0)
+                .set_background_color(Color::rgb(100, 50, 50))
+        });
+
+        Label::new("flex-grow: 2").build(state, row, |builder| {
+            builder
+                .set_flex_grow(2.0)
+                .set_background_color(Color::rgb(50, 100, 50))
+        });
+
+        Label::new("flex-grow: 1")
+            .with_color(Color::rgb(0, 0, 0))
+            .build(state, row, |builder| {
+                builder
+                    .set_flex_grow(1.0)
+                    .set_background_color(Color::rgb(200, 200, 250))
+            });
+
+        win_desc.with_title("Flex Grow")
+    });
+
+    app.run();
+}
@@ -0,0 +1,35 @@
+extern crate tuix;
+
+use tuix::*;
+
+// The outline is only shown while a textbox has focus and doesn't change its size
+static THEME: &'static str = r#"
+textbox {
+    width: 200px;
+    height: 30px;
+    margin: 10px;
+    background-color: #303030;
+    border-width: 1px;
+    border-color: #505050;
+    border-radius: 3px;
+}
+
+textbox:focus {
+    outline-width: 2px;
+    outline-offset: 2px;
+    outline-color: #4080ff;
+}
+"#;
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.insert_theme(THEME);
+
+        Textbox::new("First").build(state, window, |builder| builder);
+        Textbox::new("Second").build(state, window, |builder| builder);
+
+        win_desc.with_title("focus ring")
+    });
+
+    app.run();
+}
@@ -0,0 +1,38 @@
+extern crate tuix;
+
+use tuix::*;
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        window.set_flex_direction(state, FlexDirection::Row);
+
+        let directions = [
+            ("left to right", GradientDirection::LeftToRight),
+            ("right to left", GradientDirection::RightToLeft),
+            ("top to bottom", GradientDirection::TopToBottom),
+            ("bottom to top", GradientDirection::BottomToTop),
+            ("45 degrees", GradientDirection::Angle(45.0)),
+            ("200 degrees", GradientDirection::Angle(200.0)),
+        ];
+
+        // Each box blends from red to blue in a different direction
+        for (name, direction) in directions.iter() {
+            Label::new(name).build(state, window, |builder| {
+                builder
+                    .set_width(Length::Pixels(110.0))
+                    .set_height(Length::Pixels(110.0))
+                    .set_margin(Length::Pixels(10.0))
+                    .set_text_justify(Justify::Center)
+                    .set_background_gradient(
+                        LinearGradient::new(*direction)
+                            .with_stop(0.0, Color::rgb(200, 50, 50))
+                            .with_stop(1.0, Color::rgb(50, 50, 200)),
+                    )
+            });
+        }
+
+        win_desc.with_title("gradients")
+    });
+
+    app.run();
+}
@@ -0,0 +1,127 @@
+extern crate tuix;
+
+use tuix::*;
+
+use tuix::style::themes::DEFAULT_THEME;
+
+use femtovg::{Align, Baseline, Paint};
+
+use std::time::Duration;
+
+static THEME: &'static str = r#"
+button {
+    width: 100px;
+    height: 30px;
+    background-color: #303030;
+    text-justify: center;
+}
+
+custom {
+    width: 150px;
+    height: 90px;
+    background-color: #404040;
+    border-radius: 3px;
+}
+"#;
+
+const ITEMS: [&str; 3] = ["New", "Open", "Save"];
+
+#[derive(Debug, Clone, PartialEq)]
+enum FileMenuEvent {
+    Toggle,
+}
+
+// A menu which grows down from its top edge when opened, instead of from its center
+struct FileMenu {
+    menu: Entity,
+    open: bool,
+}
+
+impl FileMenu {
+    pub fn new() -> Self {
+        FileMenu {
+            menu: Entity::null(),
+            open: false,
+        }
+    }
+}
+
+impl BuildHandler for FileMenu {
+    type Ret = Entity;
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity
+            .set_flex_direction(state, FlexDirection::Column)
+            .set_padding(state, Length::Pixels(20.0));
+
+        Button::with_label("File")
+            .on_press(Event::new(FileMenuEvent::Toggle))
+            .build(state, entity, |builder| builder);
+
+        // The items are drawn by the menu itself so they're scaled with it
+        self.menu = CustomWidget::new(|state, entity, canvas| {
+            let font_id = match state.fonts.regular {
+                Some(font_id) => font_id,
+                None => return,
+            };
+
+            let bounds = state.content_box(entity);
+            let row_height = bounds.h / ITEMS.len() as f32;
+
+            let mut paint = Paint::color(femtovg::Color::rgb(228, 228, 228));
+            paint.set_font(&[font_id]);
+            paint.set_font_size(14.0);
+            paint.set_text_align(Align::Left);
+            paint.set_text_baseline(Baseline::Middle);
+
+            for (index, item) in ITEMS.iter().enumerate() {
+                let y = bounds.y + (index as f32 + 0.5) * row_height;
+                let _ = canvas.fill_text(bounds.x + 10.0, y, item, &paint);
+            }
+        })
+        .build(state, entity, |builder| {
+            builder
+                .set_transform_origin(TransformOrigin::TopLeft)
+                .set_display(Display::None)
+        });
+
+        entity
+    }
+}
+
+impl EventHandler for FileMenu {
+    fn on_event(&mut self, state: &mut State, _entity: Entity, event: &mut Event) -> bool {
+        if let Some(FileMenuEvent::Toggle) = event.message.downcast::<FileMenuEvent>() {
+            self.open = !self.open;
+
+            if self.open {
+                // Starts just above zero as a scale of zero can't be drawn
+                self.menu.set_display(state, Display::Flexbox).animate(
+                    state,
+                    |style| &mut style.scaley,
+                    Scale(0.01),
+                    Scale(1.0),
+                    Duration::from_millis(150),
+                );
+            } else {
+                self.menu.set_display(state, Display::None);
+            }
+
+            return true;
+        }
+
+        false
+    }
+}
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.insert_theme(DEFAULT_THEME);
+        state.insert_theme(THEME);
+
+        FileMenu::new().build(state, window, |builder| builder);
+
+        win_desc.with_title("grow menu")
+    });
+
+    app.run();
+}
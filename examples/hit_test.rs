@@ -0,0 +1,76 @@
+extern crate tuix;
+
+use tuix::*;
+
+// Prints the topmost entity under the cursor when the mouse is pressed
+struct HitTester {
+    red: Entity,
+    blue: Entity,
+}
+
+impl BuildHandler for HitTester {
+    type Ret = Entity;
+
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity.set_flex_grow(state, 1.0);
+
+        self.red = Element::new().build(state, entity, |builder| {
+            builder
+                .set_position(Position::Absolute)
+                .set_left(Length::Pixels(50.0))
+                .set_top(Length::Pixels(50.0))
+                .set_width(Length::Pixels(200.0))
+                .set_height(Length::Pixels(200.0))
+                .set_background_color(Color::rgb(200, 80, 80))
+        });
+
+        // Overlaps the bottom-right corner of the red box and is drawn above it
+        self.blue = Element::new().build(state, entity, |builder| {
+            builder
+                .set_position(Position::Absolute)
+                .set_left(Length::Pixels(150.0))
+                .set_top(Length::Pixels(150.0))
+                .set_width(Length::Pixels(200.0))
+                .set_height(Length::Pixels(200.0))
+                .set_z_order(1)
+                .set_background_color(Color::rgb(80, 80, 200))
+        });
+
+        entity
+    }
+}
+
+impl EventHandler for HitTester {
+    fn on_event(&mut self, state: &mut State, _entity: Entity, event: &mut Event) -> bool {
+        if let Some(WindowEvent::MouseDown(MouseButton::Left)) =
+            event.message.downcast::<WindowEvent>()
+        {
+            let hit = state.hit_test(state.mouse.cursorx, state.mouse.cursory);
+
+            let name = match hit {
+                Some(entity) if entity == self.red => "red",
+                Some(entity) if entity == self.blue => "blue",
+                Some(_) => "background",
+                None => "nothing",
+            };
+
+            println!("Hit {}", name);
+        }
+
+        false
+    }
+}
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        HitTester {
+            red: Entity::null(),
+            blue: Entity::null(),
+        }
+        .build(state, window, |builder| builder);
+
+        win_desc.with_title("hit test")
+    });
+
+    app.run();
+}
@@ -0,0 +1,21 @@
+extern crate tuix;
+
+use tuix::*;
+
+// Edit examples/themes/hot_reload_theme.css while this is running to see the changes applied
+// Run with: cargo run --example hot_reload --features hot-reload
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        match state.watch_stylesheet("examples/themes/hot_reload_theme.css") {
+            Ok(_) => {}
+            Err(e) => println!("Error watching stylesheet: {}", e),
+        }
+
+        Element::new().build(state, window, |builder| builder.class("panel"));
+        Button::with_label("Button").build(state, window, |builder| builder);
+
+        win_desc.with_title("hot reload")
+    });
+
+    app.run();
+}
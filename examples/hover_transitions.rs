@@ -0,0 +1,46 @@
+extern crate tuix;
+
+use tuix::*;
+
+// The properties listed in the transition animate when the hover state of a button changes
+static THEME: &'static str = r#"
+button {
+    width: 150px;
+    height: 30px;
+    margin: 10px;
+    color: #ffffff;
+    text-justify: center;
+    background-color: #404040;
+    border-radius: 3px;
+    transition: background-color 0.2 0.0, color 0.2 0.0;
+}
+
+button:hover {
+    background-color: #3a6ea5;
+    color: #ffd700;
+    transition: background-color 0.2 0.0, color 0.2 0.0;
+}
+
+button.slow {
+    transition: background-color 1.0 0.0;
+}
+
+button.slow:hover {
+    background-color: #a53a3a;
+    transition: background-color 1.0 0.0;
+}
+"#;
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.style.parse_theme(THEME);
+
+        Button::with_label("Hover me").build(state, window, |builder| builder);
+        Button::with_label("And me").build(state, window, |builder| builder);
+        Button::with_label("Slowly").build(state, window, |builder| builder.class("slow"));
+
+        win_desc.with_title("hover transitions")
+    });
+
+    app.run();
+}
@@ -0,0 +1,56 @@
+extern crate tuix;
+
+use tuix::*;
+
+use tuix::style::themes::DEFAULT_THEME;
+
+static THEME: &'static str = r#"
+.toolbar {
+    flex-direction: row;
+    height: 40px;
+    padding: 5px;
+    background-color: #303030;
+}
+
+icon {
+    width: 30px;
+    height: 30px;
+    font-size: 20px;
+    margin-right: 5px;
+}
+
+icon:hover {
+    background-color: #505050;
+}
+"#;
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.insert_theme(DEFAULT_THEME);
+        state.insert_theme(THEME);
+
+        // Names can be added for glyphs of the icons font, or of another icon font added with add_font
+        state.register_icons(&[("open", '\u{e75e}'), ("favourite", '\u{2605}')]);
+
+        let toolbar = Element::new().build(state, window, |builder| builder.class("toolbar"));
+
+        for name in [
+            "menu",
+            "home",
+            "open",
+            "save",
+            "search",
+            "favourite",
+            "settings",
+            "close",
+        ]
+        .iter()
+        {
+            Icon::new(name).build(state, toolbar, |builder| builder);
+        }
+
+        win_desc.with_title("Icon Toolbar")
+    });
+
+    app.run();
+}
@@ -0,0 +1,50 @@
+extern crate tuix;
+
+use tuix::*;
+
+use tuix::style::themes::DEFAULT_THEME;
+
+static THEME: &'static str = r#"
+.row {
+    flex-direction: row;
+    flex-grow: 1.0;
+}
+
+image {
+    flex-grow: 1.0;
+    margin: 10px;
+    padding: 5px;
+    background-color: #303030;
+}
+"#;
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.insert_theme(DEFAULT_THEME);
+        state.insert_theme(THEME);
+
+        // The images grow with the window, resize it to see how each mode scales the image
+        let row = Element::new().build(state, window, |builder| builder.class("row"));
+
+        for fit in [
+            ImageFit::Fill,
+            ImageFit::Contain,
+            ImageFit::Cover,
+            ImageFit::None,
+        ]
+        .iter()
+        {
+            Image::new("resources/icons/Calculator-128.png")
+                .with_fit(*fit)
+                .build(state, row, |builder| builder);
+        }
+
+        // A missing image is drawn as a placeholder
+        let row = Element::new().build(state, window, |builder| builder.class("row"));
+        Image::new("resources/icons/missing.png").build(state, row, |builder| builder);
+
+        win_desc.with_title("Image")
+    });
+
+    app.run();
+}
@@ -0,0 +1,36 @@
+extern crate tuix;
+
+use tuix::*;
+
+use tuix::style::themes::DEFAULT_THEME;
+
+// Hover over an entity to see its id, element name and size, press F12 to toggle the inspector
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.insert_theme(DEFAULT_THEME);
+
+        state.enable_inspector(true);
+        state.set_inspector_key(Accelerator::new(Key::F12));
+
+        let row = HBox::new().build(state, window, |builder| {
+            builder
+                .set_height(Length::Pixels(50.0))
+                .set_margin(Length::Pixels(20.0))
+        });
+
+        Button::with_label("Button").build(state, row, |builder| builder);
+        Checkbox::new(false).build(state, row, |builder| {
+            builder.set_margin_left(Length::Pixels(10.0))
+        });
+
+        Textbox::new("Textbox").build(state, window, |builder| {
+            builder
+                .set_width(Length::Pixels(200.0))
+                .set_margin(Length::Pixels(20.0))
+        });
+
+        win_desc.with_title("inspector")
+    });
+
+    app.run();
+}
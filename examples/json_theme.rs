@@ -0,0 +1,22 @@
+extern crate tuix;
+
+use tuix::*;
+
+// Styles the window from a JSON stylesheet
+// Run with: cargo run --example json_theme --features stylesheets
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        match state.load_stylesheet("examples/themes/json_theme.json") {
+            Ok(_) => {}
+            Err(e) => println!("Error loading stylesheet: {}", e),
+        }
+
+        Label::new("Styled from a JSON file").build(state, window, |builder| builder.id("title"));
+        Element::new().build(state, window, |builder| builder.class("panel"));
+        Button::with_label("Hover me").build(state, window, |builder| builder);
+
+        win_desc.with_title("json theme")
+    });
+
+    app.run();
+}
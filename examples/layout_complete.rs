@@ -0,0 +1,103 @@
+extern crate tuix;
+
+use tuix::*;
+
+// Positions itself below an anchor once the layout has given the anchor its final bounds
+struct Popup {
+    anchor: Entity,
+    placed: Option<(f32, f32)>,
+}
+
+impl Popup {
+    pub fn new(anchor: Entity) -> Self {
+        Popup {
+            anchor,
+            placed: None,
+        }
+    }
+}
+
+impl BuildHandler for Popup {
+    type Ret = Entity;
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity
+            .set_position(state, Position::Absolute)
+            .set_width(state, Length::Pixels(120.0))
+            .set_height(state, Length::Pixels(80.0));
+
+        entity
+    }
+}
+
+impl EventHandler for Popup {
+    fn on_event(&mut self, state: &mut State, entity: Entity, event: &mut Event) -> bool {
+        if let Some(window_event) = event.message.downcast::<WindowEvent>() {
+            match window_event {
+                WindowEvent::LayoutComplete => {
+                    let x = state.transform.get_posx(self.anchor);
+                    let y = state.transform.get_posy(self.anchor)
+                        + state.transform.get_height(self.anchor);
+
+                    // Moving the popup causes another layout, so it's only moved when the anchor has moved
+                    if self.placed != Some((x, y)) {
+                        self.placed = Some((x, y));
+                        entity
+                            .set_left(state, Length::Pixels(x))
+                            .set_top(state, Length::Pixels(y));
+                    }
+                }
+
+                _ => {}
+            }
+        }
+
+        false
+    }
+}
+
+// Keeps a popup below its anchor when the window is resized, without opening a window
+fn main() {
+    let mut state = State::new_headless();
+    let mut event_manager = EventManager::new();
+
+    let root = state.root;
+
+    // A quarter of the way across the window
+    let anchor = Element::new().build(&mut state, root, |builder| {
+        builder
+            .set_left(Length::Percentage(0.25))
+            .set_top(Length::Pixels(40.0))
+            .set_width(Length::Pixels(100.0))
+            .set_height(Length::Pixels(30.0))
+    });
+
+    let popup = Popup::new(anchor).build(&mut state, root, |builder| builder);
+
+    let flush = |state: &mut State, event_manager: &mut EventManager| {
+        while !state.event_queue.is_empty() {
+            event_manager.flush_events(state);
+        }
+    };
+
+    let below_anchor = |state: &State| {
+        (
+            state.transform.get_posx(popup) == state.transform.get_posx(anchor),
+            state.transform.get_posy(popup)
+                == state.transform.get_posy(anchor) + state.transform.get_height(anchor),
+        )
+    };
+
+    flush(&mut state, &mut event_manager);
+
+    assert_eq!(state.transform.get_posx(anchor), 200.0);
+    assert_eq!(below_anchor(&state), (true, true));
+
+    // The anchor moves with the window and the popup follows it
+    state.insert_event(Event::new(WindowEvent::WindowResize(400.0, 600.0)).target(root));
+    flush(&mut state, &mut event_manager);
+
+    assert_eq!(state.transform.get_posx(anchor), 100.0);
+    assert_eq!(below_anchor(&state), (true, true));
+
+    println!("Positioned the popup after layout");
+}
@@ -0,0 +1,76 @@
+extern crate tuix;
+
+use tuix::*;
+
+// A button which starts and stops a loading spinner
+struct SpinnerToggle {
+    spinning: bool,
+    button: Entity,
+    spinner: Entity,
+}
+
+impl SpinnerToggle {
+    pub fn new() -> Self {
+        SpinnerToggle {
+            spinning: false,
+            button: Entity::null(),
+            spinner: Entity::null(),
+        }
+    }
+}
+
+impl BuildHandler for SpinnerToggle {
+    type Ret = Entity;
+
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        self.button = Button::with_label("Start").build(state, entity, |builder| {
+            builder
+                .set_width(Length::Pixels(100.0))
+                .set_height(Length::Pixels(30.0))
+                .set_background_color(Color::rgb(80, 80, 200))
+        });
+
+        self.spinner = LoadingSpinner::new()
+            .with_speed(180.0)
+            .stopped()
+            .build(state, entity, |builder| {
+                builder
+                    .set_width(Length::Pixels(30.0))
+                    .set_height(Length::Pixels(30.0))
+            })
+            .set_font_size(state, 24.0);
+
+        entity
+    }
+}
+
+impl EventHandler for SpinnerToggle {
+    fn on_event(&mut self, state: &mut State, _entity: Entity, event: &mut Event) -> bool {
+        if let Some(ButtonEvent::Clicked) = event.message.downcast::<ButtonEvent>() {
+            if event.target == self.button {
+                self.spinning = !self.spinning;
+
+                let (spinner_event, label) = if self.spinning {
+                    (LoadingSpinnerEvent::Start, "Stop")
+                } else {
+                    (LoadingSpinnerEvent::Stop, "Start")
+                };
+
+                state.insert_event(Event::new(spinner_event).target(self.spinner));
+                self.button.set_text(state, label);
+            }
+        }
+
+        false
+    }
+}
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        SpinnerToggle::new().build(state, window, |builder| builder);
+
+        win_desc.with_title("loading spinner")
+    });
+
+    app.run();
+}
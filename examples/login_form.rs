@@ -0,0 +1,103 @@
+extern crate tuix;
+
+use tuix::*;
+
+static THEME: &'static str = r#"
+textbox {
+    width: 200px;
+    height: 30px;
+    margin-bottom: 10px;
+    padding-left: 5px;
+    background-color: #303030;
+    color: #e4e4e4;
+    text-justify: start;
+}
+
+button {
+    width: 200px;
+    height: 30px;
+    background-color: #4080ff;
+    text-justify: center;
+}
+"#;
+
+#[derive(Debug, Clone, PartialEq)]
+enum LoginEvent {
+    Submit,
+}
+
+// A login form with a password field which only shows mask characters
+struct LoginForm {
+    username: Entity,
+    password: Entity,
+}
+
+impl BuildHandler for LoginForm {
+    type Ret = Entity;
+
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity
+            .set_flex_direction(state, FlexDirection::Column)
+            .set_padding(state, Length::Pixels(20.0));
+
+        self.username = Textbox::new("")
+            .with_max_length(16)
+            .build(state, entity, |builder| builder);
+
+        self.password =
+            Textbox::new("")
+                .with_password(PASSWORD_MASK)
+                .build(state, entity, |builder| builder);
+
+        Button::with_label("Log in")
+            .on_press(Event::new(LoginEvent::Submit))
+            .build(state, entity, |builder| builder);
+
+        entity
+    }
+}
+
+impl EventHandler for LoginForm {
+    fn on_event(&mut self, state: &mut State, _entity: Entity, event: &mut Event) -> bool {
+        if let Some(LoginEvent::Submit) = event.message.downcast::<LoginEvent>() {
+            let username = state
+                .style
+                .text
+                .get(self.username)
+                .cloned()
+                .unwrap_or_default();
+            let password = state
+                .style
+                .text
+                .get(self.password)
+                .cloned()
+                .unwrap_or_default();
+
+            println!(
+                "Logging in as {} with a password of {} characters",
+                username.text,
+                password.text.chars().count()
+            );
+
+            return true;
+        }
+
+        false
+    }
+}
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.insert_theme(THEME);
+
+        LoginForm {
+            username: Entity::null(),
+            password: Entity::null(),
+        }
+        .build(state, window, |builder| builder);
+
+        win_desc.with_title("login form")
+    });
+
+    app.run();
+}
@@ -0,0 +1,105 @@
+extern crate tuix;
+
+use tuix::*;
+
+use tuix::widgets::{Menu, MenuBar, MenuEvent, MenuItem, MenuPosition};
+
+static THEME: &'static str = r#"
+    menu_bar {
+        height: 30px;
+        background-color: #262626;
+    }
+
+    menu {
+        width: 60px;
+        color: #e4e4e4;
+    }
+
+    menu:checked {
+        background-color: #383838;
+    }
+
+    menu>.container {
+        width: 150px;
+        background-color: #383838;
+    }
+
+    menu_item {
+        height: 30px;
+        padding-left: 5px;
+        color: #e4e4e4;
+    }
+
+    menu_item:hover {
+        background-color: #494949;
+    }
+
+    menu_item:focus {
+        background-color: #494949;
+    }
+
+    menu_item:disabled {
+        color: #7a7a7a;
+    }
+"#;
+
+// Prints the id of the chosen menu item
+struct Editor {}
+
+impl BuildHandler for Editor {
+    type Ret = Entity;
+
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity.set_flex_direction(state, FlexDirection::Column);
+
+        let menu_bar = MenuBar::new().build(state, entity, |builder| builder);
+
+        let file = Menu::new("File", MenuPosition::Down).build(state, menu_bar, |builder| {
+            builder.set_text_justify(Justify::Center)
+        });
+        MenuItem::new("new", "New").build(state, file, |builder| builder);
+        MenuItem::new("open", "Open").build(state, file, |builder| builder);
+        MenuItem::new("save", "Save")
+            .build(state, file, |builder| builder)
+            .set_disabled(state, true);
+        MenuItem::new("quit", "Quit").build(state, file, |builder| builder);
+
+        let edit = Menu::new("Edit", MenuPosition::Down).build(state, menu_bar, |builder| {
+            builder.set_text_justify(Justify::Center)
+        });
+        MenuItem::new("undo", "Undo")
+            .build(state, edit, |builder| builder)
+            .set_disabled(state, true);
+        MenuItem::new("cut", "Cut").build(state, edit, |builder| builder);
+        MenuItem::new("copy", "Copy").build(state, edit, |builder| builder);
+        MenuItem::new("paste", "Paste").build(state, edit, |builder| builder);
+
+        entity
+    }
+}
+
+impl EventHandler for Editor {
+    fn on_event(&mut self, state: &mut State, _entity: Entity, event: &mut Event) -> bool {
+        if let Some(MenuEvent::Selected(id)) = event.message.downcast::<MenuEvent>() {
+            println!("Selected: {}", id);
+
+            if id == "quit" {
+                state.insert_event(Event::new(WindowEvent::WindowClose));
+            }
+        }
+
+        false
+    }
+}
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.insert_theme(THEME);
+
+        Editor {}.build(state, window, |builder| builder.set_flex_grow(1.0));
+
+        win_desc.with_title("menu bar")
+    });
+
+    app.run();
+}
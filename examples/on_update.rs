@@ -0,0 +1,108 @@
+extern crate tuix;
+
+use tuix::*;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Settings {
+    title: String,
+    volume: String,
+}
+
+struct TitleLens;
+
+impl Lens for TitleLens {
+    type Source = Settings;
+    type Target = String;
+
+    fn view<'a>(&self, source: &'a Settings) -> &'a String {
+        &source.title
+    }
+
+    fn view_mut<'a>(&self, source: &'a mut Settings) -> &'a mut String {
+        &mut source.title
+    }
+}
+
+struct VolumeLens;
+
+impl Lens for VolumeLens {
+    type Source = Settings;
+    type Target = String;
+
+    fn view<'a>(&self, source: &'a Settings) -> &'a String {
+        &source.volume
+    }
+
+    fn view_mut<'a>(&self, source: &'a mut Settings) -> &'a mut String {
+        &mut source.volume
+    }
+}
+
+// Counts and logs the number of times the bound data is updated
+struct UpdateCounter {
+    name: String,
+    updates: usize,
+}
+
+impl UpdateCounter {
+    pub fn new(name: &str) -> Self {
+        UpdateCounter {
+            name: name.to_string(),
+            updates: 0,
+        }
+    }
+}
+
+impl Bindable for UpdateCounter {
+    type Data = String;
+
+    fn on_update(&mut self, state: &mut State, entity: Entity, data: &Self::Data) {
+        self.updates += 1;
+        println!("{} update {}: {}", self.name, self.updates, data);
+        entity.set_text(state, &format!("{}: {}", self.name, data));
+    }
+}
+
+impl BuildHandler for UpdateCounter {
+    type Ret = Entity;
+
+    fn on_build(&mut self, _state: &mut State, entity: Entity) -> Self::Ret {
+        entity
+    }
+}
+
+impl EventHandler for UpdateCounter {}
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        let settings = Settings {
+            title: "Untitled".to_string(),
+            volume: "50".to_string(),
+        };
+
+        let model = Model::new(settings).build(state, window, |builder| builder.set_flex_grow(1.0));
+
+        // Editing the title only updates the title counter, not the volume counter
+        Wrapper::new(TitleLens, Textbox::new("")).build(state, model, |builder| {
+            builder
+                .set_width(Length::Pixels(200.0))
+                .set_height(Length::Pixels(30.0))
+        });
+
+        Wrapper::new(TitleLens, UpdateCounter::new("title")).build(state, model, |builder| {
+            builder
+                .set_width(Length::Pixels(200.0))
+                .set_height(Length::Pixels(30.0))
+        });
+
+        Wrapper::new(VolumeLens, UpdateCounter::new("volume")).build(state, model, |builder| {
+            builder
+                .set_width(Length::Pixels(200.0))
+                .set_height(Length::Pixels(30.0))
+        });
+
+        win_desc.with_title("on_update")
+    });
+
+    app.run();
+}
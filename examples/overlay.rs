@@ -0,0 +1,43 @@
+extern crate tuix;
+
+use tuix::*;
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        // A small container which clips its children
+        let container = Element::new().build(state, window, |builder| {
+            builder
+                .set_width(Length::Pixels(100.0))
+                .set_height(Length::Pixels(50.0))
+                .set_margin(Length::Pixels(50.0))
+                .set_background_color(Color::rgb(60, 60, 60))
+        });
+
+        container.set_clip_widget(state, container);
+
+        // Clipped to the container
+        Element::new().build(state, container, |builder| {
+            builder
+                .set_width(Length::Pixels(200.0))
+                .set_height(Length::Pixels(20.0))
+                .set_background_color(Color::rgb(200, 80, 80))
+        });
+
+        // Escapes the clipping of the container and is drawn on top of everything else
+        // Overlays are positioned relative to the window
+        Element::new()
+            .build(state, container, |builder| {
+                builder
+                    .set_left(Length::Pixels(80.0))
+                    .set_top(Length::Pixels(80.0))
+                    .set_width(Length::Pixels(200.0))
+                    .set_height(Length::Pixels(100.0))
+                    .set_background_color(Color::rgb(80, 80, 200))
+            })
+            .set_overlay(state, true);
+
+        win_desc.with_title("overlay")
+    });
+
+    app.run();
+}
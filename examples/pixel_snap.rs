@@ -0,0 +1,67 @@
+extern crate tuix;
+
+use tuix::*;
+
+static THEME: &'static str = r#"
+    .box {
+        width: 60.3px;
+        height: 30.6px;
+        margin-left: 10.4px;
+        margin-top: 10.7px;
+        border-width: 1px;
+        border-color: #dddddd;
+        background-color: #3a3a3a;
+        color: #ffffff;
+        text-justify: center;
+    }
+"#;
+
+// Boxes with fractional positions and sizes, with 1px borders
+// Pressing S toggles pixel snapping to compare the sharpness of the borders and text
+struct SnapDemo {}
+
+impl BuildHandler for SnapDemo {
+    type Ret = Entity;
+
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity.set_flex_direction(state, FlexDirection::Column);
+
+        for _ in 0..6 {
+            let row = Element::new().build(state, entity, |builder| {
+                builder.set_flex_direction(FlexDirection::Row)
+            });
+
+            for _ in 0..6 {
+                Label::new("text").build(state, row, |builder| builder.class("box"));
+            }
+        }
+
+        entity
+    }
+}
+
+impl EventHandler for SnapDemo {
+    fn on_event(&mut self, state: &mut State, _entity: Entity, event: &mut Event) -> bool {
+        if let Some(WindowEvent::KeyDown(Code::KeyS, _, false)) =
+            event.message.downcast::<WindowEvent>()
+        {
+            let snapping = !state.pixel_snapping;
+            state.pixel_snap(snapping);
+            println!("Pixel snapping: {}", snapping);
+        }
+
+        false
+    }
+}
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.insert_theme(THEME);
+
+        SnapDemo {}.build(state, window, |builder| builder.set_flex_grow(1.0));
+
+        win_desc.with_title("pixel snapping")
+    });
+
+    app.run();
+}
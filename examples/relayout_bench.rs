@@ -0,0 +1,83 @@
+extern crate tuix;
+
+use tuix::*;
+
+use std::time::Instant;
+
+const NUM_ROWS: usize = 100;
+const NUM_COLUMNS: usize = 10;
+const NUM_CHANGES: usize = 100;
+
+// Times changing many nodes in a tree of 1000 nodes in one frame without opening a window
+// Every change queues its own relayout event, which used to run a full layout pass each,
+// while the layout now runs once after the event queue is flushed
+fn main() {
+    let mut state = State::new();
+    let mut event_manager = EventManager::new();
+
+    WindowWidget::new().build_window(&mut state);
+
+    let root = state.root;
+    state.style.width.insert(root, Length::Pixels(800.0));
+    state.style.height.insert(root, Length::Pixels(600.0));
+    state.transform.set_width(root, 800.0);
+    state.transform.set_height(root, 600.0);
+
+    let mut nodes = Vec::new();
+    for _ in 0..NUM_ROWS {
+        let row = Element::new().build(&mut state, root, |builder| {
+            builder
+                .set_flex_direction(FlexDirection::Row)
+                .set_height(Length::Pixels(6.0))
+        });
+
+        for _ in 0..NUM_COLUMNS - 1 {
+            nodes.push(Element::new().build(&mut state, row, |builder| {
+                builder.set_width(Length::Pixels(80.0))
+            }));
+        }
+    }
+
+    while !state.event_queue.is_empty() {
+        event_manager.flush_events(&mut state);
+    }
+
+    // The cost of a single full layout pass
+    let hierarchy = state.hierarchy.clone();
+    let start = Instant::now();
+    apply_layout(&mut state, &hierarchy);
+    let single_pass = start.elapsed();
+
+    // Change a different node each time so the relayout events aren't merged in the queue
+    for (i, node) in nodes.iter().step_by(nodes.len() / NUM_CHANGES).enumerate() {
+        node.set_width(&mut state, Length::Pixels(i as f32));
+    }
+
+    let relayout_events = state
+        .event_queue
+        .iter()
+        .filter(|event| event.message.equals_a(&WindowEvent::Relayout))
+        .count();
+
+    let start = Instant::now();
+    while !state.event_queue.is_empty() {
+        event_manager.flush_events(&mut state);
+    }
+    let batched = start.elapsed();
+
+    println!(
+        "{} changes to different nodes in a tree of {} nodes queued {} relayout events",
+        NUM_CHANGES,
+        NUM_ROWS * NUM_COLUMNS,
+        relayout_events
+    );
+    println!("One layout pass: {:?}", single_pass);
+    println!(
+        "One layout pass per relayout event (estimated): {:?}",
+        single_pass * relayout_events as u32
+    );
+    println!(
+        "Flushing the changes with a single layout pass: {:?}",
+        batched
+    );
+}
@@ -0,0 +1,82 @@
+extern crate tuix;
+
+use tuix::*;
+
+// Logs the sizes of its children after the window is resized
+struct SizeLogger {
+    resized: bool,
+}
+
+impl SizeLogger {
+    pub fn new() -> Self {
+        SizeLogger { resized: false }
+    }
+}
+
+impl BuildHandler for SizeLogger {
+    type Ret = Entity;
+
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        Element::new().build(state, entity, |builder| {
+            builder
+                .set_width(Length::Percentage(0.5))
+                .set_height(Length::Percentage(0.5))
+                .set_background_color(Color::rgb(200, 80, 80))
+        });
+
+        Element::new().build(state, entity, |builder| {
+            builder
+                .set_flex_grow(1.0)
+                .set_background_color(Color::rgb(80, 80, 200))
+        });
+
+        entity
+    }
+}
+
+impl EventHandler for SizeLogger {
+    fn on_event(&mut self, state: &mut State, entity: Entity, event: &mut Event) -> bool {
+        if let Some(window_event) = event.message.downcast::<WindowEvent>() {
+            match window_event {
+                WindowEvent::WindowResize(width, height) => {
+                    println!("Window Resized: {} x {}", width, height);
+                    self.resized = true;
+                }
+
+                // The new sizes are only known once the relayout has happened
+                WindowEvent::Relayout => {
+                    if self.resized {
+                        for child in entity.child_iter(&state.hierarchy) {
+                            println!(
+                                "Child {}: {} x {}",
+                                child,
+                                state.transform.get_width(child),
+                                state.transform.get_height(child)
+                            );
+                        }
+
+                        self.resized = false;
+                    }
+                }
+
+                _ => {}
+            }
+        }
+
+        false
+    }
+}
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        SizeLogger::new().build(state, window, |builder| {
+            builder
+                .set_flex_grow(1.0)
+                .set_flex_direction(FlexDirection::Row)
+        });
+
+        win_desc.with_title("resize")
+    });
+
+    app.run();
+}
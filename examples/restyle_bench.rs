@@ -0,0 +1,64 @@
+extern crate tuix;
+
+use tuix::*;
+
+use std::time::Instant;
+
+const DEPTH: usize = 100;
+const SIBLINGS: usize = 10;
+const NUM_HOVERS: usize = 100;
+
+static THEME: &'static str = r#"
+    .node {
+        background-color: #303030;
+    }
+
+    .node:hover {
+        background-color: #505050;
+    }
+
+    .node > .leaf {
+        height: 5px;
+    }
+"#;
+
+// Times restyling after hovering the deepest entity of a deep tree without opening a window
+// Compares a restyle of the whole tree with a restyle of only the hovered branch
+fn main() {
+    let mut state = State::new();
+    state.insert_theme(THEME);
+
+    let mut parent = state.root;
+    for _ in 0..DEPTH {
+        for _ in 0..SIBLINGS - 1 {
+            Element::new().build(&mut state, parent, |builder| builder.class("leaf"));
+        }
+
+        parent = Element::new().build(&mut state, parent, |builder| builder.class("node"));
+    }
+
+    let hierarchy = state.hierarchy.clone();
+    apply_styles(&mut state, &hierarchy);
+
+    let start = Instant::now();
+    for i in 0..NUM_HOVERS {
+        parent.set_hover(&mut state, i % 2 == 0);
+        apply_styles(&mut state, &hierarchy);
+    }
+    let full = start.elapsed();
+
+    let start = Instant::now();
+    for i in 0..NUM_HOVERS {
+        parent.set_hover(&mut state, i % 2 == 0);
+        apply_styles_from(&mut state, &hierarchy, parent);
+    }
+    let scoped = start.elapsed();
+
+    println!(
+        "{} hovers of the deepest of {} entities",
+        NUM_HOVERS,
+        DEPTH * SIBLINGS
+    );
+    println!("Full restyle: {:?}", full);
+    println!("Branch restyle: {:?}", scoped);
+}
@@ -0,0 +1,31 @@
+extern crate tuix;
+
+use tuix::*;
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        let column = Column::new().build(state, window, |builder| builder.set_flex_grow(1.0));
+
+        for i in 0..3 {
+            // Builder styling is applied on top of the preset flex direction
+            let row = Row::new().build(state, column, |builder| {
+                builder
+                    .set_flex_grow(1.0)
+                    .set_margin_bottom(Length::Pixels(5.0))
+            });
+
+            for j in 0..4 {
+                Element::new().build(state, row, |builder| {
+                    builder
+                        .set_flex_grow(1.0)
+                        .set_margin_right(Length::Pixels(5.0))
+                        .set_background_color(Color::rgb(50 + 50 * i, 50 + 40 * j, 150))
+                });
+            }
+        }
+
+        win_desc.with_title("rows and columns")
+    });
+
+    app.run();
+}
@@ -0,0 +1,46 @@
+extern crate tuix;
+
+use tuix::*;
+
+// Lays out the same widgets at two scale factors without opening a window
+fn main() {
+    let mut state = State::new_headless();
+    let mut event_manager = EventManager::new();
+
+    let root = state.root;
+
+    let panel = Element::new().build(&mut state, root, |builder| {
+        builder
+            .set_width(Length::Pixels(100.0))
+            .set_height(Length::Pixels(40.0))
+            .set_border_width(Length::Pixels(1.0))
+    });
+
+    let flush = |state: &mut State, event_manager: &mut EventManager| {
+        while !state.event_queue.is_empty() {
+            event_manager.flush_events(state);
+        }
+    };
+
+    flush(&mut state, &mut event_manager);
+
+    let logical_width = state.transform.get_width(panel);
+    assert_eq!(logical_width, 100.0);
+
+    // Moving to a display with twice the pixel density keeps the layout in logical pixels
+    // and doubles the number of physical pixels the panel covers when drawn
+    state.set_scale_factor(2.0);
+    flush(&mut state, &mut event_manager);
+
+    assert_eq!(state.scale_factor, 2.0);
+    assert_eq!(state.transform.get_width(panel), logical_width);
+    assert_eq!(state.transform.get_width(panel) * state.scale_factor, 200.0);
+
+    // Moving back to a standard display draws it with 100 physical pixels again
+    state.set_scale_factor(1.0);
+    flush(&mut state, &mut event_manager);
+
+    assert_eq!(state.transform.get_width(panel) * state.scale_factor, 100.0);
+
+    println!("Kept logical sizes across scale factors");
+}
@@ -0,0 +1,48 @@
+extern crate tuix;
+
+use tuix::*;
+
+const STYLE: &str = r#"
+    scroll_container {
+        flex-grow: 1.0;
+        background-color: #2e2e2e;
+    }
+
+    scroll_container>.container {
+        flex-grow: 1.0;
+    }
+
+    scroll_container>.scrollbar {
+        background-color: #bf6640;
+    }
+
+    scroll_container>.scrollbar:enabled {
+        width: 10px;
+    }
+
+    scroll_container>.scrollbar:disabled {
+        width: 0px;
+    }
+
+    label {
+        height: 30px;
+        flex-grow: 1.0;
+    }
+"#;
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.insert_theme(STYLE);
+
+        // The scroll container returns the entity that holds the content
+        let content = ScrollContainer::new().build(state, window, |builder| builder);
+
+        for i in 0..50 {
+            Label::new(&format!("Item {}", i)).build(state, content, |builder| builder);
+        }
+
+        win_desc.with_title("scroll list").with_inner_size(300, 400)
+    });
+
+    app.run();
+}
@@ -0,0 +1,48 @@
+extern crate tuix;
+
+use tuix::*;
+
+use tuix::style::themes::DEFAULT_THEME;
+
+static THEME: &'static str = r#"
+.form {
+    width: 300px;
+    padding: 10px;
+}
+
+.form>textbox {
+    margin-bottom: 10px;
+}
+
+/* Hex colors with alpha are written as #AARRGGBB */
+textbox.highlighted {
+    selection-color: #604080ff;
+    caret-color: #4080ff;
+}
+"#;
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.insert_theme(DEFAULT_THEME);
+        state.insert_theme(THEME);
+
+        let form = VBox::new().build(state, window, |builder| builder.class("form"));
+
+        // Uses the default selection and caret colors
+        Textbox::new("Default colors").build(state, form, |builder| builder);
+
+        // Styled by the theme
+        Textbox::new("Themed colors").build(state, form, |builder| builder.class("highlighted"));
+
+        // Styled inline
+        Textbox::new("Inline colors").build(state, form, |builder| {
+            builder
+                .set_selection_color(Color::rgba(80, 200, 80, 96))
+                .set_caret_color(Color::rgb(80, 200, 80))
+        });
+
+        win_desc.with_title("Selection Color")
+    });
+
+    app.run();
+}
@@ -0,0 +1,87 @@
+extern crate tuix;
+
+use tuix::*;
+
+use tuix::style::themes::DEFAULT_THEME;
+
+static THEME: &'static str = r#"
+.settings {
+    width: 300px;
+    padding: 10px;
+    background-color: var(--background);
+}
+
+.heading {
+    width: 280px;
+    color: var(--text);
+}
+
+.setting {
+    height: 30px;
+    align-items: center;
+}
+
+.setting>label {
+    width: 200px;
+    color: var(--text);
+}
+
+.setting>.spacer {
+    width: 20px;
+}
+"#;
+
+// Adds a row with a name and a checkbox
+fn add_setting(state: &mut State, parent: Entity, name: &str, checked: bool) {
+    let row = HBox::new().build(state, parent, |builder| builder.class("setting"));
+    Label::new(name).build(state, row, |builder| builder);
+    Checkbox::new(checked).build(state, row, |builder| builder);
+}
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.insert_theme(DEFAULT_THEME);
+        state.insert_theme(THEME);
+
+        let settings = VBox::new().build(state, window, |builder| builder.class("settings"));
+
+        Label::new("Display").build(state, settings, |builder| builder.class("heading"));
+        add_setting(state, settings, "Show grid", true);
+        add_setting(state, settings, "Show rulers", false);
+
+        // Dividers separate the sections of the panel
+        Divider::new().build(state, settings, |builder| {
+            builder
+                .set_margin_top(Length::Pixels(5.0))
+                .set_margin_bottom(Length::Pixels(5.0))
+        });
+
+        Label::new("Audio").build(state, settings, |builder| builder.class("heading"));
+        add_setting(state, settings, "Mute on focus loss", false);
+
+        Divider::new().build(state, settings, |builder| {
+            builder
+                .set_margin_top(Length::Pixels(5.0))
+                .set_margin_bottom(Length::Pixels(5.0))
+        });
+
+        // A vertical divider separates the buttons at the bottom of the panel
+        let buttons = HBox::new().build(state, settings, |builder| {
+            builder.set_height(Length::Pixels(30.0))
+        });
+        Button::with_label("Reset").build(state, buttons, |builder| builder);
+        Divider::new()
+            .with_orientation(Direction::Vertical)
+            .with_color(Color::rgb(100, 100, 100))
+            .build(state, buttons, |builder| {
+                builder
+                    .set_margin_left(Length::Pixels(10.0))
+                    .set_margin_right(Length::Pixels(10.0))
+            });
+        Button::with_label("Apply").build(state, buttons, |builder| builder);
+
+        win_desc.with_title("settings panel")
+    });
+
+    app.run();
+}
@@ -0,0 +1,93 @@
+extern crate tuix;
+
+use tuix::*;
+
+use std::time::Duration;
+
+static THEME: &'static str = r#"
+textbox {
+    width: 200px;
+    height: 30px;
+    margin-bottom: 10px;
+    padding-left: 5px;
+    background-color: #303030;
+    color: #e4e4e4;
+    text-justify: start;
+}
+
+label {
+    width: 200px;
+    height: 30px;
+    color: #a0a0a0;
+}
+"#;
+
+// A textbox for a number which shakes from side to side when something else is entered
+// The shake only moves the textbox when drawn, so the layout around it doesn't move
+struct NumberField {
+    textbox: Entity,
+}
+
+impl NumberField {
+    pub fn new() -> Self {
+        NumberField {
+            textbox: Entity::null(),
+        }
+    }
+
+    fn shake(&self, state: &mut State) {
+        let animation = AnimationState::new()
+            .with_duration(Duration::from_millis(400))
+            .with_keyframe((0.0, 0.0))
+            .with_keyframe((0.2, -10.0))
+            .with_keyframe((0.4, 8.0))
+            .with_keyframe((0.6, -6.0))
+            .with_keyframe((0.8, 4.0))
+            .with_keyframe((1.0, 0.0));
+
+        state.style.translate_x.play(self.textbox, animation);
+
+        self.textbox.set_translate(state, 0.0, 0.0);
+    }
+}
+
+impl BuildHandler for NumberField {
+    type Ret = Entity;
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity
+            .set_flex_direction(state, FlexDirection::Column)
+            .set_padding(state, Length::Pixels(20.0));
+
+        Label::new("Enter a number").build(state, entity, |builder| builder);
+
+        self.textbox = Textbox::new("").build(state, entity, |builder| builder);
+
+        entity
+    }
+}
+
+impl EventHandler for NumberField {
+    fn on_event(&mut self, state: &mut State, _entity: Entity, event: &mut Event) -> bool {
+        if let Some(TextboxEvent::ValueChanged(text)) = event.message.downcast::<TextboxEvent>() {
+            if event.target == self.textbox {
+                if text.trim().parse::<f64>().is_err() {
+                    self.shake(state);
+                }
+            }
+        }
+
+        false
+    }
+}
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.insert_theme(THEME);
+
+        NumberField::new().build(state, window, |builder| builder);
+
+        win_desc.with_title("shake textbox")
+    });
+
+    app.run();
+}
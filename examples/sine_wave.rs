@@ -0,0 +1,58 @@
+extern crate tuix;
+
+use tuix::*;
+
+use tuix::style::themes::DEFAULT_THEME;
+
+use femtovg::{Paint, Path};
+
+static THEME: &'static str = r#"
+custom {
+    flex-grow: 1.0;
+    margin: 20px;
+    padding: 10px;
+    background-color: #202020;
+    border-radius: 3px;
+}
+"#;
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.insert_theme(DEFAULT_THEME);
+        state.insert_theme(THEME);
+
+        // Draws two periods of a sine wave filling the content box, so it scales with the window
+        CustomWidget::new(|state, entity, canvas| {
+            let bounds = state.content_box(entity);
+            let mid = bounds.y + bounds.h / 2.0;
+            let amplitude = bounds.h / 2.0;
+
+            let mut axis = Path::new();
+            axis.move_to(bounds.x, mid);
+            axis.line_to(bounds.x + bounds.w, mid);
+            canvas.stroke_path(&mut axis, &Paint::color(femtovg::Color::rgb(80, 80, 80)));
+
+            let mut wave = Path::new();
+            let steps = bounds.w.max(1.0) as usize;
+            for step in 0..=steps {
+                let t = step as f32 / steps as f32;
+                let x = bounds.x + t * bounds.w;
+                let y = mid - amplitude * (t * 4.0 * std::f32::consts::PI).sin();
+                if step == 0 {
+                    wave.move_to(x, y);
+                } else {
+                    wave.line_to(x, y);
+                }
+            }
+
+            let mut paint = Paint::color(femtovg::Color::rgb(80, 160, 240));
+            paint.set_line_width(2.0);
+            canvas.stroke_path(&mut wave, &paint);
+        })
+        .build(state, window, |builder| builder);
+
+        win_desc.with_title("Sine Wave")
+    });
+
+    app.run();
+}
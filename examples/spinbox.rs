@@ -0,0 +1,84 @@
+extern crate tuix;
+
+use tuix::*;
+
+static THEME: &'static str = r#"
+spinbox {
+    width: 120px;
+    height: 30px;
+    margin: 10px;
+    background-color: #303030;
+}
+
+spinbox textbox {
+    padding-left: 5px;
+    color: #e4e4e4;
+    text-justify: start;
+}
+
+spinbox button {
+    background-color: #505050;
+}
+
+spinbox button:hover {
+    background-color: #606060;
+}
+"#;
+
+// Shows the value of a spinbox, which can be adjusted between 0 and 100
+struct App {
+    label: Entity,
+}
+
+impl BuildHandler for App {
+    type Ret = Entity;
+
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity.set_flex_direction(state, FlexDirection::Column);
+
+        Spinbox::new(50.0)
+            .with_range(0.0, 100.0)
+            .with_step(5.0)
+            .build(state, entity, |builder| builder);
+
+        // Shows one decimal place
+        Spinbox::new(50.0)
+            .with_range(0.0, 100.0)
+            .with_step(0.5)
+            .with_decimals(1)
+            .build(state, entity, |builder| builder);
+
+        self.label = Label::new("Value: 50").build(state, entity, |builder| {
+            builder
+                .set_height(Length::Pixels(30.0))
+                .set_margin(Length::Pixels(10.0))
+        });
+
+        entity
+    }
+}
+
+impl EventHandler for App {
+    fn on_event(&mut self, state: &mut State, _entity: Entity, event: &mut Event) -> bool {
+        if let Some(SpinboxEvent::Changed(value)) = event.message.downcast::<SpinboxEvent>() {
+            self.label.set_text(state, &format!("Value: {}", value));
+        }
+
+        false
+    }
+}
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.insert_theme(THEME);
+
+        App {
+            label: Entity::null(),
+        }
+        .build(state, window, |builder| builder);
+
+        win_desc.with_title("spinbox")
+    });
+
+    app.run();
+}
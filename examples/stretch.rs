@@ -0,0 +1,32 @@
+extern crate tuix;
+
+use tuix::*;
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        let row = Element::new().build(state, window, |builder| {
+            builder
+                .set_flex_grow(1.0)
+                .set_flex_direction(FlexDirection::Row)
+        });
+
+        // Takes one third of the free space
+        let left = Element::new().build(state, row, |builder| {
+            builder.set_background_color(Color::rgb(200, 80, 80))
+        });
+
+        left.set_width(state, Length::Stretch(1.0));
+
+        // Takes two thirds of the free space but no more than 400px
+        Element::new().build(state, row, |builder| {
+            builder
+                .set_width(Length::Stretch(2.0))
+                .set_max_width(Length::Pixels(400.0))
+                .set_background_color(Color::rgb(80, 80, 200))
+        });
+
+        win_desc.with_title("stretch")
+    });
+
+    app.run();
+}
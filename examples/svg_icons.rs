@@ -0,0 +1,50 @@
+extern crate tuix;
+
+use tuix::*;
+
+use tuix::style::themes::DEFAULT_THEME;
+
+// Run with: cargo run --example svg_icons --features svg
+static THEME: &'static str = r#"
+.row {
+    flex-direction: row;
+    padding: 10px;
+}
+
+svg {
+    margin-right: 10px;
+    background-color: #303030;
+    padding: 4px;
+}
+
+.small {
+    width: 16px;
+    height: 16px;
+}
+
+.large {
+    width: 64px;
+    height: 64px;
+}
+"#;
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.insert_theme(DEFAULT_THEME);
+        state.insert_theme(THEME);
+
+        // The same icons at two sizes stay sharp as they're drawn as paths
+        for size in ["small", "large"].iter() {
+            let row = Element::new().build(state, window, |builder| builder.class("row"));
+
+            for icon in ["close", "menu", "check"].iter() {
+                Svg::new(&format!("resources/icons/{}.svg", icon))
+                    .build(state, row, |builder| builder.class(size));
+            }
+        }
+
+        win_desc.with_title("SVG Icons")
+    });
+
+    app.run();
+}
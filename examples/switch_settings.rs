@@ -0,0 +1,85 @@
+extern crate tuix;
+
+use tuix::*;
+
+use tuix::style::themes::DEFAULT_THEME;
+
+static THEME: &'static str = r#"
+.settings {
+    width: 300px;
+    padding: 10px;
+    background-color: var(--background);
+}
+
+.setting {
+    height: 30px;
+    align-items: center;
+}
+
+.setting>label {
+    width: 200px;
+    color: var(--text);
+}
+"#;
+
+// A panel with a boolean setting toggled by a switch
+struct Settings {
+    dark_mode: bool,
+    dark_mode_switch: Entity,
+}
+
+impl Settings {
+    pub fn new() -> Self {
+        Settings {
+            dark_mode: false,
+            dark_mode_switch: Entity::null(),
+        }
+    }
+}
+
+impl BuildHandler for Settings {
+    type Ret = Entity;
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity.set_flex_direction(state, FlexDirection::Column);
+
+        let row = HBox::new().build(state, entity, |builder| builder.class("setting"));
+        Label::new("Dark mode").build(state, row, |builder| builder);
+        self.dark_mode_switch = Switch::new(self.dark_mode).build(state, row, |builder| builder);
+
+        // A disabled switch is drawn faded and ignores clicks
+        let row = HBox::new().build(state, entity, |builder| builder.class("setting"));
+        Label::new("Sync (unavailable)").build(state, row, |builder| builder);
+        Switch::new(true)
+            .disabled(true)
+            .build(state, row, |builder| builder);
+
+        entity
+    }
+}
+
+impl EventHandler for Settings {
+    fn on_event(&mut self, _state: &mut State, _entity: Entity, event: &mut Event) -> bool {
+        if let Some(SwitchEvent::Toggled(checked)) = event.message.downcast::<SwitchEvent>() {
+            if event.origin == self.dark_mode_switch {
+                self.dark_mode = *checked;
+                println!("Dark mode: {}", self.dark_mode);
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.insert_theme(DEFAULT_THEME);
+        state.insert_theme(THEME);
+
+        Settings::new().build(state, window, |builder| builder.class("settings"));
+
+        win_desc.with_title("Switch Settings")
+    });
+
+    app.run();
+}
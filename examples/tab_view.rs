@@ -0,0 +1,127 @@
+extern crate tuix;
+
+use tuix::*;
+
+static THEME: &'static str = r#"
+    tab_view {
+        width: 400px;
+        height: 300px;
+        background-color: #303030;
+    }
+
+    tab_bar {
+        height: 30px;
+        background-color: #202020;
+    }
+
+    .tab {
+        width: 80px;
+        margin-right: 1px;
+        background-color: #2e2e2e;
+        color: #e4e4e4;
+        text-justify: center;
+    }
+
+    .tab:checked {
+        background-color: #383838;
+    }
+
+    .tab_panel {
+        background-color: #383838;
+        color: #e4e4e4;
+        padding-left: 10px;
+    }
+"#;
+
+// A tab view with three tabs and buttons which add and remove tabs at runtime
+struct Notebook {
+    tab_view: Entity,
+    count: usize,
+}
+
+impl Notebook {
+    pub fn new() -> Self {
+        Notebook {
+            tab_view: Entity::null(),
+            count: 0,
+        }
+    }
+
+    fn add_page(&mut self, state: &mut State) {
+        self.count += 1;
+        let name = format!("Page {}", self.count);
+        let panel = TabView::add_tab(state, self.tab_view, &name);
+        Label::new(&format!("Contents of {}", name)).build(state, panel, |builder| builder);
+    }
+}
+
+impl BuildHandler for Notebook {
+    type Ret = Entity;
+
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        entity.set_flex_direction(state, FlexDirection::Column);
+
+        let controls = Element::new().build(state, entity, |builder| {
+            builder
+                .set_flex_direction(FlexDirection::Row)
+                .set_height(Length::Pixels(30.0))
+        });
+
+        Button::with_label("Add tab")
+            .on_press(Event::new(NotebookEvent::Add))
+            .build(state, controls, |builder| {
+                builder.set_width(Length::Pixels(100.0))
+            });
+
+        Button::with_label("Remove tab")
+            .on_press(Event::new(NotebookEvent::RemoveFirst))
+            .build(state, controls, |builder| {
+                builder.set_width(Length::Pixels(100.0))
+            });
+
+        self.tab_view = TabView::new().build(state, entity, |builder| builder);
+
+        for _ in 0..3 {
+            self.add_page(state);
+        }
+
+        entity
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum NotebookEvent {
+    Add,
+    RemoveFirst,
+}
+
+impl EventHandler for Notebook {
+    fn on_event(&mut self, state: &mut State, _entity: Entity, event: &mut Event) -> bool {
+        if let Some(notebook_event) = event.message.downcast::<NotebookEvent>() {
+            match notebook_event {
+                NotebookEvent::Add => self.add_page(state),
+                NotebookEvent::RemoveFirst => TabView::remove_tab(state, self.tab_view, 0),
+            }
+
+            return true;
+        }
+
+        if let Some(TabEvent::TabSelected(index)) = event.message.downcast::<TabEvent>() {
+            println!("Selected tab {}", index);
+        }
+
+        false
+    }
+}
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.insert_theme(THEME);
+
+        Notebook::new().build(state, window, |builder| builder);
+
+        win_desc.with_title("tab view")
+    });
+
+    app.run();
+}
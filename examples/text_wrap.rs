@@ -0,0 +1,37 @@
+extern crate tuix;
+
+use tuix::*;
+
+static PARAGRAPH: &'static str = "Long labels no longer overflow the widget. When text wrapping is enabled the text is broken into lines which fit within the content box, and the whole block of lines is aligned using text-align and text-justify.";
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        Element::new().build(state, window, |builder| {
+            builder
+                .set_width(Length::Pixels(300.0))
+                .set_height(Length::Pixels(200.0))
+                .set_padding(Length::Pixels(10.0))
+                .set_background_color(Color::rgb(50, 50, 80))
+                .set_text(PARAGRAPH)
+                .set_text_wrap(true)
+                .set_text_align(Align::Start)
+                .set_text_justify(Justify::Start)
+        });
+
+        Element::new().build(state, window, |builder| {
+            builder
+                .set_width(Length::Pixels(200.0))
+                .set_height(Length::Pixels(200.0))
+                .set_margin_top(Length::Pixels(10.0))
+                .set_background_color(Color::rgb(80, 50, 50))
+                .set_text(PARAGRAPH)
+                .set_text_wrap(true)
+                .set_text_align(Align::Center)
+                .set_text_justify(Justify::Center)
+        });
+
+        win_desc.with_title("text wrap")
+    });
+
+    app.run();
+}
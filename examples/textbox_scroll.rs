@@ -0,0 +1,33 @@
+extern crate tuix;
+
+use tuix::*;
+
+// Text wider than the textbox scrolls to keep the caret in view while typing
+static THEME: &'static str = r#"
+textbox {
+    width: 120px;
+    height: 30px;
+    margin: 10px;
+    padding-left: 5px;
+    padding-right: 5px;
+    background-color: #303030;
+    color: #e4e4e4;
+    text-justify: start;
+}
+"#;
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.insert_theme(THEME);
+
+        Textbox::new("A long line of text which doesn't fit in the textbox").build(
+            state,
+            window,
+            |builder| builder,
+        );
+
+        win_desc.with_title("textbox scroll")
+    });
+
+    app.run();
+}
@@ -0,0 +1,50 @@
+extern crate tuix;
+
+use tuix::*;
+
+use tuix::style::themes::{Theme, DEFAULT_THEME};
+
+// Stylesheets can reference the variables of the current theme
+static THEME: &'static str = r#"
+.page {
+    flex-grow: 1.0;
+    padding: 20px;
+    background-color: var(--background);
+}
+
+.page>label {
+    height: 30px;
+    color: var(--text);
+}
+"#;
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.insert_theme(DEFAULT_THEME);
+        state.insert_theme(THEME);
+
+        let page = Element::new().build(state, window, |builder| builder.class("page"));
+
+        Label::new("Click the button to switch theme").build(state, page, |builder| builder);
+        Checkbox::new(true).build(state, page, |builder| builder);
+        Textbox::new("Some text").build(state, page, |builder| builder);
+
+        Button::with_label("Toggle theme")
+            .on_press_with(|state, _| {
+                let theme = if state.theme().name == "dark" {
+                    Theme::light()
+                } else {
+                    Theme::dark()
+                };
+
+                if let Err(e) = state.set_theme(theme) {
+                    println!("Error switching theme: {}", e);
+                }
+            })
+            .build(state, page, |builder| builder);
+
+        win_desc.with_title("theme switch")
+    });
+
+    app.run();
+}
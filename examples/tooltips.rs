@@ -0,0 +1,39 @@
+extern crate tuix;
+
+use tuix::*;
+
+use tuix::style::themes::DEFAULT_THEME;
+
+use std::time::Duration;
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.insert_theme(DEFAULT_THEME);
+
+        state.tooltip_delay = Duration::from_millis(300);
+
+        let row = HBox::new().build(state, window, |builder| {
+            builder
+                .set_flex_grow(1.0)
+                .set_justify_content(JustifyContent::SpaceBetween)
+        });
+
+        // Stays below the button
+        let save = Button::with_label("Save").build(state, row, |builder| builder);
+        Tooltip::new("Save the document").build(state, save, |builder| builder);
+
+        // Trails the cursor
+        let open = Button::with_label("Open").build(state, row, |builder| builder);
+        Tooltip::new("Open a document")
+            .follow_cursor(true)
+            .build(state, open, |builder| builder);
+
+        // At the right edge of the window so the tooltip is aligned with the right of the button
+        let close = Button::with_label("Close").build(state, row, |builder| builder);
+        Tooltip::new("Close the document").build(state, close, |builder| builder);
+
+        win_desc.with_title("Tooltips").with_inner_size(400, 200)
+    });
+
+    app.run();
+}
@@ -0,0 +1,91 @@
+extern crate tuix;
+
+use tuix::*;
+
+static THEME: &'static str = r#"
+    tree_view {
+        width: 300px;
+        background-color: #2e2e2e;
+    }
+
+    .tree_row {
+        color: #e4e4e4;
+    }
+
+    .tree_row:hover {
+        background-color: #383838;
+    }
+
+    .disclosure {
+        color: #a0a0a0;
+    }
+"#;
+
+// A file-system-like tree where the contents of the target directory are only built when first expanded
+struct FileBrowser {
+    target: Entity,
+}
+
+impl BuildHandler for FileBrowser {
+    type Ret = Entity;
+
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        let tree = TreeView::new().build(state, entity, |builder| builder);
+
+        let project = TreeNode::new("project").build(state, tree, |builder| builder);
+
+        let src = TreeNode::new("src").build(state, project, |builder| builder);
+        TreeNode::new("main.rs").build(state, src, |builder| builder);
+        TreeNode::new("lib.rs").build(state, src, |builder| builder);
+
+        let widgets = TreeNode::new("widgets").build(state, src, |builder| builder);
+        TreeNode::new("button.rs").build(state, widgets, |builder| builder);
+        TreeNode::new("label.rs").build(state, widgets, |builder| builder);
+
+        self.target = TreeNode::new("target")
+            .lazy()
+            .build(state, project, |builder| builder);
+
+        TreeNode::new("Cargo.toml").build(state, project, |builder| builder);
+        TreeNode::new("README.md").build(state, project, |builder| builder);
+
+        entity
+    }
+}
+
+impl EventHandler for FileBrowser {
+    fn on_event(&mut self, state: &mut State, _entity: Entity, event: &mut Event) -> bool {
+        if let Some(tree_event) = event.message.downcast::<TreeEvent>() {
+            match tree_event {
+                TreeEvent::Populate(node) if *node == self.target => {
+                    let debug = TreeNode::new("debug").build(state, *node, |builder| builder);
+                    TreeNode::new("project").build(state, debug, |builder| builder);
+                    TreeNode::new("release").build(state, *node, |builder| builder);
+                }
+
+                TreeEvent::Toggled(node) => {
+                    println!("Toggled {:?}", node);
+                }
+
+                _ => {}
+            }
+        }
+
+        false
+    }
+}
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.insert_theme(THEME);
+
+        FileBrowser {
+            target: Entity::null(),
+        }
+        .build(state, window, |builder| builder);
+
+        win_desc.with_title("tree view")
+    });
+
+    app.run();
+}
@@ -92,7 +92,7 @@ impl EventHandler for ResizableVBox {
                 }
 
                 // Occurs when the cursor leaves the entity
-                WindowEvent::MouseOut => {
+                WindowEvent::MouseOut(_) => {
                     if !self.resizing {
                         state.insert_event(Event::new(WindowEvent::SetCursor(CursorIcon::Arrow)));
                     }
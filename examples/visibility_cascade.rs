@@ -0,0 +1,117 @@
+extern crate tuix;
+
+use tuix::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToggleEvent {
+    ToggleParent,
+    ToggleChild,
+}
+
+// Hides a parent or its child with nested panels
+// The descendants of a hidden parent are hidden too, even if they're set to visible themselves
+struct VisibilityToggle {
+    parent: Entity,
+    child: Entity,
+    parent_visible: bool,
+    child_visible: bool,
+}
+
+impl VisibilityToggle {
+    pub fn new() -> Self {
+        VisibilityToggle {
+            parent: Entity::null(),
+            child: Entity::null(),
+            parent_visible: true,
+            child_visible: true,
+        }
+    }
+}
+
+impl BuildHandler for VisibilityToggle {
+    type Ret = Entity;
+
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        let controls = Element::new().build(state, entity, |builder| {
+            builder
+                .set_flex_direction(FlexDirection::Row)
+                .set_height(Length::Pixels(30.0))
+        });
+
+        Button::with_label("Toggle Parent")
+            .on_press(Event::new(ToggleEvent::ToggleParent).target(entity))
+            .build(state, controls, |builder| {
+                builder
+                    .set_width(Length::Pixels(150.0))
+                    .set_background_color(Color::rgb(100, 100, 100))
+            });
+
+        Button::with_label("Toggle Child")
+            .on_press(Event::new(ToggleEvent::ToggleChild).target(entity))
+            .build(state, controls, |builder| {
+                builder
+                    .set_width(Length::Pixels(150.0))
+                    .set_background_color(Color::rgb(100, 100, 100))
+            });
+
+        self.parent = Element::new().build(state, entity, |builder| {
+            builder
+                .set_width(Length::Pixels(200.0))
+                .set_height(Length::Pixels(200.0))
+                .set_background_color(Color::rgb(200, 80, 80))
+        });
+
+        self.child = Element::new().build(state, self.parent, |builder| {
+            builder
+                .set_width(Length::Pixels(100.0))
+                .set_height(Length::Pixels(100.0))
+                .set_background_color(Color::rgb(80, 200, 80))
+        });
+
+        Element::new().build(state, self.child, |builder| {
+            builder
+                .set_width(Length::Pixels(50.0))
+                .set_height(Length::Pixels(50.0))
+                .set_background_color(Color::rgb(80, 80, 200))
+        });
+
+        entity
+    }
+}
+
+impl EventHandler for VisibilityToggle {
+    fn on_event(&mut self, state: &mut State, _entity: Entity, event: &mut Event) -> bool {
+        if let Some(toggle_event) = event.message.downcast::<ToggleEvent>() {
+            let (entity, visible) = match toggle_event {
+                ToggleEvent::ToggleParent => {
+                    self.parent_visible = !self.parent_visible;
+                    (self.parent, self.parent_visible)
+                }
+
+                ToggleEvent::ToggleChild => {
+                    self.child_visible = !self.child_visible;
+                    (self.child, self.child_visible)
+                }
+            };
+
+            let visibility = if visible {
+                Visibility::Visible
+            } else {
+                Visibility::Invisible
+            };
+            entity.set_visibility(state, visibility);
+        }
+
+        false
+    }
+}
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        VisibilityToggle::new().build(state, window, |builder| builder.set_flex_grow(1.0));
+
+        win_desc.with_title("Visibility Cascade")
+    });
+
+    app.run();
+}
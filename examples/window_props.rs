@@ -0,0 +1,63 @@
+extern crate tuix;
+
+use tuix::*;
+
+#[derive(Debug, Clone, PartialEq)]
+enum AppEvent {
+    ChangeTitle,
+}
+
+// Changes the title of the window each time the button is clicked
+struct App {
+    window: Entity,
+    clicks: u32,
+}
+
+impl BuildHandler for App {
+    type Ret = Entity;
+
+    fn on_build(&mut self, state: &mut State, entity: Entity) -> Self::Ret {
+        Button::with_label("Change title")
+            .on_press(Event::new(AppEvent::ChangeTitle))
+            .build(state, entity, |builder| {
+                builder
+                    .set_width(Length::Pixels(150.0))
+                    .set_height(Length::Pixels(30.0))
+                    .set_margin(Length::Pixels(10.0))
+                    .set_background_color(Color::rgb(80, 80, 120))
+                    .set_text_justify(Justify::Center)
+            });
+
+        entity
+    }
+}
+
+impl EventHandler for App {
+    fn on_event(&mut self, state: &mut State, _entity: Entity, event: &mut Event) -> bool {
+        if let Some(AppEvent::ChangeTitle) = event.message.downcast::<AppEvent>() {
+            self.clicks += 1;
+            self.window
+                .set_window_title(state, &format!("Clicked {} times", self.clicks));
+
+            return true;
+        }
+
+        false
+    }
+}
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        // Window properties can also be set while building
+        window
+            .set_window_title(state, "Window properties")
+            .set_min_size(state, 300, 200)
+            .set_resizable(state, false);
+
+        App { window, clicks: 0 }.build(state, window, |builder| builder);
+
+        win_desc.with_title("window properties")
+    });
+
+    app.run();
+}
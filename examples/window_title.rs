@@ -0,0 +1,29 @@
+extern crate tuix;
+
+use tuix::*;
+
+use tuix::style::themes::DEFAULT_THEME;
+
+fn main() {
+    let app = Application::new(|win_desc, state, window| {
+        state.insert_theme(DEFAULT_THEME);
+
+        // The title shown by the OS, replacing the title from the window description
+        window.set_window_title(state, "Window title");
+
+        // Text on the root is stored like on any other entity but isn't drawn
+        window.set_text(state, "Not drawn");
+
+        // Text is drawn by the widgets in the window
+        Label::new("Widget text").build(state, window, |builder| {
+            builder
+                .set_width(Length::Pixels(200.0))
+                .set_height(Length::Pixels(30.0))
+                .set_margin(Length::Pixels(10.0))
+        });
+
+        win_desc.with_title("Initial title")
+    });
+
+    app.run();
+}
@@ -11,7 +11,7 @@ use crate::{
 use crate::window::Window;
 
 use tuix_core::{Color, Length, Visibility};
-use tuix_core::{Entity, State};
+use tuix_core::{CursorIcon, Entity, State};
 
 use tuix_core::state::mouse::{MouseButton, MouseButtonState};
 
@@ -25,10 +25,16 @@ use tuix_core::state::style::prop::*;
 
 use tuix_core::{WindowDescription, WindowEvent, WindowWidget};
 
-use tuix_core::systems::{apply_clipping, apply_styles, apply_visibility, apply_z_ordering};
+use tuix_core::systems::{
+    apply_clipping, apply_hover, apply_styles, apply_visibility, apply_z_ordering,
+};
 
 use glutin::event::VirtualKeyCode;
 
+use keyboard_types::Code;
+
+use std::collections::HashSet;
+
 type GEvent<'a, T> = glutin::event::Event<'a, T>;
 
 pub struct Application {
@@ -59,29 +65,26 @@ impl Application {
         let bold_font = include_bytes!("../../resources/Roboto-Bold.ttf");
         let icon_font = include_bytes!("../../resources/entypo.ttf");
 
-        let fonts = Fonts {
-            regular: Some(
-                window
-                    .canvas
-                    .add_font_mem(regular_font)
-                    .expect("Cannot add font"),
-            ),
-            bold: Some(
-                window
-                    .canvas
-                    .add_font_mem(bold_font)
-                    .expect("Cannot add font"),
-            ),
-            icons: Some(
-                window
-                    .canvas
-                    .add_font_mem(icon_font)
-                    .expect("Cannot add font"),
-            ),
-        };
+        let fonts = Fonts::new(
+            window
+                .canvas
+                .add_font_mem(regular_font)
+                .expect("Cannot add font"),
+            window
+                .canvas
+                .add_font_mem(bold_font)
+                .expect("Cannot add font"),
+            window
+                .canvas
+                .add_font_mem(icon_font)
+                .expect("Cannot add font"),
+        );
 
         state.fonts = fonts;
 
+        // The window description is in logical pixels, which are scaled to the display when drawn
+        state.scale_factor = window.handle.window().scale_factor() as f32;
+
         state.style.width.insert(
             state.root,
             Length::Pixels(window_description.inner_size.width as f32),
@@ -140,6 +143,12 @@ impl Application {
 
         let mut first_time = true;
 
+        // The cursor currently shown by the window
+        let mut cursor_icon = CursorIcon::Arrow;
+
+        // The keys held down, used to tell presses repeated by the OS from the first press
+        let mut held_keys: HashSet<Code> = HashSet::new();
+
         self.event_loop.run(move |event, _, control_flow| {
             *control_flow = ControlFlow::Wait;
 
@@ -165,13 +174,38 @@ impl Application {
                     //println!("Main Events Cleared: {}", counter);
                     //counter += 1;
 
+                    if state.caret_blink_due() {
+                        state.request_redraw(state.focused);
+                    }
+
+                    state.reload_changed_stylesheets();
+
+                    state.tooltip_due();
+
                     let mut needs_redraw = false;
                     while !state.event_queue.is_empty() {
+                        apply_window_events(&mut state, &window);
+
                         if event_manager.flush_events(&mut state) {
                             needs_redraw = true;
                         }
                     }
 
+                    // Show the cursor of the hovered entity
+                    let hovered_cursor = state
+                        .style
+                        .cursor
+                        .get(state.hovered)
+                        .cloned()
+                        .unwrap_or_default();
+                    if hovered_cursor != cursor_icon {
+                        cursor_icon = hovered_cursor;
+                        window
+                            .handle
+                            .window()
+                            .set_cursor_icon(glutin_cursor_icon(cursor_icon));
+                    }
+
                     if state.apply_animations() {
                         //println!("Animate");
                         *control_flow = ControlFlow::Poll;
@@ -183,6 +217,16 @@ impl Application {
                         //state.insert_event(Event::new(WindowEvent::Redraw));
                         event_loop_proxy.send_event(());
                         window.handle.window().request_redraw();
+                    } else if let Some(wake_time) = state
+                        .next_caret_blink()
+                        .into_iter()
+                        .chain(state.next_stylesheet_check())
+                        .chain(state.next_tooltip())
+                        .min()
+                    {
+                        // Wake up to redraw the caret of the textbox being edited, to check for edited stylesheets,
+                        // or to show the tooltip of the hovered widget
+                        *control_flow = ControlFlow::WaitUntil(wake_time);
                     } else {
                         //println!("Wait");
                         *control_flow = ControlFlow::Wait;
@@ -193,7 +237,8 @@ impl Application {
                         first_time = false;
                     }
 
-                    if needs_redraw {
+                    if needs_redraw || state.needs_redraw {
+                        state.needs_redraw = false;
                         window.handle.window().request_redraw();
                     }
 
@@ -250,12 +295,28 @@ impl Application {
                         ////////////////////
                         // Focused Window //
                         ////////////////////
-                        glutin::event::WindowEvent::Focused(_) => {
+                        glutin::event::WindowEvent::Focused(focused) => {
                             state.insert_event(
                                 Event::new(WindowEvent::Restyle)
                                     .target(state.root)
                                     .origin(state.root),
                             );
+
+                            // Keys released while the window isn't focused aren't reported
+                            if !focused {
+                                held_keys.clear();
+                            }
+
+                            // Any text being composed by an input method is abandoned when the window loses focus
+                            // Composition itself isn't reported by this version of glutin, committed text arrives as ReceivedCharacter
+                            // TODO - Send ImePreedit and ImeCommit once glutin is updated to a winit which reports composition
+                            if !focused && state.focused != Entity::null() {
+                                state.insert_event(
+                                    Event::new(WindowEvent::ImePreedit(String::new(), None))
+                                        .target(state.focused)
+                                        .propagate(Propagation::Direct),
+                                );
+                            }
                         }
 
                         ////////////////////
@@ -347,22 +408,31 @@ impl Application {
 
                             match s {
                                 MouseButtonState::Pressed => {
-                                    if state.focused != Entity::null() {
-                                        state.insert_event(
-                                            Event::new(WindowEvent::KeyDown(code, key))
-                                                .target(state.focused)
-                                                .propagate(Propagation::DownUp),
-                                        );
-                                    } else {
+                                    let repeat = !held_keys.insert(code);
+
+                                    // Accelerators take priority over the focused entity
+                                    let accelerated = key
+                                        .as_ref()
+                                        .map_or(false, |key| state.trigger_accelerator(key));
+
+                                    if !accelerated {
+                                        let target = if state.focused != Entity::null() {
+                                            state.focused
+                                        } else {
+                                            state.hovered
+                                        };
+
                                         state.insert_event(
-                                            Event::new(WindowEvent::KeyDown(code, key))
-                                                .target(state.hovered)
+                                            Event::new(WindowEvent::KeyDown(code, key, repeat))
+                                                .target(target)
                                                 .propagate(Propagation::DownUp),
                                         );
                                     }
                                 }
 
                                 MouseButtonState::Released => {
+                                    held_keys.remove(&code);
+
                                     if state.focused != Entity::null() {
                                         state.insert_event(
                                             Event::new(WindowEvent::KeyUp(code, key))
@@ -383,27 +453,35 @@ impl Application {
                         glutin::event::WindowEvent::Resized(physical_size) => {
                             window.handle.resize(physical_size);
 
-                            state
-                                .style
-                                .width
-                                .insert(state.root, Length::Pixels(physical_size.width as f32));
-                            state
-                                .style
-                                .height
-                                .insert(state.root, Length::Pixels(physical_size.height as f32));
-
-                            state
-                                .transform
-                                .set_width(state.root, physical_size.width as f32);
-                            state
-                                .transform
-                                .set_height(state.root, physical_size.height as f32);
-
-                            state.insert_event(Event::new(WindowEvent::Restyle).origin(state.root));
+                            // Sent to all widgets, the window widget updates the root size and relayouts
+                            let logical_size: LogicalSize<f32> =
+                                physical_size.to_logical(state.scale_factor as f64);
+                            state.insert_event(
+                                Event::new(WindowEvent::WindowResize(
+                                    logical_size.width,
+                                    logical_size.height,
+                                ))
+                                .target(Entity::null()),
+                            );
+                        }
+
+                        glutin::event::WindowEvent::ScaleFactorChanged {
+                            scale_factor,
+                            new_inner_size,
+                        } => {
+                            window.handle.resize(*new_inner_size);
+
+                            state.set_scale_factor(scale_factor as f32);
+
+                            let logical_size: LogicalSize<f32> =
+                                new_inner_size.to_logical(scale_factor);
                             state.insert_event(
-                                Event::new(WindowEvent::Relayout).target(Entity::null()),
+                                Event::new(WindowEvent::WindowResize(
+                                    logical_size.width,
+                                    logical_size.height,
+                                ))
+                                .target(Entity::null()),
                             );
-                            state.insert_event(Event::new(WindowEvent::Redraw));
                         }
 
                         glutin::event::WindowEvent::CursorMoved {
@@ -411,136 +489,20 @@ impl Application {
                             position,
                             modifiers: _,
                         } => {
-                            let cursorx = (position.x) as f32;
-                            let cursory = (position.y) as f32;
+                            let position: LogicalPosition<f32> =
+                                position.to_logical(state.scale_factor as f64);
+                            let cursorx = position.x;
+                            let cursory = position.y;
 
                             state.mouse.cursorx = cursorx as f32;
                             state.mouse.cursory = cursory as f32;
 
-                            let mut hovered_widget = Entity::new(0, 0);
-
-                            // This only really needs to be computed when the hierarchy changes
-                            // Can be optimised
-                            let mut draw_hierarchy: Vec<Entity> =
-                                state.hierarchy.into_iter().collect();
-
-                            draw_hierarchy
-                                .sort_by_cached_key(|entity| state.transform.get_z_order(*entity));
-
-                            for widget in draw_hierarchy.into_iter() {
-                                // Skip invisible widgets
-                                if state.transform.get_visibility(widget) == Visibility::Invisible {
-                                    continue;
-                                }
-
-                                // This shouldn't be here but there's a bug if it isn't
-                                if state.transform.get_opacity(widget) == 0.0 {
-                                    continue;
-                                }
-
-                                // Skip non-hoverable widgets
-                                if state.transform.get_hoverability(widget) != true {
-                                    continue;
-                                }
-
-                                let border_width = match state
-                                    .style
-                                    .border_width
-                                    .get(widget)
-                                    .cloned()
-                                    .unwrap_or_default()
-                                {
-                                    Length::Pixels(val) => val,
-                                    //Length::Percentage(val) => parent_width * val,
-                                    _ => 0.0,
-                                };
-
-                                let posx = state.transform.get_posx(widget) - (border_width / 2.0);
-                                let posy = state.transform.get_posy(widget) - (border_width / 2.0);
-                                let width = state.transform.get_width(widget) + (border_width);
-                                let height = state.transform.get_height(widget) + (border_width);
-
-                                let clip_widget = state.transform.get_clip_widget(widget);
-
-                                let clip_posx = state.transform.get_posx(clip_widget);
-                                let clip_posy = state.transform.get_posy(clip_widget);
-                                let clip_width = state.transform.get_width(clip_widget);
-                                let clip_height = state.transform.get_height(clip_widget);
-
-                                if cursorx >= posx
-                                    && cursorx >= clip_posx
-                                    && cursorx < (posx + width)
-                                    && cursorx < (clip_posx + clip_width)
-                                    && cursory >= posy
-                                    && cursory >= clip_posy
-                                    && cursory < (posy + height)
-                                    && cursory < (clip_posy + clip_height)
-                                {
-                                    hovered_widget = widget;
-                                    if let Some(pseudo_classes) =
-                                        state.style.pseudo_classes.get_mut(hovered_widget)
-                                    {
-                                        pseudo_classes.set_over(true);
-                                    }
-                                } else {
-                                    if let Some(pseudo_classes) =
-                                        state.style.pseudo_classes.get_mut(hovered_widget)
-                                    {
-                                        pseudo_classes.set_over(false);
-                                    }
-                                }
-                            }
-
-                            if hovered_widget != state.hovered {
-                                // Useful for debugging
-
-                                // println!(
-                                //     "Hover changed to {:?} parent: {:?}, posx: {}, posy: {} width: {} height: {} z_order: {}",
-                                //     hovered_widget,
-                                //     state.hierarchy.get_parent(hovered_widget),
-                                //     state.transform.get_posx(hovered_widget),
-                                //     state.transform.get_posy(hovered_widget),
-                                //     state.transform.get_width(hovered_widget),
-                                //     state.transform.get_height(hovered_widget),
-                                //     state.transform.get_z_order(hovered_widget),
-                                // );
-
-                                if let Some(pseudo_classes) =
-                                    state.style.pseudo_classes.get_mut(hovered_widget)
-                                {
-                                    pseudo_classes.set_hover(true);
-                                }
-
-                                if let Some(pseudo_classes) =
-                                    state.style.pseudo_classes.get_mut(state.hovered)
-                                {
-                                    pseudo_classes.set_hover(false);
-                                }
+                            apply_hover(&mut state);
 
-                                state.insert_event(
-                                    Event::new(WindowEvent::MouseOver).target(hovered_widget),
-                                );
-                                state.insert_event(
-                                    Event::new(WindowEvent::MouseOut).target(state.hovered),
-                                );
-
-                                state.insert_event(
-                                    Event::new(WindowEvent::Restyle).origin(hovered_widget),
-                                );
-                                state.insert_event(
-                                    Event::new(WindowEvent::Restyle).origin(state.hovered),
-                                );
-
-                                state.hovered = hovered_widget;
-                                state.active = Entity::null();
-
-                                state.insert_event(Event::new(WindowEvent::Redraw));
-                            }
-
-                            if state.captured != Entity::null() {
+                            if state.captured() != Entity::null() {
                                 state.insert_event(
                                     Event::new(WindowEvent::MouseMove(cursorx, cursory))
-                                        .target(state.captured)
+                                        .target(state.captured())
                                         .propagate(Propagation::Direct),
                                 );
                             } else if state.hovered != Entity::new(0, 0) {
@@ -589,6 +551,11 @@ impl Application {
 
                             match s {
                                 MouseButtonState::Pressed => {
+                                    // The entity under the cursor may have been hidden or made transparent since the cursor last moved
+                                    apply_hover(&mut state);
+
+                                    let double_click = state.mouse.record_click(b);
+
                                     if state.hovered != Entity::null()
                                         && state.active != state.hovered
                                     {
@@ -596,10 +563,10 @@ impl Application {
                                         state.insert_event(Event::new(WindowEvent::Restyle));
                                     }
 
-                                    if state.captured != Entity::null() {
+                                    if state.captured() != Entity::null() {
                                         state.insert_event(
                                             Event::new(WindowEvent::MouseDown(b))
-                                                .target(state.captured)
+                                                .target(state.captured())
                                                 .propagate(Propagation::Direct),
                                         );
                                     } else {
@@ -619,27 +586,34 @@ impl Application {
                                         MouseButton::Middle => {
                                             state.mouse.middle.pos_down =
                                                 (state.mouse.cursorx, state.mouse.cursory);
-                                            state.mouse.left.pressed = state.hovered;
+                                            state.mouse.middle.pressed = state.hovered;
                                         }
 
                                         MouseButton::Right => {
                                             state.mouse.right.pos_down =
                                                 (state.mouse.cursorx, state.mouse.cursory);
-                                            state.mouse.left.pressed = state.hovered;
+                                            state.mouse.right.pressed = state.hovered;
                                         }
 
                                         _ => {}
                                     }
+
+                                    if double_click {
+                                        state.insert_event(
+                                            Event::new(WindowEvent::MouseDoubleClick(b))
+                                                .target(state.hovered),
+                                        );
+                                    }
                                 }
 
                                 MouseButtonState::Released => {
                                     state.active = Entity::null();
                                     state.insert_event(Event::new(WindowEvent::Restyle));
 
-                                    if state.captured != Entity::null() {
+                                    if state.captured() != Entity::null() {
                                         state.insert_event(
                                             Event::new(WindowEvent::MouseUp(b))
-                                                .target(state.captured)
+                                                .target(state.captured())
                                                 .propagate(Propagation::Direct),
                                         );
                                     } else {
@@ -659,17 +633,28 @@ impl Application {
                                         MouseButton::Middle => {
                                             state.mouse.middle.pos_up =
                                                 (state.mouse.cursorx, state.mouse.cursory);
-                                            state.mouse.left.released = state.hovered;
+                                            state.mouse.middle.released = state.hovered;
                                         }
 
                                         MouseButton::Right => {
                                             state.mouse.right.pos_up =
                                                 (state.mouse.cursorx, state.mouse.cursory);
-                                            state.mouse.left.released = state.hovered;
+                                            state.mouse.right.released = state.hovered;
                                         }
 
                                         _ => {}
                                     }
+
+                                    if b == MouseButton::Right && state.hovered != Entity::null() {
+                                        state.insert_event(
+                                            Event::new(WindowEvent::ContextMenu(
+                                                state.hovered,
+                                                state.mouse.cursorx,
+                                                state.mouse.cursory,
+                                            ))
+                                            .target(state.hovered),
+                                        );
+                                    }
                                 }
                             }
                         }
@@ -682,13 +667,20 @@ impl Application {
                         } => {
                             let (x, y) = match delta {
                                 glutin::event::MouseScrollDelta::LineDelta(xx, yy) => (xx, yy),
-                                _ => (0.0, 0.0),
+                                // Pixel deltas (e.g. from touchpads) are sent as fractions of a line
+                                glutin::event::MouseScrollDelta::PixelDelta(pos) => {
+                                    let pos: LogicalPosition<f32> =
+                                        pos.to_logical(state.scale_factor as f64);
+                                    (pos.x / 40.0, pos.y / 40.0)
+                                }
                             };
 
-                            if state.captured != Entity::null() {
+                            let (x, y) = state.scroll_delta(x, y);
+
+                            if state.captured() != Entity::null() {
                                 state.insert_event(
                                     Event::new(WindowEvent::MouseScroll(x, y))
-                                        .target(state.captured)
+                                        .target(state.captured())
                                         .propagate(Propagation::Direct),
                                 );
                             } else {
@@ -712,3 +704,54 @@ impl Application {
         });
     }
 }
+
+// Applies changes to the window properties requested by events in the queue
+fn apply_window_events(state: &mut State, window: &Window) {
+    for event in state.event_queue.iter_mut() {
+        if let Some(window_event) = event.message.downcast::<WindowEvent>() {
+            match window_event {
+                WindowEvent::SetTitle(title) => {
+                    window.handle.window().set_title(title);
+                }
+
+                WindowEvent::SetInnerSize(width, height) => {
+                    window
+                        .handle
+                        .window()
+                        .set_inner_size(LogicalSize::new(*width, *height));
+                }
+
+                WindowEvent::SetMinSize(width, height) => {
+                    window
+                        .handle
+                        .window()
+                        .set_min_inner_size(Some(LogicalSize::new(*width, *height)));
+                }
+
+                WindowEvent::SetResizable(resizable) => {
+                    window.handle.window().set_resizable(*resizable);
+                }
+
+                _ => {}
+            }
+        }
+    }
+}
+
+fn glutin_cursor_icon(cursor_icon: CursorIcon) -> glutin::window::CursorIcon {
+    match cursor_icon {
+        CursorIcon::Arrow => glutin::window::CursorIcon::Arrow,
+        CursorIcon::NResize => glutin::window::CursorIcon::NResize,
+        CursorIcon::EResize => glutin::window::CursorIcon::EResize,
+        CursorIcon::SResize => glutin::window::CursorIcon::SResize,
+        CursorIcon::WResize => glutin::window::CursorIcon::WResize,
+        CursorIcon::EwResize => glutin::window::CursorIcon::EwResize,
+        CursorIcon::NsResize => glutin::window::CursorIcon::NsResize,
+        CursorIcon::Text => glutin::window::CursorIcon::Text,
+        CursorIcon::Hand => glutin::window::CursorIcon::Hand,
+        CursorIcon::Move => glutin::window::CursorIcon::Move,
+        CursorIcon::Crosshair => glutin::window::CursorIcon::Crosshair,
+        CursorIcon::NotAllowed => glutin::window::CursorIcon::NotAllowed,
+        CursorIcon::Wait => glutin::window::CursorIcon::Wait,
+    }
+}
@@ -186,6 +186,43 @@ pub fn vk_to_key(vk: VirtualKeyCode) -> Option<Key> {
         VirtualKeyCode::F10 => Key::F10,
         VirtualKeyCode::F11 => Key::F11,
         VirtualKeyCode::F12 => Key::F12,
+        // Letters and digits are reported as the unshifted character so they can be matched by accelerators
+        VirtualKeyCode::A => Key::Character("a".to_string()),
+        VirtualKeyCode::B => Key::Character("b".to_string()),
+        VirtualKeyCode::C => Key::Character("c".to_string()),
+        VirtualKeyCode::D => Key::Character("d".to_string()),
+        VirtualKeyCode::E => Key::Character("e".to_string()),
+        VirtualKeyCode::F => Key::Character("f".to_string()),
+        VirtualKeyCode::G => Key::Character("g".to_string()),
+        VirtualKeyCode::H => Key::Character("h".to_string()),
+        VirtualKeyCode::I => Key::Character("i".to_string()),
+        VirtualKeyCode::J => Key::Character("j".to_string()),
+        VirtualKeyCode::K => Key::Character("k".to_string()),
+        VirtualKeyCode::L => Key::Character("l".to_string()),
+        VirtualKeyCode::M => Key::Character("m".to_string()),
+        VirtualKeyCode::N => Key::Character("n".to_string()),
+        VirtualKeyCode::O => Key::Character("o".to_string()),
+        VirtualKeyCode::P => Key::Character("p".to_string()),
+        VirtualKeyCode::Q => Key::Character("q".to_string()),
+        VirtualKeyCode::R => Key::Character("r".to_string()),
+        VirtualKeyCode::S => Key::Character("s".to_string()),
+        VirtualKeyCode::T => Key::Character("t".to_string()),
+        VirtualKeyCode::U => Key::Character("u".to_string()),
+        VirtualKeyCode::V => Key::Character("v".to_string()),
+        VirtualKeyCode::W => Key::Character("w".to_string()),
+        VirtualKeyCode::X => Key::Character("x".to_string()),
+        VirtualKeyCode::Y => Key::Character("y".to_string()),
+        VirtualKeyCode::Z => Key::Character("z".to_string()),
+        VirtualKeyCode::Key0 => Key::Character("0".to_string()),
+        VirtualKeyCode::Key1 => Key::Character("1".to_string()),
+        VirtualKeyCode::Key2 => Key::Character("2".to_string()),
+        VirtualKeyCode::Key3 => Key::Character("3".to_string()),
+        VirtualKeyCode::Key4 => Key::Character("4".to_string()),
+        VirtualKeyCode::Key5 => Key::Character("5".to_string()),
+        VirtualKeyCode::Key6 => Key::Character("6".to_string()),
+        VirtualKeyCode::Key7 => Key::Character("7".to_string()),
+        VirtualKeyCode::Key8 => Key::Character("8".to_string()),
+        VirtualKeyCode::Key9 => Key::Character("9".to_string()),
         VirtualKeyCode::Numlock => Key::NumLock,
         VirtualKeyCode::Scroll => Key::ScrollLock,
         // VirtualKeyCode::BROWSER_BACK => Key::BrowserBack,
@@ -17,14 +17,15 @@ impl Window {
     pub fn new(events_loop: &EventLoop<()>, window_description: &WindowDescription) -> Self {
         let window_builder = WindowBuilder::new()
             .with_title(&window_description.title)
-            .with_inner_size(PhysicalSize::new(
+            .with_inner_size(LogicalSize::new(
                 window_description.inner_size.width,
                 window_description.inner_size.height,
             ))
-            .with_min_inner_size(PhysicalSize::new(
+            .with_min_inner_size(LogicalSize::new(
                 window_description.min_inner_size.width,
                 window_description.min_inner_size.height,
             ))
+            .with_resizable(window_description.resizable)
             .with_window_icon(if let Some(icon) = &window_description.icon {
                 Some(
                     glutin::window::Icon::from_rgba(